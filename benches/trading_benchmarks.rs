@@ -0,0 +1,48 @@
+//! Benchmarks for the hot paths of token discovery
+//!
+//! Run with `cargo bench --bench trading_benchmarks`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use solana_sdk::pubkey::Pubkey;
+
+use solana_sniper_bot::core::types::TokenAddress;
+use solana_sniper_bot::services::solana::metadata::{decode_metadata, metadata_pda};
+
+/// Borsh-encoded metadata account with one verified creator
+fn metadata_account() -> Vec<u8> {
+    fn push_string(data: &mut Vec<u8>, value: &str) {
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        data.extend_from_slice(value.as_bytes());
+    }
+
+    let mut data = vec![4];
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    push_string(&mut data, "Benchmark Token");
+    push_string(&mut data, "BENCH");
+    push_string(&mut data, "https://example.com/bench.json");
+    data.extend_from_slice(&500u16.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(&[1, 100]);
+    data.extend_from_slice(&[0, 1]);
+    data
+}
+
+fn bench_token_discovery(c: &mut Criterion) {
+    let mint = Pubkey::new_unique();
+    let account = metadata_account();
+    let address = mint.to_string();
+
+    c.bench_function("metadata_pda", |b| b.iter(|| metadata_pda(black_box(&mint))));
+    c.bench_function("decode_metadata", |b| b.iter(|| decode_metadata(black_box(&account))));
+    c.bench_function("token_address_validation", |b| {
+        b.iter(|| TokenAddress::new(black_box(address.clone())))
+    });
+}
+
+criterion_group!(benches, bench_token_discovery);
+criterion_main!(benches);
@@ -0,0 +1,12 @@
+-- Two-factor authentication (TOTP) for Telegram users
+-- The shared secret is stored as an EncryptedData JSON document and is
+-- never persisted in plaintext.
+
+ALTER TABLE telegram_users
+    ADD COLUMN totp_secret JSONB,
+    ADD COLUMN totp_enrolled_at TIMESTAMPTZ,
+    ADD COLUMN totp_confirmed_at TIMESTAMPTZ,
+    ADD COLUMN totp_last_used_step BIGINT;
+
+COMMENT ON COLUMN telegram_users.totp_secret IS 'Encrypted TOTP shared secret';
+COMMENT ON COLUMN telegram_users.totp_last_used_step IS 'Last accepted TOTP time step for replay protection';
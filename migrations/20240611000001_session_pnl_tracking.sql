@@ -0,0 +1,95 @@
+-- Session PnL tracking
+-- Splits session PnL into realized/unrealized components, tracks fees
+-- separately, and replaces the drawdown calculation in update_session_stats.
+-- The previous implementation reported the worst single-trade pnl_percent,
+-- which is not a drawdown. Drawdown is now measured peak-to-trough on the
+-- session equity curve.
+
+ALTER TABLE sessions
+    ADD COLUMN starting_capital_sol DECIMAL(20, 8),
+    ADD COLUMN realized_pnl_sol DECIMAL(20, 8) DEFAULT 0,
+    ADD COLUMN unrealized_pnl_sol DECIMAL(20, 8) DEFAULT 0,
+    ADD COLUMN total_fees_sol DECIMAL(20, 8) DEFAULT 0,
+    ADD COLUMN peak_equity_sol DECIMAL(20, 8);
+
+COMMENT ON COLUMN sessions.total_pnl IS 'Net PnL in SOL: realized + unrealized - fees';
+COMMENT ON COLUMN sessions.max_drawdown IS 'Maximum peak-to-trough equity drawdown in percent';
+
+-- Function to update session statistics
+CREATE OR REPLACE FUNCTION update_session_stats(p_session_id UUID)
+RETURNS VOID AS $$
+DECLARE
+    v_total_trades INTEGER;
+    v_profitable_trades INTEGER;
+    v_realized_pnl DECIMAL(20, 8);
+    v_total_fees DECIMAL(20, 8);
+    v_unrealized_pnl DECIMAL(20, 8);
+    v_starting_capital DECIMAL(20, 8);
+    v_peak_equity DECIMAL(20, 8);
+    v_max_drawdown DECIMAL(10, 4);
+BEGIN
+    -- Calculate total trades
+    SELECT COUNT(*) INTO v_total_trades
+    FROM trades
+    WHERE session_id = p_session_id AND status = 'executed';
+
+    -- Calculate profitable trades
+    SELECT COUNT(*) INTO v_profitable_trades
+    FROM trades
+    WHERE session_id = p_session_id AND status = 'executed' AND pnl_sol > 0;
+
+    -- Realized PnL and fees are tracked separately
+    SELECT COALESCE(SUM(pnl_sol), 0), COALESCE(SUM(gas_fee_sol), 0)
+    INTO v_realized_pnl, v_total_fees
+    FROM trades
+    WHERE session_id = p_session_id AND status = 'executed';
+
+    -- Unrealized PnL from open positions
+    SELECT COALESCE(SUM(unrealized_pnl_sol), 0) INTO v_unrealized_pnl
+    FROM positions
+    WHERE session_id = p_session_id AND is_open = true;
+
+    SELECT COALESCE(starting_capital_sol, 0) INTO v_starting_capital
+    FROM sessions
+    WHERE id = p_session_id;
+
+    -- Peak-to-trough drawdown on the realized equity curve
+    WITH curve AS (
+        SELECT
+            ROW_NUMBER() OVER w AS seq,
+            v_starting_capital + SUM(COALESCE(pnl_sol, 0) - COALESCE(gas_fee_sol, 0)) OVER w AS equity
+        FROM trades
+        WHERE session_id = p_session_id AND status = 'executed'
+        WINDOW w AS (ORDER BY COALESCE(executed_at, created_at), id ROWS UNBOUNDED PRECEDING)
+    ),
+    peaks AS (
+        SELECT
+            equity,
+            GREATEST(v_starting_capital, MAX(equity) OVER (ORDER BY seq ROWS UNBOUNDED PRECEDING)) AS peak
+        FROM curve
+    )
+    SELECT
+        COALESCE(MAX(peak), v_starting_capital),
+        COALESCE(MAX(CASE WHEN peak > 0 THEN (peak - equity) / peak * 100 ELSE 0 END), 0)
+    INTO v_peak_equity, v_max_drawdown
+    FROM peaks;
+
+    -- Update session
+    UPDATE sessions
+    SET
+        total_trades = v_total_trades,
+        profitable_trades = v_profitable_trades,
+        realized_pnl_sol = v_realized_pnl,
+        unrealized_pnl_sol = v_unrealized_pnl,
+        total_fees_sol = v_total_fees,
+        total_pnl = v_realized_pnl + v_unrealized_pnl - v_total_fees,
+        peak_equity_sol = v_peak_equity,
+        max_drawdown = GREATEST(COALESCE(max_drawdown, 0), v_max_drawdown),
+        updated_at = NOW()
+    WHERE id = p_session_id;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Recompute existing sessions with the corrected drawdown
+UPDATE sessions SET max_drawdown = 0;
+SELECT update_session_stats(id) FROM sessions;
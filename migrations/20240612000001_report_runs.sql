@@ -0,0 +1,18 @@
+-- Scheduled report delivery history
+-- Used by the report scheduler to detect and catch up missed schedules.
+
+CREATE TABLE report_runs (
+                             id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+                             report_type VARCHAR(20) NOT NULL,
+                             scheduled_for TIMESTAMPTZ NOT NULL,
+                             period_start TIMESTAMPTZ NOT NULL,
+                             period_end TIMESTAMPTZ NOT NULL,
+                             delivery_method VARCHAR(20) NOT NULL,
+                             report JSONB NOT NULL,
+                             delivered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                             UNIQUE (report_type, scheduled_for)
+);
+
+CREATE INDEX idx_report_runs_type_scheduled ON report_runs(report_type, scheduled_for DESC);
+
+COMMENT ON TABLE report_runs IS 'Delivered scheduled reports, used for missed schedule catch-up';
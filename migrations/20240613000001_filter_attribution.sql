@@ -0,0 +1,43 @@
+-- Filter attribution tracking
+-- Every filter evaluation is persisted, including rejected tokens, and the
+-- token price is tracked for 24 hours to attribute missed winners and
+-- avoided rugs to individual filters.
+
+CREATE TABLE filter_evaluations (
+                                    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+                                    token_address VARCHAR(44) NOT NULL,
+                                    passed BOOLEAN NOT NULL,
+                                    safety_score SMALLINT NOT NULL,
+                                    rejection_reasons JSONB NOT NULL DEFAULT '[]',
+                                    warnings JSONB NOT NULL DEFAULT '[]',
+                                    price_sol DECIMAL(30, 18),
+                                    price_1h_sol DECIMAL(30, 18),
+                                    price_24h_sol DECIMAL(30, 18),
+                                    tracked_1h_at TIMESTAMPTZ,
+                                    tracked_24h_at TIMESTAMPTZ,
+                                    outcome VARCHAR(20),
+                                    evaluated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE filter_checks (
+                               id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+                               evaluation_id UUID NOT NULL REFERENCES filter_evaluations(id) ON DELETE CASCADE,
+                               filter_name VARCHAR(50) NOT NULL,
+                               passed BOOLEAN NOT NULL,
+                               blocking BOOLEAN NOT NULL,
+                               flagged BOOLEAN NOT NULL,
+                               value JSONB,
+                               expected JSONB,
+                               details TEXT
+);
+
+CREATE INDEX idx_filter_evaluations_evaluated_at ON filter_evaluations(evaluated_at DESC);
+CREATE INDEX idx_filter_evaluations_pending_1h ON filter_evaluations(evaluated_at) WHERE tracked_1h_at IS NULL;
+CREATE INDEX idx_filter_evaluations_pending_24h ON filter_evaluations(evaluated_at) WHERE tracked_24h_at IS NULL;
+CREATE INDEX idx_filter_checks_evaluation ON filter_checks(evaluation_id);
+CREATE INDEX idx_filter_checks_name_flagged ON filter_checks(filter_name) WHERE flagged;
+
+COMMENT ON TABLE filter_evaluations IS 'Token filter results with 1h/24h price follow-up';
+COMMENT ON TABLE filter_checks IS 'Per-filter checks of a filter evaluation';
+COMMENT ON COLUMN filter_checks.flagged IS 'Filter failed or raised a warning for the token';
+COMMENT ON COLUMN filter_evaluations.outcome IS 'winner, rug, neutral or unknown after 24h';
@@ -0,0 +1,24 @@
+-- Audit archive registry
+-- Each row records a signed, hash-chained archive of audit_logs,
+-- config_changes, telegram_commands and trades for one period. Retention
+-- pruning only deletes rows whose timestamp falls inside an archived period.
+
+CREATE TABLE audit_archives (
+                                id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+                                period_type VARCHAR(20) NOT NULL,
+                                period_start TIMESTAMPTZ NOT NULL,
+                                period_end TIMESTAMPTZ NOT NULL,
+                                archive_path TEXT NOT NULL,
+                                record_count BIGINT NOT NULL,
+                                genesis_hash CHAR(64) NOT NULL,
+                                final_hash CHAR(64) NOT NULL,
+                                signature CHAR(64) NOT NULL,
+                                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                                UNIQUE (period_start, period_end),
+                                CHECK (period_end > period_start)
+);
+
+CREATE INDEX idx_audit_archives_period ON audit_archives(period_start, period_end);
+
+COMMENT ON TABLE audit_archives IS 'Signed hash-chained audit archives; rows are only pruned once archived';
+COMMENT ON COLUMN audit_archives.genesis_hash IS 'Final hash of the previous archive, chaining archives together';
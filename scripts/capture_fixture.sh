@@ -0,0 +1,80 @@
+#!/usr/bin/env bash
+# Capture a getTransaction response as a decoder test fixture
+#
+# Usage: scripts/capture_fixture.sh <fixture-name> <signature> [rpc-url]
+#        scripts/capture_fixture.sh --latest <raydium|orca|meteora|pump_fun> [rpc-url]
+#
+# The response is written to src/services/solana/fixtures/<fixture-name>.json
+# and the signature is recorded in the fixtures README. With --latest, the
+# newest successful creation transaction of the DEX is found on chain and
+# captured under the DEX's fixture name.
+set -euo pipefail
+
+fixtures="$(dirname "$0")/../src/services/solana/fixtures"
+
+rpc() {
+    curl -sf -X POST "$rpc_url" -H 'content-type: application/json' -d "$1"
+}
+
+get_transaction() {
+    rpc "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"getTransaction\",\"params\":[\"$1\",{\"encoding\":\"json\",\"commitment\":\"confirmed\",\"maxSupportedTransactionVersion\":0}]}"
+}
+
+if [ "$1" = "--latest" ]; then
+    dex="$2"
+    rpc_url="${3:-${SOLANA_RPC_URL:-https://api.mainnet-beta.solana.com}}"
+
+    # Address polled for creations, and the log line that marks one
+    case "$dex" in
+        raydium)
+            name="raydium_initialize2"
+            address="7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5"
+            marker="initialize2: InitializeInstruction2"
+            ;;
+        orca)
+            name="orca_initialize_pool_v2"
+            address="2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcF2vQ4Ldo6sm"
+            marker="Instruction: InitializePoolV2"
+            ;;
+        meteora)
+            name="meteora_initialize_permissionless_pool"
+            address="Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB"
+            marker="Instruction: InitializePermissionlessPool"
+            ;;
+        pump_fun)
+            name="pump_fun_create"
+            address="6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"
+            marker="Instruction: Create"
+            ;;
+        *)
+            echo "Unknown DEX: $dex" >&2
+            exit 1
+            ;;
+    esac
+
+    signatures=$(rpc "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"getSignaturesForAddress\",\"params\":[\"$address\",{\"limit\":100,\"commitment\":\"confirmed\"}]}" \
+        | jq -r '.result[] | select(.err == null) | .signature')
+
+    signature=""
+    for candidate in $signatures; do
+        if get_transaction "$candidate" \
+            | jq -e --arg marker "$marker" '.result.meta.logMessages | any(endswith($marker) or contains($marker + " "))' > /dev/null; then
+            signature="$candidate"
+            break
+        fi
+    done
+
+    if [ -z "$signature" ]; then
+        echo "No $dex creation among the newest 100 transactions of $address" >&2
+        exit 1
+    fi
+else
+    name="$1"
+    signature="$2"
+    rpc_url="${3:-${SOLANA_RPC_URL:-https://api.mainnet-beta.solana.com}}"
+fi
+
+get_transaction "$signature" | jq -e '.result' > "$fixtures/$name.json"
+
+echo "| \`$name.json\` | \`$signature\` |" >> "$fixtures/README.md"
+echo "Captured $signature into $fixtures/$name.json"
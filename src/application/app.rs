@@ -11,8 +11,17 @@ use tracing::{info, warn, error, debug, instrument};
 use crate::config::AppConfig;
 use crate::core::result::AppResult;
 use crate::core::error::AppError;
+use crate::infrastructure::database::PostgresService;
+use crate::services::ServiceContainer;
+use crate::services::analytics::{AuditArchiver, FilterAttributionTracker, ReportScheduler};
 use super::health::{HealthService, HealthStatus};
 
+/// Interval between audit archive and retention runs
+const AUDIT_ARCHIVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// Interval between filter attribution price tracking passes
+const FILTER_ATTRIBUTION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
 /// Main application state and coordinator
 #[derive(Debug)]
 pub struct Application {
@@ -133,6 +142,34 @@ impl Application {
             service_handles.push(health_handle);
         }
 
+        // Start audit archiving if enabled
+        let audit_archiver = if self.config.analytics.enable_audit_reports {
+            info!("🗄️  Starting audit archiver");
+            Some(self.start_audit_archiver().await?)
+        } else {
+            None
+        };
+
+        // Start filter attribution price tracking if enabled
+        let filter_attribution = if self.config.analytics.enable_filter_attribution {
+            info!("🧪 Starting filter attribution tracker");
+            Some(self.start_filter_attribution().await?)
+        } else {
+            None
+        };
+
+        // Start scheduled reports if any period is enabled
+        let analytics = &self.config.analytics;
+        let report_scheduler = if analytics.enable_daily_reports
+            || analytics.enable_weekly_reports
+            || analytics.enable_monthly_reports
+        {
+            info!("🗓️  Starting report scheduler");
+            Some(self.start_report_scheduler().await?)
+        } else {
+            None
+        };
+
         info!("✅ All services started successfully");
         info!("🎯 Solana Sniper Bot is now running in {} mode", self.config.trading.scenario_mode);
 
@@ -171,6 +208,15 @@ impl Application {
         }
 
         // Graceful shutdown
+        if let Some(archiver) = audit_archiver {
+            archiver.stop().await;
+        }
+        if let Some(tracker) = filter_attribution {
+            tracker.stop().await;
+        }
+        if let Some(scheduler) = report_scheduler {
+            scheduler.stop().await;
+        }
         self.shutdown().await?;
 
         info!("👋 Solana Sniper Bot application stopped");
@@ -195,6 +241,29 @@ impl Application {
         Ok(handle)
     }
 
+    /// Start periodic audit archiving and retention pruning
+    async fn start_audit_archiver(&self) -> AppResult<AuditArchiver> {
+        let postgres = PostgresService::new(&self.config.database).await?;
+        let archiver = AuditArchiver::new(&self.config, postgres)?;
+        archiver.start(AUDIT_ARCHIVE_INTERVAL).await?;
+        Ok(archiver)
+    }
+
+    /// Start the missed-winner and avoided-rug price tracking job
+    async fn start_filter_attribution(&self) -> AppResult<FilterAttributionTracker> {
+        let postgres = PostgresService::new(&self.config.database).await?;
+        let services = ServiceContainer::initialize(&self.config).await?;
+        services.start_filter_attribution_tracker(postgres, FILTER_ATTRIBUTION_INTERVAL).await
+    }
+
+    /// Start delivery of the enabled periodic reports
+    async fn start_report_scheduler(&self) -> AppResult<ReportScheduler> {
+        let postgres = PostgresService::new(&self.config.database).await?;
+        let scheduler = ReportScheduler::new(&self.config, postgres)?;
+        scheduler.start().await?;
+        Ok(scheduler)
+    }
+
     /// Start the health check server
     async fn start_health_server(&self) -> AppResult<tokio::task::JoinHandle<()>> {
         let port = self.config.monitoring.health_check_port;
@@ -209,6 +209,18 @@ impl ConfigLoader {
             cfg.birdeye.api_key = val;
         })?;
 
+        self.apply_env_var(config, &env_config, "GEYSER_ENDPOINT", |cfg, val: String| {
+            cfg.scanner.geyser_endpoint = Some(val);
+        })?;
+
+        self.apply_env_var(config, &env_config, "GEYSER_X_TOKEN", |cfg, val: String| {
+            cfg.scanner.geyser_x_token = Some(val);
+        })?;
+
+        self.apply_env_var(config, &env_config, "SCANNER_SHARED_DEDUP", |cfg, val: bool| {
+            cfg.scanner.shared_dedup = val;
+        })?;
+
         self.apply_env_var(config, &env_config, "DATABASE_URL", |cfg, val: String| {
             cfg.database.url = val;
         })?;
@@ -422,12 +434,23 @@ impl ConfigLoader {
                 max_retries: 3,
                 retry_backoff_ms: 1000,
                 commitment: "confirmed".to_string(),
+                max_slot_lag: 10,
+                sender_urls: vec![],
+                websocket_url: None,
+                rebroadcast_interval_ms: 250,
+                jito: super::models::JitoConfig::default(),
+                blockhash_refresh_ms: 400,
+                nonce_accounts: vec![],
+                sol_usd_pool: "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2".to_string(),
             },
             helius: super::models::HeliusConfig {
                 api_key: String::new(),
                 base_url: "https://mainnet.helius-rpc.com".to_string(),
+                api_url: "https://api.helius.xyz/v0".to_string(),
                 webhook_url: None,
                 enable_webhooks: false,
+                webhook_listen_addr: "0.0.0.0:8088".to_string(),
+                webhook_auth_header: None,
                 rate_limit_per_second: 100,
             },
             birdeye: super::models::BirdeyeConfig {
@@ -487,6 +510,7 @@ impl ConfigLoader {
                 enable_position_limits: false,
                 daily_trade_limit: None,
                 preferred_dex_order: vec![],
+                max_priority_fee_sol: rust_decimal_macros::dec!(0.005),
             },
             risk: super::models::RiskConfig {
                 risk_score_threshold: 7,
@@ -516,6 +540,14 @@ impl ConfigLoader {
                 enable_contract_verification: false,
                 require_social_links: false,
                 min_trading_volume_24h: None,
+                geyser_endpoint: None,
+                geyser_x_token: None,
+                dedup_ttl_seconds: 3600,
+                shared_dedup: false,
+                filter_rules_path: None,
+                parse_concurrency: 8,
+                enable_block_scan: false,
+                copy_trade: super::models::CopyTradeConfig::default(),
             },
             analytics: super::models::AnalyticsConfig {
                 enable_metrics: true,
@@ -531,8 +563,12 @@ impl ConfigLoader {
                 enable_weekly_reports: false,
                 enable_monthly_reports: false,
                 report_delivery_method: None,
+                report_output_dir: None,
+                report_webhook_url: None,
                 enable_audit_reports: false,
                 audit_report_frequency: None,
+                audit_archive_dir: "audit_archives".to_string(),
+                enable_filter_attribution: false,
             },
             monitoring: super::models::MonitoringConfig {
                 enable_health_checks: true,
@@ -556,6 +592,7 @@ impl ConfigLoader {
                 enable_audit_logging: true,
                 audit_log_retention_days: 365,
                 enable_2fa: false,
+                two_factor_withdrawal_threshold_sol: rust_decimal_macros::dec!(1.0),
                 enable_ip_whitelisting: false,
                 allowed_ip_ranges: vec![],
                 enable_rate_limiting: false,
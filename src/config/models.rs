@@ -102,6 +102,94 @@ pub struct SolanaConfig {
     /// Commitment level (processed, confirmed, finalized)
     #[serde(default = "default_commitment")]
     pub commitment: String,
+
+    /// Slots an endpoint may trail the highest observed slot before it is demoted
+    #[serde(default = "default_max_slot_lag")]
+    pub max_slot_lag: u64,
+
+    /// Staked or sender-only endpoints that transactions are also broadcast to
+    #[serde(default)]
+    pub sender_urls: Vec<String>,
+
+    /// WebSocket endpoint for signature subscriptions; derived from `rpc_url` if unset
+    #[serde(default)]
+    pub websocket_url: Option<String>,
+
+    /// Interval between re-sends of an unconfirmed transaction in milliseconds
+    #[serde(default = "default_rebroadcast_interval")]
+    pub rebroadcast_interval_ms: u64,
+
+    /// Jito bundle submission
+    #[serde(default)]
+    pub jito: JitoConfig,
+
+    /// Interval between background blockhash refreshes in milliseconds
+    #[serde(default = "default_blockhash_refresh")]
+    pub blockhash_refresh_ms: u64,
+
+    /// Durable nonce accounts for pre-signed exit transactions
+    #[serde(default)]
+    pub nonce_accounts: Vec<String>,
+
+    /// SOL/USDC pool used by the price oracle for USD conversion
+    #[serde(default = "default_sol_usd_pool")]
+    pub sol_usd_pool: String,
+}
+
+/// Jito block-engine configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JitoConfig {
+    /// Submit transactions as bundles
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Block-engine URLs bundles are submitted to
+    #[serde(default = "default_jito_block_engine_urls")]
+    pub block_engine_urls: Vec<String>,
+
+    /// Tip floor endpoint used for dynamic tips
+    #[serde(default)]
+    pub tip_floor_url: Option<String>,
+
+    /// Tip accounts; fetched from the block engine if empty
+    #[serde(default)]
+    pub tip_accounts: Vec<String>,
+
+    /// Smallest tip in lamports
+    #[serde(default = "default_jito_min_tip")]
+    pub min_tip_lamports: u64,
+
+    /// Largest tip in lamports
+    #[serde(default = "default_jito_max_tip")]
+    pub max_tip_lamports: u64,
+
+    /// Multiplier applied to the tip floor percentile
+    #[serde(default = "default_jito_tip_multiplier")]
+    pub tip_multiplier: f64,
+
+    /// Slots to wait for a bundle to land before sending normally
+    #[serde(default = "default_jito_max_bundle_slots")]
+    pub max_bundle_slots: u64,
+
+    /// Bundle status polling interval in milliseconds
+    #[serde(default = "default_jito_status_poll_interval")]
+    pub status_poll_interval_ms: u64,
+}
+
+impl Default for JitoConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            block_engine_urls: default_jito_block_engine_urls(),
+            tip_floor_url: None,
+            tip_accounts: Vec::new(),
+            min_tip_lamports: default_jito_min_tip(),
+            max_tip_lamports: default_jito_max_tip(),
+            tip_multiplier: default_jito_tip_multiplier(),
+            max_bundle_slots: default_jito_max_bundle_slots(),
+            status_poll_interval_ms: default_jito_status_poll_interval(),
+        }
+    }
 }
 
 /// Helius API configuration
@@ -113,6 +201,10 @@ pub struct HeliusConfig {
     /// Helius base URL
     pub base_url: String,
 
+    /// Helius REST API URL, used for enhanced transactions
+    #[serde(default = "default_helius_api_url")]
+    pub api_url: String,
+
     /// Webhook URL for real-time events
     #[serde(default)]
     pub webhook_url: Option<String>,
@@ -121,6 +213,14 @@ pub struct HeliusConfig {
     #[serde(default)]
     pub enable_webhooks: bool,
 
+    /// Address the webhook receiver listens on
+    #[serde(default = "default_helius_webhook_listen_addr")]
+    pub webhook_listen_addr: String,
+
+    /// Secret Helius sends in the `Authorization` header of deliveries
+    #[serde(default)]
+    pub webhook_auth_header: Option<String>,
+
     /// Rate limit per second
     #[serde(default = "default_helius_rate_limit")]
     pub rate_limit_per_second: u32,
@@ -324,6 +424,10 @@ pub struct TradingConfig {
     /// Preferred DEX ordering
     #[serde(default)]
     pub preferred_dex_order: Vec<DexType>,
+
+    /// Maximum priority fee paid per trade in SOL
+    #[serde(default = "default_max_priority_fee")]
+    pub max_priority_fee_sol: Decimal,
 }
 
 /// Risk management configuration
@@ -425,6 +529,97 @@ pub struct ScannerConfig {
     /// Minimum 24h trading volume
     #[serde(default)]
     pub min_trading_volume_24h: Option<u64>,
+
+    /// Yellowstone gRPC (Geyser) endpoint for streaming detection
+    #[serde(default)]
+    pub geyser_endpoint: Option<String>,
+
+    /// Yellowstone gRPC access token
+    #[serde(default)]
+    pub geyser_x_token: Option<String>,
+
+    /// How long a detected token is remembered for deduplication, in seconds
+    #[serde(default = "default_dedup_ttl")]
+    pub dedup_ttl_seconds: u64,
+
+    /// Share deduplication across bot instances through Redis
+    #[serde(default)]
+    pub shared_dedup: bool,
+
+    /// YAML or TOML file of declarative filter rules
+    #[serde(default)]
+    pub filter_rules_path: Option<String>,
+
+    /// Tokens parsed and filtered concurrently per scan
+    #[serde(default = "default_parse_concurrency")]
+    pub parse_concurrency: usize,
+
+    /// Scan recent blocks for new mints, one `getBlock` call per slot
+    #[serde(default)]
+    pub enable_block_scan: bool,
+
+    /// Copy-trade wallet tracking
+    #[serde(default)]
+    pub copy_trade: CopyTradeConfig,
+}
+
+/// Copy-trade configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyTradeConfig {
+    /// Wallets to follow
+    #[serde(default)]
+    pub wallets: Vec<TrackedWalletConfig>,
+
+    /// Size mirror buys for tracked wallet purchases
+    #[serde(default)]
+    pub mirror_mode: bool,
+
+    /// Minimum delay before mirroring a buy in milliseconds
+    #[serde(default)]
+    pub mirror_delay_ms: u64,
+
+    /// Buys older than this are not mirrored, in milliseconds
+    #[serde(default = "default_mirror_window")]
+    pub mirror_window_ms: u64,
+
+    /// Wallet polling interval in milliseconds
+    #[serde(default = "default_wallet_poll_interval")]
+    pub poll_interval_ms: u64,
+}
+
+impl Default for CopyTradeConfig {
+    fn default() -> Self {
+        Self {
+            wallets: Vec::new(),
+            mirror_mode: false,
+            mirror_delay_ms: 0,
+            mirror_window_ms: default_mirror_window(),
+            poll_interval_ms: default_wallet_poll_interval(),
+        }
+    }
+}
+
+/// Tracked wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedWalletConfig {
+    /// Wallet address
+    pub address: String,
+
+    /// Display label
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// Mirrored buy size as a fraction of the wallet's buy
+    #[serde(default = "default_mirror_ratio")]
+    pub mirror_ratio: Decimal,
+
+    /// Largest single mirrored buy in SOL
+    #[serde(default)]
+    pub max_buy_sol: Option<Decimal>,
+
+    /// Largest total mirrored for this wallet in SOL
+    #[serde(default)]
+    pub max_total_sol: Option<Decimal>,
 }
 /// Analytics configuration (continued)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -481,6 +676,14 @@ pub struct AnalyticsConfig {
     #[serde(default)]
     pub report_delivery_method: Option<String>,
 
+    /// Output directory for file report delivery
+    #[serde(default)]
+    pub report_output_dir: Option<String>,
+
+    /// Webhook URL for webhook report delivery
+    #[serde(default)]
+    pub report_webhook_url: Option<String>,
+
     /// Enable audit reports
     #[serde(default)]
     pub enable_audit_reports: bool,
@@ -488,6 +691,14 @@ pub struct AnalyticsConfig {
     /// Audit report frequency
     #[serde(default)]
     pub audit_report_frequency: Option<String>,
+
+    /// Output directory for audit archives
+    #[serde(default = "default_audit_archive_dir")]
+    pub audit_archive_dir: String,
+
+    /// Track prices of filtered tokens for filter attribution
+    #[serde(default)]
+    pub enable_filter_attribution: bool,
 }
 
 /// Monitoring configuration
@@ -572,6 +783,10 @@ pub struct SecurityConfig {
     #[serde(default)]
     pub enable_2fa: bool,
 
+    /// Withdrawals above this amount require a fresh 2FA code
+    #[serde(default = "default_2fa_withdrawal_threshold")]
+    pub two_factor_withdrawal_threshold_sol: Decimal,
+
     /// Enable IP whitelisting
     #[serde(default)]
     pub enable_ip_whitelisting: bool,
@@ -769,7 +984,19 @@ fn default_connection_timeout() -> u64 { 5000 }
 fn default_max_retries() -> u32 { 3 }
 fn default_retry_backoff() -> u64 { 1000 }
 fn default_commitment() -> String { "confirmed".to_string() }
+fn default_max_slot_lag() -> u64 { 10 }
+fn default_rebroadcast_interval() -> u64 { 250 }
+fn default_blockhash_refresh() -> u64 { 400 }
+fn default_sol_usd_pool() -> String { "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2".to_string() }
+fn default_jito_block_engine_urls() -> Vec<String> { vec!["https://mainnet.block-engine.jito.wtf".to_string()] }
+fn default_jito_min_tip() -> u64 { 10_000 }
+fn default_jito_max_tip() -> u64 { 2_000_000 }
+fn default_jito_tip_multiplier() -> f64 { 1.0 }
+fn default_jito_max_bundle_slots() -> u64 { 4 }
+fn default_jito_status_poll_interval() -> u64 { 200 }
+fn default_helius_api_url() -> String { "https://api.helius.xyz/v0".to_string() }
 fn default_helius_rate_limit() -> u32 { 100 }
+fn default_helius_webhook_listen_addr() -> String { "0.0.0.0:8088".to_string() }
 fn default_birdeye_rate_limit() -> u32 { 500 }
 fn default_cache_ttl() -> u64 { 60 }
 fn default_max_connections() -> u32 { 20 }
@@ -790,6 +1017,12 @@ fn default_enable_inline_keyboards() -> bool { true }
 fn default_trade_execution_timeout() -> u64 { 50 }
 fn default_scan_interval() -> u64 { 1000 }
 fn default_max_tokens_per_scan() -> u32 { 100 }
+fn default_dedup_ttl() -> u64 { 3600 }
+fn default_parse_concurrency() -> usize { 8 }
+fn default_mirror_window() -> u64 { 5000 }
+fn default_wallet_poll_interval() -> u64 { 400 }
+fn default_mirror_ratio() -> Decimal { Decimal::new(1, 1) }
+fn default_max_priority_fee() -> Decimal { Decimal::new(5, 3) }
 fn default_metrics_port() -> u16 { 9090 }
 fn default_retention_days() -> u32 { 90 }
 fn default_health_port() -> u16 { 8080 }
@@ -799,7 +1032,9 @@ fn default_session_timeout() -> u64 { 60 }
 fn default_max_failed_attempts() -> u32 { 3 }
 fn default_lockout_duration() -> u64 { 5 }
 fn default_audit_retention() -> u32 { 365 }
+fn default_audit_archive_dir() -> String { "audit_archives".to_string() }
 fn default_encryption_algorithm() -> String { "AES-256-GCM".to_string() }
+fn default_2fa_withdrawal_threshold() -> Decimal { Decimal::ONE }
 fn default_max_memory() -> u64 { 512 }
 fn default_max_cpu() -> u8 { 30 }
 fn default_detection_latency() -> u64 { 1000 }
@@ -1059,6 +1294,7 @@ mod tests {
                 enable_position_limits: false,
                 daily_trade_limit: None,
                 preferred_dex_order: vec![],
+                max_priority_fee_sol: rust_decimal_macros::dec!(0.005),
             },
             // ... other required fields with default values
             solana: SolanaConfig {
@@ -1068,12 +1304,23 @@ mod tests {
                 max_retries: 3,
                 retry_backoff_ms: 1000,
                 commitment: "confirmed".to_string(),
+                max_slot_lag: 10,
+                sender_urls: vec![],
+                websocket_url: None,
+                rebroadcast_interval_ms: 250,
+                jito: JitoConfig::default(),
+                blockhash_refresh_ms: 400,
+                nonce_accounts: vec![],
+                sol_usd_pool: "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2".to_string(),
             },
             helius: HeliusConfig {
                 api_key: "test".to_string(),
                 base_url: "https://mainnet.helius-rpc.com".to_string(),
+                api_url: "https://api.helius.xyz/v0".to_string(),
                 webhook_url: None,
                 enable_webhooks: false,
+                webhook_listen_addr: "0.0.0.0:8088".to_string(),
+                webhook_auth_header: None,
                 rate_limit_per_second: 100,
             },
             birdeye: BirdeyeConfig {
@@ -1144,6 +1391,14 @@ mod tests {
                 enable_contract_verification: false,
                 require_social_links: false,
                 min_trading_volume_24h: None,
+                geyser_endpoint: None,
+                geyser_x_token: None,
+                dedup_ttl_seconds: 3600,
+                shared_dedup: false,
+                filter_rules_path: None,
+                parse_concurrency: 8,
+                enable_block_scan: false,
+                copy_trade: CopyTradeConfig::default(),
             },
             analytics: AnalyticsConfig {
                 enable_metrics: true,
@@ -1159,8 +1414,12 @@ mod tests {
                 enable_weekly_reports: false,
                 enable_monthly_reports: false,
                 report_delivery_method: None,
+                report_output_dir: None,
+                report_webhook_url: None,
                 enable_audit_reports: false,
                 audit_report_frequency: None,
+                audit_archive_dir: "audit_archives".to_string(),
+                enable_filter_attribution: false,
             },
             monitoring: MonitoringConfig {
                 enable_health_checks: true,
@@ -1184,6 +1443,7 @@ mod tests {
                 enable_audit_logging: true,
                 audit_log_retention_days: 365,
                 enable_2fa: false,
+                two_factor_withdrawal_threshold_sol: Decimal::ONE,
                 enable_ip_whitelisting: false,
                 allowed_ip_ranges: vec![],
                 enable_rate_limiting: false,
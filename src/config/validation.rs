@@ -150,6 +150,61 @@ impl ConfigValidator {
             self.add_warning(result, "High retry count may cause delays");
         }
 
+        for (i, url) in config.sender_urls.iter().enumerate() {
+            if let Err(e) = validation::validate_url(url) {
+                self.add_error(result, format!("Sender URL {}: {}", i, e))?;
+            }
+        }
+
+        if let Some(ref websocket_url) = config.websocket_url {
+            if let Err(e) = validation::validate_url(websocket_url) {
+                self.add_error(result, format!("Solana WebSocket URL: {}", e))?;
+            }
+        }
+
+        if config.rebroadcast_interval_ms == 0 {
+            self.add_error(result, "Rebroadcast interval must be greater than 0".to_string())?;
+        }
+
+        if config.blockhash_refresh_ms == 0 {
+            self.add_error(result, "Blockhash refresh interval must be greater than 0".to_string())?;
+        }
+
+        for account in &config.nonce_accounts {
+            if let Err(e) = validation::validate_solana_address(account) {
+                self.add_error(result, format!("Nonce account {}: {}", account, e))?;
+            }
+        }
+
+        if let Err(e) = validation::validate_solana_address(&config.sol_usd_pool) {
+            self.add_error(result, format!("SOL/USD pool: {}", e))?;
+        }
+
+        if config.jito.enabled {
+            if config.jito.block_engine_urls.is_empty() {
+                self.add_error(result, "Jito enabled but no block-engine URLs configured".to_string())?;
+            }
+            for (i, url) in config.jito.block_engine_urls.iter().enumerate() {
+                if let Err(e) = validation::validate_url(url) {
+                    self.add_error(result, format!("Jito block-engine URL {}: {}", i, e))?;
+                }
+            }
+            for account in &config.jito.tip_accounts {
+                if let Err(e) = validation::validate_solana_address(account) {
+                    self.add_error(result, format!("Jito tip account: {}", e))?;
+                }
+            }
+            if config.jito.min_tip_lamports > config.jito.max_tip_lamports {
+                self.add_error(result, "Jito min tip cannot exceed max tip".to_string())?;
+            }
+            if config.jito.tip_multiplier <= 0.0 {
+                self.add_error(result, "Jito tip multiplier must be positive".to_string())?;
+            }
+            if config.jito.max_bundle_slots == 0 {
+                self.add_error(result, "Jito max bundle slots must be greater than 0".to_string())?;
+            }
+        }
+
         // Validate commitment level
         match config.commitment.as_str() {
             "processed" | "confirmed" | "finalized" => {},
@@ -173,6 +228,10 @@ impl ConfigValidator {
         validation::validate_url(&config.base_url)
             .map_err(|e| self.add_error(result, format!("Helius base URL: {}", e)))?;
 
+        if let Err(e) = validation::validate_url(&config.api_url) {
+            self.add_error(result, format!("Helius API URL: {}", e))?;
+        }
+
         // Validate webhook URL if provided
         if let Some(ref webhook_url) = config.webhook_url {
             validation::validate_url(webhook_url)
@@ -193,6 +252,14 @@ impl ConfigValidator {
             self.add_error(result, "Webhooks enabled but no webhook URL provided")?;
         }
 
+        if config.enable_webhooks && config.webhook_auth_header.as_deref().map_or(true, str::is_empty) {
+            self.add_error(result, "Webhooks enabled but no webhook auth header provided".to_string())?;
+        }
+
+        if config.webhook_listen_addr.parse::<std::net::SocketAddr>().is_err() {
+            self.add_error(result, format!("Invalid Helius webhook listen address: {}", config.webhook_listen_addr))?;
+        }
+
         Ok(())
     }
 
@@ -396,6 +463,12 @@ impl ConfigValidator {
         validation::trading::validate_timeout_ms(config.trade_execution_timeout_ms)
             .map_err(|e| self.add_error(result, format!("Trade execution timeout: {}", e)))?;
 
+        if config.max_priority_fee_sol.is_sign_negative() {
+            self.add_error(result, "Max priority fee cannot be negative".to_string())?;
+        } else if config.max_priority_fee_sol > config.max_position_size_sol {
+            self.add_warning(result, "Max priority fee exceeds max position size");
+        }
+
         // Validate simulation settings
         if config.simulation_mode {
             if let Some(virtual_capital) = config.virtual_capital_sol {
@@ -582,6 +655,45 @@ impl ConfigValidator {
             }
         }
 
+        // Validate Geyser endpoint
+        if let Some(endpoint) = &config.geyser_endpoint {
+            if let Err(e) = validation::validate_url(endpoint) {
+                self.add_error(result, format!("Geyser endpoint: {}", e))?;
+            }
+        }
+
+        // Validate filter rules file
+        if let Some(path) = &config.filter_rules_path {
+            if !std::path::Path::new(path).exists() {
+                self.add_error(result, format!("Filter rules file not found: {}", path))?;
+            }
+        }
+
+        // Validate pipeline concurrency
+        if config.parse_concurrency == 0 {
+            self.add_error(result, "Parse concurrency cannot be zero".to_string())?;
+        }
+
+        // Validate copy-trade wallets
+        for wallet in &config.copy_trade.wallets {
+            if let Err(e) = validation::validate_solana_address(&wallet.address) {
+                self.add_error(result, format!("Tracked wallet {}: {}", wallet.address, e))?;
+            }
+
+            if wallet.mirror_ratio <= rust_decimal::Decimal::ZERO {
+                self.add_error(result, format!("Tracked wallet {}: mirror ratio must be positive", wallet.address))?;
+            }
+        }
+
+        if config.copy_trade.mirror_delay_ms >= config.copy_trade.mirror_window_ms {
+            self.add_error(result, "Copy-trade mirror delay must be shorter than the mirror window".to_string())?;
+        }
+
+        // Validate deduplication window
+        if config.dedup_ttl_seconds == 0 {
+            self.add_error(result, "Dedup TTL cannot be zero".to_string())?;
+        }
+
         // Logic validation
         if !config.enable_real_time_scanning && config.scan_interval_ms < 1000 {
             self.add_warning(result, "Fast scanning disabled but short interval set");
@@ -625,8 +737,36 @@ impl ConfigValidator {
         // Validate delivery method
         if let Some(ref method) = config.report_delivery_method {
             match method.as_str() {
-                "telegram" | "email" | "webhook" => {},
-                _ => self.add_warning(result, "Unknown report delivery method"),
+                "telegram" => {},
+                "file" => {
+                    if config.report_output_dir.is_none() {
+                        self.add_error(result, "File report delivery requires report_output_dir".to_string())?;
+                    }
+                }
+                "webhook" => {
+                    match config.report_webhook_url {
+                        Some(ref url) => {
+                            if Url::parse(url).is_err() {
+                                self.add_error(result, format!("Invalid report webhook URL: {}", url))?;
+                            }
+                        }
+                        None => self.add_error(result, "Webhook report delivery requires report_webhook_url".to_string())?,
+                    }
+                }
+                other => self.add_error(result, format!("Unsupported report delivery method: {}", other))?,
+            }
+        }
+
+        // Validate audit reports
+        if config.enable_audit_reports {
+            if let Some(ref frequency) = config.audit_report_frequency {
+                if !matches!(frequency.as_str(), "daily" | "weekly" | "monthly") {
+                    self.add_error(result, "Audit report frequency must be daily, weekly or monthly".to_string())?;
+                }
+            }
+
+            if config.audit_archive_dir.is_empty() {
+                self.add_error(result, "Audit reports require audit_archive_dir".to_string())?;
             }
         }
 
@@ -731,6 +871,11 @@ impl ConfigValidator {
             }
         }
 
+        // Validate 2FA withdrawal threshold
+        if config.two_factor_withdrawal_threshold_sol < rust_decimal::Decimal::ZERO {
+            self.add_error(result, "2FA withdrawal threshold cannot be negative".to_string())?;
+        }
+
         // Validate encryption algorithm
         match config.encryption_algorithm.as_str() {
             "AES-256-GCM" | "AES-256-CBC" | "ChaCha20-Poly1305" => {},
@@ -958,12 +1103,23 @@ mod tests {
                 max_retries: 3,
                 retry_backoff_ms: 1000,
                 commitment: "confirmed".to_string(),
+                max_slot_lag: 10,
+                sender_urls: vec![],
+                websocket_url: None,
+                rebroadcast_interval_ms: 250,
+                jito: super::models::JitoConfig::default(),
+                blockhash_refresh_ms: 400,
+                nonce_accounts: vec![],
+                sol_usd_pool: "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2".to_string(),
             },
             helius: super::models::HeliusConfig {
                 api_key: "test_key_1234567890".to_string(),
                 base_url: "https://mainnet.helius-rpc.com".to_string(),
+                api_url: "https://api.helius.xyz/v0".to_string(),
                 webhook_url: None,
                 enable_webhooks: false,
+                webhook_listen_addr: "0.0.0.0:8088".to_string(),
+                webhook_auth_header: None,
                 rate_limit_per_second: 100,
             },
             birdeye: super::models::BirdeyeConfig {
@@ -1023,6 +1179,7 @@ mod tests {
                 enable_position_limits: false,
                 daily_trade_limit: None,
                 preferred_dex_order: vec![],
+                max_priority_fee_sol: rust_decimal_macros::dec!(0.005),
             },
             risk: super::models::RiskConfig {
                 risk_score_threshold: 7,
@@ -1052,6 +1209,14 @@ mod tests {
                 enable_contract_verification: false,
                 require_social_links: false,
                 min_trading_volume_24h: None,
+                geyser_endpoint: None,
+                geyser_x_token: None,
+                dedup_ttl_seconds: 3600,
+                shared_dedup: false,
+                filter_rules_path: None,
+                parse_concurrency: 8,
+                enable_block_scan: false,
+                copy_trade: super::models::CopyTradeConfig::default(),
             },
             analytics: super::models::AnalyticsConfig {
                 enable_metrics: true,
@@ -1067,8 +1232,12 @@ mod tests {
                 enable_weekly_reports: false,
                 enable_monthly_reports: false,
                 report_delivery_method: Some("telegram".to_string()),
+                report_output_dir: None,
+                report_webhook_url: None,
                 enable_audit_reports: false,
                 audit_report_frequency: None,
+                audit_archive_dir: "audit_archives".to_string(),
+                enable_filter_attribution: false,
             },
             monitoring: super::models::MonitoringConfig {
                 enable_health_checks: true,
@@ -1092,6 +1261,7 @@ mod tests {
                 enable_audit_logging: true,
                 audit_log_retention_days: 365,
                 enable_2fa: false,
+                two_factor_withdrawal_threshold_sol: rust_decimal_macros::dec!(1.0),
                 enable_ip_whitelisting: false,
                 allowed_ip_ranges: vec![],
                 enable_rate_limiting: false,
@@ -1152,6 +1322,30 @@ mod tests {
         assert!(result.errors.iter().any(|e| e.contains("Port conflict")));
     }
 
+    #[test]
+    fn test_unsupported_report_delivery_method() {
+        let mut config = create_test_config();
+        config.analytics.report_delivery_method = Some("email".to_string());
+
+        let validator = ConfigValidator::new();
+        let result = validator.validate(&config).unwrap();
+
+        assert!(!result.is_valid);
+        assert!(result.errors.iter().any(|e| e.contains("Unsupported report delivery method")));
+    }
+
+    #[test]
+    fn test_negative_2fa_withdrawal_threshold() {
+        let mut config = create_test_config();
+        config.security.two_factor_withdrawal_threshold_sol = rust_decimal::Decimal::NEGATIVE_ONE;
+
+        let validator = ConfigValidator::new();
+        let result = validator.validate(&config).unwrap();
+
+        assert!(!result.is_valid);
+        assert!(result.errors.iter().any(|e| e.contains("2FA withdrawal threshold")));
+    }
+
     #[test]
     fn test_production_security_validation() {
         let mut config = create_test_config();
@@ -214,7 +214,7 @@ impl AppError {
     }
 
     /// Create a new database error
-    pub fn database<S: Into<String>>(message: S, operation: S) -> Self {
+    pub fn database<M: Into<String>, O: Into<String>>(message: M, operation: O) -> Self {
         Self::Database {
             message: message.into(),
             operation: operation.into(),
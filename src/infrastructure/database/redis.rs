@@ -272,6 +272,41 @@ impl RedisService {
         Ok(())
     }
 
+    /// Set a key only if it does not exist yet (`SET NX PX`)
+    ///
+    /// Returns whether the key was set.
+    #[instrument(skip(self, value))]
+    pub async fn set_if_absent<V>(&self, key: &str, value: V, ttl: Duration) -> AppResult<bool>
+    where
+        V: redis::ToRedisArgs + Send + Sync,
+    {
+        let full_key = self.build_key(key);
+        let mut conn = self.pool.write().await;
+        let start_time = std::time::Instant::now();
+
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(&full_key)
+            .arg(value)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis() as u64)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|e| AppError::database(
+                format!("Redis SET NX failed for key '{}': {}", full_key, e),
+                "set_if_absent"
+            ))?;
+
+        let operation_time = start_time.elapsed();
+        let was_set = reply.is_some();
+
+        self.update_metrics("SETNX", operation_time, true).await;
+        debug!("📝 Redis SET NX: {} = {} (TTL: {:?}, {}ms)",
+               full_key, was_set, ttl, operation_time.as_millis());
+
+        Ok(was_set)
+    }
+
     /// Get a value by key
     #[instrument(skip(self))]
     pub async fn get<V>(&self, key: &str) -> AppResult<Option<V>>
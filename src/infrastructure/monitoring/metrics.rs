@@ -37,6 +37,8 @@ impl MetricsService {
         collector.register_counter("sniper_bot_requests_total", "Total number of requests");
         collector.register_counter("sniper_bot_trades_total", "Total number of trades executed");
         collector.register_counter("sniper_bot_errors_total", "Total number of errors");
+        collector.register_counter("sniper_bot_birdeye_requests_total", "Total number of Birdeye API requests");
+        collector.register_counter("sniper_bot_birdeye_cache_hits_total", "Birdeye lookups answered from the cache");
 
         collector.register_histogram("sniper_bot_request_duration_seconds", "Request duration in seconds");
         collector.register_histogram("sniper_bot_trade_execution_duration_seconds", "Trade execution duration in seconds");
@@ -46,6 +48,15 @@ impl MetricsService {
         collector.register_gauge("sniper_bot_open_positions", "Number of open positions");
         collector.register_gauge("sniper_bot_wallet_balance_sol", "Wallet balance in SOL");
         collector.register_gauge("sniper_bot_uptime_seconds", "Application uptime in seconds");
+        collector.register_gauge("sniper_bot_realized_pnl_sol", "Realized PnL in SOL before fees");
+        collector.register_gauge("sniper_bot_unrealized_pnl_sol", "Unrealized PnL in SOL on open positions");
+        collector.register_gauge("sniper_bot_fees_sol", "Total fees paid in SOL");
+        collector.register_gauge("sniper_bot_net_pnl_sol", "Net PnL in SOL after fees");
+        collector.register_gauge("sniper_bot_equity_sol", "Session equity in SOL");
+        collector.register_gauge("sniper_bot_drawdown_percent", "Current drawdown from peak equity in percent");
+        collector.register_gauge("sniper_bot_max_drawdown_percent", "Maximum peak-to-trough drawdown in percent");
+        collector.register_gauge("sniper_bot_wallet_net_pnl_sol", "Net PnL in SOL per trading wallet");
+        collector.register_gauge("sniper_bot_birdeye_requests_remaining", "Birdeye requests left in the current minute");
 
         info!("✅ Metrics service initialized");
 
@@ -7,6 +7,7 @@ use crate::config::models::{SecurityConfig, TelegramConfig};
 use crate::core::result::AppResult;
 use crate::core::error::AppError;
 use super::{SecurityEvent, SecuritySeverity, utils};
+use super::two_factor::TwoFactorError;
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -53,6 +54,10 @@ pub enum AuthenticationError {
     #[error("Two-factor authentication required")]
     TwoFactorRequired,
 
+    /// Two-factor verification failed
+    #[error("Two-factor verification failed: {0}")]
+    TwoFactor(#[from] TwoFactorError),
+
     /// Configuration error
     #[error("Configuration error: {0}")]
     ConfigurationError(String),
@@ -144,6 +149,12 @@ impl AuthenticationService {
 
         // Check if user exists
         if let Some(user) = user_store.get_user_by_telegram_id(telegram_id).await {
+            if !user.is_active {
+                return Err(AuthenticationError::Unauthorized(
+                    "User access revoked".to_string()
+                ));
+            }
+
             // Update user info if changed
             if user.username != username || user.first_name != first_name {
                 user_store.update_user_info(telegram_id, username, first_name).await?;
@@ -183,6 +194,18 @@ impl AuthenticationService {
         self.session_manager.validate_session(token).await
     }
 
+    /// Resolve a session that may still be awaiting two-factor verification
+    #[instrument(skip(self, token))]
+    pub async fn resolve_pending_session(&self, token: &str) -> AuthenticationResult<Session> {
+        self.session_manager.resolve_pending_session(token).await
+    }
+
+    /// Mark a session as two-factor verified
+    #[instrument(skip(self, token))]
+    pub async fn mark_two_factor_verified(&self, token: &str) -> AuthenticationResult<Session> {
+        self.session_manager.mark_two_factor_verified(token).await
+    }
+
     /// Invalidate session
     #[instrument(skip(self, token))]
     pub async fn invalidate_session(&self, token: &str) -> AuthenticationResult<()> {
@@ -210,6 +233,112 @@ impl AuthenticationService {
         self.session_manager.get_user_sessions(user_id).await
     }
 
+    /// List all known users
+    pub async fn list_users(&self) -> Vec<User> {
+        let mut users = self.user_store.read().await.list_users();
+        users.sort_by_key(|u| u.telegram_id);
+        users
+    }
+
+    /// Get a user by Telegram ID
+    pub async fn get_user(&self, telegram_id: i64) -> Option<User> {
+        self.user_store.read().await.get_user_by_telegram_id(telegram_id).await
+    }
+
+    /// Load a persisted user into the store
+    pub async fn restore_user(&self, user: User) {
+        let mut user_store = self.user_store.write().await;
+        user_store.users.insert(user.telegram_id, user);
+    }
+
+    /// Undo a role or access change, restoring the user as it was before
+    ///
+    /// A user that did not exist before the change is removed again.
+    pub async fn rollback_user(&self, telegram_id: i64, previous: Option<User>) {
+        let mut user_store = self.user_store.write().await;
+        match previous {
+            Some(user) => {
+                user_store.users.insert(telegram_id, user);
+            }
+            None => {
+                user_store.users.remove(&telegram_id);
+            }
+        }
+    }
+
+    /// Change a user's role, creating the user if unknown
+    ///
+    /// Sessions carry the role they were created with, so they are
+    /// invalidated whenever the role actually changes and the user
+    /// re-authenticates with the new one.
+    #[instrument(skip(self))]
+    pub async fn set_user_role(
+        &self,
+        telegram_id: i64,
+        role: UserRole,
+    ) -> AuthenticationResult<RoleChange> {
+        let (user, previous_role) = {
+            let mut user_store = self.user_store.write().await;
+
+            let previous_role = match user_store.users.get_mut(&telegram_id) {
+                Some(user) => {
+                    let previous = user.role.clone();
+                    user.role = role.clone();
+                    user.is_active = true;
+                    Some(previous)
+                }
+                None => {
+                    user_store.create_user(telegram_id, None, None, role.clone()).await?;
+                    None
+                }
+            };
+
+            let user = user_store.get_user_by_telegram_id(telegram_id).await
+                .ok_or_else(|| AuthenticationError::UserNotFound(telegram_id.to_string()))?;
+
+            (user, previous_role)
+        };
+
+        let sessions_invalidated = match &previous_role {
+            Some(previous) if *previous != role => {
+                self.session_manager.invalidate_user_sessions(&user.id).await
+            }
+            _ => 0,
+        };
+
+        info!("👤 Role for user {} set to {} ({} sessions invalidated)",
+              user.id, role, sessions_invalidated);
+
+        Ok(RoleChange {
+            user,
+            previous_role,
+            sessions_invalidated,
+        })
+    }
+
+    /// Revoke a user's access and invalidate all of their sessions
+    #[instrument(skip(self))]
+    pub async fn revoke_user(&self, telegram_id: i64) -> AuthenticationResult<RoleChange> {
+        let user = {
+            let mut user_store = self.user_store.write().await;
+            let user = user_store.users.get_mut(&telegram_id)
+                .ok_or_else(|| AuthenticationError::UserNotFound(telegram_id.to_string()))?;
+            user.is_active = false;
+            user.clone()
+        };
+
+        let sessions_invalidated = self.session_manager.invalidate_user_sessions(&user.id).await;
+
+        info!("🚫 Access revoked for user {} ({} sessions invalidated)",
+              user.id, sessions_invalidated);
+
+        Ok(RoleChange {
+            previous_role: Some(user.role.clone()),
+            user,
+            sessions_invalidated,
+        })
+    }
+
     /// Check rate limiting
     async fn check_rate_limit(
         &self,
@@ -379,6 +508,32 @@ impl SessionManager {
         }
     }
 
+    async fn resolve_pending_session(&self, token: &str) -> AuthenticationResult<Session> {
+        let mut sessions = self.sessions.write().await;
+
+        match sessions.get(token) {
+            Some(session) if Utc::now() > session.expires_at => {
+                sessions.remove(token);
+                Err(AuthenticationError::SessionExpired)
+            }
+            Some(session) => Ok(session.clone()),
+            None => Err(AuthenticationError::SessionNotFound),
+        }
+    }
+
+    async fn mark_two_factor_verified(&self, token: &str) -> AuthenticationResult<Session> {
+        let mut sessions = self.sessions.write().await;
+
+        match sessions.get_mut(token) {
+            Some(session) => {
+                session.two_factor_verified = true;
+                session.last_activity = Utc::now();
+                Ok(session.clone())
+            }
+            None => Err(AuthenticationError::SessionNotFound),
+        }
+    }
+
     async fn invalidate_session(&self, token: &str) -> AuthenticationResult<()> {
         let mut sessions = self.sessions.write().await;
 
@@ -398,6 +553,13 @@ impl SessionManager {
             .collect()
     }
 
+    async fn invalidate_user_sessions(&self, user_id: &str) -> usize {
+        let mut sessions = self.sessions.write().await;
+        let before = sessions.len();
+        sessions.retain(|_, session| session.user_id != user_id);
+        before - sessions.len()
+    }
+
     async fn get_active_session_count(&self) -> usize {
         let sessions = self.sessions.read().await;
         sessions.len()
@@ -507,6 +669,44 @@ impl UserRole {
             },
         }
     }
+
+    /// Privilege rank, higher for more permissive roles
+    pub fn rank(&self) -> u8 {
+        match self {
+            UserRole::Admin => 3,
+            UserRole::User => 2,
+            UserRole::Viewer => 1,
+        }
+    }
+
+    /// Whether this role may grant `role` to another user
+    pub fn can_grant(&self, role: &UserRole) -> bool {
+        role.rank() <= self.rank()
+    }
+
+    /// Role name as stored in the database
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            UserRole::Admin => "admin",
+            UserRole::User => "user",
+            UserRole::Viewer => "viewer",
+        }
+    }
+}
+
+impl std::str::FromStr for UserRole {
+    type Err = AuthenticationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "admin" => Ok(UserRole::Admin),
+            "user" => Ok(UserRole::User),
+            "viewer" => Ok(UserRole::Viewer),
+            other => Err(AuthenticationError::ConfigurationError(
+                format!("Unknown role: {}", other)
+            )),
+        }
+    }
 }
 
 impl std::fmt::Display for UserRole {
@@ -559,6 +759,17 @@ pub struct User {
     pub is_active: bool,
 }
 
+/// Result of a role or access change
+#[derive(Debug, Clone)]
+pub struct RoleChange {
+    /// User after the change
+    pub user: User,
+    /// Role before the change, if the user already existed
+    pub previous_role: Option<UserRole>,
+    /// Number of sessions invalidated by the change
+    pub sessions_invalidated: usize,
+}
+
 /// User store
 #[derive(Debug)]
 struct UserStore {
@@ -622,6 +833,10 @@ impl UserStore {
         }
     }
 
+    fn list_users(&self) -> Vec<User> {
+        self.users.values().cloned().collect()
+    }
+
     fn get_user_count(&self) -> usize {
         self.users.len()
     }
@@ -741,6 +956,87 @@ mod tests {
         assert!(viewer.has_permission(&Permission::ViewDashboard));
     }
 
+    #[tokio::test]
+    async fn test_role_change_invalidates_sessions() {
+        let config = ConfigLoader::new().without_env().create_default_config();
+        let service = AuthenticationService::new(&config.security, &config.telegram).unwrap();
+
+        let session = service.authenticate_telegram_user(555, None, None, None).await.unwrap();
+        let token = session.token.value.clone();
+
+        // Setting the current role keeps sessions
+        let change = service.set_user_role(555, UserRole::User).await.unwrap();
+        assert_eq!(change.sessions_invalidated, 0);
+        assert!(service.validate_session(&token).await.is_ok());
+
+        // Promotion invalidates them so the new role takes effect
+        let change = service.set_user_role(555, UserRole::Admin).await.unwrap();
+        assert_eq!(change.previous_role, Some(UserRole::User));
+        assert_eq!(change.sessions_invalidated, 1);
+        assert!(matches!(
+            service.validate_session(&token).await,
+            Err(AuthenticationError::SessionNotFound)
+        ));
+
+        // So does demotion
+        let session = service.authenticate_telegram_user(555, None, None, None).await.unwrap();
+        let change = service.set_user_role(555, UserRole::Viewer).await.unwrap();
+        assert_eq!(change.sessions_invalidated, 1);
+        assert!(matches!(
+            service.validate_session(&session.token.value).await,
+            Err(AuthenticationError::SessionNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_revoked_user_cannot_authenticate() {
+        let config = ConfigLoader::new().without_env().create_default_config();
+        let service = AuthenticationService::new(&config.security, &config.telegram).unwrap();
+
+        service.authenticate_telegram_user(777, None, None, None).await.unwrap();
+        let change = service.revoke_user(777).await.unwrap();
+        assert_eq!(change.sessions_invalidated, 1);
+        assert!(!change.user.is_active);
+
+        let result = service.authenticate_telegram_user(777, None, None, None).await;
+        assert!(matches!(result, Err(AuthenticationError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rollback_user_restores_previous_state() {
+        let config = ConfigLoader::new().without_env().create_default_config();
+        let service = AuthenticationService::new(&config.security, &config.telegram).unwrap();
+
+        service.authenticate_telegram_user(888, None, None, None).await.unwrap();
+        let previous = service.get_user(888).await;
+        service.revoke_user(888).await.unwrap();
+
+        service.rollback_user(888, previous).await;
+        assert!(service.get_user(888).await.unwrap().is_active);
+        assert!(service.authenticate_telegram_user(888, None, None, None).await.is_ok());
+
+        // A user created by the change is removed again
+        service.set_user_role(999, UserRole::Viewer).await.unwrap();
+        service.rollback_user(999, None).await;
+        assert!(service.get_user(999).await.is_none());
+    }
+
+    #[test]
+    fn test_user_role_parsing() {
+        assert_eq!("admin".parse::<UserRole>().unwrap(), UserRole::Admin);
+        assert_eq!("Viewer".parse::<UserRole>().unwrap(), UserRole::Viewer);
+        assert!("root".parse::<UserRole>().is_err());
+        assert!(UserRole::Viewer.rank() < UserRole::User.rank());
+    }
+
+    #[test]
+    fn test_roles_cannot_grant_above_their_own() {
+        assert!(UserRole::Admin.can_grant(&UserRole::Admin));
+        assert!(UserRole::User.can_grant(&UserRole::Viewer));
+        assert!(!UserRole::User.can_grant(&UserRole::Admin));
+        assert!(!UserRole::Viewer.can_grant(&UserRole::User));
+    }
+
     #[test]
     fn test_session_token_generation() {
         let token1 = SessionToken::generate();
@@ -750,6 +1046,25 @@ mod tests {
         assert_ne!(token1.value, token2.value); // Should be unique
     }
 
+    #[tokio::test]
+    async fn test_two_factor_session_flow() {
+        let mut config = ConfigLoader::new().without_env().create_default_config();
+        config.security.enable_2fa = true;
+        let service = AuthenticationService::new(&config.security, &config.telegram).unwrap();
+
+        let session = service.authenticate_telegram_user(42, None, None, None).await.unwrap();
+        let token = session.token.value.clone();
+
+        assert!(matches!(
+            service.validate_session(&token).await,
+            Err(AuthenticationError::TwoFactorRequired)
+        ));
+        assert!(service.resolve_pending_session(&token).await.is_ok());
+
+        service.mark_two_factor_verified(&token).await.unwrap();
+        assert!(service.validate_session(&token).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_rate_limiting() {
         let mut config = ConfigLoader::new().without_env().create_default_config();
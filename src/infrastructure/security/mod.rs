@@ -5,16 +5,22 @@
 
 pub mod authentication;
 pub mod encryption;
+pub mod two_factor;
 
 // Re-export commonly used types
 pub use authentication::{
     AuthenticationService, AuthenticationResult, AuthenticationError,
     SessionManager, Session, SessionToken, UserRole, Permission,
+    User, RoleChange,
 };
 pub use encryption::{
     EncryptionService, EncryptionResult, EncryptionError,
     EncryptedData, KeyManager, KeyDerivation,
 };
+pub use two_factor::{
+    TwoFactorService, TwoFactorResult, TwoFactorError,
+    SensitiveAction, TotpProvisioning, TotpEnrollment,
+};
 
 use crate::config::AppConfig;
 use crate::core::result::AppResult;
@@ -30,6 +36,8 @@ pub struct SecurityService {
     pub encryption: EncryptionService,
     /// Authentication service
     pub authentication: AuthenticationService,
+    /// Two-factor authentication service
+    pub two_factor: Arc<TwoFactorService>,
     /// Security configuration
     config: Arc<AppConfig>,
     /// Security event logger
@@ -48,6 +56,9 @@ impl SecurityService {
         // Initialize authentication service
         let authentication = AuthenticationService::new(&config.security, &config.telegram)?;
 
+        // Initialize two-factor service
+        let two_factor = Arc::new(TwoFactorService::new(&config.security)?);
+
         // Initialize event logger
         let event_logger = Arc::new(SecurityEventLogger::new());
 
@@ -56,6 +67,7 @@ impl SecurityService {
         Ok(Self {
             encryption,
             authentication,
+            two_factor,
             config: Arc::new(config.clone()),
             event_logger,
         })
@@ -66,6 +78,88 @@ impl SecurityService {
         self.event_logger.log_event(event).await;
     }
 
+    /// Start TOTP enrollment for the session's user
+    ///
+    /// The provisioning URI is returned once and should be delivered to the
+    /// user immediately; it cannot be retrieved again.
+    pub async fn begin_two_factor_enrollment(
+        &self,
+        token: &str,
+        account_name: &str,
+    ) -> AuthenticationResult<TotpProvisioning> {
+        let session = self.authentication.resolve_pending_session(token).await?;
+        let provisioning = self.two_factor.begin_enrollment(&session.user_id, account_name).await?;
+
+        self.log_security_event(SecurityEvent::ConfigurationChanged {
+            user_id: session.user_id,
+            field: "two_factor".to_string(),
+            old_value: "disabled".to_string(),
+            new_value: "pending".to_string(),
+        }).await;
+
+        Ok(provisioning)
+    }
+
+    /// Verify a TOTP code and mark the session as two-factor verified
+    pub async fn verify_two_factor(&self, token: &str, code: &str) -> AuthenticationResult<Session> {
+        let session = self.authentication.resolve_pending_session(token).await?;
+        self.verify_code_for(&session, code).await?;
+        self.authentication.mark_two_factor_verified(token).await
+    }
+
+    /// Authorize a sensitive action, requiring a fresh TOTP code where needed
+    pub async fn authorize_sensitive_action(
+        &self,
+        session: &Session,
+        action: &SensitiveAction,
+        code: Option<&str>,
+    ) -> AuthenticationResult<()> {
+        if let SensitiveAction::Permission(permission) = action {
+            self.authentication.check_permission(session, permission.clone()).await?;
+        }
+
+        if !self.config.security.enable_2fa || !self.two_factor.requires_fresh_code(action) {
+            return Ok(());
+        }
+
+        if !self.two_factor.is_enrolled(&session.user_id).await {
+            self.log_security_event(SecurityEvent::AccessDenied {
+                user_id: Some(session.user_id.clone()),
+                resource: format!("{:?}", action),
+                reason: "Two-factor enrollment required".to_string(),
+            }).await;
+            return Err(AuthenticationError::TwoFactorRequired);
+        }
+
+        match code {
+            Some(code) => self.verify_code_for(session, code).await,
+            None => Err(AuthenticationError::TwoFactorRequired),
+        }
+    }
+
+    /// Verify a code for a session's user and record the outcome
+    async fn verify_code_for(&self, session: &Session, code: &str) -> AuthenticationResult<()> {
+        let result = self.two_factor.verify_code(&session.user_id, code).await;
+
+        if let Err(TwoFactorError::CodeReused) = result {
+            self.log_security_event(SecurityEvent::SecurityViolation {
+                violation_type: "totp_replay".to_string(),
+                details: format!("Reused TOTP code for user {}", session.user_id),
+                severity: SecuritySeverity::High,
+            }).await;
+        }
+
+        self.log_security_event(SecurityEvent::AuthenticationAttempt {
+            user_id: session.user_id.clone(),
+            success: result.is_ok(),
+            method: "totp".to_string(),
+            ip_address: session.ip_address.clone(),
+            timestamp: chrono::Utc::now(),
+        }).await;
+
+        result.map_err(AuthenticationError::from)
+    }
+
     /// Validate security configuration
     pub fn validate_configuration(&self) -> AppResult<()> {
         // Check encryption key strength
@@ -145,6 +239,7 @@ impl SecurityService {
         // Clear sensitive data from memory
         self.encryption.clear_sensitive_data().await?;
         self.authentication.clear_sessions().await?;
+        self.two_factor.clear().await;
 
         info!("✅ Security services shut down");
         Ok(())
@@ -388,12 +483,28 @@ pub mod constants {
 
     /// Account lockout duration in minutes
     pub const LOCKOUT_DURATION_MINUTES: u64 = 30;
+
+    /// TOTP issuer shown in authenticator apps
+    pub const TOTP_ISSUER: &str = "SolanaSniperBot";
+
+    /// TOTP time step in seconds
+    pub const TOTP_STEP_SECONDS: u64 = 30;
+
+    /// TOTP code length
+    pub const TOTP_DIGITS: u32 = 6;
+
+    /// TOTP shared secret length in bytes
+    pub const TOTP_SECRET_LENGTH: usize = 20;
+
+    /// Accepted clock skew in time steps on either side
+    pub const TOTP_ALLOWED_SKEW: u64 = 1;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::ConfigLoader;
+    use rust_decimal::Decimal;
 
     #[tokio::test]
     async fn test_security_service_creation() {
@@ -403,6 +514,40 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_sensitive_action_requires_code() {
+        let mut config = ConfigLoader::new().without_env().create_default_config();
+        config.security.encryption_key = base64::Engine::encode(
+            &base64::engine::general_purpose::STANDARD, [5u8; 32]
+        );
+        config.security.enable_2fa = true;
+        let service = SecurityService::new(&config).unwrap();
+
+        let session = service.authentication
+            .authenticate_telegram_user(config.telegram.admin_chat_id, None, None, None)
+            .await
+            .unwrap();
+
+        for permission in [Permission::ManageWallets, Permission::ConfigureSettings, Permission::ManageUsers] {
+            let action = SensitiveAction::Permission(permission);
+            let result = service.authorize_sensitive_action(&session, &action, None).await;
+            assert!(matches!(result, Err(AuthenticationError::TwoFactorRequired)));
+        }
+
+        // Withdrawals need a code only above the configured threshold
+        let threshold = config.security.two_factor_withdrawal_threshold_sol;
+        let action = SensitiveAction::Withdrawal { amount_sol: threshold + Decimal::ONE };
+        let result = service.authorize_sensitive_action(&session, &action, None).await;
+        assert!(matches!(result, Err(AuthenticationError::TwoFactorRequired)));
+
+        let action = SensitiveAction::Withdrawal { amount_sol: threshold };
+        assert!(service.authorize_sensitive_action(&session, &action, None).await.is_ok());
+
+        // Non-sensitive actions only need the permission
+        let action = SensitiveAction::Permission(Permission::ViewDashboard);
+        assert!(service.authorize_sensitive_action(&session, &action, None).await.is_ok());
+    }
+
     #[test]
     fn test_secure_token_generation() {
         let token1 = utils::generate_secure_token(32);
@@ -0,0 +1,583 @@
+//! Two-factor authentication (TOTP) implementation
+//!
+//! This module provides RFC 6238 time-based one-time passwords for
+//! Telegram users, including enrollment, encrypted secret storage,
+//! and replay-protected code verification for sensitive actions.
+//! Enrollments are persisted to the TOTP columns of `telegram_users`
+//! once a database is attached.
+
+use crate::config::models::SecurityConfig;
+use crate::core::result::AppResult;
+use crate::infrastructure::database::PostgresService;
+use super::authentication::Permission;
+use super::encryption::{EncryptionService, EncryptedData};
+use super::constants;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use chrono::{DateTime, Utc};
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use sqlx::Row;
+use tracing::{debug, info, warn, instrument};
+
+/// Result type for two-factor operations
+pub type TwoFactorResult<T> = Result<T, TwoFactorError>;
+
+/// Two-factor authentication errors
+#[derive(Debug, thiserror::Error)]
+pub enum TwoFactorError {
+    /// User has no TOTP enrollment
+    #[error("Two-factor authentication not enrolled for user: {0}")]
+    NotEnrolled(String),
+
+    /// User has a confirmed enrollment
+    #[error("Two-factor authentication already enrolled for user: {0}")]
+    AlreadyEnrolled(String),
+
+    /// Code did not match any accepted time step
+    #[error("Invalid two-factor code")]
+    InvalidCode,
+
+    /// Code was already used
+    #[error("Two-factor code already used")]
+    CodeReused,
+
+    /// Secret could not be stored or loaded
+    #[error("Two-factor secret storage error: {0}")]
+    Storage(String),
+}
+
+/// Action that may require a fresh TOTP code
+#[derive(Debug, Clone, PartialEq)]
+pub enum SensitiveAction {
+    /// Command gated by a permission
+    Permission(Permission),
+    /// Withdrawal of SOL from a managed wallet
+    Withdrawal {
+        /// Withdrawal amount in SOL
+        amount_sol: Decimal,
+    },
+}
+
+/// Provisioning details delivered to the user once at enrollment
+#[derive(Debug, Clone)]
+pub struct TotpProvisioning {
+    /// Base32 encoded shared secret
+    pub secret: String,
+    /// `otpauth://` URI suitable for QR rendering
+    pub otpauth_uri: String,
+}
+
+/// Stored TOTP enrollment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotpEnrollment {
+    /// User ID
+    pub user_id: String,
+    /// Encrypted shared secret
+    pub secret: EncryptedData,
+    /// Enrollment timestamp
+    pub created_at: DateTime<Utc>,
+    /// Set once the user has entered their first valid code
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// Last accepted time step, used for replay protection
+    pub last_used_step: Option<u64>,
+}
+
+/// TOTP two-factor authentication service
+#[derive(Debug)]
+pub struct TwoFactorService {
+    /// Encryption service for secret storage
+    encryption: EncryptionService,
+    /// Enrollments by user ID
+    enrollments: Arc<RwLock<HashMap<String, TotpEnrollment>>>,
+    /// Database enrollments are persisted to, once attached
+    postgres: Arc<RwLock<Option<PostgresService>>>,
+    /// Withdrawals above this amount require a fresh code
+    withdrawal_threshold_sol: Decimal,
+    /// Secure random generator
+    rng: SystemRandom,
+}
+
+impl TwoFactorService {
+    /// Create a new two-factor service
+    #[instrument(skip(config))]
+    pub fn new(config: &SecurityConfig) -> AppResult<Self> {
+        info!("🔑 Initializing two-factor authentication service");
+
+        let encryption = EncryptionService::new(config)?;
+
+        info!("✅ Two-factor authentication service initialized");
+
+        Ok(Self {
+            encryption,
+            enrollments: Arc::new(RwLock::new(HashMap::new())),
+            postgres: Arc::new(RwLock::new(None)),
+            withdrawal_threshold_sol: config.two_factor_withdrawal_threshold_sol,
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Check whether an action requires a fresh TOTP code
+    pub fn requires_fresh_code(&self, action: &SensitiveAction) -> bool {
+        match action {
+            SensitiveAction::Permission(permission) => matches!(
+                permission,
+                Permission::ManageWallets | Permission::ConfigureSettings | Permission::ManageUsers
+            ),
+            SensitiveAction::Withdrawal { amount_sol } => *amount_sol > self.withdrawal_threshold_sol,
+        }
+    }
+
+    /// Persist enrollments to the database from now on
+    pub async fn attach_database(&self, postgres: PostgresService) {
+        *self.postgres.write().await = Some(postgres);
+    }
+
+    /// Load persisted enrollments from `telegram_users`
+    #[instrument(skip(self))]
+    pub async fn load_persisted_enrollments(&self) -> TwoFactorResult<usize> {
+        let postgres = self.postgres.read().await.clone()
+            .ok_or_else(|| TwoFactorError::Storage("No database attached".to_string()))?;
+
+        let rows = sqlx::query(
+            "SELECT telegram_id, totp_secret, totp_enrolled_at, totp_confirmed_at, totp_last_used_step
+             FROM telegram_users
+             WHERE totp_secret IS NOT NULL"
+        )
+            .fetch_all(postgres.pool())
+            .await
+            .map_err(|e| TwoFactorError::Storage(format!("Failed to load TOTP enrollments: {}", e)))?;
+
+        let mut enrollments = self.enrollments.write().await;
+        for row in &rows {
+            let enrollment = enrollment_from_row(row)?;
+            enrollments.insert(enrollment.user_id.clone(), enrollment);
+        }
+
+        info!("🔑 Loaded {} persisted TOTP enrollments", rows.len());
+        Ok(rows.len())
+    }
+
+    /// Start TOTP enrollment for a user
+    ///
+    /// The returned provisioning data is only ever produced once per enrollment;
+    /// the secret is kept encrypted afterwards and cannot be retrieved again.
+    /// A pending enrollment that was never confirmed is replaced, so a user
+    /// who lost the provisioning message can start over.
+    #[instrument(skip(self))]
+    pub async fn begin_enrollment(
+        &self,
+        user_id: &str,
+        account_name: &str,
+    ) -> TwoFactorResult<TotpProvisioning> {
+        let mut enrollments = self.enrollments.write().await;
+
+        if enrollments.get(user_id).is_some_and(|e| e.confirmed_at.is_some()) {
+            return Err(TwoFactorError::AlreadyEnrolled(user_id.to_string()));
+        }
+
+        let mut secret = [0u8; constants::TOTP_SECRET_LENGTH];
+        self.rng.fill(&mut secret)
+            .map_err(|_| TwoFactorError::Storage("Failed to generate secret".to_string()))?;
+
+        let encrypted = self.encryption.encrypt(&secret).await
+            .map_err(|e| TwoFactorError::Storage(e.to_string()))?;
+
+        let encoded = base32_encode(&secret);
+        let otpauth_uri = build_otpauth_uri(&encoded, account_name);
+
+        let enrollment = TotpEnrollment {
+            user_id: user_id.to_string(),
+            secret: encrypted,
+            created_at: Utc::now(),
+            confirmed_at: None,
+            last_used_step: None,
+        };
+        self.persist(&enrollment).await?;
+        enrollments.insert(user_id.to_string(), enrollment);
+
+        info!("🔑 TOTP enrollment started for user: {}", user_id);
+
+        Ok(TotpProvisioning {
+            secret: encoded,
+            otpauth_uri,
+        })
+    }
+
+    /// Verify a TOTP code for a user
+    ///
+    /// A successful verification confirms a pending enrollment. Each time step
+    /// is accepted at most once, so a code cannot be replayed.
+    #[instrument(skip(self, code))]
+    pub async fn verify_code(&self, user_id: &str, code: &str) -> TwoFactorResult<()> {
+        self.verify_code_at(user_id, code, Utc::now().timestamp()).await
+    }
+
+    async fn verify_code_at(&self, user_id: &str, code: &str, unix_time: i64) -> TwoFactorResult<()> {
+        // Hold the write lock across verification so concurrent submissions
+        // of the same code cannot both succeed
+        let mut enrollments = self.enrollments.write().await;
+
+        let enrollment = enrollments.get(user_id)
+            .ok_or_else(|| TwoFactorError::NotEnrolled(user_id.to_string()))?;
+
+        let code = code.trim();
+        if code.len() != constants::TOTP_DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+            return Err(TwoFactorError::InvalidCode);
+        }
+
+        let secret = self.encryption.decrypt(&enrollment.secret).await
+            .map_err(|e| TwoFactorError::Storage(e.to_string()))?;
+
+        let current_step = (unix_time.max(0) as u64) / constants::TOTP_STEP_SECONDS;
+        let skew = constants::TOTP_ALLOWED_SKEW;
+
+        let matched_step = (current_step.saturating_sub(skew)..=current_step + skew)
+            .find(|step| {
+                let expected = format_code(hotp(&secret, *step));
+                crate::utils::crypto::constant_time_compare(&expected, code)
+            })
+            .ok_or(TwoFactorError::InvalidCode)?;
+
+        if enrollment.last_used_step.is_some_and(|last| matched_step <= last) {
+            warn!("🚫 TOTP code replay rejected for user: {}", user_id);
+            return Err(TwoFactorError::CodeReused);
+        }
+
+        // The step is stored before the code is accepted, so a restart
+        // cannot reopen it for replay
+        let mut updated = enrollment.clone();
+        updated.last_used_step = Some(matched_step);
+        let confirmed = updated.confirmed_at.is_none();
+        updated.confirmed_at.get_or_insert_with(Utc::now);
+        self.persist(&updated).await?;
+        enrollments.insert(user_id.to_string(), updated);
+
+        if confirmed {
+            info!("✅ TOTP enrollment confirmed for user: {}", user_id);
+        }
+
+        debug!("TOTP code accepted for user: {}", user_id);
+        Ok(())
+    }
+
+    /// Check whether a user has a confirmed enrollment
+    pub async fn is_enrolled(&self, user_id: &str) -> bool {
+        self.enrollments.read().await
+            .get(user_id)
+            .is_some_and(|e| e.confirmed_at.is_some())
+    }
+
+    /// Remove a user's enrollment so they can enroll again
+    pub async fn reset_enrollment(&self, user_id: &str) -> TwoFactorResult<()> {
+        let mut enrollments = self.enrollments.write().await;
+
+        if !enrollments.contains_key(user_id) {
+            return Err(TwoFactorError::NotEnrolled(user_id.to_string()));
+        }
+
+        self.clear_persisted(user_id).await?;
+        enrollments.remove(user_id);
+        info!("🔑 TOTP enrollment reset for user: {}", user_id);
+        Ok(())
+    }
+
+    /// Export an enrollment for persistence
+    pub async fn export_enrollment(&self, user_id: &str) -> Option<TotpEnrollment> {
+        self.enrollments.read().await.get(user_id).cloned()
+    }
+
+    /// Restore a previously persisted enrollment
+    pub async fn restore_enrollment(&self, enrollment: TotpEnrollment) {
+        let mut enrollments = self.enrollments.write().await;
+        enrollments.insert(enrollment.user_id.clone(), enrollment);
+    }
+
+    /// Clear all enrollments from memory
+    pub async fn clear(&self) {
+        self.enrollments.write().await.clear();
+    }
+
+    /// Write an enrollment to the TOTP columns of `telegram_users`
+    async fn persist(&self, enrollment: &TotpEnrollment) -> TwoFactorResult<()> {
+        let Some(postgres) = self.postgres.read().await.clone() else {
+            return Ok(());
+        };
+
+        let telegram_id = telegram_id(&enrollment.user_id)?;
+        let secret = serde_json::to_value(&enrollment.secret)
+            .map_err(|e| TwoFactorError::Storage(e.to_string()))?;
+
+        sqlx::query(
+            "INSERT INTO telegram_users
+                 (telegram_id, totp_secret, totp_enrolled_at, totp_confirmed_at, totp_last_used_step, updated_at)
+             VALUES ($1, $2, $3, $4, $5, NOW())
+             ON CONFLICT (telegram_id) DO UPDATE
+             SET totp_secret = EXCLUDED.totp_secret,
+                 totp_enrolled_at = EXCLUDED.totp_enrolled_at,
+                 totp_confirmed_at = EXCLUDED.totp_confirmed_at,
+                 totp_last_used_step = EXCLUDED.totp_last_used_step,
+                 updated_at = NOW()"
+        )
+            .bind(telegram_id)
+            .bind(secret)
+            .bind(enrollment.created_at)
+            .bind(enrollment.confirmed_at)
+            .bind(enrollment.last_used_step.map(|step| step as i64))
+            .execute(postgres.pool())
+            .await
+            .map_err(|e| TwoFactorError::Storage(format!("Failed to persist TOTP enrollment: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Clear the TOTP columns of a user in `telegram_users`
+    async fn clear_persisted(&self, user_id: &str) -> TwoFactorResult<()> {
+        let Some(postgres) = self.postgres.read().await.clone() else {
+            return Ok(());
+        };
+
+        sqlx::query(
+            "UPDATE telegram_users
+             SET totp_secret = NULL, totp_enrolled_at = NULL, totp_confirmed_at = NULL,
+                 totp_last_used_step = NULL, updated_at = NOW()
+             WHERE telegram_id = $1"
+        )
+            .bind(telegram_id(user_id)?)
+            .execute(postgres.pool())
+            .await
+            .map_err(|e| TwoFactorError::Storage(format!("Failed to reset TOTP enrollment: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+/// Telegram ID of a `user_<telegram_id>` user ID
+fn telegram_id(user_id: &str) -> TwoFactorResult<i64> {
+    user_id.strip_prefix("user_")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| TwoFactorError::Storage(format!("Not a Telegram user ID: {}", user_id)))
+}
+
+/// Build an enrollment from a `telegram_users` row
+fn enrollment_from_row(row: &sqlx::postgres::PgRow) -> TwoFactorResult<TotpEnrollment> {
+    let storage = |e: sqlx::Error| TwoFactorError::Storage(e.to_string());
+
+    let telegram_id: i64 = row.try_get("telegram_id").map_err(storage)?;
+    let secret: serde_json::Value = row.try_get("totp_secret").map_err(storage)?;
+    let created_at: Option<DateTime<Utc>> = row.try_get("totp_enrolled_at").map_err(storage)?;
+    let last_used_step: Option<i64> = row.try_get("totp_last_used_step").map_err(storage)?;
+
+    Ok(TotpEnrollment {
+        user_id: format!("user_{}", telegram_id),
+        secret: serde_json::from_value(secret).map_err(|e| TwoFactorError::Storage(e.to_string()))?,
+        created_at: created_at.unwrap_or_else(Utc::now),
+        confirmed_at: row.try_get("totp_confirmed_at").map_err(storage)?,
+        last_used_step: last_used_step.map(|step| step as u64),
+    })
+}
+
+/// Compute an HOTP value (RFC 4226) for the given counter
+fn hotp(secret: &[u8], counter: u64) -> u32 {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+    let tag = hmac::sign(&key, &counter.to_be_bytes());
+    let digest = tag.as_ref();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = (u32::from(digest[offset]) & 0x7f) << 24
+        | u32::from(digest[offset + 1]) << 16
+        | u32::from(digest[offset + 2]) << 8
+        | u32::from(digest[offset + 3]);
+
+    binary % 10u32.pow(constants::TOTP_DIGITS)
+}
+
+/// Format a code with leading zeros
+fn format_code(value: u32) -> String {
+    format!("{:0width$}", value, width = constants::TOTP_DIGITS as usize)
+}
+
+/// Build an `otpauth://` provisioning URI
+fn build_otpauth_uri(secret: &str, account_name: &str) -> String {
+    let label = format!("{}:{}", constants::TOTP_ISSUER, account_name);
+    format!(
+        "otpauth://totp/{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(&label),
+        secret,
+        percent_encode(constants::TOTP_ISSUER),
+        constants::TOTP_DIGITS,
+        constants::TOTP_STEP_SECONDS,
+    )
+}
+
+/// Percent-encode a URI component
+fn percent_encode(input: &str) -> String {
+    input.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encode bytes as unpadded RFC 4648 base32
+fn base32_encode(data: &[u8]) -> String {
+    let mut output = String::with_capacity((data.len() * 8).div_ceil(5));
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in data {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigLoader;
+    use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+
+    fn create_service() -> TwoFactorService {
+        let mut config = ConfigLoader::new().without_env().create_default_config();
+        config.security.encryption_key = BASE64.encode([7u8; 32]);
+        TwoFactorService::new(&config.security).unwrap()
+    }
+
+    fn current_code(provisioning: &TotpProvisioning, unix_time: i64) -> String {
+        let secret = base32_decode(&provisioning.secret);
+        format_code(hotp(&secret, unix_time as u64 / constants::TOTP_STEP_SECONDS))
+    }
+
+    fn base32_decode(input: &str) -> Vec<u8> {
+        let mut output = Vec::new();
+        let mut buffer: u32 = 0;
+        let mut bits = 0;
+
+        for c in input.bytes() {
+            let value = BASE32_ALPHABET.iter().position(|&a| a == c).unwrap() as u32;
+            buffer = (buffer << 5) | value;
+            bits += 5;
+
+            if bits >= 8 {
+                bits -= 8;
+                output.push((buffer >> bits) as u8);
+            }
+        }
+
+        output
+    }
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        // RFC 6238 Appendix B SHA1 vectors, truncated to 6 digits
+        let secret = b"12345678901234567890";
+        assert_eq!(format_code(hotp(secret, 59 / 30)), "287082");
+        assert_eq!(format_code(hotp(secret, 1111111109 / 30)), "081804");
+        assert_eq!(format_code(hotp(secret, 1234567890 / 30)), "005924");
+    }
+
+    #[test]
+    fn test_base32_encoding() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(base32_decode("MZXW6YTBOI"), b"foobar");
+    }
+
+    #[tokio::test]
+    async fn test_enrollment_delivered_once() {
+        let service = create_service();
+
+        let provisioning = service.begin_enrollment("user_1", "alice").await.unwrap();
+        assert!(provisioning.otpauth_uri.starts_with("otpauth://totp/"));
+        assert!(provisioning.otpauth_uri.contains(&provisioning.secret));
+
+        // Secret must not be stored in plaintext
+        let stored = service.export_enrollment("user_1").await.unwrap();
+        assert!(!stored.secret.data.contains(&provisioning.secret));
+
+        // A pending enrollment can be started over with a new secret
+        let second = service.begin_enrollment("user_1", "alice").await.unwrap();
+        assert_ne!(second.secret, provisioning.secret);
+
+        let now = Utc::now().timestamp();
+        service.verify_code_at("user_1", &current_code(&second, now), now).await.unwrap();
+
+        // A confirmed enrollment cannot
+        let third = service.begin_enrollment("user_1", "alice").await;
+        assert!(matches!(third, Err(TwoFactorError::AlreadyEnrolled(_))));
+    }
+
+    #[test]
+    fn test_telegram_id() {
+        assert_eq!(telegram_id("user_42").unwrap(), 42);
+        assert!(telegram_id("admin").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_code_verification_and_replay() {
+        let service = create_service();
+        let provisioning = service.begin_enrollment("user_1", "alice").await.unwrap();
+        assert!(!service.is_enrolled("user_1").await);
+
+        let now = Utc::now().timestamp();
+        let code = current_code(&provisioning, now);
+
+        assert!(service.verify_code_at("user_1", &code, now).await.is_ok());
+        assert!(service.is_enrolled("user_1").await);
+
+        // Same code cannot be used twice
+        let replay = service.verify_code_at("user_1", &code, now).await;
+        assert!(matches!(replay, Err(TwoFactorError::CodeReused)));
+
+        // Next time step is accepted
+        let next = now + constants::TOTP_STEP_SECONDS as i64;
+        let next_code = current_code(&provisioning, next);
+        assert!(service.verify_code_at("user_1", &next_code, next).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_code_rejected() {
+        let service = create_service();
+        service.begin_enrollment("user_1", "alice").await.unwrap();
+
+        assert!(matches!(
+            service.verify_code("user_1", "abc123").await,
+            Err(TwoFactorError::InvalidCode)
+        ));
+        assert!(matches!(
+            service.verify_code("user_2", "123456").await,
+            Err(TwoFactorError::NotEnrolled(_))
+        ));
+    }
+
+    #[test]
+    fn test_sensitive_actions() {
+        let service = create_service();
+
+        assert!(service.requires_fresh_code(&SensitiveAction::Permission(Permission::ManageWallets)));
+        assert!(service.requires_fresh_code(&SensitiveAction::Permission(Permission::ConfigureSettings)));
+        assert!(service.requires_fresh_code(&SensitiveAction::Permission(Permission::ManageUsers)));
+        assert!(!service.requires_fresh_code(&SensitiveAction::Permission(Permission::ExecuteTrades)));
+
+        let above = Decimal::ONE + Decimal::ONE;
+        assert!(service.requires_fresh_code(&SensitiveAction::Withdrawal { amount_sol: above }));
+        assert!(!service.requires_fresh_code(&SensitiveAction::Withdrawal { amount_sol: Decimal::ONE }));
+    }
+}
@@ -0,0 +1,323 @@
+//! Filter attribution analytics
+//!
+//! This module follows the price of every filtered token, including rejected
+//! ones, for 1h and 24h after evaluation. Each evaluation is classified as a
+//! winner, rug or neutral outcome so missed winners and avoided rugs can be
+//! attributed to the individual filters that flagged the token.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::Row;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn, instrument};
+
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use crate::core::types::TokenAddress;
+use crate::infrastructure::database::PostgresService;
+use super::models::{FilterAttribution, TokenOutcome};
+use super::tracker::PriceSource;
+
+/// Price multiple over the evaluation price that makes a token a winner
+const WINNER_PRICE_MULTIPLE: Decimal = dec!(2);
+
+/// Price drop from the evaluation price, in percent, that makes a token a rug
+const RUG_PRICE_DROP_PERCENT: Decimal = dec!(80);
+
+/// Maximum evaluations priced per tracking pass
+const TRACKING_BATCH_SIZE: i64 = 200;
+
+/// Classify the outcome of a token from its tracked prices
+///
+/// The evaluation price is the baseline, falling back to the 1h price when
+/// the evaluation price is missing or not positive. A token that reached the
+/// winner multiple at either mark is a winner even if it dumped afterwards. A
+/// token is a rug only when its 24h price collapsed; without a 24h price the
+/// outcome is unknown, since a missing price does not mean the pool is gone.
+pub fn classify_outcome(
+    price_sol: Option<Decimal>,
+    price_1h_sol: Option<Decimal>,
+    price_24h_sol: Option<Decimal>,
+) -> TokenOutcome {
+    let is_positive = |p: &Decimal| *p > Decimal::ZERO;
+    let baseline = match price_sol.filter(is_positive).or(price_1h_sol.filter(is_positive)) {
+        Some(price) => price,
+        None => return TokenOutcome::Unknown,
+    };
+
+    let peak = price_1h_sol.max(price_24h_sol).unwrap_or(Decimal::ZERO);
+    if peak >= baseline * WINNER_PRICE_MULTIPLE {
+        return TokenOutcome::Winner;
+    }
+
+    let rug_threshold = baseline * (Decimal::ONE_HUNDRED - RUG_PRICE_DROP_PERCENT) / Decimal::ONE_HUNDRED;
+    match price_24h_sol {
+        Some(price) if price > rug_threshold => TokenOutcome::Neutral,
+        Some(_) => TokenOutcome::Rug,
+        None => TokenOutcome::Unknown,
+    }
+}
+
+/// Background price tracker for filter evaluations
+#[derive(Debug, Clone)]
+pub struct FilterAttributionTracker {
+    /// PostgreSQL service
+    postgres: PostgresService,
+    /// Price source
+    price_source: Arc<dyn PriceSource>,
+    /// Tracker running
+    is_running: Arc<RwLock<bool>>,
+}
+
+impl FilterAttributionTracker {
+    /// Create a new filter attribution tracker
+    pub fn new(postgres: PostgresService, price_source: Arc<dyn PriceSource>) -> Self {
+        Self {
+            postgres,
+            price_source,
+            is_running: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    /// Start the background tracking job
+    pub async fn start(&self, interval: Duration) -> AppResult<()> {
+        {
+            let mut running = self.is_running.write().await;
+            if *running {
+                return Err(AppError::internal("Filter attribution tracker already running"));
+            }
+            *running = true;
+        }
+
+        let tracker = self.clone();
+
+        tokio::spawn(async move {
+            info!("🧪 Filter attribution tracker started");
+
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                ticker.tick().await;
+
+                if !*tracker.is_running.read().await {
+                    break;
+                }
+
+                match tracker.track_pending(Utc::now()).await {
+                    Ok(0) => {}
+                    Ok(count) => debug!("Tracked prices for {} filter evaluations", count),
+                    Err(e) => error!("Filter attribution tracking failed: {}", e),
+                }
+            }
+
+            info!("Filter attribution tracker ended");
+        });
+
+        Ok(())
+    }
+
+    /// Stop the background tracking job
+    pub async fn stop(&self) {
+        *self.is_running.write().await = false;
+    }
+
+    /// Record 1h and 24h prices for evaluations that are due
+    ///
+    /// Returns the number of evaluations updated.
+    #[instrument(skip(self))]
+    pub async fn track_pending(&self, now: DateTime<Utc>) -> AppResult<usize> {
+        let mut prices = HashMap::new();
+        let mut updated = 0;
+
+        let due_1h = sqlx::query(
+            "SELECT id, token_address FROM filter_evaluations
+             WHERE tracked_1h_at IS NULL AND evaluated_at <= $1
+             ORDER BY evaluated_at
+             LIMIT $2"
+        )
+            .bind(now - chrono::Duration::hours(1))
+            .bind(TRACKING_BATCH_SIZE)
+            .fetch_all(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to query pending 1h evaluations: {}", e), "filter_tracking_1h"))?;
+
+        for row in due_1h {
+            let id: uuid::Uuid = row.try_get("id")
+                .map_err(|e| AppError::database(e.to_string(), "filter_tracking_1h"))?;
+            let address: String = row.try_get("token_address").unwrap_or_default();
+
+            let Some(price) = self.price_for(&address, &mut prices).await else {
+                continue;
+            };
+
+            sqlx::query("UPDATE filter_evaluations SET price_1h_sol = $2, tracked_1h_at = NOW() WHERE id = $1")
+                .bind(id)
+                .bind(price)
+                .execute(self.postgres.pool())
+                .await
+                .map_err(|e| AppError::database(format!("Failed to record 1h price: {}", e), "filter_tracking_1h"))?;
+
+            updated += 1;
+        }
+
+        let due_24h = sqlx::query(
+            "SELECT id, token_address, price_sol, price_1h_sol FROM filter_evaluations
+             WHERE tracked_24h_at IS NULL AND tracked_1h_at IS NOT NULL AND evaluated_at <= $1
+             ORDER BY evaluated_at
+             LIMIT $2"
+        )
+            .bind(now - chrono::Duration::hours(24))
+            .bind(TRACKING_BATCH_SIZE)
+            .fetch_all(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to query pending 24h evaluations: {}", e), "filter_tracking_24h"))?;
+
+        for row in due_24h {
+            let id: uuid::Uuid = row.try_get("id")
+                .map_err(|e| AppError::database(e.to_string(), "filter_tracking_24h"))?;
+            let address: String = row.try_get("token_address").unwrap_or_default();
+
+            let Some(price) = self.price_for(&address, &mut prices).await else {
+                continue;
+            };
+
+            let outcome = classify_outcome(
+                row.try_get("price_sol").unwrap_or(None),
+                row.try_get("price_1h_sol").unwrap_or(None),
+                price,
+            );
+
+            sqlx::query(
+                "UPDATE filter_evaluations
+                 SET price_24h_sol = $2, tracked_24h_at = NOW(), outcome = $3
+                 WHERE id = $1"
+            )
+                .bind(id)
+                .bind(price)
+                .bind(outcome.as_str())
+                .execute(self.postgres.pool())
+                .await
+                .map_err(|e| AppError::database(format!("Failed to record 24h price: {}", e), "filter_tracking_24h"))?;
+
+            updated += 1;
+        }
+
+        Ok(updated)
+    }
+
+    /// Look up a token price once per tracking pass
+    ///
+    /// Returns `None` when the lookup failed so the evaluation is retried on
+    /// the next pass, and `Some(None)` when the token has no price.
+    async fn price_for(
+        &self,
+        address: &str,
+        prices: &mut HashMap<String, Option<Decimal>>,
+    ) -> Option<Option<Decimal>> {
+        if let Some(price) = prices.get(address) {
+            return Some(*price);
+        }
+
+        let token = TokenAddress::new_unchecked(address.to_string());
+        match self.price_source.get_price_sol(&token).await {
+            Ok(price) => {
+                prices.insert(address.to_string(), price);
+                Some(price)
+            }
+            Err(e) => {
+                warn!("Price lookup failed for {}: {}", address, e);
+                None
+            }
+        }
+    }
+}
+
+/// Aggregate per-filter attribution for evaluations in `[start, end)`
+///
+/// Tokens are counted once per filter even when evaluated repeatedly. Blocked
+/// winners, avoided rugs and pending outcomes only count tokens the filter
+/// actually rejected, not warnings or tokens that were bought anyway.
+pub async fn query_filter_attribution(
+    postgres: &PostgresService,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> AppResult<Vec<FilterAttribution>> {
+    let rows = sqlx::query(
+        "SELECT
+            c.filter_name,
+            BOOL_OR(c.blocking) AS blocking,
+            COUNT(DISTINCT e.token_address) AS evaluated,
+            COUNT(DISTINCT e.token_address) FILTER (WHERE c.flagged) AS flagged,
+            COUNT(DISTINCT e.token_address) FILTER (WHERE c.blocking AND NOT c.passed AND NOT e.passed
+                                                      AND e.outcome = 'winner') AS winners,
+            COUNT(DISTINCT e.token_address) FILTER (WHERE c.blocking AND NOT c.passed AND NOT e.passed
+                                                      AND e.outcome = 'rug') AS rugs,
+            COUNT(DISTINCT e.token_address) FILTER (WHERE c.blocking AND NOT c.passed AND NOT e.passed
+                                                      AND e.outcome IS NULL) AS pending
+         FROM filter_checks c
+         JOIN filter_evaluations e ON e.id = c.evaluation_id
+         WHERE e.evaluated_at >= $1 AND e.evaluated_at < $2
+         GROUP BY c.filter_name
+         ORDER BY c.filter_name"
+    )
+        .bind(start)
+        .bind(end)
+        .fetch_all(postgres.pool())
+        .await
+        .map_err(|e| AppError::database(format!("Failed to query filter attribution: {}", e), "filter_attribution"))?;
+
+    Ok(rows.into_iter()
+        .map(|row| {
+            let count = |name: &str| row.try_get::<i64, _>(name).unwrap_or(0).max(0) as u64;
+
+            FilterAttribution {
+                filter_name: row.try_get("filter_name").unwrap_or_default(),
+                blocking: row.try_get::<Option<bool>, _>("blocking").unwrap_or(None).unwrap_or(true),
+                tokens_evaluated: count("evaluated"),
+                tokens_flagged: count("flagged"),
+                winners_blocked: count("winners"),
+                rugs_avoided: count("rugs"),
+                outcome_pending: count("pending"),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_winner_and_rug() {
+        // Pump at 1h counts as a winner even if it dumped by 24h
+        assert_eq!(classify_outcome(Some(dec!(1)), Some(dec!(2.5)), Some(dec!(0.1))), TokenOutcome::Winner);
+        assert_eq!(classify_outcome(Some(dec!(1)), Some(dec!(1.1)), Some(dec!(3))), TokenOutcome::Winner);
+
+        assert_eq!(classify_outcome(Some(dec!(1)), Some(dec!(0.5)), Some(dec!(0.2))), TokenOutcome::Rug);
+        assert_eq!(classify_outcome(Some(dec!(1)), Some(dec!(0.05)), Some(Decimal::ZERO)), TokenOutcome::Rug);
+
+        assert_eq!(classify_outcome(Some(dec!(1)), Some(dec!(1.2)), Some(dec!(0.8))), TokenOutcome::Neutral);
+    }
+
+    #[test]
+    fn test_classify_missing_24h_price_is_unknown() {
+        // An unindexed token has no price; that alone is not a rug
+        assert_eq!(classify_outcome(Some(dec!(1)), Some(dec!(0.9)), None), TokenOutcome::Unknown);
+        assert_eq!(classify_outcome(Some(dec!(1)), None, None), TokenOutcome::Unknown);
+    }
+
+    #[test]
+    fn test_classify_baseline_fallback() {
+        assert_eq!(classify_outcome(None, None, Some(dec!(5))), TokenOutcome::Unknown);
+        assert_eq!(classify_outcome(Some(Decimal::ZERO), None, None), TokenOutcome::Unknown);
+
+        // Missing or zero evaluation price falls back to the 1h price
+        assert_eq!(classify_outcome(None, Some(dec!(1)), Some(dec!(2))), TokenOutcome::Winner);
+        assert_eq!(classify_outcome(Some(Decimal::ZERO), Some(dec!(1)), Some(dec!(2))), TokenOutcome::Winner);
+        assert_eq!(classify_outcome(Some(Decimal::ZERO), Some(dec!(1)), Some(dec!(0.2))), TokenOutcome::Rug);
+    }
+}
@@ -0,0 +1,694 @@
+//! Compliance audit archives
+//!
+//! This module compiles `audit_logs`, `config_changes`, `telegram_commands`
+//! and trades for a period into a tamper-evident archive: a hash-chained JSONL
+//! file plus an HMAC-signed manifest. Each archive chains onto the final hash
+//! of the previous one. Rows past their retention period are pruned only when
+//! their timestamp falls inside an archived period and they have not changed
+//! since that archive was written.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use ring::{digest, hkdf, hmac};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn, instrument};
+
+use crate::config::AppConfig;
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use crate::infrastructure::database::PostgresService;
+use super::models::ReportPeriod;
+
+/// Archive format version
+///
+/// Version 2 archives top-level numeric columns as strings.
+pub const ARCHIVE_FORMAT_VERSION: u32 = 2;
+
+/// Records file name inside an archive directory
+const RECORDS_FILE: &str = "records.jsonl";
+
+/// Manifest file name inside an archive directory
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// HKDF salt for the archive signing key
+const SIGNING_KEY_SALT: &[u8] = b"solana-sniper-bot/audit-archive";
+
+/// HKDF info for the archive signing key
+const SIGNING_KEY_INFO: &[u8] = b"manifest-hmac-sha256";
+
+/// Maximum archives generated per run, so a long backlog is spread out
+const MAX_ARCHIVES_PER_RUN: usize = 12;
+
+/// Table included in audit archives
+#[derive(Debug, Clone, Copy)]
+struct ArchiveSource {
+    /// Table name
+    table: &'static str,
+    /// Timestamp column used for periods and retention
+    timestamp_column: &'static str,
+    /// Last modification column, for tables whose rows change after insert
+    updated_column: Option<&'static str>,
+    /// Whether trade history retention applies instead of audit retention
+    is_trade_history: bool,
+}
+
+/// Tables included in audit archives
+const ARCHIVE_SOURCES: [ArchiveSource; 4] = [
+    ArchiveSource { table: "audit_logs", timestamp_column: "performed_at", updated_column: None, is_trade_history: false },
+    ArchiveSource { table: "config_changes", timestamp_column: "changed_at", updated_column: None, is_trade_history: false },
+    ArchiveSource { table: "telegram_commands", timestamp_column: "executed_at", updated_column: None, is_trade_history: false },
+    ArchiveSource { table: "trades", timestamp_column: "created_at", updated_column: Some("updated_at"), is_trade_history: true },
+];
+
+/// Hash of the first record's predecessor in a new chain
+fn genesis_hash() -> String {
+    "0".repeat(64)
+}
+
+/// Archived database row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainedRecord {
+    /// Position in the archive, starting at 1
+    pub seq: u64,
+    /// Source table
+    pub table: String,
+    /// Row id
+    pub id: String,
+    /// Row timestamp
+    pub recorded_at: DateTime<Utc>,
+    /// Full row as JSON, with numeric columns as strings so DECIMAL
+    /// values keep their exact digits
+    pub data: serde_json::Value,
+    /// Hash of the previous record
+    pub prev_hash: String,
+}
+
+/// Line of the archive records file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    /// Archived record
+    pub record: ChainedRecord,
+    /// SHA-256 of the serialized record, hex encoded
+    pub hash: String,
+}
+
+/// Signed archive manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    /// Archive format version
+    pub version: u32,
+    /// Archive period type
+    pub period: ReportPeriod,
+    /// Period start (inclusive)
+    pub period_start: DateTime<Utc>,
+    /// Period end (exclusive)
+    pub period_end: DateTime<Utc>,
+    /// Archive generation time
+    pub generated_at: DateTime<Utc>,
+    /// Record count per source table
+    pub record_counts: BTreeMap<String, u64>,
+    /// Total record count
+    pub total_records: u64,
+    /// Chain start hash, the final hash of the previous archive
+    pub genesis_hash: String,
+    /// Hash of the last record
+    pub final_hash: String,
+    /// Records file name
+    pub records_file: String,
+    /// SHA-256 of the records file, hex encoded
+    pub records_sha256: String,
+    /// Signature algorithm
+    pub signature_algorithm: String,
+    /// Manifest signature, hex encoded
+    pub signature: String,
+}
+
+impl ArchiveManifest {
+    /// Bytes covered by the signature: the manifest with an empty signature
+    fn signing_payload(&self) -> AppResult<Vec<u8>> {
+        let mut unsigned = self.clone();
+        unsigned.signature = String::new();
+        Ok(serde_json::to_vec(&unsigned)?)
+    }
+}
+
+/// Hash a chained record
+fn record_hash(record: &ChainedRecord) -> AppResult<String> {
+    let bytes = serde_json::to_vec(record)?;
+    Ok(hex::encode(digest::digest(&digest::SHA256, &bytes)))
+}
+
+/// Derive the manifest signing key from the configured encryption key
+fn derive_signing_key(encryption_key: &str) -> AppResult<hmac::Key> {
+    let secret = BASE64.decode(encryption_key)
+        .map_err(|e| AppError::security(format!("Invalid encryption key: {}", e)))?;
+
+    if secret.is_empty() {
+        return Err(AppError::security("Encryption key not configured"));
+    }
+
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, SIGNING_KEY_SALT).extract(&secret);
+    let okm = prk.expand(&[SIGNING_KEY_INFO], hmac::HMAC_SHA256)
+        .map_err(|_| AppError::security("Failed to derive archive signing key"))?;
+
+    Ok(hmac::Key::from(okm))
+}
+
+/// Streaming writer for a single archive
+struct ArchiveWriter {
+    /// Archive directory
+    directory: PathBuf,
+    /// Records file writer
+    file: BufWriter<tokio::fs::File>,
+    /// Running digest of the records file
+    file_digest: digest::Context,
+    /// Chain start hash
+    genesis_hash: String,
+    /// Hash of the last written record
+    prev_hash: String,
+    /// Records written
+    seq: u64,
+    /// Record count per table
+    counts: BTreeMap<String, u64>,
+}
+
+impl ArchiveWriter {
+    /// Create the archive directory and records file
+    async fn create(directory: PathBuf, genesis_hash: String) -> AppResult<Self> {
+        tokio::fs::create_dir_all(&directory).await?;
+        let file = tokio::fs::File::create(directory.join(RECORDS_FILE)).await?;
+
+        Ok(Self {
+            directory,
+            file: BufWriter::new(file),
+            file_digest: digest::Context::new(&digest::SHA256),
+            prev_hash: genesis_hash.clone(),
+            genesis_hash,
+            seq: 0,
+            counts: BTreeMap::new(),
+        })
+    }
+
+    /// Append a row to the chain
+    async fn append(
+        &mut self,
+        table: &str,
+        id: String,
+        recorded_at: DateTime<Utc>,
+        data: serde_json::Value,
+    ) -> AppResult<()> {
+        self.seq += 1;
+
+        let record = ChainedRecord {
+            seq: self.seq,
+            table: table.to_string(),
+            id,
+            recorded_at,
+            data,
+            prev_hash: self.prev_hash.clone(),
+        };
+        let hash = record_hash(&record)?;
+
+        let mut line = serde_json::to_vec(&ArchiveEntry { record, hash: hash.clone() })?;
+        line.push(b'\n');
+
+        self.file.write_all(&line).await?;
+        self.file_digest.update(&line);
+
+        self.prev_hash = hash;
+        *self.counts.entry(table.to_string()).or_default() += 1;
+
+        Ok(())
+    }
+
+    /// Flush the records and write the signed manifest
+    async fn finish(
+        mut self,
+        period: ReportPeriod,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        key: &hmac::Key,
+    ) -> AppResult<ArchiveManifest> {
+        self.file.flush().await?;
+        self.file.get_ref().sync_all().await?;
+
+        let mut manifest = ArchiveManifest {
+            version: ARCHIVE_FORMAT_VERSION,
+            period,
+            period_start,
+            period_end,
+            generated_at: Utc::now(),
+            record_counts: self.counts,
+            total_records: self.seq,
+            genesis_hash: self.genesis_hash,
+            final_hash: self.prev_hash,
+            records_file: RECORDS_FILE.to_string(),
+            records_sha256: hex::encode(self.file_digest.finish()),
+            signature_algorithm: "HMAC-SHA256".to_string(),
+            signature: String::new(),
+        };
+        manifest.signature = hex::encode(hmac::sign(key, &manifest.signing_payload()?));
+
+        // The manifest is written last, so its presence marks a complete archive
+        let body = serde_json::to_vec_pretty(&manifest)?;
+        tokio::fs::write(self.directory.join(MANIFEST_FILE), body).await?;
+
+        Ok(manifest)
+    }
+}
+
+/// Verify an archive's signature, file digest and hash chain
+async fn verify_archive_with_key(directory: &Path, key: &hmac::Key) -> AppResult<ArchiveManifest> {
+    let manifest_bytes = tokio::fs::read(directory.join(MANIFEST_FILE)).await?;
+    let manifest: ArchiveManifest = serde_json::from_slice(&manifest_bytes)?;
+
+    let signature = hex::decode(&manifest.signature)
+        .map_err(|_| AppError::security("Audit archive signature is not valid hex"))?;
+    hmac::verify(key, &manifest.signing_payload()?, &signature)
+        .map_err(|_| AppError::security("Audit archive manifest signature mismatch"))?;
+
+    let records = tokio::fs::read(directory.join(&manifest.records_file)).await?;
+    if hex::encode(digest::digest(&digest::SHA256, &records)) != manifest.records_sha256 {
+        return Err(AppError::security("Audit archive records digest mismatch"));
+    }
+
+    let mut prev_hash = manifest.genesis_hash.clone();
+    let mut count = 0u64;
+
+    for line in records.split(|b| *b == b'\n').filter(|l| !l.is_empty()) {
+        let entry: ArchiveEntry = serde_json::from_slice(line)?;
+        count += 1;
+
+        if entry.record.seq != count || entry.record.prev_hash != prev_hash {
+            return Err(AppError::security(format!("Audit archive chain broken at record {}", count)));
+        }
+        if record_hash(&entry.record)? != entry.hash {
+            return Err(AppError::security(format!("Audit archive record {} hash mismatch", count)));
+        }
+
+        prev_hash = entry.hash;
+    }
+
+    if count != manifest.total_records || prev_hash != manifest.final_hash {
+        return Err(AppError::security("Audit archive does not match its manifest"));
+    }
+
+    Ok(manifest)
+}
+
+/// Audit archive generator and retention pruner
+#[derive(Debug, Clone)]
+pub struct AuditArchiver {
+    /// PostgreSQL service
+    postgres: PostgresService,
+    /// Archive period
+    period: ReportPeriod,
+    /// Archive root directory
+    archive_dir: PathBuf,
+    /// Manifest signing key
+    signing_key: hmac::Key,
+    /// Audit table retention in days (0 disables pruning)
+    audit_retention_days: u32,
+    /// Trade history retention in days (0 disables pruning)
+    trade_retention_days: u32,
+    /// Archiver running
+    is_running: Arc<RwLock<bool>>,
+}
+
+impl AuditArchiver {
+    /// Create a new audit archiver
+    #[instrument(skip(config, postgres))]
+    pub fn new(config: &AppConfig, postgres: PostgresService) -> AppResult<Self> {
+        let period = match config.analytics.audit_report_frequency.as_deref() {
+            Some(frequency) => frequency.parse()?,
+            None => ReportPeriod::Monthly,
+        };
+
+        Ok(Self {
+            postgres,
+            period,
+            archive_dir: PathBuf::from(&config.analytics.audit_archive_dir),
+            signing_key: derive_signing_key(&config.security.encryption_key)?,
+            audit_retention_days: config.security.audit_log_retention_days,
+            trade_retention_days: config.analytics.trade_history_retention_days,
+            is_running: Arc::new(RwLock::new(false)),
+        })
+    }
+
+    /// Start periodic archiving and pruning
+    pub async fn start(&self, interval: Duration) -> AppResult<()> {
+        {
+            let mut running = self.is_running.write().await;
+            if *running {
+                return Err(AppError::internal("Audit archiver already running"));
+            }
+            *running = true;
+        }
+
+        let archiver = self.clone();
+
+        tokio::spawn(async move {
+            info!("🗄️  Audit archiver started ({} archives)", archiver.period.as_str());
+
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                ticker.tick().await;
+
+                if !*archiver.is_running.read().await {
+                    break;
+                }
+
+                if let Err(e) = archiver.run(Utc::now()).await {
+                    error!("Audit archive run failed: {}", e);
+                }
+            }
+
+            info!("Audit archiver ended");
+        });
+
+        Ok(())
+    }
+
+    /// Stop the archiver
+    pub async fn stop(&self) {
+        *self.is_running.write().await = false;
+    }
+
+    /// Archive complete periods, then prune expired archived rows
+    #[instrument(skip(self))]
+    pub async fn run(&self, now: DateTime<Utc>) -> AppResult<(usize, u64)> {
+        let archived = self.archive_due(now).await?;
+        let pruned = self.prune(now).await?;
+
+        Ok((archived, pruned))
+    }
+
+    /// Archive every complete period not yet archived
+    ///
+    /// Returns the number of archives written.
+    pub async fn archive_due(&self, now: DateTime<Utc>) -> AppResult<usize> {
+        let Some((mut start, mut genesis)) = self.next_period().await? else {
+            return Ok(0);
+        };
+
+        let mut archived = 0;
+
+        while archived < MAX_ARCHIVES_PER_RUN {
+            let end = self.period.next(start);
+            if end > now {
+                break;
+            }
+
+            let manifest = self.archive_period(start, end, genesis).await?;
+            genesis = manifest.final_hash;
+            start = end;
+            archived += 1;
+        }
+
+        if archived == MAX_ARCHIVES_PER_RUN {
+            warn!("Audit archive backlog remaining, continuing on next run");
+        }
+
+        Ok(archived)
+    }
+
+    /// Write, verify and register the archive for one period
+    #[instrument(skip(self, genesis))]
+    pub async fn archive_period(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        genesis: String,
+    ) -> AppResult<ArchiveManifest> {
+        let directory = self.archive_dir.join(format!(
+            "{}_{}_{}",
+            self.period.as_str(),
+            start.format("%Y%m%d"),
+            end.format("%Y%m%d"),
+        ));
+
+        let mut writer = ArchiveWriter::create(directory.clone(), genesis).await?;
+
+        for source in ARCHIVE_SOURCES {
+            // Numbers are rendered as text by Postgres: decoding them into
+            // JSON numbers would round DECIMAL columns through f64
+            let query = format!(
+                "SELECT t.id::text AS id, t.{ts} AS recorded_at,
+                        (SELECT jsonb_object_agg(
+                                    c.key,
+                                    CASE WHEN jsonb_typeof(c.value) = 'number' THEN to_jsonb(c.value #>> '{{}}')
+                                         ELSE c.value END)
+                         FROM jsonb_each(to_jsonb(t)) c) AS data
+                 FROM {table} t
+                 WHERE t.{ts} >= $1 AND t.{ts} < $2
+                 ORDER BY t.{ts}, t.id",
+                table = source.table,
+                ts = source.timestamp_column,
+            );
+
+            let mut rows = sqlx::query(&query)
+                .bind(start)
+                .bind(end)
+                .fetch(self.postgres.pool());
+
+            while let Some(row) = rows.try_next()
+                .await
+                .map_err(|e| AppError::database(format!("Failed to read {}: {}", source.table, e), "audit_archive"))?
+            {
+                // A row that does not decode must abort the archive: prune
+                // would otherwise delete the real row behind a placeholder
+                let decode_error = |e: sqlx::Error| {
+                    AppError::database(format!("Failed to decode {} row: {}", source.table, e), "audit_archive")
+                };
+                writer.append(
+                    source.table,
+                    row.try_get("id").map_err(decode_error)?,
+                    row.try_get("recorded_at").map_err(decode_error)?,
+                    row.try_get("data").map_err(decode_error)?,
+                ).await?;
+            }
+        }
+
+        let manifest = writer.finish(self.period, start, end, &self.signing_key).await?;
+
+        // Never register an archive that does not verify
+        verify_archive_with_key(&directory, &self.signing_key).await?;
+
+        sqlx::query(
+            "INSERT INTO audit_archives
+                (period_type, period_start, period_end, archive_path, record_count, genesis_hash, final_hash, signature)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+        )
+            .bind(self.period.as_str())
+            .bind(start)
+            .bind(end)
+            .bind(directory.to_string_lossy().to_string())
+            .bind(manifest.total_records as i64)
+            .bind(&manifest.genesis_hash)
+            .bind(&manifest.final_hash)
+            .bind(&manifest.signature)
+            .execute(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to register audit archive: {}", e), "audit_archive"))?;
+
+        info!("🗄️  Audit archive {} → {} written: {} records", start, end, manifest.total_records);
+        Ok(manifest)
+    }
+
+    /// Verify an archive written with this archiver's key
+    pub async fn verify(&self, directory: &Path) -> AppResult<ArchiveManifest> {
+        verify_archive_with_key(directory, &self.signing_key).await
+    }
+
+    /// Delete rows past retention that are covered by an archive
+    ///
+    /// Returns the number of rows deleted.
+    #[instrument(skip(self))]
+    pub async fn prune(&self, now: DateTime<Utc>) -> AppResult<u64> {
+        let mut pruned = 0;
+
+        for source in ARCHIVE_SOURCES {
+            let retention_days = if source.is_trade_history {
+                self.trade_retention_days
+            } else {
+                self.audit_retention_days
+            };
+
+            if retention_days == 0 {
+                continue;
+            }
+
+            // Trades still referenced by positions are kept
+            let referenced = if source.is_trade_history {
+                " AND NOT EXISTS (SELECT 1 FROM positions p WHERE p.entry_trade_id = t.id OR p.exit_trade_id = t.id)"
+            } else {
+                ""
+            };
+
+            // A row changed after its archive was written is not in the
+            // archive as it is now, so it is kept
+            let unchanged = match source.updated_column {
+                Some(column) => format!(" AND t.{} <= a.created_at", column),
+                None => String::new(),
+            };
+
+            let query = format!(
+                "DELETE FROM {table} t
+                 WHERE t.{ts} < $1
+                   AND EXISTS (
+                       SELECT 1 FROM audit_archives a
+                       WHERE t.{ts} >= a.period_start AND t.{ts} < a.period_end{unchanged}
+                   ){referenced}",
+                table = source.table,
+                ts = source.timestamp_column,
+                unchanged = unchanged,
+                referenced = referenced,
+            );
+
+            let result = sqlx::query(&query)
+                .bind(now - chrono::Duration::days(retention_days as i64))
+                .execute(self.postgres.pool())
+                .await
+                .map_err(|e| AppError::database(format!("Failed to prune {}: {}", source.table, e), "audit_prune"))?;
+
+            if result.rows_affected() > 0 {
+                debug!("Pruned {} archived rows from {}", result.rows_affected(), source.table);
+            }
+            pruned += result.rows_affected();
+        }
+
+        if pruned > 0 {
+            info!("🧹 Pruned {} archived rows past retention", pruned);
+        }
+
+        Ok(pruned)
+    }
+
+    /// Start of the next period to archive and its chain start hash
+    async fn next_period(&self) -> AppResult<Option<(DateTime<Utc>, String)>> {
+        let last = sqlx::query(
+            "SELECT period_end, final_hash FROM audit_archives ORDER BY period_end DESC LIMIT 1"
+        )
+            .fetch_optional(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to query audit archives: {}", e), "audit_archive"))?;
+
+        if let Some(row) = last {
+            let end: DateTime<Utc> = row.try_get("period_end")
+                .map_err(|e| AppError::database(e.to_string(), "audit_archive"))?;
+            let hash: String = row.try_get("final_hash")
+                .map_err(|e| AppError::database(e.to_string(), "audit_archive"))?;
+            return Ok(Some((end, hash)));
+        }
+
+        // First run: start at the period of the oldest archivable row
+        let oldest_query = format!(
+            "SELECT LEAST({}) AS oldest",
+            ARCHIVE_SOURCES.iter()
+                .map(|s| format!("(SELECT MIN({}) FROM {})", s.timestamp_column, s.table))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let row = sqlx::query(&oldest_query)
+            .fetch_one(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to query oldest audit row: {}", e), "audit_archive"))?;
+
+        let oldest: Option<DateTime<Utc>> = row.try_get("oldest").unwrap_or(None);
+        Ok(oldest.map(|ts| (self.period.floor(ts), genesis_hash())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn test_key(byte: u8) -> hmac::Key {
+        derive_signing_key(&BASE64.encode([byte; 32])).unwrap()
+    }
+
+    async fn write_test_archive(directory: &Path, key: &hmac::Key) -> ArchiveManifest {
+        let start = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+
+        let mut writer = ArchiveWriter::create(directory.to_path_buf(), genesis_hash()).await.unwrap();
+        for i in 0..3 {
+            writer.append(
+                "audit_logs",
+                format!("id-{}", i),
+                start + chrono::Duration::hours(i),
+                serde_json::json!({ "action": "user_role_granted", "details": { "telegram_id": i } }),
+            ).await.unwrap();
+        }
+        writer.append("trades", "trade-1".to_string(), start, serde_json::json!({ "amount_sol": "0.5" }))
+            .await
+            .unwrap();
+
+        writer.finish(ReportPeriod::Monthly, start, end, key).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_archive_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = test_key(0);
+
+        let manifest = write_test_archive(dir.path(), &key).await;
+        assert_eq!(manifest.total_records, 4);
+        assert_eq!(manifest.record_counts["audit_logs"], 3);
+        assert_eq!(manifest.genesis_hash, genesis_hash());
+
+        let verified = verify_archive_with_key(dir.path(), &key).await.unwrap();
+        assert_eq!(verified.final_hash, manifest.final_hash);
+    }
+
+    #[tokio::test]
+    async fn test_tampered_archive_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = test_key(0);
+        write_test_archive(dir.path(), &key).await;
+
+        // Edited record
+        let records_path = dir.path().join(RECORDS_FILE);
+        let original = std::fs::read_to_string(&records_path).unwrap();
+        std::fs::write(&records_path, original.replace("user_role_granted", "user_role_revoked")).unwrap();
+        assert!(verify_archive_with_key(dir.path(), &key).await.is_err());
+
+        // Dropped record
+        let lines: Vec<&str> = original.lines().collect();
+        let truncated = format!("{}\n{}\n", lines[0], lines[2]);
+        std::fs::write(&records_path, &truncated).unwrap();
+        assert!(verify_archive_with_key(dir.path(), &key).await.is_err());
+
+        // Wrong signing key
+        std::fs::write(&records_path, &original).unwrap();
+        let other_key = test_key(1);
+        assert!(verify_archive_with_key(dir.path(), &other_key).await.is_err());
+        assert!(verify_archive_with_key(dir.path(), &key).await.is_ok());
+    }
+
+    #[test]
+    fn test_period_boundaries() {
+        let ts = Utc.with_ymd_and_hms(2024, 6, 12, 15, 30, 0).unwrap();
+
+        let week_start = ReportPeriod::Weekly.floor(ts);
+        assert_eq!(week_start, Utc.with_ymd_and_hms(2024, 6, 10, 0, 0, 0).unwrap());
+        assert_eq!(ReportPeriod::Weekly.next(week_start), Utc.with_ymd_and_hms(2024, 6, 17, 0, 0, 0).unwrap());
+
+        let month_start = ReportPeriod::Monthly.floor(ts);
+        assert_eq!(month_start, Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap());
+        assert_eq!(ReportPeriod::Monthly.next(month_start), Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap());
+
+        assert!("yearly".parse::<ReportPeriod>().is_err());
+    }
+}
@@ -0,0 +1,20 @@
+//! Analytics service module
+//!
+//! This module provides trading performance tracking, PnL accounting,
+//! drawdown analysis, filter attribution, scheduled period reports and
+//! compliance audit archives.
+
+pub mod attribution;
+pub mod audit;
+pub mod models;
+pub mod reporter;
+pub mod tracker;
+
+pub use attribution::{FilterAttributionTracker, classify_outcome, query_filter_attribution};
+pub use audit::{ArchiveEntry, ArchiveManifest, AuditArchiver, ChainedRecord};
+pub use models::{
+    EquityPoint, FilterAttribution, LatencyPercentiles, PeriodReport, PnlSnapshot, PositionSnapshot,
+    RejectionReason, RejectionStats, ReportPeriod, TokenOutcome, TokenPerformance, TradeFill, TradeSide, WalletPnl,
+};
+pub use reporter::{ReportChannel, ReportSchedule, ReportScheduler};
+pub use tracker::{PerformanceTracker, PriceSource};
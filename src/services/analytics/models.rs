@@ -0,0 +1,408 @@
+//! Analytics data models
+//!
+//! This module defines the data structures used for performance tracking,
+//! including trade fills, position snapshots and PnL summaries.
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::AppError;
+use crate::core::types::{SessionId, TokenAddress};
+
+/// Trade side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradeSide {
+    /// Buy tokens with SOL
+    Buy,
+    /// Sell tokens for SOL
+    Sell,
+}
+
+/// Executed trade fill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeFill {
+    /// Wallet that executed the trade
+    pub wallet: String,
+    /// Token traded
+    pub token: TokenAddress,
+    /// Trade side
+    pub side: TradeSide,
+    /// Token quantity
+    pub quantity: Decimal,
+    /// Execution price in SOL per token
+    pub price_sol: Decimal,
+    /// Network and priority fees paid in SOL
+    pub fee_sol: Decimal,
+    /// Execution timestamp
+    pub executed_at: DateTime<Utc>,
+}
+
+/// Open position marked to market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionSnapshot {
+    /// Wallet holding the position
+    pub wallet: String,
+    /// Token held
+    pub token: TokenAddress,
+    /// Token quantity held
+    pub quantity: Decimal,
+    /// Volume-weighted average entry price in SOL
+    pub avg_entry_price_sol: Decimal,
+    /// Latest mark price in SOL
+    pub mark_price_sol: Decimal,
+    /// Cost basis in SOL
+    pub cost_basis_sol: Decimal,
+    /// Market value in SOL at the mark price
+    pub market_value_sol: Decimal,
+    /// Unrealized PnL in SOL
+    pub unrealized_pnl_sol: Decimal,
+    /// Position open time
+    pub opened_at: DateTime<Utc>,
+    /// Last time the position was marked
+    pub marked_at: DateTime<Utc>,
+}
+
+/// PnL of one trading wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletPnl {
+    /// Wallet address
+    pub wallet: String,
+    /// Realized PnL in SOL, before fees
+    pub realized_pnl_sol: Decimal,
+    /// Unrealized PnL in SOL on the wallet's open positions
+    pub unrealized_pnl_sol: Decimal,
+    /// Fees paid in SOL
+    pub fees_sol: Decimal,
+    /// Net PnL in SOL: realized + unrealized - fees
+    pub net_pnl_sol: Decimal,
+    /// Number of executed trades
+    pub total_trades: u64,
+    /// Number of closing trades with positive realized PnL
+    pub profitable_trades: u64,
+}
+
+/// Point on the equity curve
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EquityPoint {
+    /// Timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Equity in SOL
+    pub equity_sol: Decimal,
+}
+
+/// Session PnL summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlSnapshot {
+    /// Trading session
+    pub session_id: SessionId,
+    /// Starting capital in SOL
+    pub starting_capital_sol: Decimal,
+    /// Realized PnL in SOL, before fees
+    pub realized_pnl_sol: Decimal,
+    /// Unrealized PnL in SOL on open positions
+    pub unrealized_pnl_sol: Decimal,
+    /// Total fees paid in SOL
+    pub fees_sol: Decimal,
+    /// Net PnL in SOL: realized + unrealized - fees
+    pub net_pnl_sol: Decimal,
+    /// Current equity in SOL
+    pub equity_sol: Decimal,
+    /// Highest equity reached in SOL
+    pub peak_equity_sol: Decimal,
+    /// Current drawdown from peak in percent
+    pub current_drawdown_percent: Decimal,
+    /// Maximum peak-to-trough drawdown in percent
+    pub max_drawdown_percent: Decimal,
+    /// Number of executed trades
+    pub total_trades: u64,
+    /// Number of closing trades with positive realized PnL
+    pub profitable_trades: u64,
+    /// Open positions
+    pub open_positions: Vec<PositionSnapshot>,
+    /// PnL per trading wallet
+    pub wallets: Vec<WalletPnl>,
+    /// Snapshot time
+    pub taken_at: DateTime<Utc>,
+}
+
+/// Scheduled report period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReportPeriod {
+    /// Previous calendar day
+    Daily,
+    /// Previous seven days
+    Weekly,
+    /// Previous calendar month
+    Monthly,
+}
+
+impl ReportPeriod {
+    /// Period name as stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportPeriod::Daily => "daily",
+            ReportPeriod::Weekly => "weekly",
+            ReportPeriod::Monthly => "monthly",
+        }
+    }
+
+    /// Start of the period containing `timestamp`
+    ///
+    /// Weeks start on Monday, months on the first day, all at 00:00 UTC.
+    pub fn floor(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let date = timestamp.date_naive();
+        let start = match self {
+            ReportPeriod::Daily => date,
+            ReportPeriod::Weekly => date - Days::new(date.weekday().num_days_from_monday() as u64),
+            ReportPeriod::Monthly => date.with_day(1).unwrap_or(date),
+        };
+        start.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    /// Start of the period following the one starting at `start`
+    pub fn next(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            ReportPeriod::Daily => start + Days::new(1),
+            ReportPeriod::Weekly => start + Days::new(7),
+            ReportPeriod::Monthly => start + Months::new(1),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportPeriod {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "daily" => Ok(ReportPeriod::Daily),
+            "weekly" => Ok(ReportPeriod::Weekly),
+            "monthly" => Ok(ReportPeriod::Monthly),
+            other => Err(AppError::config(format!("Unknown report period: {}", other))),
+        }
+    }
+}
+
+impl std::fmt::Display for ReportPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportPeriod::Daily => write!(f, "Daily"),
+            ReportPeriod::Weekly => write!(f, "Weekly"),
+            ReportPeriod::Monthly => write!(f, "Monthly"),
+        }
+    }
+}
+
+/// Token performance within a report period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenPerformance {
+    /// Token address
+    pub address: String,
+    /// Token symbol
+    pub symbol: Option<String>,
+    /// Realized PnL in SOL
+    pub pnl_sol: Decimal,
+    /// Number of trades
+    pub trades: u64,
+}
+
+/// Execution latency percentiles in milliseconds
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    /// Median latency
+    pub p50_ms: Option<f64>,
+    /// 90th percentile latency
+    pub p90_ms: Option<f64>,
+    /// 99th percentile latency
+    pub p99_ms: Option<f64>,
+}
+
+/// Token rejection statistics from filter evaluations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RejectionStats {
+    /// Tokens evaluated by the filters during the period
+    pub tokens_evaluated: u64,
+    /// Tokens that passed every blocking filter
+    pub tokens_passed: u64,
+    /// Tokens rejected by at least one blocking filter
+    pub tokens_rejected: u64,
+    /// Blocking filter failures by filter and reason, most frequent first
+    pub by_reason: Vec<RejectionReason>,
+}
+
+/// Number of rejections of one filter for one reason
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectionReason {
+    /// Filter name
+    pub filter_name: String,
+    /// Failure details reported by the filter
+    pub reason: Option<String>,
+    /// Tokens rejected for this reason
+    pub count: u64,
+}
+
+/// Price outcome of a filtered token after the tracking window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenOutcome {
+    /// Price reached the winner multiple
+    Winner,
+    /// Price collapsed
+    Rug,
+    /// Neither a winner nor a rug
+    Neutral,
+    /// Missing baseline or 24h price
+    Unknown,
+}
+
+impl TokenOutcome {
+    /// Outcome name as stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenOutcome::Winner => "winner",
+            TokenOutcome::Rug => "rug",
+            TokenOutcome::Neutral => "neutral",
+            TokenOutcome::Unknown => "unknown",
+        }
+    }
+}
+
+/// Outcome attribution for a single filter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterAttribution {
+    /// Filter name
+    pub filter_name: String,
+    /// Whether the filter rejects tokens or only warns
+    pub blocking: bool,
+    /// Distinct tokens evaluated by the filter
+    pub tokens_evaluated: u64,
+    /// Distinct tokens the filter failed or warned on
+    pub tokens_flagged: u64,
+    /// Flagged tokens that turned out to be winners
+    pub winners_blocked: u64,
+    /// Flagged tokens that turned out to be rugs
+    pub rugs_avoided: u64,
+    /// Flagged tokens still inside the tracking window
+    pub outcome_pending: u64,
+}
+
+/// Periodic performance report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodReport {
+    /// Report period
+    pub period: ReportPeriod,
+    /// Period start (inclusive)
+    pub period_start: DateTime<Utc>,
+    /// Period end (exclusive)
+    pub period_end: DateTime<Utc>,
+    /// Executed trades
+    pub total_trades: u64,
+    /// Closing trades with positive PnL
+    pub winning_trades: u64,
+    /// Closing trades with negative PnL
+    pub losing_trades: u64,
+    /// Win rate in percent over closing trades
+    pub win_rate_percent: Decimal,
+    /// Realized PnL in SOL, before fees
+    pub realized_pnl_sol: Decimal,
+    /// Fees paid in SOL
+    pub fees_sol: Decimal,
+    /// Best performing tokens
+    pub best_tokens: Vec<TokenPerformance>,
+    /// Worst performing tokens
+    pub worst_tokens: Vec<TokenPerformance>,
+    /// Execution latency percentiles
+    pub latency: LatencyPercentiles,
+    /// Rejected token statistics
+    pub rejections: RejectionStats,
+    /// Per-filter winner and rug attribution
+    #[serde(default)]
+    pub filter_attribution: Vec<FilterAttribution>,
+    /// Report generation time
+    pub generated_at: DateTime<Utc>,
+}
+
+impl PeriodReport {
+    /// Render the report as plain text for chat delivery
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "📊 {} Report\n{} → {}\n\n",
+            self.period,
+            self.period_start.format("%Y-%m-%d %H:%M UTC"),
+            self.period_end.format("%Y-%m-%d %H:%M UTC"),
+        );
+
+        text.push_str(&format!(
+            "Trades: {} (✅ {} / ❌ {})\nWin rate: {}%\nRealized PnL: {} SOL\nFees: {} SOL\nNet: {} SOL\n",
+            self.total_trades,
+            self.winning_trades,
+            self.losing_trades,
+            self.win_rate_percent.round_dp(2),
+            self.realized_pnl_sol.round_dp(4),
+            self.fees_sol.round_dp(4),
+            (self.realized_pnl_sol - self.fees_sol).round_dp(4),
+        ));
+
+        let format_latency = |value: Option<f64>| {
+            value.map_or_else(|| "n/a".to_string(), |v| format!("{:.0}ms", v))
+        };
+        text.push_str(&format!(
+            "\nLatency p50/p90/p99: {} / {} / {}\n",
+            format_latency(self.latency.p50_ms),
+            format_latency(self.latency.p90_ms),
+            format_latency(self.latency.p99_ms),
+        ));
+
+        for (title, tokens) in [("🏆 Best", &self.best_tokens), ("💀 Worst", &self.worst_tokens)] {
+            if tokens.is_empty() {
+                continue;
+            }
+            text.push_str(&format!("\n{} tokens:\n", title));
+            for token in tokens {
+                text.push_str(&format!(
+                    "• {} {} SOL ({} trades)\n",
+                    token.symbol.as_deref().unwrap_or(&token.address),
+                    token.pnl_sol.round_dp(4),
+                    token.trades,
+                ));
+            }
+        }
+
+        text.push_str(&format!(
+            "\nTokens evaluated: {} | passed: {} | rejected: {}\n",
+            self.rejections.tokens_evaluated,
+            self.rejections.tokens_passed,
+            self.rejections.tokens_rejected,
+        ));
+        for rejection in &self.rejections.by_reason {
+            text.push_str(&format!(
+                "• {}: {} ({})\n",
+                rejection.filter_name,
+                rejection.reason.as_deref().unwrap_or("check failed"),
+                rejection.count,
+            ));
+        }
+
+        if !self.filter_attribution.is_empty() {
+            text.push_str("\n🧪 Filters (flagged / winners blocked / rugs avoided):\n");
+            for filter in &self.filter_attribution {
+                text.push_str(&format!(
+                    "• {}{}: {} / {} / {}{}\n",
+                    filter.filter_name,
+                    if filter.blocking { "" } else { " (warn)" },
+                    filter.tokens_flagged,
+                    filter.winners_blocked,
+                    filter.rugs_avoided,
+                    if filter.outcome_pending > 0 {
+                        format!(" ({} pending)", filter.outcome_pending)
+                    } else {
+                        String::new()
+                    },
+                ));
+            }
+        }
+
+        text
+    }
+}
@@ -0,0 +1,702 @@
+//! Scheduled performance reporting
+//!
+//! This module builds daily, weekly and monthly period reports from the
+//! trade history and delivers them over the configured channel. Delivered
+//! reports are recorded in `report_runs` so schedules missed during downtime
+//! are caught up on the next start.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveTime, Utc, Weekday};
+use rust_decimal::Decimal;
+use sqlx::Row;
+use teloxide::prelude::Requester;
+use teloxide::types::ChatId;
+use teloxide::Bot;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn, instrument};
+
+use crate::config::AppConfig;
+use crate::config::models::AnalyticsConfig;
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use crate::infrastructure::database::PostgresService;
+use super::attribution::query_filter_attribution;
+use super::models::{
+    LatencyPercentiles, PeriodReport, RejectionReason, RejectionStats, ReportPeriod, TokenPerformance,
+};
+
+/// Scheduler check interval
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+/// Maximum number of missed reports delivered per period type on catch-up
+const MAX_CATCH_UP_REPORTS: usize = 7;
+
+/// Number of best and worst tokens included in a report
+const TOP_TOKENS: i64 = 3;
+
+/// Number of most frequent rejection reasons included in a report
+const TOP_REJECTION_REASONS: i64 = 10;
+
+/// Report delivery channel
+#[derive(Debug, Clone)]
+pub enum ReportChannel {
+    /// Telegram message to the admin chat
+    Telegram {
+        /// Bot client
+        bot: Bot,
+        /// Destination chat
+        chat_id: i64,
+        /// Maximum message length
+        max_length: usize,
+    },
+    /// JSON file written to a directory
+    File {
+        /// Output directory
+        directory: PathBuf,
+    },
+    /// JSON POST to a webhook
+    Webhook {
+        /// HTTP client
+        client: reqwest::Client,
+        /// Webhook URL
+        url: String,
+    },
+}
+
+impl ReportChannel {
+    /// Build the delivery channel from configuration
+    pub fn from_config(config: &AppConfig) -> AppResult<Self> {
+        let method = config.analytics.report_delivery_method.as_deref().unwrap_or("telegram");
+
+        match method {
+            "telegram" => Ok(Self::Telegram {
+                bot: Bot::new(config.telegram.bot_token.clone()),
+                chat_id: config.telegram.admin_chat_id,
+                max_length: config.telegram.max_message_length,
+            }),
+            "file" => {
+                let directory = config.analytics.report_output_dir.as_ref()
+                    .ok_or_else(|| AppError::config("File report delivery requires report_output_dir"))?;
+                Ok(Self::File { directory: PathBuf::from(directory) })
+            }
+            "webhook" => {
+                let url = config.analytics.report_webhook_url.clone()
+                    .ok_or_else(|| AppError::config("Webhook report delivery requires report_webhook_url"))?;
+                let client = reqwest::Client::builder()
+                    .timeout(Duration::from_secs(10))
+                    .build()
+                    .map_err(|e| AppError::network(format!("Failed to create webhook client: {}", e)))?;
+                Ok(Self::Webhook { client, url })
+            }
+            other => Err(AppError::config(format!("Unsupported report delivery method: {}", other))),
+        }
+    }
+
+    /// Delivery method name
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::Telegram { .. } => "telegram",
+            Self::File { .. } => "file",
+            Self::Webhook { .. } => "webhook",
+        }
+    }
+
+    /// Deliver a report
+    pub async fn deliver(&self, report: &PeriodReport) -> AppResult<()> {
+        match self {
+            Self::Telegram { bot, chat_id, max_length } => {
+                let mut text = report.to_text();
+                if text.len() > *max_length {
+                    let mut cut = *max_length;
+                    while !text.is_char_boundary(cut) {
+                        cut -= 1;
+                    }
+                    text.truncate(cut);
+                }
+
+                bot.send_message(ChatId(*chat_id), text)
+                    .await
+                    .map_err(|e| AppError::network(format!("Failed to send Telegram report: {}", e)))?;
+            }
+            Self::File { directory } => {
+                tokio::fs::create_dir_all(directory).await?;
+
+                let path = directory.join(format!(
+                    "{}_{}.json",
+                    report.period.as_str(),
+                    report.period_start.format("%Y%m%d"),
+                ));
+                let body = serde_json::to_vec_pretty(report)?;
+                tokio::fs::write(&path, body).await?;
+
+                debug!("Report written to {}", path.display());
+            }
+            Self::Webhook { client, url } => {
+                let response = client.post(url).json(report).send().await?;
+
+                if !response.status().is_success() {
+                    return Err(AppError::network(format!(
+                        "Report webhook returned status {}", response.status()
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Report schedule derived from analytics configuration
+#[derive(Debug, Clone)]
+pub struct ReportSchedule {
+    /// Enabled report periods
+    pub periods: Vec<ReportPeriod>,
+    /// Time of day reports are due (UTC)
+    pub report_time: NaiveTime,
+    /// Day weekly reports are due
+    pub weekly_day: Weekday,
+}
+
+impl ReportSchedule {
+    /// Build the schedule from configuration
+    pub fn from_config(config: &AnalyticsConfig) -> AppResult<Self> {
+        let report_time = match config.daily_report_time.as_deref() {
+            Some(time) => NaiveTime::parse_from_str(time, "%H:%M")
+                .map_err(|e| AppError::config(format!("Invalid daily_report_time '{}': {}", time, e)))?,
+            None => NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default(),
+        };
+
+        let weekly_day = match config.weekly_report_day.as_deref() {
+            Some(day) => day.parse::<Weekday>()
+                .map_err(|_| AppError::config(format!("Invalid weekly_report_day '{}'", day)))?,
+            None => Weekday::Mon,
+        };
+
+        let mut periods = Vec::new();
+        if config.enable_daily_reports {
+            periods.push(ReportPeriod::Daily);
+        }
+        if config.enable_weekly_reports {
+            periods.push(ReportPeriod::Weekly);
+        }
+        if config.enable_monthly_reports {
+            periods.push(ReportPeriod::Monthly);
+        }
+
+        Ok(Self { periods, report_time, weekly_day })
+    }
+
+    /// Whether a report of the given period is due on a date
+    fn is_due_date(&self, period: ReportPeriod, date: NaiveDate) -> bool {
+        match period {
+            ReportPeriod::Daily => true,
+            ReportPeriod::Weekly => date.weekday() == self.weekly_day,
+            ReportPeriod::Monthly => date.day() == 1,
+        }
+    }
+
+    /// Due times in `(after, until]` for a period
+    pub fn due_times(
+        &self,
+        period: ReportPeriod,
+        after: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        let mut due = Vec::new();
+        let mut date = after.date_naive();
+
+        while date <= until.date_naive() {
+            if self.is_due_date(period, date) {
+                let at = date.and_time(self.report_time).and_utc();
+                if at > after && at <= until {
+                    due.push(at);
+                }
+            }
+
+            match date.checked_add_days(Days::new(1)) {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+
+        due
+    }
+
+    /// Reporting window covered by a report due at `due`
+    ///
+    /// Windows end at midnight UTC on the due date.
+    pub fn bounds(period: ReportPeriod, due: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let end_date = due.date_naive();
+        let start_date = match period {
+            ReportPeriod::Daily => end_date - Days::new(1),
+            ReportPeriod::Weekly => end_date - Days::new(7),
+            ReportPeriod::Monthly => (end_date - Months::new(1)).with_day(1).unwrap_or(end_date),
+        };
+
+        (
+            start_date.and_time(NaiveTime::MIN).and_utc(),
+            end_date.and_time(NaiveTime::MIN).and_utc(),
+        )
+    }
+
+    /// Longest lookback used when there is no delivery history
+    fn initial_lookback(period: ReportPeriod) -> chrono::Duration {
+        match period {
+            ReportPeriod::Daily => chrono::Duration::days(1),
+            ReportPeriod::Weekly => chrono::Duration::days(7),
+            ReportPeriod::Monthly => chrono::Duration::days(31),
+        }
+    }
+}
+
+/// Report scheduler service
+#[derive(Debug, Clone)]
+pub struct ReportScheduler {
+    /// Report schedule
+    schedule: Arc<ReportSchedule>,
+    /// PostgreSQL service
+    postgres: PostgresService,
+    /// Delivery channel
+    channel: Arc<ReportChannel>,
+    /// Scheduler running
+    is_running: Arc<RwLock<bool>>,
+}
+
+impl ReportScheduler {
+    /// Create a new report scheduler
+    #[instrument(skip(config, postgres))]
+    pub fn new(config: &AppConfig, postgres: PostgresService) -> AppResult<Self> {
+        info!("🗓️  Initializing report scheduler");
+
+        let schedule = ReportSchedule::from_config(&config.analytics)?;
+        let channel = ReportChannel::from_config(config)?;
+
+        info!("✅ Report scheduler initialized ({:?} via {})", schedule.periods, channel.method());
+
+        Ok(Self {
+            schedule: Arc::new(schedule),
+            postgres,
+            channel: Arc::new(channel),
+            is_running: Arc::new(RwLock::new(false)),
+        })
+    }
+
+    /// Start the scheduler, catching up missed reports first
+    pub async fn start(&self) -> AppResult<()> {
+        if self.schedule.periods.is_empty() {
+            info!("No scheduled reports enabled");
+            return Ok(());
+        }
+
+        {
+            let mut running = self.is_running.write().await;
+            if *running {
+                return Err(AppError::internal("Report scheduler already running"));
+            }
+            *running = true;
+        }
+
+        let scheduler = self.clone();
+
+        tokio::spawn(async move {
+            info!("🗓️  Report scheduler task started");
+
+            let mut ticker = tokio::time::interval(SCHEDULER_TICK);
+
+            loop {
+                ticker.tick().await;
+
+                if !*scheduler.is_running.read().await {
+                    break;
+                }
+
+                match scheduler.run_due_reports(Utc::now()).await {
+                    Ok(0) => {}
+                    Ok(count) => info!("📨 Delivered {} scheduled report(s)", count),
+                    Err(e) => error!("Scheduled report run failed: {}", e),
+                }
+            }
+
+            info!("Report scheduler task ended");
+        });
+
+        Ok(())
+    }
+
+    /// Stop the scheduler
+    pub async fn stop(&self) {
+        *self.is_running.write().await = false;
+    }
+
+    /// Build and deliver every report due up to `now`
+    ///
+    /// A failing period is logged and retried next tick without holding
+    /// back the other periods. Returns the number of reports delivered.
+    #[instrument(skip(self))]
+    pub async fn run_due_reports(&self, now: DateTime<Utc>) -> AppResult<usize> {
+        let mut delivered = 0;
+
+        for period in &self.schedule.periods {
+            match self.run_due_period(*period, now).await {
+                Ok(count) => delivered += count,
+                Err(e) => error!("Failed to deliver {} report: {}", period.as_str(), e),
+            }
+        }
+
+        Ok(delivered)
+    }
+
+    /// Build and deliver the reports of one period due up to `now`
+    async fn run_due_period(&self, period: ReportPeriod, now: DateTime<Utc>) -> AppResult<usize> {
+        let last_run = self.last_scheduled_for(period).await?;
+
+        let mut due = match last_run {
+            Some(last) => self.schedule.due_times(period, last, now),
+            None => {
+                // No history: only deliver the most recent schedule
+                let after = now - ReportSchedule::initial_lookback(period);
+                self.schedule.due_times(period, after, now).into_iter().last().into_iter().collect()
+            }
+        };
+
+        if due.len() > MAX_CATCH_UP_REPORTS {
+            warn!("Skipping {} missed {} report(s), catching up the latest {}",
+                  due.len() - MAX_CATCH_UP_REPORTS, period.as_str(), MAX_CATCH_UP_REPORTS);
+            due.drain(0..due.len() - MAX_CATCH_UP_REPORTS);
+        }
+
+        let mut delivered = 0;
+        for scheduled_for in due {
+            if last_run.is_some() && scheduled_for < now - chrono::Duration::minutes(2) {
+                info!("⏪ Catching up missed {} report scheduled for {}", period.as_str(), scheduled_for);
+            }
+
+            let (start, end) = ReportSchedule::bounds(period, scheduled_for);
+            let report = self.build_report(period, start, end).await?;
+
+            // Stop on delivery failure so the schedule is retried next tick
+            self.channel.deliver(&report).await?;
+            self.record_run(scheduled_for, &report).await?;
+
+            delivered += 1;
+        }
+
+        Ok(delivered)
+    }
+
+    /// Build a report for a period window
+    #[instrument(skip(self))]
+    pub async fn build_report(
+        &self,
+        period: ReportPeriod,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> AppResult<PeriodReport> {
+        let pool = self.postgres.pool();
+
+        let summary = sqlx::query(
+            "SELECT
+                COUNT(*) AS total_trades,
+                COUNT(*) FILTER (WHERE pnl_sol > 0) AS winning_trades,
+                COUNT(*) FILTER (WHERE pnl_sol < 0) AS losing_trades,
+                COALESCE(SUM(pnl_sol), 0) AS realized_pnl,
+                COALESCE(SUM(gas_fee_sol), 0) AS fees,
+                percentile_cont(0.5) WITHIN GROUP (ORDER BY execution_time_ms) AS p50,
+                percentile_cont(0.9) WITHIN GROUP (ORDER BY execution_time_ms) AS p90,
+                percentile_cont(0.99) WITHIN GROUP (ORDER BY execution_time_ms) AS p99
+             FROM trades
+             WHERE status = 'executed' AND executed_at >= $1 AND executed_at < $2"
+        )
+            .bind(start)
+            .bind(end)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::database(format!("Failed to query trade summary: {}", e), "report_summary"))?;
+
+        let get_count = |name: &str| summary.try_get::<i64, _>(name).unwrap_or(0).max(0) as u64;
+        let total_trades = get_count("total_trades");
+        let winning_trades = get_count("winning_trades");
+        let losing_trades = get_count("losing_trades");
+
+        let closed = winning_trades + losing_trades;
+        let win_rate_percent = if closed > 0 {
+            Decimal::from(winning_trades) * Decimal::ONE_HUNDRED / Decimal::from(closed)
+        } else {
+            Decimal::ZERO
+        };
+
+        let best_tokens = self.query_token_performance(start, end, "DESC").await?;
+        let worst_tokens = self.query_token_performance(start, end, "ASC").await?
+            .into_iter()
+            .filter(|t| t.pnl_sol < Decimal::ZERO)
+            .collect();
+
+        let rejections = self.query_rejection_stats(start, end).await?;
+        let filter_attribution = query_filter_attribution(&self.postgres, start, end).await?;
+
+        Ok(PeriodReport {
+            period,
+            period_start: start,
+            period_end: end,
+            total_trades,
+            winning_trades,
+            losing_trades,
+            win_rate_percent,
+            realized_pnl_sol: summary.try_get("realized_pnl").unwrap_or_default(),
+            fees_sol: summary.try_get("fees").unwrap_or_default(),
+            best_tokens: best_tokens.into_iter().filter(|t| t.pnl_sol > Decimal::ZERO).collect(),
+            worst_tokens,
+            latency: LatencyPercentiles {
+                p50_ms: summary.try_get("p50").unwrap_or(None),
+                p90_ms: summary.try_get("p90").unwrap_or(None),
+                p99_ms: summary.try_get("p99").unwrap_or(None),
+            },
+            rejections,
+            filter_attribution,
+            generated_at: Utc::now(),
+        })
+    }
+
+    /// Query per-token realized PnL ordered by PnL
+    async fn query_token_performance(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        order: &str,
+    ) -> AppResult<Vec<TokenPerformance>> {
+        let query = format!(
+            "SELECT tk.address, tk.symbol, SUM(t.pnl_sol) AS pnl, COUNT(*) AS trades
+             FROM trades t
+             JOIN tokens tk ON tk.id = t.token_id
+             WHERE t.status = 'executed' AND t.pnl_sol IS NOT NULL
+               AND t.executed_at >= $1 AND t.executed_at < $2
+             GROUP BY tk.address, tk.symbol
+             ORDER BY pnl {}
+             LIMIT $3",
+            if order == "ASC" { "ASC" } else { "DESC" }
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(start)
+            .bind(end)
+            .bind(TOP_TOKENS)
+            .fetch_all(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to query token performance: {}", e), "report_tokens"))?;
+
+        Ok(rows.into_iter()
+            .map(|row| TokenPerformance {
+                address: row.try_get("address").unwrap_or_default(),
+                symbol: row.try_get("symbol").unwrap_or(None),
+                pnl_sol: row.try_get("pnl").unwrap_or_default(),
+                trades: row.try_get::<i64, _>("trades").unwrap_or(0).max(0) as u64,
+            })
+            .collect())
+    }
+
+    /// Query filter evaluation outcomes and the most frequent rejection reasons
+    async fn query_rejection_stats(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> AppResult<RejectionStats> {
+        let totals = sqlx::query(
+            "SELECT
+                COUNT(*) AS evaluated,
+                COUNT(*) FILTER (WHERE passed) AS passed
+             FROM filter_evaluations
+             WHERE evaluated_at >= $1 AND evaluated_at < $2"
+        )
+            .bind(start)
+            .bind(end)
+            .fetch_one(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to query rejection stats: {}", e), "report_rejections"))?;
+
+        let reasons = sqlx::query(
+            "SELECT c.filter_name, c.details, COUNT(*) AS rejections
+             FROM filter_checks c
+             JOIN filter_evaluations e ON e.id = c.evaluation_id
+             WHERE e.evaluated_at >= $1 AND e.evaluated_at < $2
+               AND c.blocking AND NOT c.passed
+             GROUP BY c.filter_name, c.details
+             ORDER BY rejections DESC, c.filter_name
+             LIMIT $3"
+        )
+            .bind(start)
+            .bind(end)
+            .bind(TOP_REJECTION_REASONS)
+            .fetch_all(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to query rejection reasons: {}", e), "report_rejections"))?;
+
+        let evaluated = totals.try_get::<i64, _>("evaluated").unwrap_or(0).max(0) as u64;
+        let passed = totals.try_get::<i64, _>("passed").unwrap_or(0).max(0) as u64;
+
+        Ok(RejectionStats {
+            tokens_evaluated: evaluated,
+            tokens_passed: passed,
+            tokens_rejected: evaluated.saturating_sub(passed),
+            by_reason: reasons.into_iter()
+                .map(|row| RejectionReason {
+                    filter_name: row.try_get("filter_name").unwrap_or_default(),
+                    reason: row.try_get("details").unwrap_or(None),
+                    count: row.try_get::<i64, _>("rejections").unwrap_or(0).max(0) as u64,
+                })
+                .collect(),
+        })
+    }
+
+    /// Most recent delivered schedule for a period
+    async fn last_scheduled_for(&self, period: ReportPeriod) -> AppResult<Option<DateTime<Utc>>> {
+        let row = sqlx::query("SELECT MAX(scheduled_for) AS last FROM report_runs WHERE report_type = $1")
+            .bind(period.as_str())
+            .fetch_one(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to query report runs: {}", e), "report_runs"))?;
+
+        Ok(row.try_get("last").unwrap_or(None))
+    }
+
+    /// Record a delivered report
+    async fn record_run(&self, scheduled_for: DateTime<Utc>, report: &PeriodReport) -> AppResult<()> {
+        sqlx::query(
+            "INSERT INTO report_runs (report_type, scheduled_for, period_start, period_end, delivery_method, report)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (report_type, scheduled_for) DO NOTHING"
+        )
+            .bind(report.period.as_str())
+            .bind(scheduled_for)
+            .bind(report.period_start)
+            .bind(report.period_end)
+            .bind(self.channel.method())
+            .bind(serde_json::to_value(report)?)
+            .execute(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to record report run: {}", e), "record_report_run"))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigLoader;
+    use chrono::TimeZone;
+
+    fn schedule() -> ReportSchedule {
+        ReportSchedule {
+            periods: vec![ReportPeriod::Daily, ReportPeriod::Weekly, ReportPeriod::Monthly],
+            report_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            weekly_day: Weekday::Mon,
+        }
+    }
+
+    fn empty_report() -> PeriodReport {
+        PeriodReport {
+            period: ReportPeriod::Daily,
+            period_start: Utc.with_ymd_and_hms(2024, 6, 2, 0, 0, 0).unwrap(),
+            period_end: Utc.with_ymd_and_hms(2024, 6, 3, 0, 0, 0).unwrap(),
+            total_trades: 0,
+            winning_trades: 0,
+            losing_trades: 0,
+            win_rate_percent: Decimal::ZERO,
+            realized_pnl_sol: Decimal::ZERO,
+            fees_sol: Decimal::ZERO,
+            best_tokens: vec![],
+            worst_tokens: vec![],
+            latency: LatencyPercentiles::default(),
+            rejections: RejectionStats::default(),
+            filter_attribution: vec![],
+            generated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_missed_daily_schedules_are_listed() {
+        // Last delivered Jun 1 09:00, back up Jun 4 10:00 → Jun 2, 3, 4 are due
+        let after = Utc.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 6, 4, 10, 0, 0).unwrap();
+
+        let due = schedule().due_times(ReportPeriod::Daily, after, until);
+        assert_eq!(due.len(), 3);
+        assert_eq!(due[0], Utc.with_ymd_and_hms(2024, 6, 2, 9, 0, 0).unwrap());
+        assert_eq!(due[2], Utc.with_ymd_and_hms(2024, 6, 4, 9, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_weekly_and_monthly_due_dates() {
+        let after = Utc.with_ymd_and_hms(2024, 5, 25, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 6, 12, 0, 0, 0).unwrap();
+
+        // Mondays: May 27, Jun 3, Jun 10
+        let weekly = schedule().due_times(ReportPeriod::Weekly, after, until);
+        assert_eq!(weekly.len(), 3);
+        assert!(weekly.iter().all(|d| d.weekday() == Weekday::Mon));
+
+        let monthly = schedule().due_times(ReportPeriod::Monthly, after, until);
+        assert_eq!(monthly, vec![Utc.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap()]);
+    }
+
+    #[test]
+    fn test_report_bounds() {
+        let due = Utc.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap();
+
+        let (start, end) = ReportSchedule::bounds(ReportPeriod::Monthly, due);
+        assert_eq!(start, Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap());
+
+        let (start, _) = ReportSchedule::bounds(ReportPeriod::Weekly, due);
+        assert_eq!(start, Utc.with_ymd_and_hms(2024, 5, 25, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_schedule_from_config() {
+        let mut config = ConfigLoader::new().without_env().create_default_config();
+        config.analytics.enable_daily_reports = true;
+        config.analytics.daily_report_time = Some("18:30".to_string());
+        config.analytics.weekly_report_day = Some("friday".to_string());
+
+        let schedule = ReportSchedule::from_config(&config.analytics).unwrap();
+        assert_eq!(schedule.periods, vec![ReportPeriod::Daily]);
+        assert_eq!(schedule.report_time, NaiveTime::from_hms_opt(18, 30, 0).unwrap());
+        assert_eq!(schedule.weekly_day, Weekday::Fri);
+
+        config.analytics.daily_report_time = Some("25:99".to_string());
+        assert!(ReportSchedule::from_config(&config.analytics).is_err());
+    }
+
+    #[test]
+    fn test_report_text_lists_rejection_reasons() {
+        let mut report = empty_report();
+        report.rejections = RejectionStats {
+            tokens_evaluated: 10,
+            tokens_passed: 3,
+            tokens_rejected: 7,
+            by_reason: vec![
+                RejectionReason {
+                    filter_name: "liquidity".to_string(),
+                    reason: Some("Liquidity below minimum".to_string()),
+                    count: 5,
+                },
+                RejectionReason { filter_name: "honeypot".to_string(), reason: None, count: 2 },
+            ],
+        };
+
+        let text = report.to_text();
+        assert!(text.contains("Tokens evaluated: 10 | passed: 3 | rejected: 7"));
+        assert!(text.contains("• liquidity: Liquidity below minimum (5)"));
+        assert!(text.contains("• honeypot: check failed (2)"));
+    }
+
+    #[tokio::test]
+    async fn test_file_delivery() {
+        let dir = tempfile::tempdir().unwrap();
+        let channel = ReportChannel::File { directory: dir.path().to_path_buf() };
+
+        channel.deliver(&empty_report()).await.unwrap();
+
+        let written = std::fs::read_to_string(dir.path().join("daily_20240602.json")).unwrap();
+        let parsed: PeriodReport = serde_json::from_str(&written).unwrap();
+        assert_eq!(parsed.period, ReportPeriod::Daily);
+    }
+}
@@ -0,0 +1,766 @@
+//! Trading performance tracker
+//!
+//! This module keeps an in-process view of session performance: positions
+//! marked to market, realized and unrealized PnL per session and per
+//! wallet, fees, and the equity curve used for peak-to-trough drawdown.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn, instrument};
+
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use crate::core::types::{SessionId, TokenAddress};
+use crate::infrastructure::database::PostgresService;
+use crate::infrastructure::monitoring::MetricsService;
+use sqlx::Row;
+use super::models::{EquityPoint, PnlSnapshot, PositionSnapshot, TradeFill, TradeSide, WalletPnl};
+
+/// Maximum number of equity curve points kept in memory
+const MAX_EQUITY_POINTS: usize = 10_000;
+
+/// Source of current token prices for mark-to-market
+#[async_trait::async_trait]
+pub trait PriceSource: Send + Sync + std::fmt::Debug {
+    /// Get the current price of a token in SOL, if known
+    async fn get_price_sol(&self, token: &TokenAddress) -> AppResult<Option<Decimal>>;
+
+    /// A position in a token was opened; sources that stream prices start here
+    fn position_opened(self: Arc<Self>, _token: &TokenAddress) {}
+
+    /// The last position in a token was closed
+    fn position_closed(self: Arc<Self>, _token: &TokenAddress) {}
+}
+
+/// In-process performance tracker for a trading session
+#[derive(Debug, Clone)]
+pub struct PerformanceTracker {
+    /// Trading session
+    session_id: SessionId,
+    /// Tracker state
+    state: Arc<RwLock<TrackerState>>,
+    /// Metrics service
+    metrics: Arc<MetricsService>,
+    /// Price source told about opened and closed positions, once started
+    prices: Arc<RwLock<Option<Arc<dyn PriceSource>>>>,
+    /// Periodic update task running
+    is_running: Arc<RwLock<bool>>,
+}
+
+/// Tracker state
+#[derive(Debug)]
+struct TrackerState {
+    starting_capital_sol: Decimal,
+    realized_pnl_sol: Decimal,
+    fees_sol: Decimal,
+    positions: HashMap<PositionKey, OpenPosition>,
+    wallets: BTreeMap<String, WalletTotals>,
+    total_trades: u64,
+    profitable_trades: u64,
+    drawdown: DrawdownTracker,
+    equity_curve: Vec<EquityPoint>,
+}
+
+/// Positions are held per wallet and token
+type PositionKey = (String, String);
+
+/// Realized results of one wallet
+#[derive(Debug, Clone, Default)]
+struct WalletTotals {
+    realized_pnl_sol: Decimal,
+    fees_sol: Decimal,
+    total_trades: u64,
+    profitable_trades: u64,
+}
+
+/// Open position with average-cost accounting
+#[derive(Debug, Clone)]
+struct OpenPosition {
+    wallet: String,
+    token: TokenAddress,
+    quantity: Decimal,
+    cost_basis_sol: Decimal,
+    mark_price_sol: Decimal,
+    opened_at: chrono::DateTime<Utc>,
+    marked_at: chrono::DateTime<Utc>,
+}
+
+impl OpenPosition {
+    fn avg_entry_price(&self) -> Decimal {
+        if self.quantity.is_zero() {
+            Decimal::ZERO
+        } else {
+            self.cost_basis_sol / self.quantity
+        }
+    }
+
+    fn market_value(&self) -> Decimal {
+        self.quantity * self.mark_price_sol
+    }
+
+    fn unrealized_pnl(&self) -> Decimal {
+        self.market_value() - self.cost_basis_sol
+    }
+
+    fn snapshot(&self) -> PositionSnapshot {
+        PositionSnapshot {
+            wallet: self.wallet.clone(),
+            token: self.token.clone(),
+            quantity: self.quantity,
+            avg_entry_price_sol: self.avg_entry_price(),
+            mark_price_sol: self.mark_price_sol,
+            cost_basis_sol: self.cost_basis_sol,
+            market_value_sol: self.market_value(),
+            unrealized_pnl_sol: self.unrealized_pnl(),
+            opened_at: self.opened_at,
+            marked_at: self.marked_at,
+        }
+    }
+}
+
+/// Peak-to-trough drawdown on the equity curve
+#[derive(Debug, Clone, Default)]
+struct DrawdownTracker {
+    peak_equity: Decimal,
+    current_percent: Decimal,
+    max_percent: Decimal,
+}
+
+impl DrawdownTracker {
+    fn new(starting_equity: Decimal) -> Self {
+        Self {
+            peak_equity: starting_equity,
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, equity: Decimal) {
+        if equity > self.peak_equity {
+            self.peak_equity = equity;
+        }
+
+        self.current_percent = if self.peak_equity > Decimal::ZERO {
+            ((self.peak_equity - equity) / self.peak_equity * Decimal::ONE_HUNDRED).max(Decimal::ZERO)
+        } else {
+            Decimal::ZERO
+        };
+
+        self.max_percent = self.max_percent.max(self.current_percent);
+    }
+}
+
+impl TrackerState {
+    /// Whether any wallet has a position in a token
+    fn holds(&self, token: &TokenAddress) -> bool {
+        self.positions.values().any(|p| &p.token == token)
+    }
+
+    fn unrealized_pnl(&self) -> Decimal {
+        self.positions.values().map(OpenPosition::unrealized_pnl).sum()
+    }
+
+    fn equity(&self) -> Decimal {
+        self.starting_capital_sol + self.realized_pnl_sol + self.unrealized_pnl() - self.fees_sol
+    }
+
+    /// Record the current equity on the curve and update drawdown
+    fn record_equity(&mut self) {
+        let equity = self.equity();
+        self.drawdown.update(equity);
+
+        self.equity_curve.push(EquityPoint {
+            timestamp: Utc::now(),
+            equity_sol: equity,
+        });
+
+        if self.equity_curve.len() > MAX_EQUITY_POINTS {
+            let excess = self.equity_curve.len() - MAX_EQUITY_POINTS;
+            self.equity_curve.drain(0..excess);
+        }
+    }
+
+    fn wallet_pnl(&self) -> Vec<WalletPnl> {
+        self.wallets.iter()
+            .map(|(wallet, totals)| {
+                let unrealized: Decimal = self.positions.values()
+                    .filter(|p| &p.wallet == wallet)
+                    .map(OpenPosition::unrealized_pnl)
+                    .sum();
+
+                WalletPnl {
+                    wallet: wallet.clone(),
+                    realized_pnl_sol: totals.realized_pnl_sol,
+                    unrealized_pnl_sol: unrealized,
+                    fees_sol: totals.fees_sol,
+                    net_pnl_sol: totals.realized_pnl_sol + unrealized - totals.fees_sol,
+                    total_trades: totals.total_trades,
+                    profitable_trades: totals.profitable_trades,
+                }
+            })
+            .collect()
+    }
+}
+
+impl PerformanceTracker {
+    /// Create a new performance tracker
+    pub fn new(session_id: SessionId, starting_capital_sol: Decimal, metrics: Arc<MetricsService>) -> Self {
+        info!("📈 Initializing performance tracker for session {}", session_id);
+
+        let mut state = TrackerState {
+            starting_capital_sol,
+            realized_pnl_sol: Decimal::ZERO,
+            fees_sol: Decimal::ZERO,
+            positions: HashMap::new(),
+            wallets: BTreeMap::new(),
+            total_trades: 0,
+            profitable_trades: 0,
+            drawdown: DrawdownTracker::new(starting_capital_sol),
+            equity_curve: Vec::new(),
+        };
+        state.record_equity();
+
+        Self {
+            session_id,
+            state: Arc::new(RwLock::new(state)),
+            metrics,
+            prices: Arc::new(RwLock::new(None)),
+            is_running: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    /// Record an executed trade fill
+    #[instrument(skip(self))]
+    pub async fn record_fill(&self, fill: TradeFill) -> AppResult<()> {
+        if fill.quantity <= Decimal::ZERO {
+            return Err(AppError::validation("Fill quantity must be positive"));
+        }
+        if fill.price_sol < Decimal::ZERO || fill.fee_sol < Decimal::ZERO {
+            return Err(AppError::validation("Fill price and fee cannot be negative"));
+        }
+
+        let mut state = self.state.write().await;
+        let key = (fill.wallet.clone(), fill.token.to_string());
+
+        // Reject before touching any counter, so a bad fill leaves no trace
+        if fill.side == TradeSide::Sell && !state.positions.contains_key(&key) {
+            return Err(AppError::trading(format!(
+                "No open position for token {} in wallet {}", fill.token, fill.wallet
+            )));
+        }
+
+        let held_before = state.holds(&fill.token);
+
+        let realized = match fill.side {
+            TradeSide::Buy => {
+                let position = state.positions.entry(key).or_insert_with(|| OpenPosition {
+                    wallet: fill.wallet.clone(),
+                    token: fill.token.clone(),
+                    quantity: Decimal::ZERO,
+                    cost_basis_sol: Decimal::ZERO,
+                    mark_price_sol: fill.price_sol,
+                    opened_at: fill.executed_at,
+                    marked_at: fill.executed_at,
+                });
+
+                position.quantity += fill.quantity;
+                position.cost_basis_sol += fill.quantity * fill.price_sol;
+                position.mark_price_sol = fill.price_sol;
+                position.marked_at = fill.executed_at;
+                None
+            }
+            TradeSide::Sell => {
+                let position = state.positions.get_mut(&key)
+                    .ok_or_else(|| AppError::internal("Position vanished while locked"))?;
+
+                let quantity = if fill.quantity > position.quantity {
+                    warn!("Sell quantity {} exceeds position {} for {}, clamping",
+                          fill.quantity, position.quantity, fill.token);
+                    position.quantity
+                } else {
+                    fill.quantity
+                };
+
+                let released_cost = position.cost_basis_sol * quantity / position.quantity;
+                let realized = quantity * fill.price_sol - released_cost;
+
+                position.quantity -= quantity;
+                position.cost_basis_sol -= released_cost;
+                position.mark_price_sol = fill.price_sol;
+                position.marked_at = fill.executed_at;
+
+                if position.quantity.is_zero() {
+                    state.positions.remove(&key);
+                }
+
+                debug!("Realized {} SOL on {} in wallet {}", realized, fill.token, fill.wallet);
+                Some(realized)
+            }
+        };
+
+        let profitable = realized.is_some_and(|r| r > Decimal::ZERO);
+        let realized = realized.unwrap_or_default();
+
+        state.fees_sol += fill.fee_sol;
+        state.total_trades += 1;
+        state.realized_pnl_sol += realized;
+        if profitable {
+            state.profitable_trades += 1;
+        }
+
+        let wallet = state.wallets.entry(fill.wallet.clone()).or_default();
+        wallet.fees_sol += fill.fee_sol;
+        wallet.total_trades += 1;
+        wallet.realized_pnl_sol += realized;
+        if profitable {
+            wallet.profitable_trades += 1;
+        }
+
+        state.record_equity();
+        let held_after = state.holds(&fill.token);
+        drop(state);
+
+        if held_before != held_after {
+            if let Some(prices) = self.prices.read().await.clone() {
+                if held_after {
+                    prices.position_opened(&fill.token);
+                } else {
+                    prices.position_closed(&fill.token);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Update the mark price of a token's open positions in every wallet
+    pub async fn mark_price(&self, token: &TokenAddress, price_sol: Decimal) {
+        let mut state = self.state.write().await;
+        let now = Utc::now();
+
+        let mut marked = false;
+        for position in state.positions.values_mut().filter(|p| &p.token == token) {
+            position.mark_price_sol = price_sol;
+            position.marked_at = now;
+            marked = true;
+        }
+
+        if marked {
+            state.record_equity();
+        }
+    }
+
+    /// Mark all open positions to market using a price source
+    ///
+    /// Returns the number of positions that were re-priced.
+    pub async fn mark_to_market(&self, prices: &dyn PriceSource) -> AppResult<usize> {
+        let tokens: HashSet<TokenAddress> = {
+            let state = self.state.read().await;
+            state.positions.values().map(|p| p.token.clone()).collect()
+        };
+
+        let mut marked = 0;
+        for token in tokens {
+            match prices.get_price_sol(&token).await {
+                Ok(Some(price)) => {
+                    self.mark_price(&token, price).await;
+                    marked += 1;
+                }
+                Ok(None) => debug!("No price available for {}", token),
+                Err(e) => warn!("Failed to price {}: {}", token, e),
+            }
+        }
+
+        Ok(marked)
+    }
+
+    /// Get a PnL snapshot
+    pub async fn snapshot(&self) -> PnlSnapshot {
+        let state = self.state.read().await;
+        let unrealized = state.unrealized_pnl();
+
+        PnlSnapshot {
+            session_id: self.session_id,
+            starting_capital_sol: state.starting_capital_sol,
+            realized_pnl_sol: state.realized_pnl_sol,
+            unrealized_pnl_sol: unrealized,
+            fees_sol: state.fees_sol,
+            net_pnl_sol: state.realized_pnl_sol + unrealized - state.fees_sol,
+            equity_sol: state.equity(),
+            peak_equity_sol: state.drawdown.peak_equity,
+            current_drawdown_percent: state.drawdown.current_percent,
+            max_drawdown_percent: state.drawdown.max_percent,
+            total_trades: state.total_trades,
+            profitable_trades: state.profitable_trades,
+            open_positions: state.positions.values().map(OpenPosition::snapshot).collect(),
+            wallets: state.wallet_pnl(),
+            taken_at: Utc::now(),
+        }
+    }
+
+    /// Get the recorded equity curve
+    pub async fn equity_curve(&self) -> Vec<EquityPoint> {
+        self.state.read().await.equity_curve.clone()
+    }
+
+    /// Publish PnL gauges through the metrics service
+    pub async fn publish_gauges(&self) {
+        let snapshot = self.snapshot().await;
+
+        let mut labels = HashMap::new();
+        labels.insert("session_id".to_string(), self.session_id.to_string());
+
+        let gauges = [
+            ("sniper_bot_realized_pnl_sol", snapshot.realized_pnl_sol),
+            ("sniper_bot_unrealized_pnl_sol", snapshot.unrealized_pnl_sol),
+            ("sniper_bot_fees_sol", snapshot.fees_sol),
+            ("sniper_bot_net_pnl_sol", snapshot.net_pnl_sol),
+            ("sniper_bot_equity_sol", snapshot.equity_sol),
+            ("sniper_bot_drawdown_percent", snapshot.current_drawdown_percent),
+            ("sniper_bot_max_drawdown_percent", snapshot.max_drawdown_percent),
+        ];
+
+        for (name, value) in gauges {
+            self.metrics.set_gauge(name, value.to_f64().unwrap_or_default(), Some(labels.clone()));
+        }
+
+        self.metrics.set_gauge(
+            "sniper_bot_open_positions",
+            snapshot.open_positions.len() as f64,
+            Some(labels.clone()),
+        );
+
+        for wallet in &snapshot.wallets {
+            let mut wallet_labels = labels.clone();
+            wallet_labels.insert("wallet".to_string(), wallet.wallet.clone());
+
+            self.metrics.set_gauge(
+                "sniper_bot_wallet_net_pnl_sol",
+                wallet.net_pnl_sol.to_f64().unwrap_or_default(),
+                Some(wallet_labels),
+            );
+        }
+    }
+
+    /// Restore session counters stored by an earlier run
+    ///
+    /// Without this, the first `persist` after a restart would overwrite the
+    /// stored trade counts, realized PnL and fees with this run's values.
+    #[instrument(skip(self, postgres))]
+    pub async fn restore(&self, postgres: &PostgresService) -> AppResult<()> {
+        let row = sqlx::query(
+            "SELECT total_trades, profitable_trades, realized_pnl_sol, total_fees_sol,
+                    peak_equity_sol, max_drawdown
+             FROM sessions
+             WHERE id = $1"
+        )
+            .bind(self.session_id.0)
+            .fetch_optional(postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to load session stats: {}", e), "load_session_stats"))?;
+
+        let Some(row) = row else {
+            return Ok(());
+        };
+
+        let column = |e: sqlx::Error| AppError::database(e.to_string(), "load_session_stats");
+        let total_trades: Option<i32> = row.try_get("total_trades").map_err(column)?;
+        let profitable_trades: Option<i32> = row.try_get("profitable_trades").map_err(column)?;
+        let realized_pnl: Option<Decimal> = row.try_get("realized_pnl_sol").map_err(column)?;
+        let fees: Option<Decimal> = row.try_get("total_fees_sol").map_err(column)?;
+        let peak_equity: Option<Decimal> = row.try_get("peak_equity_sol").map_err(column)?;
+        let max_drawdown: Option<Decimal> = row.try_get("max_drawdown").map_err(column)?;
+
+        let mut state = self.state.write().await;
+        state.total_trades += total_trades.unwrap_or_default().max(0) as u64;
+        state.profitable_trades += profitable_trades.unwrap_or_default().max(0) as u64;
+        state.realized_pnl_sol += realized_pnl.unwrap_or_default();
+        state.fees_sol += fees.unwrap_or_default();
+        if let Some(peak) = peak_equity {
+            state.drawdown.peak_equity = state.drawdown.peak_equity.max(peak);
+        }
+        state.drawdown.max_percent = state.drawdown.max_percent.max(max_drawdown.unwrap_or_default());
+        state.record_equity();
+
+        info!("📈 Restored {} trades for session {}", state.total_trades, self.session_id);
+        Ok(())
+    }
+
+    /// Persist the current snapshot to the `sessions` table
+    #[instrument(skip(self, postgres))]
+    pub async fn persist(&self, postgres: &PostgresService) -> AppResult<()> {
+        let snapshot = self.snapshot().await;
+
+        sqlx::query(
+            "UPDATE sessions
+             SET total_trades = $2,
+                 profitable_trades = $3,
+                 starting_capital_sol = $4,
+                 realized_pnl_sol = $5,
+                 unrealized_pnl_sol = $6,
+                 total_fees_sol = $7,
+                 total_pnl = $8,
+                 peak_equity_sol = $9,
+                 max_drawdown = GREATEST(COALESCE(max_drawdown, 0), $10),
+                 updated_at = NOW()
+             WHERE id = $1"
+        )
+            .bind(self.session_id.0)
+            .bind(snapshot.total_trades as i32)
+            .bind(snapshot.profitable_trades as i32)
+            .bind(snapshot.starting_capital_sol)
+            .bind(snapshot.realized_pnl_sol)
+            .bind(snapshot.unrealized_pnl_sol)
+            .bind(snapshot.fees_sol)
+            .bind(snapshot.net_pnl_sol)
+            .bind(snapshot.peak_equity_sol)
+            .bind(snapshot.max_drawdown_percent.round_dp(4))
+            .execute(postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to update session stats: {}", e), "update_session_stats"))?;
+
+        Ok(())
+    }
+
+    /// Start periodic mark-to-market, persistence and gauge publishing
+    pub async fn start(
+        &self,
+        postgres: PostgresService,
+        prices: Arc<dyn PriceSource>,
+        interval: Duration,
+    ) -> AppResult<()> {
+        {
+            let mut running = self.is_running.write().await;
+            if *running {
+                return Err(AppError::internal("Performance tracker already running"));
+            }
+            *running = true;
+        }
+
+        if let Err(e) = self.restore(&postgres).await {
+            *self.is_running.write().await = false;
+            return Err(e);
+        }
+
+        // Positions opened before the tracker started
+        let tokens: HashSet<TokenAddress> = {
+            let state = self.state.read().await;
+            state.positions.values().map(|p| p.token.clone()).collect()
+        };
+        for token in &tokens {
+            prices.clone().position_opened(token);
+        }
+        *self.prices.write().await = Some(prices.clone());
+
+        let tracker = self.clone();
+
+        tokio::spawn(async move {
+            info!("📈 Performance tracker task started for session {}", tracker.session_id);
+
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                ticker.tick().await;
+
+                if !*tracker.is_running.read().await {
+                    break;
+                }
+
+                if let Err(e) = tracker.mark_to_market(prices.as_ref()).await {
+                    warn!("Mark-to-market failed: {}", e);
+                }
+
+                tracker.publish_gauges().await;
+
+                if let Err(e) = tracker.persist(&postgres).await {
+                    error!("Failed to persist session stats: {}", e);
+                }
+            }
+
+            info!("Performance tracker task ended for session {}", tracker.session_id);
+        });
+
+        Ok(())
+    }
+
+    /// Stop periodic updates
+    pub async fn stop(&self) {
+        *self.is_running.write().await = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigLoader;
+    use rust_decimal_macros::dec;
+
+    fn create_tracker(capital: Decimal) -> PerformanceTracker {
+        let config = ConfigLoader::new().without_env().create_default_config();
+        let metrics = Arc::new(MetricsService::new(&config).unwrap());
+        PerformanceTracker::new(SessionId::new(), capital, metrics)
+    }
+
+    fn fill(side: TradeSide, quantity: Decimal, price: Decimal, fee: Decimal) -> TradeFill {
+        TradeFill {
+            wallet: "wallet_a".to_string(),
+            token: TokenAddress::new_unchecked("So11111111111111111111111111111111111111112".to_string()),
+            side,
+            quantity,
+            price_sol: price,
+            fee_sol: fee,
+            executed_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_realized_unrealized_split() {
+        let tracker = create_tracker(dec!(10));
+
+        tracker.record_fill(fill(TradeSide::Buy, dec!(100), dec!(0.01), dec!(0.001))).await.unwrap();
+        tracker.record_fill(fill(TradeSide::Buy, dec!(100), dec!(0.03), dec!(0.001))).await.unwrap();
+
+        // Average entry is 0.02; sell half at 0.04
+        tracker.record_fill(fill(TradeSide::Sell, dec!(100), dec!(0.04), dec!(0.001))).await.unwrap();
+
+        let snapshot = tracker.snapshot().await;
+        assert_eq!(snapshot.realized_pnl_sol, dec!(2));
+        assert_eq!(snapshot.fees_sol, dec!(0.003));
+        assert_eq!(snapshot.open_positions.len(), 1);
+        assert_eq!(snapshot.open_positions[0].avg_entry_price_sol, dec!(0.02));
+
+        // Remaining 100 tokens marked at 0.04 → 2 SOL unrealized
+        assert_eq!(snapshot.unrealized_pnl_sol, dec!(2));
+        assert_eq!(snapshot.net_pnl_sol, dec!(3.997));
+        assert_eq!(snapshot.profitable_trades, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mark_to_market() {
+        let tracker = create_tracker(dec!(10));
+        let token = TokenAddress::new_unchecked("So11111111111111111111111111111111111111112".to_string());
+
+        tracker.record_fill(fill(TradeSide::Buy, dec!(100), dec!(0.01), Decimal::ZERO)).await.unwrap();
+        tracker.mark_price(&token, dec!(0.005)).await;
+
+        let snapshot = tracker.snapshot().await;
+        assert_eq!(snapshot.unrealized_pnl_sol, dec!(-0.5));
+        assert_eq!(snapshot.realized_pnl_sol, Decimal::ZERO);
+        assert_eq!(snapshot.equity_sol, dec!(9.5));
+    }
+
+    #[tokio::test]
+    async fn test_peak_to_trough_drawdown() {
+        let tracker = create_tracker(dec!(10));
+        let token = TokenAddress::new_unchecked("So11111111111111111111111111111111111111112".to_string());
+
+        tracker.record_fill(fill(TradeSide::Buy, dec!(100), dec!(0.01), Decimal::ZERO)).await.unwrap();
+
+        // Equity: 10 → 12 → 9 → 11
+        tracker.mark_price(&token, dec!(0.03)).await;
+        tracker.mark_price(&token, Decimal::ZERO).await;
+        tracker.mark_price(&token, dec!(0.02)).await;
+
+        let snapshot = tracker.snapshot().await;
+        assert_eq!(snapshot.peak_equity_sol, dec!(12));
+        assert_eq!(snapshot.max_drawdown_percent, dec!(25));
+        assert!(snapshot.current_drawdown_percent < snapshot.max_drawdown_percent);
+    }
+
+    #[tokio::test]
+    async fn test_sell_without_position_fails() {
+        let tracker = create_tracker(dec!(10));
+        let result = tracker.record_fill(fill(TradeSide::Sell, dec!(1), dec!(1), Decimal::ZERO)).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_sell_leaves_counters_untouched() {
+        let tracker = create_tracker(dec!(10));
+        let result = tracker.record_fill(fill(TradeSide::Sell, dec!(1), dec!(1), dec!(0.01))).await;
+        assert!(result.is_err());
+
+        let snapshot = tracker.snapshot().await;
+        assert_eq!(snapshot.total_trades, 0);
+        assert_eq!(snapshot.fees_sol, Decimal::ZERO);
+        assert_eq!(snapshot.equity_sol, dec!(10));
+        assert!(snapshot.wallets.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pnl_per_wallet() {
+        let tracker = create_tracker(dec!(10));
+
+        let mut other = fill(TradeSide::Buy, dec!(100), dec!(0.02), dec!(0.002));
+        other.wallet = "wallet_b".to_string();
+
+        tracker.record_fill(fill(TradeSide::Buy, dec!(100), dec!(0.01), dec!(0.001))).await.unwrap();
+        tracker.record_fill(other.clone()).await.unwrap();
+
+        // Selling from wallet B must not touch wallet A's position
+        other.side = TradeSide::Sell;
+        other.price_sol = dec!(0.03);
+        tracker.record_fill(other).await.unwrap();
+
+        let snapshot = tracker.snapshot().await;
+        assert_eq!(snapshot.open_positions.len(), 1);
+        assert_eq!(snapshot.open_positions[0].wallet, "wallet_a");
+
+        let a = snapshot.wallets.iter().find(|w| w.wallet == "wallet_a").unwrap();
+        assert_eq!(a.realized_pnl_sol, Decimal::ZERO);
+        assert_eq!(a.total_trades, 1);
+
+        let b = snapshot.wallets.iter().find(|w| w.wallet == "wallet_b").unwrap();
+        assert_eq!(b.realized_pnl_sol, dec!(1));
+        assert_eq!(b.fees_sol, dec!(0.004));
+        assert_eq!(b.net_pnl_sol, dec!(0.996));
+        assert_eq!(b.profitable_trades, 1);
+        assert_eq!(snapshot.realized_pnl_sol, dec!(1));
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingSource {
+        events: std::sync::Mutex<Vec<(bool, TokenAddress)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl PriceSource for RecordingSource {
+        async fn get_price_sol(&self, _token: &TokenAddress) -> AppResult<Option<Decimal>> {
+            Ok(None)
+        }
+
+        fn position_opened(self: Arc<Self>, token: &TokenAddress) {
+            self.events.lock().unwrap().push((true, token.clone()));
+        }
+
+        fn position_closed(self: Arc<Self>, token: &TokenAddress) {
+            self.events.lock().unwrap().push((false, token.clone()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_price_source_follows_held_tokens() {
+        let tracker = create_tracker(dec!(10));
+        let source = Arc::new(RecordingSource::default());
+        *tracker.prices.write().await = Some(source.clone());
+
+        let mut other = fill(TradeSide::Buy, dec!(100), dec!(0.02), Decimal::ZERO);
+        other.wallet = "wallet_b".to_string();
+
+        // Only the first buy and the last sell across wallets are reported
+        tracker.record_fill(fill(TradeSide::Buy, dec!(100), dec!(0.01), Decimal::ZERO)).await.unwrap();
+        tracker.record_fill(other.clone()).await.unwrap();
+        tracker.record_fill(fill(TradeSide::Sell, dec!(100), dec!(0.01), Decimal::ZERO)).await.unwrap();
+        assert_eq!(source.events.lock().unwrap().len(), 1);
+
+        other.side = TradeSide::Sell;
+        tracker.record_fill(other.clone()).await.unwrap();
+
+        let events = source.events.lock().unwrap().clone();
+        assert_eq!(events, vec![(true, other.token.clone()), (false, other.token)]);
+    }
+}
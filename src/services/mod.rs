@@ -3,14 +3,22 @@
 //! This module contains all business services including blockchain integration,
 //! trading execution, risk management, and external API integrations.
 
+pub mod analytics;
+pub mod scanner;
 pub mod solana;
+pub mod telegram;
 
 // Re-export commonly used types
 pub use solana::{SolanaService, HeliusClient, TokenMetadata, RpcClient};
 
 use crate::config::AppConfig;
 use crate::core::result::AppResult;
+use crate::core::types::SessionId;
+use crate::infrastructure::database::PostgresService;
+use crate::infrastructure::monitoring::MetricsService;
+use rust_decimal::Decimal;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Services collection for dependency injection
 #[derive(Clone)]
@@ -32,6 +40,37 @@ impl ServiceContainer {
         Ok(Self { solana })
     }
 
+    /// Start tracking the performance of a trading session
+    ///
+    /// Open positions are marked to market from the on-chain price oracle,
+    /// which streams the prices of every token held.
+    pub async fn start_performance_tracker(
+        &self,
+        session_id: SessionId,
+        starting_capital_sol: Decimal,
+        metrics: Arc<MetricsService>,
+        postgres: PostgresService,
+        interval: Duration,
+    ) -> AppResult<analytics::PerformanceTracker> {
+        let tracker = analytics::PerformanceTracker::new(session_id, starting_capital_sol, metrics);
+        tracker.start(postgres, self.solana.price_oracle().clone(), interval).await?;
+        Ok(tracker)
+    }
+
+    /// Start following the prices of filtered tokens for filter attribution
+    ///
+    /// Evaluations are priced from the on-chain price oracle 1h and 24h after
+    /// the filters ran.
+    pub async fn start_filter_attribution_tracker(
+        &self,
+        postgres: PostgresService,
+        interval: Duration,
+    ) -> AppResult<analytics::FilterAttributionTracker> {
+        let tracker = analytics::FilterAttributionTracker::new(postgres, self.solana.price_oracle().clone());
+        tracker.start(interval).await?;
+        Ok(tracker)
+    }
+
     /// Graceful shutdown of all services
     pub async fn shutdown(&self) -> AppResult<()> {
         tracing::info!("🛑 Shutting down services");
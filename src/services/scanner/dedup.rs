@@ -0,0 +1,365 @@
+//! Cross-source token deduplication
+//!
+//! The periodic scanner, the event listener and streaming sources can all
+//! surface the same mint. Every sighting goes through the deduplicator so a
+//! token is parsed and broadcast once, by whichever source saw it first. With
+//! a database attached, the first sighting is claimed in Redis with
+//! `SET NX` so several bot instances share the same view.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::core::types::TokenAddress;
+use crate::infrastructure::database::DatabaseService;
+
+/// Maximum number of tokens remembered locally
+const MAX_TRACKED_TOKENS: usize = 100_000;
+
+/// Outcome of reporting a token sighting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sighting {
+    /// The reporting source is the first to see the token
+    First,
+
+    /// The token was already seen
+    Duplicate {
+        /// Source that saw the token first
+        first_source: String,
+
+        /// Time since the first sighting in milliseconds
+        delta_ms: u64,
+
+        /// Whether this is the reporting source's first sighting
+        new_source: bool,
+    },
+}
+
+impl Sighting {
+    /// Whether the token should be processed
+    pub fn is_first(&self) -> bool {
+        matches!(self, Sighting::First)
+    }
+}
+
+/// Token deduplicator shared by all detection sources
+#[derive(Debug)]
+pub struct TokenDeduplicator {
+    /// How long a token is remembered
+    ttl: Duration,
+
+    /// Database for cross-instance claims, if shared
+    database: Option<Arc<DatabaseService>>,
+
+    /// Locally known tokens
+    seen: Arc<RwLock<SeenTokens>>,
+
+    /// First-seen and lag statistics
+    stats: Arc<RwLock<DedupStatistics>>,
+}
+
+/// Locally known tokens in insertion order
+#[derive(Debug, Default)]
+struct SeenTokens {
+    entries: HashMap<String, SeenEntry>,
+    order: VecDeque<(String, i64)>,
+}
+
+/// First sighting of a token and the sources that reported it since
+#[derive(Debug)]
+struct SeenEntry {
+    first_source: String,
+    first_seen_ms: i64,
+    sources: HashSet<String>,
+}
+
+/// First sighting as stored in Redis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FirstSeenRecord {
+    source: String,
+    seen_at_ms: i64,
+}
+
+/// Deduplication statistics
+#[derive(Debug, Clone, Default)]
+pub struct DedupStatistics {
+    /// Tokens first seen by each source
+    pub first_seen_by_source: HashMap<String, u64>,
+
+    /// Lag behind the first sighting, by later source
+    pub lag_by_source: HashMap<String, SourceLagStats>,
+
+    /// Sightings suppressed as duplicates
+    pub duplicates_suppressed: u64,
+}
+
+/// How far a source trails the first sighting of tokens
+#[derive(Debug, Clone, Default)]
+pub struct SourceLagStats {
+    /// Tokens the source saw after another source
+    pub count: u64,
+
+    /// Average lag in milliseconds
+    pub avg_ms: f64,
+
+    /// Maximum lag in milliseconds
+    pub max_ms: u64,
+}
+
+impl SourceLagStats {
+    fn record(&mut self, delta_ms: u64) {
+        self.avg_ms = (self.avg_ms * self.count as f64 + delta_ms as f64) / (self.count + 1) as f64;
+        self.count += 1;
+        self.max_ms = self.max_ms.max(delta_ms);
+    }
+}
+
+impl TokenDeduplicator {
+    /// Create a deduplicator; pass a database to share claims across instances
+    pub fn new(ttl: Duration, database: Option<Arc<DatabaseService>>) -> Self {
+        Self {
+            ttl,
+            database,
+            seen: Arc::new(RwLock::new(SeenTokens::default())),
+            stats: Arc::new(RwLock::new(DedupStatistics::default())),
+        }
+    }
+
+    /// Report a sighting of `token` by `source`
+    ///
+    /// Redis failures degrade to local deduplication rather than dropping
+    /// the token.
+    pub async fn observe(&self, token: &TokenAddress, source: &str) -> Sighting {
+        let now_ms = Utc::now().timestamp_millis();
+
+        {
+            let mut seen = self.seen.write().await;
+            seen.prune(now_ms, self.ttl_ms());
+
+            if seen.entries.contains_key(token.as_str()) {
+                let sighting = seen.sight(token.as_str(), source, None, now_ms);
+                drop(seen);
+                self.record(source, &sighting).await;
+                return sighting;
+            }
+        }
+
+        let claimed_elsewhere = match &self.database {
+            Some(database) => self.claim(database, token, source, now_ms).await,
+            None => None,
+        };
+
+        let sighting = {
+            let mut seen = self.seen.write().await;
+            let sighting = seen.sight(token.as_str(), source, claimed_elsewhere, now_ms);
+            seen.evict_oldest();
+            sighting
+        };
+
+        self.record(source, &sighting).await;
+        sighting
+    }
+
+    /// Claim the first sighting in Redis; returns the existing claim if
+    /// another source or instance got there first
+    async fn claim(
+        &self,
+        database: &DatabaseService,
+        token: &TokenAddress,
+        source: &str,
+        now_ms: i64,
+    ) -> Option<FirstSeenRecord> {
+        let key = format!("scanner:first_seen:{}", token);
+        let record = FirstSeenRecord { source: source.to_string(), seen_at_ms: now_ms };
+        let value = serde_json::to_string(&record).unwrap_or_default();
+
+        match database.redis.set_if_absent(&key, value, self.ttl).await {
+            Ok(true) => None,
+            Ok(false) => match database.redis.get_cached_json::<FirstSeenRecord>(&key).await {
+                Ok(existing) => existing,
+                Err(e) => {
+                    warn!("Failed to read first sighting of {}: {}", token, e);
+                    None
+                }
+            },
+            Err(e) => {
+                warn!("Failed to claim first sighting of {}: {}", token, e);
+                None
+            }
+        }
+    }
+
+    /// Update statistics for a sighting
+    async fn record(&self, source: &str, sighting: &Sighting) {
+        let mut stats = self.stats.write().await;
+
+        match sighting {
+            Sighting::First => {
+                *stats.first_seen_by_source.entry(source.to_string()).or_insert(0) += 1;
+            }
+            Sighting::Duplicate { first_source, delta_ms, new_source } => {
+                stats.duplicates_suppressed += 1;
+                if *new_source {
+                    debug!("'{}' trails '{}' by {}ms", source, first_source, delta_ms);
+                    stats.lag_by_source.entry(source.to_string()).or_default().record(*delta_ms);
+                }
+            }
+        }
+    }
+
+    /// Number of locally remembered tokens
+    pub async fn tracked_tokens(&self) -> usize {
+        self.seen.read().await.entries.len()
+    }
+
+    /// Get deduplication statistics
+    pub async fn statistics(&self) -> DedupStatistics {
+        self.stats.read().await.clone()
+    }
+
+    /// Reset statistics, keeping remembered tokens
+    pub async fn clear_statistics(&self) {
+        *self.stats.write().await = DedupStatistics::default();
+    }
+
+    fn ttl_ms(&self) -> i64 {
+        self.ttl.as_millis() as i64
+    }
+}
+
+impl SeenTokens {
+    /// Record a sighting, inserting the token if unknown
+    ///
+    /// `claimed_elsewhere` is the first sighting recorded by another
+    /// instance, if any.
+    fn sight(
+        &mut self,
+        token: &str,
+        source: &str,
+        claimed_elsewhere: Option<FirstSeenRecord>,
+        now_ms: i64,
+    ) -> Sighting {
+        match self.entries.entry(token.to_string()) {
+            Entry::Occupied(entry) => {
+                let entry = entry.into_mut();
+                let new_source = entry.sources.insert(source.to_string());
+
+                Sighting::Duplicate {
+                    first_source: entry.first_source.clone(),
+                    delta_ms: (now_ms - entry.first_seen_ms).max(0) as u64,
+                    new_source,
+                }
+            }
+            Entry::Vacant(entry) => {
+                let first = claimed_elsewhere.clone().unwrap_or(FirstSeenRecord {
+                    source: source.to_string(),
+                    seen_at_ms: now_ms,
+                });
+
+                entry.insert(SeenEntry {
+                    first_source: first.source.clone(),
+                    first_seen_ms: first.seen_at_ms,
+                    sources: HashSet::from([first.source.clone(), source.to_string()]),
+                });
+                self.order.push_back((token.to_string(), first.seen_at_ms));
+
+                match claimed_elsewhere {
+                    None => Sighting::First,
+                    Some(first) => Sighting::Duplicate {
+                        new_source: first.source != source,
+                        delta_ms: (now_ms - first.seen_at_ms).max(0) as u64,
+                        first_source: first.source,
+                    },
+                }
+            }
+        }
+    }
+
+    /// Forget tokens first seen more than `ttl_ms` ago
+    fn prune(&mut self, now_ms: i64, ttl_ms: i64) {
+        while let Some((_, seen_at_ms)) = self.order.front() {
+            if now_ms - seen_at_ms < ttl_ms {
+                break;
+            }
+            if let Some((token, _)) = self.order.pop_front() {
+                self.entries.remove(&token);
+            }
+        }
+    }
+
+    /// Keep the local set bounded
+    fn evict_oldest(&mut self) {
+        while self.entries.len() > MAX_TRACKED_TOKENS {
+            match self.order.pop_front() {
+                Some((token, _)) => {
+                    self.entries.remove(&token);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(address: &str) -> TokenAddress {
+        TokenAddress::new_unchecked(address.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_first_seen_arbitration() {
+        let dedup = TokenDeduplicator::new(Duration::from_secs(60), None);
+        let mint = token("So11111111111111111111111111111111111111112");
+
+        assert_eq!(dedup.observe(&mint, "geyser").await, Sighting::First);
+
+        match dedup.observe(&mint, "websocket").await {
+            Sighting::Duplicate { first_source, new_source, .. } => {
+                assert_eq!(first_source, "geyser");
+                assert!(new_source);
+            }
+            other => panic!("expected duplicate, got {:?}", other),
+        }
+
+        // A source repeating itself is suppressed but adds no lag sample
+        assert!(!dedup.observe(&mint, "websocket").await.is_first());
+
+        let stats = dedup.statistics().await;
+        assert_eq!(stats.first_seen_by_source["geyser"], 1);
+        assert_eq!(stats.duplicates_suppressed, 2);
+        assert_eq!(stats.lag_by_source["websocket"].count, 1);
+        assert!(!stats.lag_by_source.contains_key("geyser"));
+        assert_eq!(dedup.tracked_tokens().await, 1);
+    }
+
+    #[test]
+    fn test_seen_tokens_expire() {
+        let mut seen = SeenTokens::default();
+        assert_eq!(seen.sight("a", "websocket", None, 1_000), Sighting::First);
+        assert_eq!(seen.sight("b", "websocket", None, 5_000), Sighting::First);
+
+        seen.prune(11_000, 10_000);
+        assert!(!seen.entries.contains_key("a"));
+        assert!(seen.entries.contains_key("b"));
+
+        assert_eq!(seen.sight("a", "periodic", None, 11_000), Sighting::First);
+    }
+
+    #[test]
+    fn test_claim_from_other_instance() {
+        let mut seen = SeenTokens::default();
+        let claim = FirstSeenRecord { source: "geyser".to_string(), seen_at_ms: 1_000 };
+
+        assert_eq!(
+            seen.sight("a", "websocket", Some(claim), 1_250),
+            Sighting::Duplicate { first_source: "geyser".to_string(), delta_ms: 250, new_source: true }
+        );
+    }
+}
@@ -6,15 +6,34 @@
 use std::sync::Arc;
 use std::collections::{HashSet, HashMap};
 use tokio::sync::RwLock;
+use futures::StreamExt;
 use tracing::{debug, info, warn, instrument};
 use chrono::{DateTime, Utc};
 
 use crate::config::models::ScannerConfig;
 use crate::core::result::AppResult;
-use crate::core::error::AppError;
 use crate::core::types::{TokenAddress, Timestamp};
 use crate::services::solana::SolanaService;
-use super::{TokenMetadata, FilterResult};
+use crate::services::solana::types::SignatureInfo;
+use crate::services::solana::DecoderRegistry;
+use crate::services::solana::decoders::{RAYDIUM_AMM_V4_PROGRAM_ID, ORCA_WHIRLPOOL_PROGRAM_ID};
+use super::{TokenMetadata, FilterResult, MirrorOrder};
+use super::dedup::{TokenDeduplicator, SourceLagStats};
+
+/// Raydium pool creation fee account, written only by `initialize2`
+const RAYDIUM_POOL_FEE_ACCOUNT: &str = "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5";
+
+/// Orca whirlpools config, referenced by pool initialization but not by swaps
+const ORCA_WHIRLPOOLS_CONFIG: &str = "2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcF2vQ4Ldo6sm";
+
+/// Newest slots read by one program account scan
+const PROGRAM_SCAN_MAX_SLOTS: u64 = 8;
+
+/// Signatures requested per page when polling for new pools
+const POOL_SIGNATURE_PAGE_SIZE: usize = 100;
+
+/// Pool transactions fetched concurrently per DEX scan
+const POOL_TRANSACTION_CONCURRENCY: usize = 8;
 
 /// Detected token information
 #[derive(Debug, Clone)]
@@ -36,6 +55,9 @@ pub struct DetectedToken {
 
     /// Detection latency in milliseconds
     pub detection_latency_ms: u64,
+
+    /// Sized buy when the token came from a mirrored wallet
+    pub mirror_order: Option<MirrorOrder>,
 }
 
 /// Token detector for discovering new tokens
@@ -47,8 +69,11 @@ pub struct TokenDetector {
     /// Solana service
     solana: Arc<SolanaService>,
 
-    /// Known tokens cache
-    known_tokens: Arc<RwLock<HashSet<String>>>,
+    /// Blacklisted tokens, never reported
+    blacklisted_tokens: HashSet<String>,
+
+    /// Deduplication shared with the other detection sources
+    dedup: Arc<TokenDeduplicator>,
 
     /// Detection strategies
     strategies: Vec<Box<dyn DetectionStrategy>>,
@@ -114,10 +139,17 @@ impl DetectionStrategy for ProgramAccountScanner {
             return Ok(Vec::new());
         }
 
+        // Every block of the range is read, so only the newest slots are kept
+        let start_slot = (last_slot + 1).max(current_slot.saturating_sub(PROGRAM_SCAN_MAX_SLOTS - 1));
+        if start_slot > last_slot + 1 && last_slot > 0 {
+            warn!("Block scan skipped {} slots ({}..{}) behind its {}-slot window",
+                  start_slot - last_slot - 1, last_slot + 1, start_slot, PROGRAM_SCAN_MAX_SLOTS);
+        }
+
         // Get new token accounts created between slots
         let new_accounts = self.solana.get_program_accounts_in_slot_range(
             "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
-            last_slot + 1,
+            start_slot,
             current_slot,
             max_tokens,
         ).await?;
@@ -148,7 +180,26 @@ impl DetectionStrategy for ProgramAccountScanner {
 struct LiquidityPoolScanner {
     solana: Arc<SolanaService>,
     dex_programs: Vec<String>,
-    last_scan_time: Arc<RwLock<DateTime<Utc>>>,
+    /// Pool initialize decoders
+    decoders: Arc<DecoderRegistry>,
+    /// Newest processed pool transaction per DEX program
+    slot_cursors: Arc<RwLock<HashMap<String, PoolCursor>>>,
+}
+
+/// Slot cursor of a DEX program's pool scan
+#[derive(Debug, Clone, PartialEq)]
+struct PoolCursor {
+    /// Slot of the newest processed transaction
+    slot: u64,
+    /// Newest processed transaction, separating it from the others in its slot
+    signature: String,
+}
+
+impl PoolCursor {
+    /// Whether a signature, listed newest first, was already processed
+    fn covers(&self, entry: &SignatureInfo) -> bool {
+        entry.slot < self.slot || entry.signature == self.signature
+    }
 }
 
 #[async_trait::async_trait]
@@ -176,21 +227,112 @@ impl DetectionStrategy for LiquidityPoolScanner {
             }
         }
 
-        // Update last scan time
-        {
-            let mut last_time = self.last_scan_time.write().await;
-            *last_time = Utc::now();
-        }
-
         Ok(all_tokens)
     }
 }
 
 impl LiquidityPoolScanner {
+    /// Find pools created on a DEX since the slot cursor
+    ///
+    /// Signatures are paged newest first back to the cursor, and the
+    /// initialize instructions of each transaction are decoded oldest first.
+    /// Transactions are fetched a few at a time, in order. The cursor only
+    /// advances past transactions that were decoded, so a capped or failed
+    /// scan resumes where it stopped. A program without a cursor starts from
+    /// the newest page rather than its full history.
     async fn scan_dex_pools(&self, dex_program: &str, max_pools: u32) -> AppResult<Vec<TokenAddress>> {
-        // This would scan for new pools on specific DEX
-        // For now, returning empty
-        Ok(Vec::new())
+        let cursor = self.slot_cursors.read().await.get(dex_program).cloned();
+        let poll_address = pool_signal_address(dex_program);
+
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+
+        'paging: loop {
+            let page = self.solana
+                .get_signatures_for_address(poll_address, before.as_deref(), POOL_SIGNATURE_PAGE_SIZE)
+                .await?;
+            let page_len = page.len();
+            before = page.last().map(|entry| entry.signature.clone());
+
+            for entry in page {
+                if cursor.as_ref().is_some_and(|cursor| cursor.covers(&entry)) {
+                    break 'paging;
+                }
+                signatures.push(entry);
+            }
+
+            if cursor.is_none() || page_len < POOL_SIGNATURE_PAGE_SIZE {
+                break;
+            }
+        }
+
+        let mut processed: Option<PoolCursor> = None;
+        let mut tokens = Vec::new();
+
+        // Buffered keeps the oldest-first order the cursor relies on
+        let mut fetches = futures::stream::iter(signatures.into_iter().rev())
+            .map(|entry| {
+                let solana = self.solana.clone();
+                async move {
+                    let transaction = if entry.is_error {
+                        None
+                    } else {
+                        Some(solana.get_transaction(&entry.signature).await)
+                    };
+                    (entry, transaction)
+                }
+            })
+            .buffered(POOL_TRANSACTION_CONCURRENCY);
+
+        while let Some((entry, transaction)) = fetches.next().await {
+            if tokens.len() >= max_pools as usize {
+                break;
+            }
+
+            if let Some(transaction) = transaction {
+                let transaction = match transaction {
+                    Ok(transaction) => transaction,
+                    Err(e) => {
+                        // Retried on the next scan, together with everything after it
+                        warn!("Failed to fetch pool transaction {}: {}", entry.signature, e);
+                        break;
+                    }
+                };
+
+                for event in self.decoders.decode_transaction(&transaction) {
+                    if event.program_id != dex_program {
+                        continue;
+                    }
+
+                    debug!("New pool on {} in {}: {:?}", dex_program, entry.signature, event.token_mints());
+                    self.solana.price_oracle().register_event(&event).await;
+                    tokens.extend(event.token_mints()
+                        .into_iter()
+                        .filter_map(|mint| TokenAddress::new(mint).ok()));
+                }
+            }
+
+            processed = Some(PoolCursor { slot: entry.slot, signature: entry.signature.clone() });
+        }
+
+        if let Some(cursor) = processed {
+            self.slot_cursors.write().await.insert(dex_program.to_string(), cursor);
+        }
+
+        Ok(tokens)
+    }
+}
+
+/// Address to poll for pool creation signatures
+///
+/// Swaps dominate the signatures of a DEX program, so known DEXes are polled
+/// through an account that only pool initialization touches. Other programs
+/// are polled directly.
+fn pool_signal_address(dex_program: &str) -> &str {
+    match dex_program {
+        RAYDIUM_AMM_V4_PROGRAM_ID => RAYDIUM_POOL_FEE_ACCOUNT,
+        ORCA_WHIRLPOOL_PROGRAM_ID => ORCA_WHIRLPOOLS_CONFIG,
+        other => other,
     }
 }
 
@@ -198,7 +340,8 @@ impl LiquidityPoolScanner {
 #[derive(Debug)]
 struct HeliusApiScanner {
     solana: Arc<SolanaService>,
-    last_cursor: Arc<RwLock<Option<String>>>,
+    /// Last processed pool creation signature by DEX program
+    cursors: Arc<RwLock<HashMap<String, String>>>,
 }
 
 #[async_trait::async_trait]
@@ -210,45 +353,21 @@ impl DetectionStrategy for HeliusApiScanner {
     async fn detect(&self, max_tokens: u32) -> AppResult<Vec<TokenAddress>> {
         debug!("Running Helius API scanner");
 
-        // Get last cursor for pagination
-        let last_cursor = {
-            let cursor = self.last_cursor.read().await;
-            cursor.clone()
-        };
+        // Get cursors for pagination
+        let cursors = self.cursors.read().await.clone();
 
         // Fetch new tokens from Helius
-        let response = self.solana.fetch_helius_new_tokens(last_cursor, max_tokens).await?;
+        let response = self.solana.fetch_helius_new_tokens(&cursors, max_tokens).await?;
 
-        // Extract tokens and update cursor
-        let tokens = self.parse_helius_response(response).await?;
+        // Update cursors for the next run
+        *self.cursors.write().await = response.cursors;
 
-        Ok(tokens)
-    }
-}
-
-impl HeliusApiScanner {
-    async fn parse_helius_response(&self, response: serde_json::Value) -> AppResult<Vec<TokenAddress>> {
-        let tokens = response.get("tokens")
-            .and_then(|t| t.as_array())
-            .ok_or_else(|| AppError::internal("Invalid Helius response format"))?;
-
-        // Update cursor if present
-        if let Some(cursor) = response.get("nextCursor").and_then(|c| c.as_str()) {
-            let mut last_cursor = self.last_cursor.write().await;
-            *last_cursor = Some(cursor.to_string());
-        }
-
-        // Parse token addresses
-        let addresses: Vec<TokenAddress> = tokens
-            .iter()
-            .filter_map(|token| {
-                token.get("address")
-                    .and_then(|a| a.as_str())
-                    .and_then(|addr| TokenAddress::new(addr.to_string()).ok())
-            })
+        let tokens = response.mints
+            .into_iter()
+            .filter_map(|mint| TokenAddress::new(mint).ok())
             .collect();
 
-        Ok(addresses)
+        Ok(tokens)
     }
 }
 
@@ -257,43 +376,48 @@ impl TokenDetector {
     pub async fn new(
         config: Arc<ScannerConfig>,
         solana: Arc<SolanaService>,
+        dedup: Arc<TokenDeduplicator>,
     ) -> AppResult<Self> {
         info!("Initializing token detector");
 
         // Initialize detection strategies
         let mut strategies: Vec<Box<dyn DetectionStrategy>> = Vec::new();
 
-        // Add program account scanner
-        strategies.push(Box::new(ProgramAccountScanner {
-            solana: solana.clone(),
-            last_scan_slot: Arc::new(RwLock::new(0)),
-        }));
+        // Block scanning reads up to PROGRAM_SCAN_MAX_SLOTS full blocks per
+        // scan, so it only runs when enabled
+        if config.enable_block_scan {
+            strategies.push(Box::new(ProgramAccountScanner {
+                solana: solana.clone(),
+                last_scan_slot: Arc::new(RwLock::new(0)),
+            }));
+        }
 
         // Add liquidity pool scanner
         strategies.push(Box::new(LiquidityPoolScanner {
             solana: solana.clone(),
             dex_programs: vec![
-                "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8".to_string(), // Raydium
-                "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string(), // Orca
+                RAYDIUM_AMM_V4_PROGRAM_ID.to_string(),
+                ORCA_WHIRLPOOL_PROGRAM_ID.to_string(),
             ],
-            last_scan_time: Arc::new(RwLock::new(Utc::now())),
+            decoders: Arc::new(DecoderRegistry::with_defaults()),
+            slot_cursors: Arc::new(RwLock::new(HashMap::new())),
         }));
 
         // Add Helius scanner if API key is available
         if solana.has_helius_api_key() {
             strategies.push(Box::new(HeliusApiScanner {
                 solana: solana.clone(),
-                last_cursor: Arc::new(RwLock::new(None)),
+                cursors: Arc::new(RwLock::new(HashMap::new())),
             }));
         }
 
-        // Load known tokens from database
-        let known_tokens = Self::load_known_tokens(&config).await?;
+        let blacklisted_tokens = config.blacklisted_tokens.iter().cloned().collect();
 
         Ok(Self {
             config,
             solana,
-            known_tokens: Arc::new(RwLock::new(known_tokens)),
+            blacklisted_tokens,
+            dedup,
             strategies,
             detection_history: Arc::new(RwLock::new(DetectionHistory {
                 recent_detections: HashMap::new(),
@@ -302,25 +426,12 @@ impl TokenDetector {
         })
     }
 
-    /// Load known tokens from database or cache
-    async fn load_known_tokens(config: &ScannerConfig) -> AppResult<HashSet<String>> {
-        // This would load from database
-        // For now, including blacklisted tokens
-        let mut known = HashSet::new();
-
-        for token in &config.blacklisted_tokens {
-            known.insert(token.clone());
-        }
-
-        Ok(known)
-    }
-
     /// Detect new tokens using all strategies
     #[instrument(skip(self))]
     pub async fn detect_new_tokens(&self, max_tokens: u32) -> AppResult<Vec<TokenAddress>> {
         debug!("Starting token detection, max_tokens: {}", max_tokens);
 
-        let mut all_tokens = Vec::new();
+        let mut new_tokens = Vec::new();
         let tokens_per_strategy = (max_tokens as usize / self.strategies.len()).max(1);
 
         // Run all strategies in parallel
@@ -347,7 +458,15 @@ impl TokenDetector {
                     // Record detections
                     self.record_detections(&strategy_name, &tokens).await;
 
-                    all_tokens.extend(tokens);
+                    // Keep tokens no other source has reported yet
+                    for token in tokens {
+                        if self.blacklisted_tokens.contains(token.as_str()) {
+                            continue;
+                        }
+                        if self.dedup.observe(&token, &strategy_name).await.is_first() {
+                            new_tokens.push(token);
+                        }
+                    }
                 }
                 Err(e) => {
                     warn!("Strategy '{}' failed: {}", strategy_name, e);
@@ -355,12 +474,6 @@ impl TokenDetector {
             }
         }
 
-        // Filter out known tokens
-        let new_tokens = self.filter_new_tokens(all_tokens).await?;
-
-        // Update known tokens
-        self.update_known_tokens(&new_tokens).await;
-
         info!("Total new tokens detected: {}", new_tokens.len());
         Ok(new_tokens)
     }
@@ -395,41 +508,17 @@ impl TokenDetector {
         }
     }
 
-    /// Filter out known tokens
-    async fn filter_new_tokens(&self, tokens: Vec<TokenAddress>) -> AppResult<Vec<TokenAddress>> {
-        let known_tokens = self.known_tokens.read().await;
-
-        let new_tokens: Vec<TokenAddress> = tokens
-            .into_iter()
-            .filter(|token| !known_tokens.contains(token.as_str()))
-            .collect();
-
-        Ok(new_tokens)
-    }
-
-    /// Update known tokens cache
-    async fn update_known_tokens(&self, new_tokens: &[TokenAddress]) {
-        let mut known_tokens = self.known_tokens.write().await;
-
-        for token in new_tokens {
-            known_tokens.insert(token.to_string());
-        }
-
-        // Limit cache size to prevent unbounded growth
-        if known_tokens.len() > 100000 {
-            // This is a simple strategy - in production, we'd use LRU or similar
-            known_tokens.clear();
-            warn!("Known tokens cache cleared due to size limit");
-        }
-    }
-
     /// Get detection statistics
     pub async fn get_statistics(&self) -> DetectionStatistics {
         let history = self.detection_history.read().await;
-        let known_tokens_count = self.known_tokens.read().await.len();
+        let known_tokens_count = self.dedup.tracked_tokens().await;
+        let dedup = self.dedup.statistics().await;
 
         DetectionStatistics {
             total_by_source: history.total_by_source.clone(),
+            first_seen_by_source: dedup.first_seen_by_source,
+            lag_by_source: dedup.lag_by_source,
+            duplicates_suppressed: dedup.duplicates_suppressed,
             known_tokens_count,
             available_strategies: self.strategies.iter().map(|s| s.name().to_string()).collect(),
         }
@@ -440,6 +529,7 @@ impl TokenDetector {
         let mut history = self.detection_history.write().await;
         history.recent_detections.clear();
         history.total_by_source.clear();
+        self.dedup.clear_statistics().await;
 
         info!("Detection history cleared");
     }
@@ -451,6 +541,15 @@ pub struct DetectionStatistics {
     /// Total detections by source
     pub total_by_source: HashMap<String, u64>,
 
+    /// Tokens each source reported before any other
+    pub first_seen_by_source: HashMap<String, u64>,
+
+    /// Lag behind the first sighting, by later source
+    pub lag_by_source: HashMap<String, SourceLagStats>,
+
+    /// Sightings suppressed as duplicates
+    pub duplicates_suppressed: u64,
+
     /// Number of known tokens in cache
     pub known_tokens_count: usize,
 
@@ -461,13 +560,14 @@ pub struct DetectionStatistics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
     use crate::config::ConfigLoader;
 
     #[tokio::test]
     async fn test_token_detector_creation() {
         let config = ConfigLoader::new().without_env().create_default_config();
         let solana_service = Arc::new(
-            SolanaService::new(&config.solana, &config.helius)
+            SolanaService::new(&config)
                 .await
                 .unwrap()
         );
@@ -475,6 +575,7 @@ mod tests {
         let detector = TokenDetector::new(
             Arc::new(config.scanner),
             solana_service,
+            Arc::new(TokenDeduplicator::new(Duration::from_secs(60), None)),
         ).await;
 
         assert!(detector.is_ok());
@@ -484,4 +585,21 @@ mod tests {
 
         assert!(!stats.available_strategies.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_pool_cursor_covers_processed_signatures() {
+        let cursor = PoolCursor { slot: 100, signature: "processed".to_string() };
+        let entry = |signature: &str, slot: u64| SignatureInfo {
+            signature: signature.to_string(),
+            slot,
+            block_time: None,
+            is_error: false,
+        };
+
+        assert!(!cursor.covers(&entry("newer", 101)));
+        // Newer transactions in the cursor's slot are listed before it
+        assert!(!cursor.covers(&entry("same_slot", 100)));
+        assert!(cursor.covers(&entry("processed", 100)));
+        assert!(cursor.covers(&entry("older", 99)));
+    }
+}
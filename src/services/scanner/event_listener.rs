@@ -3,18 +3,32 @@
 //! This module provides WebSocket and webhook-based event listening
 //! for real-time token creation and liquidity pool events.
 
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock, Mutex};
+use tokio::task::JoinHandle;
 use tracing::{info, warn, error, debug, instrument};
 use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
-use futures_util::StreamExt;
+use futures::StreamExt;
 
 use crate::config::models::ScannerConfig;
 use crate::core::result::AppResult;
 use crate::core::error::AppError;
 use crate::core::types::{TokenAddress, Timestamp};
-use crate::services::solana::{SolanaService, HeliusWebsocket};
+use crate::services::solana::{SolanaService, DecodedEvent, DecodedEventKind, DecoderRegistry, PriceOracle};
+use super::geyser::GeyserSource;
+use super::wallet_tracker::WalletTracker;
+use super::webhook::WebhookReceiver;
+
+/// Number of latency samples kept per detection source
+const LATENCY_SAMPLE_WINDOW: usize = 1024;
+
+/// Transaction signatures remembered for cross-source latency
+const SIGHTING_CAPACITY: usize = 10_000;
+
+/// Detection source label for WebSocket log subscriptions
+pub(super) const WEBSOCKET_SOURCE: &str = "websocket";
 
 /// Event types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +43,8 @@ pub enum EventType {
     LiquidityAdded,
     /// Token account created
     TokenAccount,
+    /// Tracked wallet bought a new token
+    WalletBuy,
 }
 
 /// Token event data
@@ -51,30 +67,206 @@ pub struct TokenEvent {
 
     /// Additional event data
     pub data: serde_json::Value,
+
+    /// Creation event the token was decoded from
+    pub decoded: Option<DecodedEvent>,
+
+    /// Signature of the transaction the event was seen in
+    pub signature: Option<String>,
 }
 
 /// Event listener state
 #[derive(Debug, Clone)]
-struct ListenerState {
+pub(super) struct ListenerState {
     /// Is listener running
-    is_running: bool,
+    pub(super) is_running: bool,
 
     /// Total events received
-    total_events: u64,
+    pub(super) total_events: u64,
 
     /// Events by type
-    events_by_type: std::collections::HashMap<String, u64>,
+    pub(super) events_by_type: HashMap<String, u64>,
 
     /// Last event timestamp
-    last_event: Option<Timestamp>,
+    pub(super) last_event: Option<Timestamp>,
 
     /// Connection status
-    connection_status: ConnectionStatus,
+    pub(super) connection_status: ConnectionStatus,
+
+    /// Latency samples by detection source
+    pub(super) latency_by_source: HashMap<String, LatencySamples>,
+
+    /// First sighting of each recent transaction across sources
+    pub(super) sightings: SignatureSightings,
+
+    /// Slot gaps detected on streaming sources
+    pub(super) slot_gaps: u64,
+
+    /// Slots missed across all gaps
+    pub(super) missed_slots: u64,
+}
+
+impl ListenerState {
+    pub(super) fn new() -> Self {
+        Self {
+            is_running: false,
+            total_events: 0,
+            events_by_type: HashMap::new(),
+            last_event: None,
+            connection_status: ConnectionStatus::Disconnected,
+            latency_by_source: HashMap::new(),
+            sightings: SignatureSightings::default(),
+            slot_gaps: 0,
+            missed_slots: 0,
+        }
+    }
+}
+
+/// Bounded memory of when each transaction was first reported, and by which sources
+#[derive(Debug, Clone, Default)]
+pub(super) struct SignatureSightings {
+    first_seen: HashMap<String, (Instant, HashSet<String>)>,
+    order: VecDeque<String>,
+}
+
+impl SignatureSightings {
+    /// Delay of `source` behind the first source to report `signature`,
+    /// or None when `source` already reported it
+    fn delay(&mut self, signature: &str, source: &str, received_at: Instant) -> Option<Duration> {
+        if let Some((first_at, sources)) = self.first_seen.get_mut(signature) {
+            if !sources.insert(source.to_string()) {
+                return None;
+            }
+            return Some(received_at.saturating_duration_since(*first_at));
+        }
+
+        if self.order.len() >= SIGHTING_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.first_seen.remove(&oldest);
+            }
+        }
+
+        self.first_seen.insert(signature.to_string(), (received_at, HashSet::from([source.to_string()])));
+        self.order.push_back(signature.to_string());
+        Some(Duration::ZERO)
+    }
+}
+
+/// Rolling latency samples for one detection source
+#[derive(Debug, Clone, Default)]
+pub(super) struct LatencySamples {
+    samples: VecDeque<u64>,
+    total_events: u64,
+    max_ms: u64,
+}
+
+impl LatencySamples {
+    fn record(&mut self, latency_ms: u64) {
+        if self.samples.len() == LATENCY_SAMPLE_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency_ms);
+        self.total_events += 1;
+        self.max_ms = self.max_ms.max(latency_ms);
+    }
+
+    pub(super) fn stats(&self) -> SourceLatencyStats {
+        let mut sorted: Vec<u64> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+
+        let percentile = |p: usize| {
+            if sorted.is_empty() {
+                0
+            } else {
+                sorted[((sorted.len() - 1) * p) / 100]
+            }
+        };
+
+        SourceLatencyStats {
+            total_events: self.total_events,
+            avg_ms: if sorted.is_empty() {
+                0.0
+            } else {
+                sorted.iter().sum::<u64>() as f64 / sorted.len() as f64
+            },
+            p50_ms: percentile(50),
+            p99_ms: percentile(99),
+            max_ms: self.max_ms,
+        }
+    }
+}
+
+/// Handle detection sources use to publish events
+#[derive(Debug, Clone)]
+pub(super) struct EventSink {
+    sender: broadcast::Sender<TokenEvent>,
+    state: Arc<RwLock<ListenerState>>,
+    /// Oracle the pools of published creation events are registered with
+    price_oracle: Option<Arc<PriceOracle>>,
+}
+
+impl EventSink {
+    pub(super) fn new(sender: broadcast::Sender<TokenEvent>, state: Arc<RwLock<ListenerState>>) -> Self {
+        Self { sender, state, price_oracle: None }
+    }
+
+    /// Register the pools of published creation events with `price_oracle`
+    pub(super) fn with_price_oracle(mut self, price_oracle: Arc<PriceOracle>) -> Self {
+        self.price_oracle = Some(price_oracle);
+        self
+    }
+
+    /// Whether the listener is still running
+    pub(super) async fn is_running(&self) -> bool {
+        self.state.read().await.is_running
+    }
+
+    /// Record and broadcast an event, measuring latency from `received_at`
+    ///
+    /// A source's latency sample is its delay behind the first source that
+    /// reported the same transaction, so the samples compare sources rather
+    /// than local decoding time. The pool of a decoded creation event is
+    /// registered with the price oracle first, so every source's tokens are
+    /// priced from their pool.
+    pub(super) async fn publish(&self, source: &str, mut event: TokenEvent, received_at: Instant) {
+        event.latency_ms = received_at.elapsed().as_millis() as u64;
+
+        if let (Some(price_oracle), Some(decoded)) = (&self.price_oracle, &event.decoded) {
+            price_oracle.register_event(decoded).await;
+        }
+
+        {
+            let mut state = self.state.write().await;
+            state.total_events += 1;
+            state.last_event = Some(Timestamp::now());
+
+            let event_type_str = format!("{:?}", event.event_type);
+            *state.events_by_type.entry(event_type_str).or_insert(0) += 1;
+
+            let lag = event.signature.as_deref()
+                .and_then(|signature| state.sightings.delay(signature, source, received_at));
+            if let Some(lag) = lag {
+                state.latency_by_source
+                    .entry(source.to_string())
+                    .or_default()
+                    .record(lag.as_millis() as u64);
+            }
+        }
+
+        let _ = self.sender.send(event);
+    }
+
+    /// Record a gap of `missed` slots on a streaming source
+    pub(super) async fn record_slot_gap(&self, missed: u64) {
+        let mut state = self.state.write().await;
+        state.slot_gaps += 1;
+        state.missed_slots += missed;
+    }
 }
 
 /// Connection status
 #[derive(Debug, Clone)]
-enum ConnectionStatus {
+pub(super) enum ConnectionStatus {
     Disconnected,
     Connecting,
     Connected,
@@ -99,8 +291,20 @@ pub struct EventListener {
     /// Reconnection attempts
     reconnect_attempts: Arc<Mutex<u32>>,
 
-    /// WebSocket connections
-    websockets: Arc<RwLock<Vec<HeliusWebsocket>>>,
+    /// WebSocket listener tasks
+    websockets: Arc<RwLock<Vec<JoinHandle<()>>>>,
+
+    /// Pool and mint creation decoders
+    decoders: Arc<DecoderRegistry>,
+
+    /// Geyser streaming task
+    geyser_task: Arc<Mutex<Option<JoinHandle<()>>>>,
+
+    /// Copy-trade wallet tracking task
+    wallet_task: Arc<Mutex<Option<JoinHandle<()>>>>,
+
+    /// Helius webhook receiver
+    webhook: Arc<Mutex<Option<WebhookReceiver>>>,
 }
 
 impl EventListener {
@@ -114,13 +318,7 @@ impl EventListener {
 
         let (event_sender, _) = broadcast::channel(10000);
 
-        let state = Arc::new(RwLock::new(ListenerState {
-            is_running: false,
-            total_events: 0,
-            events_by_type: std::collections::HashMap::new(),
-            last_event: None,
-            connection_status: ConnectionStatus::Disconnected,
-        }));
+        let state = Arc::new(RwLock::new(ListenerState::new()));
 
         Ok(Self {
             config,
@@ -129,6 +327,10 @@ impl EventListener {
             state,
             reconnect_attempts: Arc::new(Mutex::new(0)),
             websockets: Arc::new(RwLock::new(Vec::new())),
+            decoders: Arc::new(DecoderRegistry::with_defaults()),
+            geyser_task: Arc::new(Mutex::new(None)),
+            wallet_task: Arc::new(Mutex::new(None)),
+            webhook: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -152,9 +354,41 @@ impl EventListener {
             self.start_websocket_listeners().await?;
         }
 
+        // Start Geyser stream alongside Helius if configured
+        if let Some(endpoint) = &self.config.geyser_endpoint {
+            let source = GeyserSource::new(
+                endpoint.clone(),
+                self.config.geyser_x_token.clone(),
+                self.decoders.clone(),
+                self.sink(),
+            );
+            *self.geyser_task.lock().await = Some(source.spawn());
+        }
+
+        // Follow copy-trade wallets
+        let mut tracked_wallets = Vec::new();
+        if !self.config.copy_trade.wallets.is_empty() {
+            let tracker = WalletTracker::new(
+                self.solana.clone(),
+                self.config.copy_trade.clone(),
+                self.sink(),
+            );
+            tracked_wallets = tracker.addresses();
+            *self.wallet_task.lock().await = Some(tracker.spawn());
+        }
+
         // Start webhook server if configured
         if let Some(ref webhook_url) = self.solana.helius_webhook_url() {
             self.start_webhook_server(webhook_url).await?;
+
+            // Deliver the launches of tracked wallets too
+            if !tracked_wallets.is_empty() {
+                let mut addresses = self.decoders.program_ids();
+                addresses.extend(tracked_wallets);
+                if let Err(e) = self.update_webhook_addresses(addresses).await {
+                    warn!("Failed to add tracked wallets to the Helius webhook: {}", e);
+                }
+            }
         }
 
         info!("✅ Event listener started");
@@ -173,11 +407,28 @@ impl EventListener {
             state.connection_status = ConnectionStatus::Disconnected;
         }
 
+        // Stop Geyser stream
+        if let Some(task) = self.geyser_task.lock().await.take() {
+            task.abort();
+        }
+
+        // Stop wallet tracking
+        if let Some(task) = self.wallet_task.lock().await.take() {
+            task.abort();
+        }
+
+        // Delete the Helius webhook
+        if let Some(receiver) = self.webhook.lock().await.take() {
+            if let Err(e) = receiver.stop().await {
+                warn!("Failed to delete Helius webhook: {}", e);
+            }
+        }
+
         // Close WebSocket connections
         {
             let mut websockets = self.websockets.write().await;
-            for ws in websockets.drain(..) {
-                ws.close().await;
+            for task in websockets.drain(..) {
+                task.abort();
             }
         }
 
@@ -187,19 +438,15 @@ impl EventListener {
 
     /// Start WebSocket listeners
     async fn start_websocket_listeners(&self) -> AppResult<()> {
-        // Connect to Helius WebSocket for program events
-        let ws_url = self.solana.helius_websocket_url()?;
-
-        // Programs to monitor
-        let programs_to_monitor = vec![
-            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", // Token Program
-            "11111111111111111111111111111112", // System Program (for new accounts)
-            "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8", // Raydium AMM
-            "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc", // Orca Whirlpool
-            "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB", // Meteora
+        // Programs to monitor: token and system programs plus every program
+        // with a registered creation decoder
+        let mut programs_to_monitor = vec![
+            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(), // Token Program
+            "11111111111111111111111111111112".to_string(), // System Program (for new accounts)
         ];
+        programs_to_monitor.extend(self.decoders.program_ids());
 
-        for program_id in programs_to_monitor {
+        for program_id in &programs_to_monitor {
             self.connect_program_websocket(program_id).await?;
         }
 
@@ -215,18 +462,18 @@ impl EventListener {
     /// Connect to program-specific WebSocket
     async fn connect_program_websocket(&self, program_id: &str) -> AppResult<()> {
         let solana = self.solana.clone();
-        let event_sender = self.event_sender.clone();
-        let state = self.state.clone();
+        let sink = self.sink();
         let reconnect_attempts = self.reconnect_attempts.clone();
+        let decoders = self.decoders.clone();
         let program_id = program_id.to_string();
 
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             loop {
                 match Self::handle_program_websocket(
                     &solana,
+                    &decoders,
                     &program_id,
-                    &event_sender,
-                    &state,
+                    &sink,
                 ).await {
                     Ok(_) => {
                         warn!("WebSocket connection closed for program {}", program_id);
@@ -237,11 +484,8 @@ impl EventListener {
                 }
 
                 // Check if we should reconnect
-                {
-                    let state = state.read().await;
-                    if !state.is_running {
-                        break;
-                    }
+                if !sink.is_running().await {
+                    break;
                 }
 
                 // Exponential backoff for reconnection
@@ -256,6 +500,7 @@ impl EventListener {
                 tokio::time::sleep(delay).await;
             }
         });
+        self.websockets.write().await.push(task);
 
         Ok(())
     }
@@ -263,9 +508,9 @@ impl EventListener {
     /// Handle program WebSocket connection
     async fn handle_program_websocket(
         solana: &SolanaService,
+        decoders: &DecoderRegistry,
         program_id: &str,
-        event_sender: &broadcast::Sender<TokenEvent>,
-        state: &Arc<RwLock<ListenerState>>,
+        sink: &EventSink,
     ) -> AppResult<()> {
         debug!("Connecting to WebSocket for program {}", program_id);
 
@@ -277,29 +522,11 @@ impl EventListener {
                     let event_time = Instant::now();
 
                     // Parse event
-                    match Self::parse_program_event(program_id, event_data) {
-                        Ok(Some(token_event)) => {
-                            let latency = event_time.elapsed().as_millis() as u64;
-
-                            // Update state
-                            {
-                                let mut state = state.write().await;
-                                state.total_events += 1;
-                                state.last_event = Some(Timestamp::now());
-
-                                let event_type_str = format!("{:?}", token_event.event_type);
-                                *state.events_by_type.entry(event_type_str).or_insert(0) += 1;
+                    match Self::parse_program_event(solana, decoders, program_id, event_data).await {
+                        Ok(token_events) => {
+                            for event in token_events {
+                                sink.publish(WEBSOCKET_SOURCE, event, event_time).await;
                             }
-
-                            // Create event with latency
-                            let mut event = token_event;
-                            event.latency_ms = latency;
-
-                            // Broadcast event
-                            let _ = event_sender.send(event);
-                        }
-                        Ok(None) => {
-                            // Event not relevant
                         }
                         Err(e) => {
                             debug!("Failed to parse event: {}", e);
@@ -315,11 +542,42 @@ impl EventListener {
         Ok(())
     }
 
-    /// Parse program event into token event
-    fn parse_program_event(
+    /// Parse program event into token events
+    ///
+    /// Log notifications go through the decoder registry; the transaction is
+    /// fetched only when a decoder needs its accounts.
+    async fn parse_program_event(
+        solana: &SolanaService,
+        decoders: &DecoderRegistry,
         program_id: &str,
         event_data: serde_json::Value,
-    ) -> AppResult<Option<TokenEvent>> {
+    ) -> AppResult<Vec<TokenEvent>> {
+        if let Some(logs) = event_data.pointer("/value/logs").and_then(|l| l.as_array()) {
+            // Failed transactions create nothing
+            if event_data.pointer("/value/err").map_or(false, |err| !err.is_null()) {
+                return Ok(Vec::new());
+            }
+
+            let signature = event_data.pointer("/value/signature")
+                .and_then(|s| s.as_str())
+                .ok_or_else(|| AppError::internal("Missing signature in log notification"))?;
+            let slot = event_data.pointer("/context/slot")
+                .and_then(|s| s.as_u64())
+                .unwrap_or_default();
+            let logs: Vec<String> = logs.iter()
+                .filter_map(|line| line.as_str().map(str::to_string))
+                .collect();
+
+            let decoded = if decoders.requires_transaction(&logs) {
+                let transaction = solana.get_transaction(signature).await?;
+                decoders.decode_transaction(&transaction)
+            } else {
+                decoders.decode_logs(signature, slot, &logs)
+            };
+
+            return Ok(decoded.iter().flat_map(Self::decoded_to_token_events).collect());
+        }
+
         // Extract instruction data
         let instruction = event_data.get("instruction")
             .ok_or_else(|| AppError::internal("Missing instruction in event"))?;
@@ -327,19 +585,38 @@ impl EventListener {
         match program_id {
             // Token Program
             "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" => {
-                Self::parse_token_program_event(instruction)
+                Ok(Self::parse_token_program_event(instruction)?.into_iter().collect())
             }
-            // Raydium AMM
-            "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8" => {
-                Self::parse_raydium_event(instruction)
-            }
-            // Other DEX programs
             _ => {
-                Self::parse_generic_dex_event(program_id, instruction)
+                debug!("Unhandled event from {}: {:?}", program_id, instruction);
+                Ok(Vec::new())
             }
         }
     }
 
+    /// Convert a decoded creation event into token events, one per new mint
+    pub(super) fn decoded_to_token_events(decoded: &DecodedEvent) -> Vec<TokenEvent> {
+        let (event_type, source) = match &decoded.kind {
+            DecodedEventKind::PoolCreated(pool) => (EventType::LiquidityPool, pool.dex.clone()),
+            DecodedEventKind::TokenCreated(_) => (EventType::TokenMint, decoded.program_id.clone()),
+        };
+        let data = serde_json::to_value(decoded).unwrap_or_default();
+
+        decoded.token_mints()
+            .into_iter()
+            .map(|mint| TokenEvent {
+                event_type: event_type.clone(),
+                token_address: TokenAddress::new_unchecked(mint),
+                source: source.clone(),
+                timestamp: Timestamp::now(),
+                latency_ms: 0,
+                data: data.clone(),
+                decoded: Some(decoded.clone()),
+                signature: Some(decoded.signature.clone()),
+            })
+            .collect()
+    }
+
     /// Parse Token Program event
     fn parse_token_program_event(instruction: &serde_json::Value) -> AppResult<Option<TokenEvent>> {
         let instruction_type = instruction.get("type")
@@ -359,101 +636,42 @@ impl EventListener {
                     timestamp: Timestamp::now(),
                     latency_ms: 0,
                     data: instruction.clone(),
+                    decoded: None,
+                    signature: None,
                 }))
             }
             _ => Ok(None),
         }
     }
 
-    /// Parse Raydium event
-    fn parse_raydium_event(instruction: &serde_json::Value) -> AppResult<Option<TokenEvent>> {
-        let instruction_type = instruction.get("type")
-            .and_then(|t| t.as_str())
-            .ok_or_else(|| AppError::internal("Missing instruction type"))?;
-
-        match instruction_type {
-            "initialize" | "initialize2" => {
-                // New liquidity pool created
-                let token_a = instruction.get("tokenA")
-                    .and_then(|t| t.as_str())
-                    .ok_or_else(|| AppError::internal("Missing token A"))?;
-
-                let token_b = instruction.get("tokenB")
-                    .and_then(|t| t.as_str())
-                    .ok_or_else(|| AppError::internal("Missing token B"))?;
-
-                // Check if one of the tokens is SOL
-                let sol_mint = "So11111111111111111111111111111111111111112";
-                let new_token = if token_a == sol_mint {
-                    token_b
-                } else if token_b == sol_mint {
-                    token_a
-                } else {
-                    return Ok(None); // Not a SOL pair
-                };
-
-                Ok(Some(TokenEvent {
-                    event_type: EventType::LiquidityPool,
-                    token_address: TokenAddress::new_unchecked(new_token.to_string()),
-                    source: "raydium".to_string(),
-                    timestamp: Timestamp::now(),
-                    latency_ms: 0,
-                    data: instruction.clone(),
-                }))
-            }
-            "deposit" | "depositAllTokenTypes" => {
-                // Liquidity added
-                let amount = instruction.get("amount")
-                    .and_then(|a| a.as_u64())
-                    .unwrap_or(0);
-
-                // Only interested in large liquidity additions
-                if amount > 1_000_000_000 { // 1 SOL worth
-                    let pool_id = instruction.get("poolId")
-                        .and_then(|p| p.as_str())
-                        .ok_or_else(|| AppError::internal("Missing pool ID"))?;
-
-                    // Would need to look up token from pool ID
-                    // For now, we'll skip this
-                    return Ok(None);
-                }
-
-                Ok(None)
-            }
-            _ => Ok(None),
-        }
-    }
-
-    /// Parse generic DEX event
-    fn parse_generic_dex_event(
-        program_id: &str,
-        instruction: &serde_json::Value,
-    ) -> AppResult<Option<TokenEvent>> {
-        // Generic parsing for other DEX programs
-        // This would be expanded based on specific DEX requirements
-
-        debug!("Generic DEX event from {}: {:?}", program_id, instruction);
-        Ok(None)
-    }
-
     /// Start webhook server
     async fn start_webhook_server(&self, webhook_url: &str) -> AppResult<()> {
-        info!("🌐 Starting webhook server at {}", webhook_url);
+        info!("🌐 Starting webhook server for {}", webhook_url);
 
-        // This would implement an HTTP server to receive webhooks
-        // For now, we'll use a placeholder
+        let receiver = WebhookReceiver::new(
+            self.solana.clone(),
+            webhook_url.to_string(),
+            self.decoders.clone(),
+            self.sink(),
+        )?;
+        receiver.start().await?;
+        *self.webhook.lock().await = Some(receiver);
 
-        let event_sender = self.event_sender.clone();
-        let state = self.state.clone();
-
-        tokio::spawn(async move {
-            // Webhook server implementation would go here
-            // Using axum or warp to create HTTP endpoints
+        Ok(())
+    }
 
-            warn!("Webhook server not yet implemented");
-        });
+    /// Replace the accounts the Helius webhook delivers transactions of
+    pub async fn update_webhook_addresses(&self, addresses: Vec<String>) -> AppResult<()> {
+        match self.webhook.lock().await.as_ref() {
+            Some(receiver) => receiver.update_addresses(addresses).await,
+            None => Err(AppError::internal("Webhook receiver not running")),
+        }
+    }
 
-        Ok(())
+    /// Publishing handle for detection sources
+    fn sink(&self) -> EventSink {
+        EventSink::new(self.event_sender.clone(), self.state.clone())
+            .with_price_oracle(self.solana.price_oracle().clone())
     }
 
     /// Subscribe to token events
@@ -471,6 +689,11 @@ impl EventListener {
             events_by_type: state.events_by_type.clone(),
             last_event: state.last_event,
             connection_status: format!("{:?}", state.connection_status),
+            latency_by_source: state.latency_by_source.iter()
+                .map(|(source, samples)| (source.clone(), samples.stats()))
+                .collect(),
+            slot_gaps: state.slot_gaps,
+            missed_slots: state.missed_slots,
         }
     }
 }
@@ -480,9 +703,31 @@ impl EventListener {
 pub struct ListenerStats {
     pub is_running: bool,
     pub total_events: u64,
-    pub events_by_type: std::collections::HashMap<String, u64>,
+    pub events_by_type: HashMap<String, u64>,
     pub last_event: Option<Timestamp>,
     pub connection_status: String,
+    /// Delay behind the first source to report a transaction, by source (websocket, geyser, ...)
+    pub latency_by_source: HashMap<String, SourceLatencyStats>,
+    /// Slot gaps detected on streaming sources
+    pub slot_gaps: u64,
+    /// Slots missed across all gaps
+    pub missed_slots: u64,
+}
+
+/// Delay of one source behind the first source to report the same
+/// transaction, over the recent sample window
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceLatencyStats {
+    /// Transactions reported by the source
+    pub total_events: u64,
+    /// Average latency in milliseconds
+    pub avg_ms: f64,
+    /// Median latency in milliseconds
+    pub p50_ms: u64,
+    /// 99th percentile latency in milliseconds
+    pub p99_ms: u64,
+    /// Maximum latency in milliseconds
+    pub max_ms: u64,
 }
 
 #[cfg(test)]
@@ -495,4 +740,41 @@ mod tests {
         let serialized = serde_json::to_string(&event_type).unwrap();
         assert!(serialized.contains("TokenMint"));
     }
+
+    #[test]
+    fn test_latency_samples_stats() {
+        let mut samples = LatencySamples::default();
+        for latency in 1..=100 {
+            samples.record(latency);
+        }
+
+        let stats = samples.stats();
+        assert_eq!(stats.total_events, 100);
+        assert_eq!(stats.p50_ms, 50);
+        assert_eq!(stats.p99_ms, 99);
+        assert_eq!(stats.max_ms, 100);
+        assert!((stats.avg_ms - 50.5).abs() < f64::EPSILON);
+
+        // Only the most recent window is kept for percentiles
+        for _ in 0..LATENCY_SAMPLE_WINDOW {
+            samples.record(5);
+        }
+        assert_eq!(samples.stats().p99_ms, 5);
+        assert_eq!(samples.stats().max_ms, 100);
+    }
+
+    #[test]
+    fn test_signature_sightings_delay() {
+        let mut sightings = SignatureSightings::default();
+        let first = Instant::now();
+
+        assert_eq!(sightings.delay("sig", "geyser", first), Some(Duration::ZERO));
+        assert_eq!(
+            sightings.delay("sig", "websocket", first + Duration::from_millis(120)),
+            Some(Duration::from_millis(120))
+        );
+        // A second mint in the same transaction is not a new sighting
+        assert_eq!(sightings.delay("sig", "geyser", first + Duration::from_millis(5)), None);
+        assert_eq!(sightings.delay("other", "websocket", first), Some(Duration::ZERO));
+    }
 }
\ No newline at end of file
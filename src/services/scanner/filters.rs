@@ -5,15 +5,29 @@
 
 use std::sync::Arc;
 use std::collections::HashMap;
-use tracing::{debug, warn, instrument};
+use std::path::Path;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use tokio::sync::{mpsc, RwLock};
+use tracing::{debug, error, info, warn, instrument};
 use serde::{Deserialize, Serialize};
 use rust_decimal::Decimal;
 
-use crate::config::models::ScannerConfig;
+use crate::config::models::{RiskConfig, ScannerConfig};
 use crate::core::result::AppResult;
 use crate::core::error::AppError;
 use crate::infrastructure::database::DatabaseService;
 use super::{ParsedToken, TokenMetadata, MarketData, OnChainData};
+use super::rules::{CompiledRule, FilterRule, FilterRuleSet, RuleSeverity, rule_context};
+
+/// Filter evaluations that can wait for persistence before new ones are dropped
+const EVALUATION_QUEUE_CAPACITY: usize = 4096;
+
+/// Maximum filter evaluations written per batch
+const EVALUATION_BATCH_SIZE: usize = 256;
+
+/// Longest a filter evaluation waits for its batch to fill
+const EVALUATION_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Filter criteria
 #[derive(Debug, Clone)]
@@ -47,6 +61,9 @@ pub struct FilterCriteria {
 
     /// Whitelisted tokens (bypass filters)
     pub whitelisted_tokens: Vec<String>,
+
+    /// Declarative rules evaluated after the built-in filters
+    pub rules: Vec<FilterRule>,
 }
 
 /// Filter result
@@ -77,6 +94,10 @@ pub struct FilterCheck {
     /// Whether filter passed
     pub passed: bool,
 
+    /// Whether a failure rejects the token
+    #[serde(default)]
+    pub blocking: bool,
+
     /// Filter value
     pub value: serde_json::Value,
 
@@ -93,14 +114,28 @@ pub struct TokenFilter {
     /// Configuration
     config: Arc<ScannerConfig>,
 
-    /// Database service
-    database: Arc<DatabaseService>,
-
     /// Filter criteria
-    criteria: FilterCriteria,
+    criteria: Arc<RwLock<FilterCriteria>>,
+
+    /// Individual filters, rebuilt when the criteria change
+    filters: Arc<RwLock<Vec<Box<dyn Filter>>>>,
+
+    /// Filter evaluations queued for the batch writer
+    evaluations: mpsc::Sender<PendingEvaluation>,
+}
 
-    /// Individual filters
-    filters: Vec<Box<dyn Filter>>,
+/// Filter evaluation awaiting persistence
+#[derive(Debug)]
+struct PendingEvaluation {
+    id: uuid::Uuid,
+    token_address: String,
+    passed: bool,
+    safety_score: u8,
+    rejection_reasons: Vec<String>,
+    warnings: Vec<String>,
+    price_sol: Option<Decimal>,
+    evaluated_at: DateTime<Utc>,
+    checks: Vec<FilterCheck>,
 }
 
 /// Filter trait
@@ -116,6 +151,11 @@ trait Filter: Send + Sync + std::fmt::Debug {
     fn is_blocking(&self) -> bool {
         true
     }
+
+    /// Weight toward the safety score
+    fn weight(&self) -> f64 {
+        1.0
+    }
 }
 
 /// Liquidity filter
@@ -137,6 +177,7 @@ impl Filter for LiquidityFilter {
         Ok(FilterCheck {
             name: self.name().to_string(),
             passed,
+            blocking: self.is_blocking(),
             value: serde_json::json!(liquidity.to_string()),
             expected: serde_json::json!(self.min_liquidity_sol.to_string()),
             details: if !passed {
@@ -168,6 +209,7 @@ impl Filter for TokenAgeFilter {
         Ok(FilterCheck {
             name: self.name().to_string(),
             passed,
+            blocking: self.is_blocking(),
             value: serde_json::json!(age),
             expected: serde_json::json!(self.max_age_seconds),
             details: if !passed {
@@ -194,18 +236,19 @@ impl Filter for HolderCountFilter {
 
     async fn apply(&self, token: &ParsedToken) -> AppResult<FilterCheck> {
         let holders = token.on_chain_data.holder_count;
-        let passed = holders >= self.min_holders;
+        let passed = holders.is_some_and(|count| count >= self.min_holders);
 
         Ok(FilterCheck {
             name: self.name().to_string(),
             passed,
+            blocking: self.is_blocking(),
             value: serde_json::json!(holders),
             expected: serde_json::json!(self.min_holders),
-            details: if !passed {
-                Some(format!("Holder count {} is below minimum {}",
-                             holders, self.min_holders))
-            } else {
-                None
+            details: match holders {
+                Some(count) if !passed => Some(format!("Holder count {} is below minimum {}",
+                                                       count, self.min_holders)),
+                None => Some("Holder count unavailable".to_string()),
+                _ => None,
             },
         })
     }
@@ -238,6 +281,7 @@ impl Filter for MarketCapFilter {
         Ok(FilterCheck {
             name: self.name().to_string(),
             passed,
+            blocking: self.is_blocking(),
             value: serde_json::json!(market_cap.map(|c| c.to_string())),
             expected: serde_json::json!({
                 "min": self.min_market_cap.map(|m| m.to_string()),
@@ -279,6 +323,7 @@ impl Filter for BlacklistFilter {
         Ok(FilterCheck {
             name: self.name().to_string(),
             passed,
+            blocking: self.is_blocking(),
             value: serde_json::json!({
                 "token_blacklisted": token_blacklisted,
                 "developer_blacklisted": dev_blacklisted,
@@ -322,6 +367,7 @@ impl Filter for SocialLinksFilter {
         Ok(FilterCheck {
             name: self.name().to_string(),
             passed,
+            blocking: self.is_blocking(),
             value: serde_json::json!({
                 "has_website": token.metadata.social_links.website.is_some(),
                 "has_twitter": token.metadata.social_links.twitter.is_some(),
@@ -370,6 +416,7 @@ impl Filter for AuthorityStatusFilter {
         Ok(FilterCheck {
             name: self.name().to_string(),
             passed,
+            blocking: self.is_blocking(),
             value: serde_json::json!({
                 "mint_disabled": mint_disabled,
                 "freeze_disabled": freeze_safe,
@@ -391,19 +438,59 @@ impl Filter for AuthorityStatusFilter {
     }
 }
 
+/// Declarative rule filter
+#[derive(Debug)]
+struct RuleFilter {
+    rule: CompiledRule,
+}
+
+#[async_trait::async_trait]
+impl Filter for RuleFilter {
+    fn name(&self) -> &str {
+        &self.rule.rule().name
+    }
+
+    async fn apply(&self, token: &ParsedToken) -> AppResult<FilterCheck> {
+        let context = rule_context(token);
+        let passed = self.rule.evaluate(&context);
+        let rule = self.rule.rule();
+
+        Ok(FilterCheck {
+            name: rule.name.clone(),
+            passed,
+            blocking: self.is_blocking(),
+            value: self.rule.referenced_values(&context),
+            expected: serde_json::json!(rule.expression),
+            details: if !passed {
+                Some(rule.description.clone()
+                    .unwrap_or_else(|| format!("Rule `{}` not satisfied", rule.expression)))
+            } else {
+                None
+            },
+        })
+    }
+
+    fn is_blocking(&self) -> bool {
+        self.rule.rule().severity == RuleSeverity::Blocking
+    }
+
+    fn weight(&self) -> f64 {
+        self.rule.rule().weight
+    }
+}
+
 impl TokenFilter {
     /// Create a new token filter
     pub async fn new(
         config: Arc<ScannerConfig>,
+        risk: &RiskConfig,
         database: Arc<DatabaseService>,
     ) -> AppResult<Self> {
+        // Liquidity, age and holder thresholds are risk limits
         let criteria = FilterCriteria {
-            min_liquidity_sol: Some(
-                Decimal::try_from(config.min_liquidity_sol.unwrap_or(1.0))
-                    .map_err(|e| AppError::config(format!("Invalid liquidity value: {}", e)))?
-            ),
-            max_token_age_seconds: Some(config.max_token_age_seconds),
-            min_holder_count: Some(config.min_holder_count),
+            min_liquidity_sol: Some(risk.min_liquidity_sol),
+            max_token_age_seconds: Some(risk.max_token_age_seconds),
+            min_holder_count: Some(risk.min_holder_count),
             min_market_cap_usd: config.min_market_cap_usd.map(|v| Decimal::from(v)),
             max_market_cap_usd: config.max_market_cap_usd.map(|v| Decimal::from(v)),
             require_social_links: config.require_social_links,
@@ -411,9 +498,38 @@ impl TokenFilter {
             blacklisted_tokens: config.blacklisted_tokens.clone(),
             blacklisted_developers: config.blacklisted_developers.clone(),
             whitelisted_tokens: config.whitelisted_tokens.clone(),
+            rules: match &config.filter_rules_path {
+                Some(path) => FilterRuleSet::load(Path::new(path)).await?.rules,
+                None => Vec::new(),
+            },
         };
 
-        // Build filter chain
+        // The writer exits once the filter, and with it the sender, is dropped
+        let (evaluations, queue) = mpsc::channel(EVALUATION_QUEUE_CAPACITY);
+        tokio::spawn(Self::write_evaluations(database, queue));
+
+        Self::with_criteria(config, criteria, evaluations)
+    }
+
+    /// Create a token filter that queues evaluations on a channel
+    fn with_criteria(
+        config: Arc<ScannerConfig>,
+        criteria: FilterCriteria,
+        evaluations: mpsc::Sender<PendingEvaluation>,
+    ) -> AppResult<Self> {
+        let filters = Self::build_filters(&criteria)?;
+        info!("Token filter initialized with {} filters ({} rules)", filters.len(), criteria.rules.len());
+
+        Ok(Self {
+            config,
+            criteria: Arc::new(RwLock::new(criteria)),
+            filters: Arc::new(RwLock::new(filters)),
+            evaluations,
+        })
+    }
+
+    /// Build the filter chain for a set of criteria
+    fn build_filters(criteria: &FilterCriteria) -> AppResult<Vec<Box<dyn Filter>>> {
         let mut filters: Vec<Box<dyn Filter>> = Vec::new();
 
         // Add liquidity filter
@@ -455,12 +571,12 @@ impl TokenFilter {
         // Add authority filter
         filters.push(Box::new(AuthorityStatusFilter));
 
-        Ok(Self {
-            config,
-            database,
-            criteria,
-            filters,
-        })
+        // Add declarative rules
+        for rule in &criteria.rules {
+            filters.push(Box::new(RuleFilter { rule: CompiledRule::compile(rule.clone())? }));
+        }
+
+        Ok(filters)
     }
 
     /// Apply all filters to a token
@@ -468,8 +584,11 @@ impl TokenFilter {
     pub async fn apply_filters(&self, token: &ParsedToken) -> AppResult<FilterResult> {
         debug!("Applying filters to token: {}", token.address);
 
+        let criteria = self.criteria.read().await;
+        let filters = self.filters.read().await;
+
         // Check whitelist first
-        if self.criteria.whitelisted_tokens.contains(&token.address.to_string()) {
+        if criteria.whitelisted_tokens.contains(&token.address.to_string()) {
             debug!("Token is whitelisted, bypassing filters");
             return Ok(FilterResult {
                 passed: true,
@@ -483,17 +602,17 @@ impl TokenFilter {
         let mut filter_results = HashMap::new();
         let mut rejection_reasons = Vec::new();
         let mut warnings = Vec::new();
-        let mut passed_filters = 0;
-        let mut total_filters = 0;
+        let mut passed_weight = 0.0;
+        let mut total_weight = 0.0;
 
         // Apply each filter
-        for filter in &self.filters {
+        for filter in filters.iter() {
             let filter_name = filter.name().to_string();
 
             match filter.apply(token).await {
                 Ok(result) => {
                     if result.passed {
-                        passed_filters += 1;
+                        passed_weight += filter.weight();
                     } else if filter.is_blocking() {
                         if let Some(ref details) = result.details {
                             rejection_reasons.push(format!("{}: {}", filter_name, details));
@@ -508,7 +627,7 @@ impl TokenFilter {
                     }
 
                     filter_results.insert(filter_name, result);
-                    total_filters += 1;
+                    total_weight += filter.weight();
                 }
                 Err(e) => {
                     warn!("Filter '{}' error: {}", filter_name, e);
@@ -518,8 +637,8 @@ impl TokenFilter {
         }
 
         // Calculate safety score
-        let safety_score = if total_filters > 0 {
-            ((passed_filters as f64 / total_filters as f64) * 100.0) as u8
+        let safety_score = if total_weight > 0.0 {
+            ((passed_weight / total_weight) * 100.0) as u8
         } else {
             0
         };
@@ -538,16 +657,148 @@ impl TokenFilter {
         })
     }
 
-    /// Update filter criteria
-    pub async fn update_criteria(&mut self, criteria: FilterCriteria) -> AppResult<()> {
-        self.criteria = criteria;
+    /// Queue a filter result and its checks for attribution analytics
+    ///
+    /// Rejected tokens are recorded as well so their price can be followed
+    /// after the fact. The write happens in batches off the scan path; when
+    /// the queue is full the evaluation is dropped. Returns the evaluation id.
+    pub fn record_result(&self, token: &ParsedToken, result: &FilterResult) -> AppResult<uuid::Uuid> {
+        let evaluation = PendingEvaluation {
+            id: uuid::Uuid::new_v4(),
+            token_address: token.address.to_string(),
+            passed: result.passed,
+            safety_score: result.safety_score,
+            rejection_reasons: result.rejection_reasons.clone(),
+            warnings: result.warnings.clone(),
+            price_sol: token.market_data.price_sol,
+            evaluated_at: Utc::now(),
+            checks: result.filter_results.values().cloned().collect(),
+        };
+        let id = evaluation.id;
+
+        self.evaluations.try_send(evaluation).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => AppError::internal("Filter evaluation queue is full"),
+            mpsc::error::TrySendError::Closed(_) => AppError::internal("Filter evaluation writer has stopped"),
+        })?;
+
+        Ok(id)
+    }
 
-        // Rebuild filters with new criteria
-        // This would be implemented similar to the constructor
+    /// Write queued filter evaluations in batches
+    async fn write_evaluations(database: Arc<DatabaseService>, mut queue: mpsc::Receiver<PendingEvaluation>) {
+        while let Some(first) = queue.recv().await {
+            let mut batch = vec![first];
+            let deadline = tokio::time::Instant::now() + EVALUATION_FLUSH_INTERVAL;
+
+            while batch.len() < EVALUATION_BATCH_SIZE {
+                match tokio::time::timeout_at(deadline, queue.recv()).await {
+                    Ok(Some(evaluation)) => batch.push(evaluation),
+                    _ => break,
+                }
+            }
+
+            match Self::insert_evaluations(&database, &batch).await {
+                Ok(()) => debug!("Recorded {} filter evaluations", batch.len()),
+                Err(e) => error!("Failed to record {} filter evaluations: {}", batch.len(), e),
+            }
+        }
+
+        debug!("Filter evaluation writer stopped");
+    }
+
+    /// Insert a batch of filter evaluations and their checks
+    async fn insert_evaluations(database: &DatabaseService, batch: &[PendingEvaluation]) -> AppResult<()> {
+        let mut tx = database.postgres.begin_transaction().await?;
+
+        let mut evaluations = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+            "INSERT INTO filter_evaluations
+                (id, token_address, passed, safety_score, rejection_reasons, warnings, price_sol, evaluated_at) "
+        );
+        evaluations.push_values(batch, |mut row, evaluation| {
+            row.push_bind(evaluation.id)
+                .push_bind(&evaluation.token_address)
+                .push_bind(evaluation.passed)
+                .push_bind(evaluation.safety_score as i16)
+                .push_bind(serde_json::json!(evaluation.rejection_reasons))
+                .push_bind(serde_json::json!(evaluation.warnings))
+                .push_bind(evaluation.price_sol)
+                .push_bind(evaluation.evaluated_at);
+        });
+        evaluations.build()
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::database(format!("Failed to record filter evaluations: {}", e), "insert_filter_evaluation"))?;
+
+        let checks: Vec<(uuid::Uuid, &FilterCheck)> = batch.iter()
+            .flat_map(|evaluation| evaluation.checks.iter().map(move |check| (evaluation.id, check)))
+            .collect();
+
+        if !checks.is_empty() {
+            let mut rows = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+                "INSERT INTO filter_checks
+                    (evaluation_id, filter_name, passed, blocking, flagged, value, expected, details) "
+            );
+            rows.push_values(checks, |mut row, (evaluation_id, check)| {
+                // Warnings count as flagged so non-blocking filters can be attributed too
+                let flagged = !check.passed || check.details.is_some();
+
+                row.push_bind(evaluation_id)
+                    .push_bind(&check.name)
+                    .push_bind(check.passed)
+                    .push_bind(check.blocking)
+                    .push_bind(flagged)
+                    .push_bind(&check.value)
+                    .push_bind(&check.expected)
+                    .push_bind(&check.details);
+            });
+            rows.build()
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::database(format!("Failed to record filter checks: {}", e), "insert_filter_check"))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::database(format!("Failed to commit filter evaluations: {}", e), "insert_filter_evaluation"))?;
 
-        info!("Filter criteria updated");
         Ok(())
     }
+
+    /// Update filter criteria
+    ///
+    /// The filter chain is rebuilt first, so invalid rules leave the current
+    /// criteria in place.
+    pub async fn update_criteria(&self, criteria: FilterCriteria) -> AppResult<()> {
+        let filters = Self::build_filters(&criteria)?;
+
+        // Lock in the same order as apply_filters
+        let mut current_criteria = self.criteria.write().await;
+        let mut current_filters = self.filters.write().await;
+        *current_criteria = criteria;
+        *current_filters = filters;
+
+        info!("Filter criteria updated: {} filters ({} rules)", current_filters.len(), current_criteria.rules.len());
+        Ok(())
+    }
+
+    /// Reload declarative rules from the configured rules file
+    pub async fn reload_rules(&self) -> AppResult<usize> {
+        let path = self.config.filter_rules_path.as_ref()
+            .ok_or_else(|| AppError::config("No filter rules file configured"))?;
+        let rules = FilterRuleSet::load(Path::new(path)).await?.rules;
+        let count = rules.len();
+
+        let mut criteria = self.criteria.read().await.clone();
+        criteria.rules = rules;
+        self.update_criteria(criteria).await?;
+
+        Ok(count)
+    }
+
+    /// Current filter criteria
+    pub async fn criteria(&self) -> FilterCriteria {
+        self.criteria.read().await.clone()
+    }
 }
 
 #[cfg(test)]
@@ -572,6 +823,176 @@ mod tests {
         assert!(!result.passed);
     }
 
+    #[tokio::test]
+    async fn test_check_records_blocking_flag() {
+        let token = create_test_token();
+
+        let check = SocialLinksFilter { required: true }.apply(&token).await.unwrap();
+        assert!(!check.passed);
+        assert!(!check.blocking);
+
+        let check = HolderCountFilter { min_holders: 100 }.apply(&token).await.unwrap();
+        assert!(!check.passed);
+        assert!(check.blocking);
+    }
+
+    #[tokio::test]
+    async fn test_rule_filter() {
+        let mut token = create_test_token();
+        token.market_data.liquidity_sol = Some(Decimal::from(7));
+
+        let rule = |expression: &str, severity| RuleFilter {
+            rule: CompiledRule::compile(FilterRule {
+                name: "fresh_pool".to_string(),
+                expression: expression.to_string(),
+                severity,
+                weight: 3.0,
+                description: None,
+            }).unwrap(),
+        };
+
+        let filter = rule("market_data.liquidity_sol >= 5 && on_chain.age_seconds < 120", RuleSeverity::Blocking);
+        let check = filter.apply(&token).await.unwrap();
+        assert!(!check.passed);
+        assert!(check.blocking);
+        assert_eq!(check.value["on_chain.age_seconds"], serde_json::json!(300));
+        assert_eq!(filter.weight(), 3.0);
+
+        let filter = rule("market_data.liquidity_sol >= 5 && on_chain.age_seconds < 600", RuleSeverity::Warning);
+        let check = filter.apply(&token).await.unwrap();
+        assert!(check.passed);
+        assert!(!check.blocking);
+    }
+
+    fn empty_criteria() -> FilterCriteria {
+        FilterCriteria {
+            min_liquidity_sol: None,
+            max_token_age_seconds: None,
+            min_holder_count: None,
+            min_market_cap_usd: None,
+            max_market_cap_usd: None,
+            require_social_links: false,
+            require_contract_verification: false,
+            blacklisted_tokens: Vec::new(),
+            blacklisted_developers: Vec::new(),
+            whitelisted_tokens: Vec::new(),
+            rules: Vec::new(),
+        }
+    }
+
+    fn test_rule(name: &str, expression: &str, severity: RuleSeverity, weight: f64) -> FilterRule {
+        FilterRule {
+            name: name.to_string(),
+            expression: expression.to_string(),
+            severity,
+            weight,
+            description: None,
+        }
+    }
+
+    fn create_test_filter(
+        config: ScannerConfig,
+        criteria: FilterCriteria,
+    ) -> (TokenFilter, mpsc::Receiver<PendingEvaluation>) {
+        let (evaluations, queue) = mpsc::channel(8);
+        let filter = TokenFilter::with_criteria(Arc::new(config), criteria, evaluations).unwrap();
+        (filter, queue)
+    }
+
+    fn test_scanner_config() -> ScannerConfig {
+        crate::config::ConfigLoader::new().without_env().create_default_config().scanner
+    }
+
+    #[tokio::test]
+    async fn test_weighted_safety_score() {
+        let mut criteria = empty_criteria();
+        criteria.rules = vec![
+            test_rule("young", "on_chain.age_seconds < 600", RuleSeverity::Blocking, 3.0),
+            test_rule("holders", "on_chain.holder_count >= 100", RuleSeverity::Warning, 1.0),
+        ];
+        let (filter, mut queue) = create_test_filter(test_scanner_config(), criteria);
+
+        let token = create_test_token();
+        let result = filter.apply_filters(&token).await.unwrap();
+
+        // Authority check (1) and age rule (3) pass, holder warning (1) fails
+        assert_eq!(result.safety_score, 80);
+        assert!(result.passed);
+        assert_eq!(result.warnings.len(), 1);
+
+        let id = filter.record_result(&token, &result).unwrap();
+        let queued = queue.try_recv().unwrap();
+        assert_eq!(queued.id, id);
+        assert_eq!(queued.checks.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_update_criteria() {
+        let (filter, _queue) = create_test_filter(test_scanner_config(), empty_criteria());
+        let token = create_test_token();
+        assert!(filter.apply_filters(&token).await.unwrap().passed);
+
+        let mut criteria = empty_criteria();
+        criteria.min_holder_count = Some(100);
+        filter.update_criteria(criteria).await.unwrap();
+        assert!(!filter.apply_filters(&token).await.unwrap().passed);
+
+        // An invalid rule leaves the current criteria in place
+        let mut invalid = empty_criteria();
+        invalid.rules = vec![test_rule("broken", "on_chain.age_seconds <", RuleSeverity::Blocking, 1.0)];
+        assert!(filter.update_criteria(invalid).await.is_err());
+        assert_eq!(filter.criteria().await.min_holder_count, Some(100));
+        assert!(!filter.apply_filters(&token).await.unwrap().passed);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_holder_count_fails() {
+        let filter = HolderCountFilter { min_holders: 10 };
+        let mut token = create_test_token();
+
+        assert!(filter.apply(&token).await.unwrap().passed);
+
+        token.on_chain_data.holder_count = None;
+        let check = filter.apply(&token).await.unwrap();
+        assert!(!check.passed);
+        assert_eq!(check.details.as_deref(), Some("Holder count unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_reload_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.yaml");
+        tokio::fs::write(&path, r#"
+rules:
+  - name: young
+    expression: "on_chain.age_seconds < 60"
+"#).await.unwrap();
+
+        let mut config = test_scanner_config();
+        let (unconfigured, _queue) = create_test_filter(config.clone(), empty_criteria());
+        assert!(unconfigured.reload_rules().await.is_err());
+
+        config.filter_rules_path = Some(path.to_string_lossy().to_string());
+        let (filter, _queue) = create_test_filter(config, empty_criteria());
+        let token = create_test_token();
+
+        assert_eq!(filter.reload_rules().await.unwrap(), 1);
+        assert!(!filter.apply_filters(&token).await.unwrap().passed);
+
+        tokio::fs::write(&path, r#"
+rules:
+  - name: young
+    expression: "on_chain.age_seconds < 600"
+"#).await.unwrap();
+        assert_eq!(filter.reload_rules().await.unwrap(), 1);
+        assert!(filter.apply_filters(&token).await.unwrap().passed);
+
+        // A broken file keeps the last good rules
+        tokio::fs::write(&path, "rules: [").await.unwrap();
+        assert!(filter.reload_rules().await.is_err());
+        assert_eq!(filter.criteria().await.rules[0].expression, "on_chain.age_seconds < 600");
+    }
+
     fn create_test_token() -> ParsedToken {
         ParsedToken {
             address: TokenAddress::new_unchecked("TestToken111111111111111111111111111111111".to_string()),
@@ -586,6 +1007,8 @@ mod tests {
                 metadata_program: None,
                 is_verified: false,
                 social_links: super::super::SocialLinks::default(),
+                update_authority: None,
+                creators: Vec::new(),
             },
             market_data: MarketData {
                 market_cap_usd: None,
@@ -599,9 +1022,10 @@ mod tests {
             },
             on_chain_data: OnChainData {
                 age_seconds: 300,
-                holder_count: 50,
+                holder_count: Some(50),
                 creator_address: None,
                 first_tx_signature: None,
+                creation_slot: None,
                 program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
                 associated_token_program: None,
                 is_mutable: true,
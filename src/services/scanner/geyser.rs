@@ -0,0 +1,602 @@
+//! Yellowstone gRPC (Geyser) streaming detection source
+//!
+//! This module subscribes to a Yellowstone-compatible gRPC endpoint for
+//! transactions touching the DEX programs of the decoder registry, decodes
+//! pool and mint creations, and publishes them through the event listener.
+//! Streams are reconnected with exponential backoff, resuming from the last
+//! seen slot, and gaps in the slot sequence are reported in listener stats.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use base58::ToBase58;
+use futures::{SinkExt, StreamExt};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::{info, warn, error, debug};
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::prelude::{
+    subscribe_update::UpdateOneof, CommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterSlots, SubscribeRequestFilterTransactions, SubscribeRequestPing,
+    SubscribeUpdateSlot, SubscribeUpdateTransaction, TokenBalance,
+};
+use yellowstone_grpc_proto::tonic::transport::ClientTlsConfig;
+
+use crate::core::result::AppResult;
+use crate::core::error::AppError;
+use crate::services::solana::DecoderRegistry;
+use crate::services::solana::rpc::{balance_changes, token_balance_changes, RawTokenBalance};
+use crate::services::solana::types::{InstructionInfo, TransactionInfo, TransactionStatus};
+use super::event_listener::{EventListener, EventSink};
+
+/// Detection source label for the Geyser stream
+pub const GEYSER_SOURCE: &str = "geyser";
+
+/// Initial reconnect delay, doubled per failed attempt
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+/// Maximum reconnect delay
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Connection timeout
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A stream with no messages for this long is treated as dead
+const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Geyser gRPC detection source
+#[derive(Debug, Clone)]
+pub struct GeyserSource {
+    /// gRPC endpoint
+    endpoint: String,
+
+    /// Access token sent as `x-token`
+    x_token: Option<String>,
+
+    /// Pool and mint creation decoders; their programs form the filter
+    decoders: Arc<DecoderRegistry>,
+
+    /// Listener event sink
+    sink: EventSink,
+
+    /// Initial reconnect delay
+    reconnect_delay: Duration,
+
+    /// Highest slot seen on the stream
+    last_slot: Arc<RwLock<Option<u64>>>,
+}
+
+impl GeyserSource {
+    /// Create a new Geyser source
+    pub(super) fn new(
+        endpoint: String,
+        x_token: Option<String>,
+        decoders: Arc<DecoderRegistry>,
+        sink: EventSink,
+    ) -> Self {
+        Self {
+            endpoint,
+            x_token,
+            decoders,
+            sink,
+            reconnect_delay: INITIAL_RECONNECT_DELAY,
+            last_slot: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Set the initial reconnect delay
+    pub fn with_reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    /// Spawn the streaming loop
+    pub fn spawn(self) -> JoinHandle<()> {
+        info!("📡 Starting Geyser stream from {}", self.endpoint);
+        tokio::spawn(async move { self.run().await })
+    }
+
+    /// Stream until the listener stops, reconnecting on failure
+    async fn run(&self) {
+        let mut delay = self.reconnect_delay;
+
+        loop {
+            let resume_from = *self.last_slot.read().await;
+
+            match self.stream(resume_from).await {
+                Ok(received) => {
+                    warn!("Geyser stream closed by {}", self.endpoint);
+                    if received {
+                        delay = self.reconnect_delay;
+                    }
+                }
+                Err(e) => {
+                    error!("Geyser stream error from {}: {}", self.endpoint, e);
+                }
+            }
+
+            if !self.sink.is_running().await {
+                break;
+            }
+
+            warn!("Reconnecting to Geyser in {:?} (resuming from slot {:?})", delay, resume_from);
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+
+        info!("🛑 Geyser stream stopped");
+    }
+
+    /// Run one subscription; returns whether any message was received
+    async fn stream(&self, resume_from: Option<u64>) -> AppResult<bool> {
+        let mut builder = GeyserGrpcClient::build_from_shared(self.endpoint.clone())
+            .map_err(|e| AppError::network(format!("Invalid Geyser endpoint: {}", e)))?
+            .x_token(self.x_token.clone())
+            .map_err(|e| AppError::network(format!("Invalid Geyser token: {}", e)))?
+            .connect_timeout(CONNECT_TIMEOUT);
+
+        if self.endpoint.starts_with("https://") {
+            builder = builder.tls_config(ClientTlsConfig::new().with_native_roots())
+                .map_err(|e| AppError::network(format!("Geyser TLS config failed: {}", e)))?;
+        }
+
+        let mut client = builder.connect().await
+            .map_err(|e| AppError::network(format!("Geyser connect failed: {}", e)))?;
+
+        let (mut requests, mut updates) = client
+            .subscribe_with_request(Some(self.subscribe_request(resume_from)))
+            .await
+            .map_err(|e| AppError::network(format!("Geyser subscribe failed: {}", e)))?;
+
+        debug!("Subscribed to Geyser for {} programs", self.decoders.program_ids().len());
+        let mut received = false;
+
+        while self.sink.is_running().await {
+            let update = match tokio::time::timeout(STREAM_IDLE_TIMEOUT, updates.next()).await {
+                Ok(Some(Ok(update))) => update,
+                Ok(Some(Err(status))) => {
+                    return Err(AppError::network(format!("Geyser stream failed: {}", status)));
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    return Err(AppError::network(format!(
+                        "Geyser stream idle for {:?}", STREAM_IDLE_TIMEOUT
+                    )));
+                }
+            };
+
+            let received_at = Instant::now();
+            received = true;
+
+            match update.update_oneof {
+                Some(UpdateOneof::Transaction(transaction)) => {
+                    self.handle_transaction(&transaction, received_at).await;
+                }
+                Some(UpdateOneof::Slot(slot)) => {
+                    self.handle_slot(&slot).await;
+                }
+                Some(UpdateOneof::Ping(_)) => {
+                    // Answer server pings so load balancers keep the stream open
+                    let ping = SubscribeRequest {
+                        ping: Some(SubscribeRequestPing { id: 1 }),
+                        ..Default::default()
+                    };
+                    requests.send(ping).await
+                        .map_err(|e| AppError::network(format!("Geyser ping failed: {}", e)))?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(received)
+    }
+
+    /// Subscription for non-vote, successful transactions of the decoder
+    /// programs plus processed slot updates
+    fn subscribe_request(&self, resume_from: Option<u64>) -> SubscribeRequest {
+        let transactions = SubscribeRequestFilterTransactions {
+            vote: Some(false),
+            failed: Some(false),
+            account_include: self.decoders.program_ids(),
+            ..Default::default()
+        };
+
+        let slots = SubscribeRequestFilterSlots {
+            filter_by_commitment: Some(true),
+            ..Default::default()
+        };
+
+        SubscribeRequest {
+            transactions: HashMap::from([(GEYSER_SOURCE.to_string(), transactions)]),
+            slots: HashMap::from([(GEYSER_SOURCE.to_string(), slots)]),
+            commitment: Some(CommitmentLevel::Processed as i32),
+            from_slot: resume_from,
+            ..Default::default()
+        }
+    }
+
+    /// Decode a transaction update and publish its creation events
+    async fn handle_transaction(&self, update: &SubscribeUpdateTransaction, received_at: Instant) {
+        let Some(transaction) = transaction_info_from_update(update) else {
+            debug!("Skipping undecodable Geyser transaction at slot {}", update.slot);
+            return;
+        };
+
+        for decoded in self.decoders.decode_transaction(&transaction) {
+            for event in EventListener::decoded_to_token_events(&decoded) {
+                self.sink.publish(GEYSER_SOURCE, event, received_at).await;
+            }
+        }
+    }
+
+    /// Track the slot sequence and report gaps
+    ///
+    /// Skipped leader slots never appear on the stream, so a gap is detected
+    /// from the parent: a slot whose parent is newer than the last seen slot
+    /// means the updates in between were missed.
+    async fn handle_slot(&self, update: &SubscribeUpdateSlot) {
+        let mut last_slot = self.last_slot.write().await;
+
+        if let Some(missed) = missed_slots(*last_slot, update.slot, update.parent) {
+            warn!("Geyser slot gap: {} slots missed before slot {}", missed, update.slot);
+            self.sink.record_slot_gap(missed).await;
+        }
+
+        if last_slot.map_or(true, |last| update.slot > last) {
+            *last_slot = Some(update.slot);
+        }
+    }
+}
+
+/// Number of slots missed between the last seen slot and a new slot
+fn missed_slots(last_slot: Option<u64>, slot: u64, parent: Option<u64>) -> Option<u64> {
+    let last_slot = last_slot?;
+    let parent = parent?;
+
+    (slot > last_slot && parent > last_slot).then(|| parent - last_slot)
+}
+
+/// Convert a Geyser transaction update into a `TransactionInfo`
+///
+/// Account keys include addresses loaded from lookup tables, so account
+/// indexes of v0 transactions resolve correctly.
+fn transaction_info_from_update(update: &SubscribeUpdateTransaction) -> Option<TransactionInfo> {
+    let info = update.transaction.as_ref()?;
+    let message = info.transaction.as_ref()?.message.as_ref()?;
+    let meta = info.meta.as_ref();
+
+    let mut account_keys: Vec<String> = message.account_keys.iter().map(|key| key.to_base58()).collect();
+    if let Some(meta) = meta {
+        account_keys.extend(meta.loaded_writable_addresses.iter().map(|key| key.to_base58()));
+        account_keys.extend(meta.loaded_readonly_addresses.iter().map(|key| key.to_base58()));
+    }
+
+    let resolve = |program_id_index: u32, accounts: &[u8], data: &[u8]| -> Option<InstructionInfo> {
+        Some(InstructionInfo {
+            program_id: account_keys.get(program_id_index as usize)?.clone(),
+            data: data.to_vec(),
+            accounts: accounts.iter()
+                .map(|index| account_keys.get(*index as usize).cloned())
+                .collect::<Option<Vec<_>>>()?,
+        })
+    };
+
+    let instructions = message.instructions.iter()
+        .filter_map(|ix| resolve(ix.program_id_index, &ix.accounts, &ix.data))
+        .collect();
+
+    let inner_instructions = meta
+        .map(|meta| meta.inner_instructions.iter()
+            .flat_map(|inner| inner.instructions.iter())
+            .filter_map(|ix| resolve(ix.program_id_index, &ix.accounts, &ix.data))
+            .collect())
+        .unwrap_or_default();
+
+    let token_balances = |balances: &[TokenBalance]| -> Vec<RawTokenBalance> {
+        balances.iter()
+            .map(|balance| {
+                let amount = balance.ui_token_amount.as_ref();
+                RawTokenBalance {
+                    account_index: balance.account_index as usize,
+                    mint: balance.mint.clone(),
+                    owner: (!balance.owner.is_empty()).then(|| balance.owner.clone()),
+                    amount: amount.and_then(|a| a.amount.parse().ok()).unwrap_or_default(),
+                    decimals: amount.map_or(0, |a| a.decimals as u8),
+                }
+            })
+            .collect()
+    };
+
+    Some(TransactionInfo {
+        signature: info.signature.to_base58(),
+        slot: update.slot,
+        block_time: None,
+        status: match meta.map(|meta| meta.err.is_some()) {
+            Some(true) => TransactionStatus::Failed,
+            _ => TransactionStatus::Success,
+        },
+        fee: meta.map_or(0, |meta| meta.fee),
+        instructions,
+        inner_instructions,
+        logs: meta.map(|meta| meta.log_messages.clone()).unwrap_or_default(),
+        balance_changes: meta
+            .map(|meta| balance_changes(&account_keys, &meta.pre_balances, &meta.post_balances))
+            .unwrap_or_default(),
+        token_balance_changes: meta
+            .map(|meta| token_balance_changes(
+                &account_keys,
+                token_balances(&meta.pre_token_balances),
+                token_balances(&meta.post_token_balances),
+            ))
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use solana_sdk::pubkey::Pubkey;
+    use tokio::sync::broadcast;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use yellowstone_grpc_proto::geyser::geyser_server::{Geyser, GeyserServer};
+    use yellowstone_grpc_proto::prelude::*;
+    use yellowstone_grpc_proto::tonic::{self, Request, Response, Status, Streaming};
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use crate::config::ConfigLoader;
+    use crate::services::solana::decoders::{ORCA_WHIRLPOOL_PROGRAM_ID, WSOL_MINT};
+    use crate::services::solana::{bonding_curve_address, DecodedEventKind, PriceOracle, RpcPool};
+    use super::super::event_listener::ListenerState;
+
+    /// Anchor discriminator of Whirlpool `initialize_pool_v2`
+    const INITIALIZE_POOL_V2: [u8; 8] = [207, 45, 87, 242, 27, 63, 204, 67];
+
+    /// Mock Geyser server replaying one batch of updates per subscription
+    #[derive(Debug, Clone, Default)]
+    struct MockGeyser {
+        batches: Arc<std::sync::Mutex<VecDeque<Vec<SubscribeUpdate>>>>,
+        subscriptions: Arc<AtomicUsize>,
+    }
+
+    type UpdateStream = Pin<Box<dyn futures::Stream<Item = Result<SubscribeUpdate, Status>> + Send>>;
+
+    #[tonic::async_trait]
+    impl Geyser for MockGeyser {
+        type SubscribeStream = UpdateStream;
+
+        async fn subscribe(
+            &self,
+            _request: Request<Streaming<SubscribeRequest>>,
+        ) -> Result<Response<Self::SubscribeStream>, Status> {
+            self.subscriptions.fetch_add(1, Ordering::SeqCst);
+            let batch = self.batches.lock().unwrap().pop_front();
+
+            // Replay the batch and close; with no batch left, stay open
+            let stream: UpdateStream = match batch {
+                Some(batch) => Box::pin(futures::stream::iter(batch.into_iter().map(Ok))),
+                None => Box::pin(futures::stream::pending()),
+            };
+            Ok(Response::new(stream))
+        }
+
+        async fn subscribe_replay_info(
+            &self,
+            _request: Request<SubscribeReplayInfoRequest>,
+        ) -> Result<Response<SubscribeReplayInfoResponse>, Status> {
+            Err(Status::unimplemented("mock"))
+        }
+
+        async fn ping(&self, _request: Request<PingRequest>) -> Result<Response<PongResponse>, Status> {
+            Err(Status::unimplemented("mock"))
+        }
+
+        async fn get_latest_blockhash(
+            &self,
+            _request: Request<GetLatestBlockhashRequest>,
+        ) -> Result<Response<GetLatestBlockhashResponse>, Status> {
+            Err(Status::unimplemented("mock"))
+        }
+
+        async fn get_block_height(
+            &self,
+            _request: Request<GetBlockHeightRequest>,
+        ) -> Result<Response<GetBlockHeightResponse>, Status> {
+            Err(Status::unimplemented("mock"))
+        }
+
+        async fn get_slot(&self, _request: Request<GetSlotRequest>) -> Result<Response<GetSlotResponse>, Status> {
+            Err(Status::unimplemented("mock"))
+        }
+
+        async fn is_blockhash_valid(
+            &self,
+            _request: Request<IsBlockhashValidRequest>,
+        ) -> Result<Response<IsBlockhashValidResponse>, Status> {
+            Err(Status::unimplemented("mock"))
+        }
+
+        async fn get_version(
+            &self,
+            _request: Request<GetVersionRequest>,
+        ) -> Result<Response<GetVersionResponse>, Status> {
+            Err(Status::unimplemented("mock"))
+        }
+    }
+
+    fn slot_update(slot: u64, parent: u64) -> SubscribeUpdate {
+        SubscribeUpdate {
+            update_oneof: Some(UpdateOneof::Slot(SubscribeUpdateSlot {
+                slot,
+                parent: Some(parent),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    }
+
+    /// Orca `initialize_pool_v2` for a new mint against SOL
+    fn pool_update(slot: u64, new_mint: Pubkey) -> SubscribeUpdate {
+        let wsol: Pubkey = WSOL_MINT.parse().unwrap();
+        let orca: Pubkey = ORCA_WHIRLPOOL_PROGRAM_ID.parse().unwrap();
+
+        let mut account_keys: Vec<Vec<u8>> = (0..12).map(|_| Pubkey::new_unique().to_bytes().to_vec()).collect();
+        account_keys[1] = new_mint.to_bytes().to_vec();
+        account_keys[2] = wsol.to_bytes().to_vec();
+        account_keys.push(orca.to_bytes().to_vec());
+
+        let mut data = INITIALIZE_POOL_V2.to_vec();
+        data.extend_from_slice(&64u16.to_le_bytes());
+
+        SubscribeUpdate {
+            update_oneof: Some(UpdateOneof::Transaction(SubscribeUpdateTransaction {
+                slot,
+                transaction: Some(SubscribeUpdateTransactionInfo {
+                    signature: vec![7; 64],
+                    transaction: Some(Transaction {
+                        signatures: vec![vec![7; 64]],
+                        message: Some(Message {
+                            account_keys,
+                            instructions: vec![CompiledInstruction {
+                                program_id_index: 12,
+                                accounts: (0..12).collect(),
+                                data,
+                            }],
+                            ..Default::default()
+                        }),
+                    }),
+                    meta: Some(TransactionStatusMeta::default()),
+                    ..Default::default()
+                }),
+            })),
+            ..Default::default()
+        }
+    }
+
+    /// Serve `mock` on a local port, returning its endpoint
+    async fn serve(mock: MockGeyser) -> (String, tokio::task::JoinHandle<Result<(), tonic::transport::Error>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(GeyserServer::new(mock))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        (endpoint, server)
+    }
+
+    /// Price oracle over a mock RPC endpoint
+    async fn price_oracle(server: &MockServer) -> Arc<PriceOracle> {
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": "getHealth" })))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": "ok" })))
+            .mount(server)
+            .await;
+
+        let mut config = ConfigLoader::new().without_env().create_default_config().solana;
+        config.rpc_url = server.uri();
+        config.fallback_rpc_urls = Vec::new();
+        let pool = Arc::new(RpcPool::new(&config).await.unwrap());
+        Arc::new(PriceOracle::new(pool, &config).unwrap())
+    }
+
+    #[test]
+    fn test_missed_slots() {
+        assert_eq!(missed_slots(None, 100, Some(99)), None);
+        assert_eq!(missed_slots(Some(99), 100, Some(99)), None);
+        // Slot 101 was skipped by its leader; 102 builds on 100
+        assert_eq!(missed_slots(Some(100), 102, Some(100)), None);
+        assert_eq!(missed_slots(Some(100), 105, Some(103)), Some(3));
+        // Replayed slots after a reconnect are not gaps
+        assert_eq!(missed_slots(Some(105), 101, Some(100)), None);
+    }
+
+    #[tokio::test]
+    async fn test_stream_reconnects_and_detects_slot_gaps() {
+        let new_mint = Pubkey::new_unique();
+        let mock = MockGeyser::default();
+        mock.batches.lock().unwrap().extend([
+            vec![slot_update(100, 99), pool_update(100, new_mint)],
+            vec![slot_update(104, 103)],
+        ]);
+
+        let (endpoint, server) = serve(mock.clone()).await;
+
+        let (sender, mut receiver) = broadcast::channel(16);
+        let mut state = ListenerState::new();
+        state.is_running = true;
+        let state = Arc::new(RwLock::new(state));
+
+        let source = GeyserSource::new(
+            endpoint,
+            None,
+            Arc::new(DecoderRegistry::with_defaults()),
+            EventSink::new(sender, state.clone()),
+        ).with_reconnect_delay(Duration::from_millis(10));
+        let task = source.spawn();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await.unwrap().unwrap();
+        assert_eq!(event.token_address.as_str(), new_mint.to_string());
+        assert_eq!(event.source, "orca");
+
+        // The second subscription reports a gap: slots 101-103 were never seen
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while state.read().await.slot_gaps == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.unwrap();
+
+        {
+            let state = state.read().await;
+            assert_eq!(state.slot_gaps, 1);
+            assert_eq!(state.missed_slots, 3);
+            assert_eq!(state.latency_by_source[GEYSER_SOURCE].stats().total_events, 1);
+        }
+        assert!(mock.subscriptions.load(Ordering::SeqCst) >= 2);
+
+        state.write().await.is_running = false;
+        task.abort();
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_published_pools_registered_with_oracle() {
+        let new_mint = Pubkey::new_unique();
+        let mock = MockGeyser::default();
+        mock.batches.lock().unwrap().push_back(vec![pool_update(100, new_mint)]);
+        let (endpoint, server) = serve(mock).await;
+
+        let rpc = MockServer::start().await;
+        let oracle = price_oracle(&rpc).await;
+
+        let (sender, mut receiver) = broadcast::channel(16);
+        let mut state = ListenerState::new();
+        state.is_running = true;
+        let state = Arc::new(RwLock::new(state));
+
+        let source = GeyserSource::new(
+            endpoint,
+            None,
+            Arc::new(DecoderRegistry::with_defaults()),
+            EventSink::new(sender, state.clone()).with_price_oracle(oracle.clone()),
+        );
+        let task = source.spawn();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await.unwrap().unwrap();
+        let pool = match event.decoded.map(|decoded| decoded.kind) {
+            Some(DecodedEventKind::PoolCreated(pool)) => pool.pool_address.parse::<Pubkey>().unwrap(),
+            other => panic!("expected a decoded pool creation, got {:?}", other),
+        };
+
+        // Priced from the Orca pool instead of the pump.fun bonding curve
+        assert_eq!(oracle.pool_for(&new_mint).await, pool);
+        assert_ne!(pool, bonding_curve_address(&new_mint));
+
+        state.write().await.is_running = false;
+        task.abort();
+        server.abort();
+    }
+}
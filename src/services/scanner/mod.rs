@@ -3,10 +3,16 @@
 //! This module provides real-time token discovery, metadata extraction,
 //! and intelligent filtering for the Solana blockchain.
 
+pub mod dedup;
 pub mod detector;
 pub mod event_listener;
 pub mod filters;
+pub mod geyser;
+pub mod pipeline;
+pub mod rules;
 pub mod token_parser;
+pub mod wallet_tracker;
+pub mod webhook;
 
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock, Mutex};
@@ -14,17 +20,27 @@ use tracing::{info, warn, error, debug, instrument};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
-use crate::config::models::ScannerConfig;
+use crate::config::models::{PerformanceConfig, RiskConfig, ScannerConfig};
 use crate::core::result::AppResult;
 use crate::core::error::AppError;
 use crate::core::types::{TokenAddress, Timestamp};
 use crate::infrastructure::database::DatabaseService;
-use crate::services::solana::SolanaService;
+use crate::services::solana::{BirdeyeClient, SolanaService};
 
+pub use dedup::{TokenDeduplicator, Sighting};
 pub use detector::{TokenDetector, DetectedToken};
 pub use event_listener::{EventListener, TokenEvent, EventType};
 pub use filters::{TokenFilter, FilterCriteria, FilterResult};
-pub use token_parser::{TokenParser, TokenMetadata, ParsedToken};
+pub use pipeline::{ScanPipeline, PipelineReport, StageHistogram};
+pub use rules::{FilterRule, FilterRuleSet, RuleSeverity};
+pub use token_parser::{
+    TokenParser, TokenMetadata, ParsedToken, MarketData, OnChainData, MintAuthorityStatus, FreezeAuthorityStatus,
+};
+pub use wallet_tracker::{WalletTracker, MirrorOrder, MirrorSizer};
+pub use webhook::WebhookReceiver;
+
+/// How often the filter rules file is checked for changes
+const FILTER_RULES_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 /// Scanner service coordinator
 #[derive(Debug)]
@@ -35,6 +51,9 @@ pub struct ScannerService {
     /// Token detector
     detector: Arc<TokenDetector>,
 
+    /// Deduplication across detection sources
+    dedup: Arc<TokenDeduplicator>,
+
     /// Event listener
     event_listener: Arc<EventListener>,
 
@@ -44,6 +63,9 @@ pub struct ScannerService {
     /// Token filter
     filter: Arc<TokenFilter>,
 
+    /// Parse/filter/broadcast pipeline for periodic scans
+    pipeline: Arc<ScanPipeline>,
+
     /// Database service
     database: Arc<DatabaseService>,
 
@@ -99,6 +121,9 @@ pub struct ScannerMetrics {
 
     /// Filter pass rate
     pub filter_pass_rate: f64,
+
+    /// Latency histograms by scan pipeline stage
+    pub stage_latencies: HashMap<String, StageHistogram>,
 }
 
 impl ScannerService {
@@ -106,8 +131,11 @@ impl ScannerService {
     #[instrument(skip_all)]
     pub async fn new(
         config: Arc<ScannerConfig>,
+        performance: &PerformanceConfig,
+        risk: &RiskConfig,
         database: Arc<DatabaseService>,
         solana: Arc<SolanaService>,
+        birdeye: Arc<BirdeyeClient>,
     ) -> AppResult<Self> {
         info!("🔍 Initializing token scanner service");
 
@@ -115,10 +143,18 @@ impl ScannerService {
         let (token_broadcaster, _) = broadcast::channel(1000);
 
         // Initialize components
-        let detector = Arc::new(TokenDetector::new(config.clone(), solana.clone()).await?);
+        let dedup = Arc::new(TokenDeduplicator::new(
+            Duration::from_secs(config.dedup_ttl_seconds),
+            config.shared_dedup.then(|| database.clone()),
+        ));
+        let detector = Arc::new(TokenDetector::new(config.clone(), solana.clone(), dedup.clone()).await?);
         let event_listener = Arc::new(EventListener::new(config.clone(), solana.clone()).await?);
-        let parser = Arc::new(TokenParser::new(solana.clone()).await?);
-        let filter = Arc::new(TokenFilter::new(config.clone(), database.clone()).await?);
+        let parser = Arc::new(TokenParser::new(solana.clone(), database.clone(), birdeye).await?);
+        let filter = Arc::new(TokenFilter::new(config.clone(), risk, database.clone()).await?);
+        let pipeline = Arc::new(ScanPipeline::new(
+            config.parse_concurrency,
+            Duration::from_millis(performance.target_detection_latency_ms),
+        ));
 
         // Initialize state
         let state = Arc::new(RwLock::new(ScannerState {
@@ -137,6 +173,7 @@ impl ScannerService {
             max_scan_duration_ms: 0,
             detection_latencies: Vec::new(),
             filter_pass_rate: 0.0,
+            stage_latencies: HashMap::new(),
         }));
 
         info!("✅ Token scanner service initialized");
@@ -144,9 +181,11 @@ impl ScannerService {
         Ok(Self {
             config,
             detector,
+            dedup,
             event_listener,
             parser,
             filter,
+            pipeline,
             database,
             solana,
             token_broadcaster,
@@ -177,6 +216,11 @@ impl ScannerService {
             self.start_periodic_scanner().await?;
         }
 
+        // Reload filter rules when their file changes
+        if let Some(path) = self.config.filter_rules_path.clone() {
+            self.start_rules_watcher(path);
+        }
+
         info!("✅ Token scanner service started");
         Ok(())
     }
@@ -202,6 +246,7 @@ impl ScannerService {
     /// Start event listener task
     async fn start_event_listener(&self) -> AppResult<()> {
         let event_listener = self.event_listener.clone();
+        let dedup = self.dedup.clone();
         let parser = self.parser.clone();
         let filter = self.filter.clone();
         let broadcaster = self.token_broadcaster.clone();
@@ -219,6 +264,7 @@ impl ScannerService {
 
                 match Self::process_token_event(
                     event,
+                    &dedup,
                     &parser,
                     &filter,
                     &broadcaster,
@@ -253,11 +299,46 @@ impl ScannerService {
         Ok(())
     }
 
+    /// Watch the filter rules file and reload the rules when it changes
+    ///
+    /// A file that fails to load is logged and the current rules stay in
+    /// place until the next change.
+    fn start_rules_watcher(&self, path: String) {
+        let filter = self.filter.clone();
+        let state = self.state.clone();
+
+        tokio::spawn(async move {
+            let modified = |path: &str| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            let mut last_modified = modified(&path);
+            let mut interval = tokio::time::interval(FILTER_RULES_POLL_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                if !state.read().await.is_running {
+                    break;
+                }
+
+                let current = modified(&path);
+                if current.is_none() || current == last_modified {
+                    continue;
+                }
+                last_modified = current;
+
+                match filter.reload_rules().await {
+                    Ok(count) => info!("🔄 Reloaded {} filter rules from {}", count, path),
+                    Err(e) => error!("Failed to reload filter rules from {}: {}", path, e),
+                }
+            }
+        });
+    }
+
     /// Start periodic scanner task
     async fn start_periodic_scanner(&self) -> AppResult<()> {
         let detector = self.detector.clone();
         let parser = self.parser.clone();
         let filter = self.filter.clone();
+        let pipeline = self.pipeline.clone();
         let broadcaster = self.token_broadcaster.clone();
         let state = self.state.clone();
         let metrics = self.metrics.clone();
@@ -286,8 +367,10 @@ impl ScannerService {
                     &detector,
                     &parser,
                     &filter,
+                    &pipeline,
                     &broadcaster,
                     &state,
+                    &metrics,
                     &config,
                 ).await {
                     Ok(tokens_found) => {
@@ -320,6 +403,7 @@ impl ScannerService {
     /// Process a token event
     async fn process_token_event(
         event: TokenEvent,
+        dedup: &Arc<TokenDeduplicator>,
         parser: &Arc<TokenParser>,
         filter: &Arc<TokenFilter>,
         broadcaster: &broadcast::Sender<DetectedToken>,
@@ -327,12 +411,25 @@ impl ScannerService {
     ) -> AppResult<bool> {
         debug!("Processing token event: {:?}", event.event_type);
 
+        // Another source may have reported the token already; a tracked
+        // wallet's buy is a signal of its own and goes through regardless
+        let is_wallet_buy = matches!(event.event_type, EventType::WalletBuy);
+        if !dedup.observe(&event.token_address, &event.source).await.is_first() && !is_wallet_buy {
+            debug!("Duplicate token {} from '{}'", event.token_address, event.source);
+            return Ok(false);
+        }
+
         // Parse token metadata
         let parsed_token = parser.parse_token(&event.token_address).await?;
 
         // Apply filters
         let filter_result = filter.apply_filters(&parsed_token).await?;
 
+        // Record the result, including rejections, for attribution analytics
+        if let Err(e) = filter.record_result(&parsed_token, &filter_result) {
+            warn!("Failed to record filter result for {}: {}", parsed_token.address, e);
+        }
+
         // Update state
         {
             let mut state = state.write().await;
@@ -350,6 +447,8 @@ impl ScannerService {
                 metadata: parsed_token.metadata,
                 filter_result,
                 detected_at: Timestamp::now(),
+                mirror_order: event.data.get("mirror_order")
+                    .and_then(|order| serde_json::from_value(order.clone()).ok()),
                 event_source: event.source,
                 detection_latency_ms: event.latency_ms,
             };
@@ -365,50 +464,71 @@ impl ScannerService {
     }
 
     /// Perform a periodic scan
+    ///
+    /// Detected tokens are parsed and filtered concurrently; tokens that miss
+    /// their stage deadline are dropped.
+    #[allow(clippy::too_many_arguments)]
     async fn perform_scan(
         detector: &Arc<TokenDetector>,
         parser: &Arc<TokenParser>,
         filter: &Arc<TokenFilter>,
+        pipeline: &ScanPipeline,
         broadcaster: &broadcast::Sender<DetectedToken>,
         state: &Arc<RwLock<ScannerState>>,
+        metrics: &Arc<Mutex<ScannerMetrics>>,
         config: &ScannerConfig,
     ) -> AppResult<u32> {
         // Detect new tokens
+        let detect_start = Instant::now();
         let new_tokens = detector.detect_new_tokens(config.max_tokens_per_scan).await?;
-        let mut passed_count = 0;
-
-        for token_address in new_tokens {
-            // Parse metadata
-            match parser.parse_token(&token_address).await {
-                Ok(parsed_token) => {
-                    // Apply filters
-                    match filter.apply_filters(&parsed_token).await {
-                        Ok(filter_result) => {
-                            if filter_result.passed {
-                                passed_count += 1;
-
-                                // Create detected token
-                                let detected_token = DetectedToken {
-                                    address: token_address,
-                                    metadata: parsed_token.metadata,
-                                    filter_result,
-                                    detected_at: Timestamp::now(),
-                                    event_source: "periodic_scan".to_string(),
-                                    detection_latency_ms: 0,
-                                };
-
-                                // Broadcast
-                                let _ = broadcaster.send(detected_token);
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Filter error for token {}: {}", token_address, e);
-                        }
+        let detect_duration = detect_start.elapsed();
+        let detected_count = new_tokens.len() as u64;
+
+        let mut report = pipeline.run(
+            tokio::time::Instant::from_std(detect_start),
+            new_tokens,
+            |token_address| {
+                let parser = parser.clone();
+                async move { parser.parse_token(&token_address).await }
+            },
+            |parsed_token| {
+                let filter = filter.clone();
+                async move {
+                    let filter_result = filter.apply_filters(&parsed_token).await?;
+
+                    if let Err(e) = filter.record_result(&parsed_token, &filter_result) {
+                        warn!("Failed to record filter result for {}: {}", parsed_token.address, e);
                     }
+
+                    Ok(filter_result.passed.then(|| (parsed_token, filter_result)))
                 }
-                Err(e) => {
-                    warn!("Failed to parse token {}: {}", token_address, e);
-                }
+            },
+            |(parsed_token, filter_result), latency| {
+                let detected_token = DetectedToken {
+                    address: parsed_token.address,
+                    metadata: parsed_token.metadata,
+                    filter_result,
+                    detected_at: Timestamp::now(),
+                    event_source: "periodic_scan".to_string(),
+                    detection_latency_ms: latency.as_millis() as u64,
+                    mirror_order: None,
+                };
+
+                let _ = broadcaster.send(detected_token);
+            },
+        ).await;
+
+        report.stages.entry(pipeline::STAGE_DETECT.to_string()).or_default().record(detect_duration);
+
+        if report.dropped() > 0 {
+            warn!("⏱️  {} tokens dropped for missing a stage deadline", report.dropped());
+        }
+
+        // Update metrics
+        {
+            let mut metrics = metrics.lock().await;
+            for (stage, histogram) in &report.stages {
+                metrics.stage_latencies.entry(stage.clone()).or_default().merge(histogram);
             }
         }
 
@@ -416,11 +536,11 @@ impl ScannerService {
         {
             let mut state = state.write().await;
             state.last_scan = Some(Timestamp::now());
-            state.total_detected += new_tokens.len() as u64;
-            state.total_passed += passed_count as u64;
+            state.total_detected += detected_count;
+            state.total_passed += report.passed as u64;
         }
 
-        Ok(passed_count)
+        Ok(report.passed)
     }
 
     /// Subscribe to new token events
@@ -460,6 +580,18 @@ impl ScannerService {
         Ok(())
     }
 
+    /// Reload declarative filter rules from the configured rules file
+    pub async fn reload_filter_rules(&self) -> AppResult<usize> {
+        let count = self.filter.reload_rules().await?;
+        info!("🔄 Reloaded {} filter rules", count);
+        Ok(count)
+    }
+
+    /// Replace the filter criteria without restarting the scanner
+    pub async fn update_filter_criteria(&self, criteria: FilterCriteria) -> AppResult<()> {
+        self.filter.update_criteria(criteria).await
+    }
+
     /// Manually trigger a scan
     #[instrument(skip(self))]
     pub async fn trigger_scan(&self) -> AppResult<u32> {
@@ -471,8 +603,10 @@ impl ScannerService {
             &self.detector,
             &self.parser,
             &self.filter,
+            &self.pipeline,
             &self.token_broadcaster,
             &self.state,
+            &self.metrics,
             &self.config,
         ).await?;
 
@@ -491,13 +625,17 @@ mod tests {
     #[tokio::test]
     async fn test_scanner_service_creation() {
         let config = ConfigLoader::new().without_env().create_default_config();
-        let solana_service = Arc::new(SolanaService::new(&config.solana, &config.helius).await.unwrap());
+        let solana_service = Arc::new(SolanaService::new(&config).await.unwrap());
         let database_service = Arc::new(DatabaseService::new(&config).await.unwrap());
+        let birdeye = Arc::new(BirdeyeClient::new(&config.birdeye, Some(database_service.redis.clone()), None).unwrap());
 
         let scanner = ScannerService::new(
             Arc::new(config.scanner),
+            &config.performance,
+            &config.risk,
             database_service,
             solana_service,
+            birdeye,
         ).await;
 
         assert!(scanner.is_ok());
@@ -0,0 +1,380 @@
+//! Concurrent token processing pipeline
+//!
+//! Tokens found by a scan flow through parse, filter and broadcast stages.
+//! Parsing and filtering run with bounded concurrency, so one slow market
+//! data lookup no longer holds up the rest of the batch. Each stage has its
+//! own per-token budget derived from
+//! `PerformanceConfig::target_detection_latency_ms`. A token's deadline for a
+//! stage starts when the token enters that stage, and a token that misses it
+//! is dropped because it would reach the trader too late to matter.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use futures::StreamExt;
+use tokio::time::{timeout, Instant};
+use tracing::{debug, warn};
+
+use crate::core::result::AppResult;
+use crate::core::types::TokenAddress;
+
+/// Detection stage label
+pub const STAGE_DETECT: &str = "detect";
+
+/// Parse stage label
+pub const STAGE_PARSE: &str = "parse";
+
+/// Filter stage label
+pub const STAGE_FILTER: &str = "filter";
+
+/// Broadcast stage label
+pub const STAGE_BROADCAST: &str = "broadcast";
+
+/// End-to-end label, from detection to broadcast
+pub const STAGE_TOTAL: &str = "total";
+
+/// Parse budget as a multiple of the latency target
+///
+/// Parsing walks mint signatures, scans holders, queries market data and
+/// fetches off-chain metadata with a 3s timeout of its own.
+const PARSE_BUDGET_FACTOR: f64 = 5.0;
+
+/// Filter budget as a multiple of the latency target
+const FILTER_BUDGET_FACTOR: f64 = 1.0;
+
+/// Budget between clearing filters and broadcast, as a multiple of the latency target
+const BROADCAST_BUDGET_FACTOR: f64 = 0.1;
+
+/// Upper bounds of the latency histogram buckets in milliseconds
+const LATENCY_BUCKETS_MS: [u64; 12] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Latency histogram for one pipeline stage
+#[derive(Debug, Clone)]
+pub struct StageHistogram {
+    /// Counts per bucket of `LATENCY_BUCKETS_MS`, plus one overflow bucket
+    pub buckets: Vec<u64>,
+
+    /// Samples recorded
+    pub count: u64,
+
+    /// Sum of all samples in milliseconds
+    pub sum_ms: u64,
+
+    /// Slowest sample in milliseconds
+    pub max_ms: u64,
+
+    /// Tokens dropped at this stage for missing their deadline
+    pub dropped: u64,
+}
+
+impl Default for StageHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: vec![0; LATENCY_BUCKETS_MS.len() + 1],
+            count: 0,
+            sum_ms: 0,
+            max_ms: 0,
+            dropped: 0,
+        }
+    }
+}
+
+impl StageHistogram {
+    /// Record a latency sample
+    pub fn record(&mut self, latency: Duration) {
+        let ms = latency.as_millis() as u64;
+        let bucket = LATENCY_BUCKETS_MS.iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum_ms += ms;
+        self.max_ms = self.max_ms.max(ms);
+    }
+
+    /// Average latency in milliseconds
+    pub fn avg_ms(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum_ms as f64 / self.count as f64
+        }
+    }
+
+    /// Upper bucket bound below which `percentile` percent of samples fall
+    pub fn percentile_ms(&self, percentile: f64) -> u64 {
+        let target = (self.count as f64 * percentile / 100.0).ceil() as u64;
+        let mut seen = 0;
+
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target && seen > 0 {
+                return LATENCY_BUCKETS_MS.get(index).copied().unwrap_or(self.max_ms);
+            }
+        }
+
+        0
+    }
+
+    /// Add another histogram's samples to this one
+    pub fn merge(&mut self, other: &StageHistogram) {
+        for (bucket, count) in self.buckets.iter_mut().zip(&other.buckets) {
+            *bucket += count;
+        }
+        self.count += other.count;
+        self.sum_ms += other.sum_ms;
+        self.max_ms = self.max_ms.max(other.max_ms);
+        self.dropped += other.dropped;
+    }
+}
+
+/// Outcome of running a batch through the pipeline
+#[derive(Debug, Clone, Default)]
+pub struct PipelineReport {
+    /// Tokens broadcast
+    pub passed: u32,
+
+    /// Tokens rejected by filters
+    pub rejected: u32,
+
+    /// Tokens whose parse or filter failed
+    pub failed: u32,
+
+    /// Stage latencies of this batch
+    pub stages: HashMap<String, StageHistogram>,
+}
+
+impl PipelineReport {
+    fn stage(&mut self, stage: &str) -> &mut StageHistogram {
+        self.stages.entry(stage.to_string()).or_default()
+    }
+
+    /// Tokens dropped for missing a deadline, across all stages
+    pub fn dropped(&self) -> u64 {
+        self.stages.values().map(|stage| stage.dropped).sum()
+    }
+}
+
+/// Bounded concurrent parse/filter/broadcast pipeline
+#[derive(Debug, Clone)]
+pub struct ScanPipeline {
+    /// Tokens parsed or filtered at once
+    concurrency: usize,
+
+    /// Per-token budget of each stage
+    budgets: StageBudgets,
+}
+
+/// Time a token may spend in each stage
+#[derive(Debug, Clone, Copy)]
+struct StageBudgets {
+    parse: Duration,
+    filter: Duration,
+    broadcast: Duration,
+}
+
+impl ScanPipeline {
+    /// Create a pipeline with a concurrency limit and a latency target
+    ///
+    /// Stage budgets are fixed multiples of `target`.
+    pub fn new(concurrency: usize, target: Duration) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            budgets: StageBudgets {
+                parse: target.mul_f64(PARSE_BUDGET_FACTOR),
+                filter: target.mul_f64(FILTER_BUDGET_FACTOR),
+                broadcast: target.mul_f64(BROADCAST_BUDGET_FACTOR),
+            },
+        }
+    }
+
+    /// Run detected tokens through the pipeline
+    ///
+    /// Each stage's deadline starts when the token enters the stage, so time
+    /// spent detecting the batch or waiting for a parse slot is not charged
+    /// to the token. `detected_at` is when detection of the batch started and
+    /// only feeds the reported latency. `filter` returns `None` for rejected
+    /// tokens. Tokens are handed to `broadcast` with their latency since
+    /// detection as soon as they clear filtering, in completion order.
+    pub async fn run<T, U, P, PFut, F, FFut, B>(
+        &self,
+        detected_at: Instant,
+        tokens: Vec<TokenAddress>,
+        parse: P,
+        filter: F,
+        mut broadcast: B,
+    ) -> PipelineReport
+    where
+        P: Fn(TokenAddress) -> PFut,
+        PFut: Future<Output = AppResult<T>>,
+        F: Fn(T) -> FFut,
+        FFut: Future<Output = AppResult<Option<U>>>,
+        B: FnMut(U, Duration),
+    {
+        let budgets = self.budgets;
+        let report = Mutex::new(PipelineReport::default());
+        let parse = &parse;
+        let filter = &filter;
+        let report_ref = &report;
+
+        futures::stream::iter(tokens)
+            .map(|token| async move {
+                let started = Instant::now();
+                let result = timeout(budgets.parse, parse(token.clone())).await;
+                (token, started.elapsed(), result)
+            })
+            .buffer_unordered(self.concurrency)
+            .filter_map(|(token, elapsed, result)| {
+                let mut report = report_ref.lock().unwrap();
+                let parsed = match result {
+                    Ok(Ok(parsed)) => {
+                        report.stage(STAGE_PARSE).record(elapsed);
+                        Some((token, parsed))
+                    }
+                    Ok(Err(e)) => {
+                        warn!("Failed to parse token {}: {}", token, e);
+                        report.stage(STAGE_PARSE).record(elapsed);
+                        report.failed += 1;
+                        None
+                    }
+                    Err(_) => {
+                        debug!("Dropping token {}: parse deadline missed", token);
+                        report.stage(STAGE_PARSE).dropped += 1;
+                        None
+                    }
+                };
+                futures::future::ready(parsed)
+            })
+            .map(|(token, parsed)| async move {
+                let started = Instant::now();
+                let result = timeout(budgets.filter, filter(parsed)).await;
+                (token, started.elapsed(), Instant::now(), result)
+            })
+            .buffer_unordered(self.concurrency)
+            .for_each(|(token, elapsed, filtered_at, result)| {
+                let mut report = report_ref.lock().unwrap();
+                match result {
+                    Ok(Ok(Some(passed))) => {
+                        report.stage(STAGE_FILTER).record(elapsed);
+
+                        let started = Instant::now();
+                        if started > filtered_at + budgets.broadcast {
+                            debug!("Dropping token {}: broadcast deadline missed", token);
+                            report.stage(STAGE_BROADCAST).dropped += 1;
+                        } else {
+                            broadcast(passed, detected_at.elapsed());
+                            report.stage(STAGE_BROADCAST).record(started.elapsed());
+                            report.stage(STAGE_TOTAL).record(detected_at.elapsed());
+                            report.passed += 1;
+                        }
+                    }
+                    Ok(Ok(None)) => {
+                        report.stage(STAGE_FILTER).record(elapsed);
+                        report.rejected += 1;
+                    }
+                    Ok(Err(e)) => {
+                        warn!("Filter error for token {}: {}", token, e);
+                        report.stage(STAGE_FILTER).record(elapsed);
+                        report.failed += 1;
+                    }
+                    Err(_) => {
+                        debug!("Dropping token {}: filter deadline missed", token);
+                        report.stage(STAGE_FILTER).dropped += 1;
+                    }
+                }
+                futures::future::ready(())
+            })
+            .await;
+
+        report.into_inner().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(n: u8) -> TokenAddress {
+        TokenAddress::new_unchecked(format!("Token{}", n))
+    }
+
+    #[test]
+    fn test_stage_histogram() {
+        let mut histogram = StageHistogram::default();
+        for ms in [3, 8, 40, 40, 700] {
+            histogram.record(Duration::from_millis(ms));
+        }
+
+        assert_eq!(histogram.count, 5);
+        assert_eq!(histogram.max_ms, 700);
+        assert_eq!(histogram.avg_ms(), 158.2);
+        assert_eq!(histogram.percentile_ms(50.0), 50);
+        assert_eq!(histogram.percentile_ms(99.0), 1000);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_tokens_dropped_without_blocking_others() {
+        let pipeline = ScanPipeline::new(4, Duration::from_millis(1000));
+        let mut broadcast = Vec::new();
+
+        let report = pipeline.run(
+            Instant::now(),
+            (0..4).map(token).collect(),
+            |token| async move {
+                // Token0 is stuck on a slow market data lookup
+                let delay = if token.as_str() == "Token0" { 5000 } else { 100 };
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                Ok(token)
+            },
+            |token| async move {
+                Ok((token.as_str() != "Token3").then_some(token))
+            },
+            |token, _| broadcast.push(token),
+        ).await;
+
+        assert_eq!(report.passed, 2);
+        assert_eq!(report.rejected, 1);
+        assert_eq!(report.stages[STAGE_PARSE].dropped, 1);
+        assert_eq!(report.stages[STAGE_PARSE].count, 3);
+        assert_eq!(report.dropped(), 1);
+        assert_eq!(broadcast.len(), 2);
+        // The healthy tokens finished without waiting for the slow one
+        assert!(report.stages[STAGE_TOTAL].max_ms < 600);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stage_deadlines_start_per_token_and_stage() {
+        let pipeline = ScanPipeline::new(1, Duration::from_millis(1000));
+        let detected_at = Instant::now();
+        let mut latencies = Vec::new();
+
+        // Detection alone took longer than the latency target
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        let report = pipeline.run(
+            detected_at,
+            (0..3).map(token).collect(),
+            |token| async move {
+                // Token2 overruns its own parse budget
+                let delay = if token.as_str() == "Token2" { 5500 } else { 4000 };
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                Ok(token)
+            },
+            |token| async move {
+                tokio::time::sleep(Duration::from_millis(900)).await;
+                Ok(Some(token))
+            },
+            |_, latency| latencies.push(latency),
+        ).await;
+
+        // Token1 waited for the only parse slot and still got a full budget
+        assert_eq!(report.passed, 2);
+        assert_eq!(report.stages[STAGE_PARSE].dropped, 1);
+        assert_eq!(report.stages[STAGE_FILTER].dropped, 0);
+        assert_eq!(latencies.len(), 2);
+        // Reported latency still counts from detection
+        assert!(latencies[0] >= Duration::from_millis(6400));
+    }
+}
@@ -0,0 +1,540 @@
+//! Declarative filter rules
+//!
+//! Rules are small boolean expressions over a parsed token, loaded from a
+//! YAML or TOML file, for example:
+//!
+//! ```yaml
+//! rules:
+//!   - name: fresh_liquid_pool
+//!     expression: "market_data.liquidity_sol >= 5 && on_chain.age_seconds < 120"
+//!     severity: blocking
+//!     weight: 2.0
+//! ```
+//!
+//! Expressions support field paths, number/string/boolean/null literals,
+//! comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`), `&&`, `||`, `!` and
+//! parentheses. Comparisons against a missing value are false, so a rule on
+//! absent market data fails rather than passes.
+
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::result::AppResult;
+use crate::core::error::AppError;
+use super::ParsedToken;
+
+/// Top-level fields a rule may reference
+const ROOT_FIELDS: &[&str] = &["address", "metadata", "market_data", "on_chain_data", "on_chain"];
+
+/// Severity of a failed rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    /// A failure rejects the token
+    Blocking,
+    /// A failure is reported as a warning
+    Warning,
+}
+
+/// Filter rule as written in a rules file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterRule {
+    /// Rule name, reported in filter results
+    pub name: String,
+
+    /// Boolean expression over the parsed token
+    pub expression: String,
+
+    /// Severity of a failure
+    #[serde(default = "default_severity")]
+    pub severity: RuleSeverity,
+
+    /// Weight toward the safety score
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+
+    /// Explanation reported when the rule fails
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+fn default_severity() -> RuleSeverity { RuleSeverity::Blocking }
+fn default_weight() -> f64 { 1.0 }
+
+/// Set of filter rules
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FilterRuleSet {
+    /// Rules in evaluation order
+    #[serde(default)]
+    pub rules: Vec<FilterRule>,
+}
+
+impl FilterRuleSet {
+    /// Parse a YAML rule set
+    pub fn from_yaml(content: &str) -> AppResult<Self> {
+        serde_yaml::from_str(content)
+            .map_err(|e| AppError::config(format!("Invalid YAML filter rules: {}", e)))
+    }
+
+    /// Parse a TOML rule set
+    pub fn from_toml(content: &str) -> AppResult<Self> {
+        toml::from_str(content)
+            .map_err(|e| AppError::config(format!("Invalid TOML filter rules: {}", e)))
+    }
+
+    /// Load a rule set, choosing the format from the file extension
+    pub async fn load(path: &Path) -> AppResult<Self> {
+        let content = tokio::fs::read_to_string(path).await
+            .map_err(|e| AppError::config(format!("Failed to read filter rules {}: {}", path.display(), e)))?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&content),
+            Some("yaml") | Some("yml") => Self::from_yaml(&content),
+            other => Err(AppError::config(format!("Unsupported filter rules format: {:?}", other))),
+        }
+    }
+}
+
+/// Rule with its parsed expression
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    rule: FilterRule,
+    expr: Expr,
+}
+
+impl CompiledRule {
+    /// Parse and check a rule
+    pub fn compile(rule: FilterRule) -> AppResult<Self> {
+        if !rule.weight.is_finite() || rule.weight < 0.0 {
+            return Err(AppError::config(format!("Rule '{}': weight must be non-negative", rule.name)));
+        }
+
+        let expr = Parser::new(&rule.expression)
+            .and_then(|parser| parser.parse())
+            .map_err(|e| AppError::config(format!("Rule '{}': {}", rule.name, e)))?;
+
+        let mut paths = Vec::new();
+        expr.paths(&mut paths);
+        if let Some(path) = paths.iter().find(|path| !ROOT_FIELDS.contains(&path[0].as_str())) {
+            return Err(AppError::config(format!(
+                "Rule '{}': unknown field '{}'", rule.name, path.join(".")
+            )));
+        }
+
+        Ok(Self { rule, expr })
+    }
+
+    /// The rule definition
+    pub fn rule(&self) -> &FilterRule {
+        &self.rule
+    }
+
+    /// Evaluate against a token context from [`rule_context`]
+    pub fn evaluate(&self, context: &Value) -> bool {
+        self.expr.eval(context).truthy()
+    }
+
+    /// Values of the fields the rule references
+    pub fn referenced_values(&self, context: &Value) -> Value {
+        let mut paths = Vec::new();
+        self.expr.paths(&mut paths);
+
+        Value::Object(
+            paths.into_iter()
+                .map(|path| {
+                    let value = resolve(context, &path).clone();
+                    (path.join("."), value)
+                })
+                .collect()
+        )
+    }
+}
+
+/// JSON view of a parsed token that rules are evaluated against
+///
+/// `on_chain` is an alias of `on_chain_data`.
+pub fn rule_context(token: &ParsedToken) -> Value {
+    let on_chain = serde_json::to_value(&token.on_chain_data).unwrap_or_default();
+
+    serde_json::json!({
+        "address": token.address.to_string(),
+        "metadata": serde_json::to_value(&token.metadata).unwrap_or_default(),
+        "market_data": serde_json::to_value(&token.market_data).unwrap_or_default(),
+        "on_chain_data": on_chain.clone(),
+        "on_chain": on_chain,
+    })
+}
+
+fn resolve<'a>(context: &'a Value, path: &[String]) -> &'a Value {
+    path.iter().fold(context, |value, key| value.get(key).unwrap_or(&Value::Null))
+}
+
+/// Expression tree
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Path(Vec<String>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, CompareOp, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Evaluated operand, borrowing from the context where possible
+enum Operand<'a> {
+    Value(&'a Value),
+    Owned(Value),
+}
+
+impl Operand<'_> {
+    fn value(&self) -> &Value {
+        match self {
+            Operand::Value(value) => value,
+            Operand::Owned(value) => value,
+        }
+    }
+
+    fn truthy(&self) -> bool {
+        match self.value() {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+            Value::String(s) => !s.is_empty(),
+            Value::Array(a) => !a.is_empty(),
+            Value::Object(_) => true,
+        }
+    }
+}
+
+impl Expr {
+    fn eval<'a>(&'a self, context: &'a Value) -> Operand<'a> {
+        match self {
+            Expr::Literal(value) => Operand::Value(value),
+            Expr::Path(path) => Operand::Value(resolve(context, path)),
+            Expr::Not(inner) => Operand::Owned(Value::Bool(!inner.eval(context).truthy())),
+            Expr::And(left, right) => {
+                Operand::Owned(Value::Bool(left.eval(context).truthy() && right.eval(context).truthy()))
+            }
+            Expr::Or(left, right) => {
+                Operand::Owned(Value::Bool(left.eval(context).truthy() || right.eval(context).truthy()))
+            }
+            Expr::Compare(left, op, right) => {
+                let left = left.eval(context);
+                let right = right.eval(context);
+                Operand::Owned(Value::Bool(compare(left.value(), *op, right.value())))
+            }
+        }
+    }
+
+    fn paths(&self, out: &mut Vec<Vec<String>>) {
+        match self {
+            Expr::Literal(_) => {}
+            Expr::Path(path) => {
+                if !out.contains(path) {
+                    out.push(path.clone());
+                }
+            }
+            Expr::Not(inner) => inner.paths(out),
+            Expr::And(left, right) | Expr::Or(left, right) | Expr::Compare(left, _, right) => {
+                left.paths(out);
+                right.paths(out);
+            }
+        }
+    }
+}
+
+fn compare(left: &Value, op: CompareOp, right: &Value) -> bool {
+    use std::cmp::Ordering;
+
+    let ordering = match (left, right) {
+        (Value::Number(l), Value::Number(r)) => match (l.as_f64(), r.as_f64()) {
+            (Some(l), Some(r)) => l.partial_cmp(&r),
+            _ => None,
+        },
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        (Value::Bool(l), Value::Bool(r)) => Some(l.cmp(r)),
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        _ => None,
+    };
+
+    match (op, ordering) {
+        (CompareOp::Eq, ordering) => ordering == Some(Ordering::Equal),
+        // A missing value is not "different", it is unknown
+        (CompareOp::Ne, _) if left.is_null() && !right.is_null() => false,
+        (CompareOp::Ne, ordering) => ordering != Some(Ordering::Equal),
+        // Ordering against null or mismatched types never holds
+        (_, None) => false,
+        (_, Some(_)) if left.is_null() => false,
+        (CompareOp::Lt, Some(ordering)) => ordering == Ordering::Less,
+        (CompareOp::Le, Some(ordering)) => ordering != Ordering::Greater,
+        (CompareOp::Gt, Some(ordering)) => ordering == Ordering::Greater,
+        (CompareOp::Ge, Some(ordering)) => ordering != Ordering::Less,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Dot,
+    LParen,
+    RParen,
+    Not,
+    And,
+    Or,
+    Op(CompareOp),
+}
+
+/// Recursive-descent expression parser
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(input: &str) -> Result<Self, String> {
+        Ok(Self { tokens: tokenize(input)?, pos: 0 })
+    }
+
+    fn parse(mut self) -> Result<Expr, String> {
+        let expr = self.parse_or()?;
+        match self.tokens.get(self.pos) {
+            None => Ok(expr),
+            Some((at, token)) => Err(format!("unexpected {:?} at column {}", token, at + 1)),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self.tokens.get(self.pos).map(|(_, token)| token.clone())
+            .ok_or_else(|| "unexpected end of expression".to_string())?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        let left = self.parse_operand()?;
+        if let Some(Token::Op(op)) = self.peek().cloned() {
+            self.pos += 1;
+            let right = self.parse_operand()?;
+            return Ok(Expr::Compare(Box::new(left), op, Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn parse_operand(&mut self) -> Result<Expr, String> {
+        match self.next()? {
+            Token::LParen => {
+                let expr = self.parse_or()?;
+                match self.next()? {
+                    Token::RParen => Ok(expr),
+                    token => Err(format!("expected ')', found {:?}", token)),
+                }
+            }
+            Token::Number(n) => Ok(Expr::Literal(serde_json::json!(n))),
+            Token::Str(s) => Ok(Expr::Literal(Value::String(s))),
+            Token::Ident(ident) => match ident.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ => {
+                    let mut path = vec![ident];
+                    while self.peek() == Some(&Token::Dot) {
+                        self.pos += 1;
+                        match self.next()? {
+                            Token::Ident(segment) => path.push(segment),
+                            token => return Err(format!("expected field name, found {:?}", token)),
+                        }
+                    }
+                    Ok(Expr::Path(path))
+                }
+            },
+            token => Err(format!("unexpected {:?}", token)),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => { i += 1; Token::LParen }
+            ')' => { i += 1; Token::RParen }
+            '.' => { i += 1; Token::Dot }
+            _ if two == "&&" => { i += 2; Token::And }
+            _ if two == "||" => { i += 2; Token::Or }
+            _ if two == "==" => { i += 2; Token::Op(CompareOp::Eq) }
+            _ if two == "!=" => { i += 2; Token::Op(CompareOp::Ne) }
+            _ if two == "<=" => { i += 2; Token::Op(CompareOp::Le) }
+            _ if two == ">=" => { i += 2; Token::Op(CompareOp::Ge) }
+            '<' => { i += 1; Token::Op(CompareOp::Lt) }
+            '>' => { i += 1; Token::Op(CompareOp::Gt) }
+            '!' => { i += 1; Token::Not }
+            '"' | '\'' => {
+                let quote = c;
+                i += 1;
+                let end = chars[i..].iter().position(|&ch| ch == quote)
+                    .ok_or_else(|| format!("unterminated string at column {}", start + 1))?;
+                let s: String = chars[i..i + end].iter().collect();
+                i += end + 1;
+                Token::Str(s)
+            }
+            c if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) => {
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '_') {
+                    i += 1;
+                }
+                let literal: String = chars[start..i].iter().filter(|&&ch| ch != '_').collect();
+                Token::Number(literal.parse()
+                    .map_err(|_| format!("invalid number '{}' at column {}", literal, start + 1))?)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                Token::Ident(chars[start..i].iter().collect())
+            }
+            c => return Err(format!("unexpected character '{}' at column {}", c, start + 1)),
+        };
+
+        tokens.push((start, token));
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(expression: &str) -> AppResult<CompiledRule> {
+        CompiledRule::compile(FilterRule {
+            name: "test".to_string(),
+            expression: expression.to_string(),
+            severity: RuleSeverity::Blocking,
+            weight: 1.0,
+            description: None,
+        })
+    }
+
+    #[test]
+    fn test_rule_evaluation() {
+        let context = serde_json::json!({
+            "market_data": { "liquidity_sol": 7.5, "primary_dex": "raydium", "market_cap_usd": null },
+            "on_chain": { "age_seconds": 90, "is_mutable": false },
+        });
+
+        let cases = [
+            ("market_data.liquidity_sol >= 5 && on_chain.age_seconds < 120", true),
+            ("market_data.liquidity_sol >= 10 || on_chain.age_seconds < 60", false),
+            ("!(market_data.liquidity_sol < 5) && !on_chain.is_mutable", true),
+            ("market_data.primary_dex == 'raydium'", true),
+            ("market_data.primary_dex != \"orca\"", true),
+            ("market_data.market_cap_usd == null", true),
+            // Missing values never satisfy an ordering
+            ("market_data.market_cap_usd < 1_000_000", false),
+            ("market_data.volume_24h_usd >= 0", false),
+            ("market_data.volume_24h_usd != 0", false),
+            ("market_data.market_cap_usd != 'x'", false),
+            ("market_data.market_cap_usd != null", false),
+            ("market_data.liquidity_sol != null", true),
+        ];
+
+        for (expression, expected) in cases {
+            assert_eq!(rule(expression).unwrap().evaluate(&context), expected, "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_invalid_rules_rejected() {
+        assert!(rule("market_data.liquidity_sol >=").is_err());
+        assert!(rule("(on_chain.age_seconds < 120").is_err());
+        assert!(rule("market.liquidity_sol > 5").is_err());
+        assert!(rule("on_chain.age_seconds < 120 120").is_err());
+    }
+
+    #[test]
+    fn test_rule_set_formats() {
+        let yaml = FilterRuleSet::from_yaml(r#"
+rules:
+  - name: liquidity
+    expression: "market_data.liquidity_sol >= 5"
+    weight: 2.0
+  - name: socials
+    expression: "metadata.social_links.twitter != null"
+    severity: warning
+"#).unwrap();
+
+        let toml = FilterRuleSet::from_toml(r#"
+[[rules]]
+name = "liquidity"
+expression = "market_data.liquidity_sol >= 5"
+weight = 2.0
+
+[[rules]]
+name = "socials"
+expression = "metadata.social_links.twitter != null"
+severity = "warning"
+"#).unwrap();
+
+        for set in [yaml, toml] {
+            assert_eq!(set.rules.len(), 2);
+            assert_eq!(set.rules[0].severity, RuleSeverity::Blocking);
+            assert_eq!(set.rules[0].weight, 2.0);
+            assert_eq!(set.rules[1].severity, RuleSeverity::Warning);
+            assert_eq!(set.rules[1].weight, 1.0);
+        }
+    }
+}
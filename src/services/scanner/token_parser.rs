@@ -3,7 +3,12 @@
 //! This module handles parsing token metadata from on-chain accounts,
 //! including SPL token data, Metaplex metadata, and liquidity information.
 
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use solana_sdk::pubkey::Pubkey;
 use tracing::{debug, warn, error, instrument};
 use serde::{Deserialize, Serialize};
 use rust_decimal::Decimal;
@@ -11,7 +16,38 @@ use rust_decimal::Decimal;
 use crate::core::result::AppResult;
 use crate::core::error::AppError;
 use crate::core::types::{TokenAddress, Timestamp};
-use crate::services::solana::SolanaService;
+use crate::infrastructure::database::DatabaseService;
+use crate::services::solana::{AccountInfo, BirdeyeClient, MintCreation, PoolPrice, SolanaService, TokenOverview};
+use crate::services::solana::metadata::{
+    decode_metadata, metadata_pda, MetadataCreator, MetaplexMetadata, METADATA_PROGRAM_ID,
+};
+
+/// Timeout for off-chain metadata requests
+const OFF_CHAIN_METADATA_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Maximum off-chain metadata document size in bytes
+const OFF_CHAIN_METADATA_MAX_BYTES: usize = 64 * 1024;
+
+/// Public IPFS gateway for `ipfs://` metadata URIs
+const IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// Arweave gateway for `ar://` metadata URIs
+const ARWEAVE_GATEWAY: &str = "https://arweave.net/";
+
+/// Redirects followed per off-chain metadata request
+const OFF_CHAIN_METADATA_MAX_REDIRECTS: usize = 3;
+
+/// Legacy SPL token program
+const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// Maximum signature pages walked back when looking for a mint's first transaction
+const MAX_CREATION_SIGNATURE_PAGES: usize = 10;
+
+/// Cache TTL for mint creation data
+const CREATION_CACHE_TTL: Duration = Duration::from_secs(600);
+
+/// Cache TTL for holder counts
+const HOLDER_COUNT_CACHE_TTL: Duration = Duration::from_secs(30);
 
 /// Parsed token information
 #[derive(Debug, Clone)]
@@ -64,6 +100,14 @@ pub struct TokenMetadata {
 
     /// Social links
     pub social_links: SocialLinks,
+
+    /// Metadata update authority
+    #[serde(default)]
+    pub update_authority: Option<String>,
+
+    /// Metadata creators
+    #[serde(default)]
+    pub creators: Vec<MetadataCreator>,
 }
 
 /// Social links extracted from metadata
@@ -109,15 +153,19 @@ pub struct OnChainData {
     /// Token age in seconds
     pub age_seconds: u64,
 
-    /// Number of holders
-    pub holder_count: u32,
+    /// Number of holders, unknown when the lookup failed
+    pub holder_count: Option<u32>,
 
     /// Creator/deployer address
     pub creator_address: Option<String>,
 
-    /// First transaction signature
+    /// First transaction signature, unknown when the history walk was truncated
     pub first_tx_signature: Option<String>,
 
+    /// Slot of the first transaction, unknown when the history walk was truncated
+    #[serde(default)]
+    pub creation_slot: Option<u64>,
+
     /// Token program ID
     pub program_id: String,
 
@@ -155,12 +203,46 @@ pub enum FreezeAuthorityStatus {
 pub struct TokenParser {
     /// Solana service
     solana: Arc<SolanaService>,
+
+    /// Database service for caching on-chain lookups
+    database: Arc<DatabaseService>,
+
+    /// Birdeye client for market data
+    birdeye: Arc<BirdeyeClient>,
+
+    /// HTTP client for off-chain metadata
+    http: reqwest::Client,
 }
 
 impl TokenParser {
     /// Create a new token parser
-    pub async fn new(solana: Arc<SolanaService>) -> AppResult<Self> {
-        Ok(Self { solana })
+    pub async fn new(
+        solana: Arc<SolanaService>,
+        database: Arc<DatabaseService>,
+        birdeye: Arc<BirdeyeClient>,
+    ) -> AppResult<Self> {
+        // Metadata URIs are chosen by token creators, so requests may only
+        // reach public hosts. Gateways such as arweave.net redirect, so a few
+        // redirects are followed; named hosts are checked by the resolver and
+        // IP literal hosts by the policy
+        let redirect = reqwest::redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= OFF_CHAIN_METADATA_MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if has_public_host(attempt.url()) {
+                attempt.follow()
+            } else {
+                attempt.error("redirect to a non-public host")
+            }
+        });
+        let http = reqwest::Client::builder()
+            .timeout(OFF_CHAIN_METADATA_TIMEOUT)
+            .https_only(true)
+            .redirect(redirect)
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()
+            .map_err(|e| AppError::network(format!("Failed to create metadata HTTP client: {}", e)))?;
+
+        Ok(Self { solana, database, birdeye, http })
     }
 
     /// Parse token information
@@ -175,13 +257,14 @@ impl TokenParser {
         let basic_info = self.parse_basic_info(&token_account)?;
 
         // Fetch and parse metadata
-        let metadata = self.fetch_token_metadata(token_address, &basic_info).await?;
+        let metaplex = self.get_metadata_account(token_address).await?;
+        let metadata = self.fetch_token_metadata(metaplex.as_ref(), &basic_info).await?;
 
         // Fetch market data
         let market_data = self.fetch_market_data(token_address).await?;
 
         // Fetch on-chain data
-        let on_chain_data = self.fetch_on_chain_data(token_address, &token_account).await?;
+        let on_chain_data = self.fetch_on_chain_data(token_address, &token_account, metaplex.as_ref()).await?;
 
         Ok(ParsedToken {
             address: token_address.clone(),
@@ -193,16 +276,16 @@ impl TokenParser {
     }
 
     /// Fetch token account data
-    async fn fetch_token_account(&self, token_address: &TokenAddress) -> AppResult<serde_json::Value> {
+    async fn fetch_token_account(&self, token_address: &TokenAddress) -> AppResult<AccountInfo> {
         self.solana.get_account_info(token_address.as_str()).await
             .map_err(|e| AppError::internal(format!("Failed to fetch token account: {}", e)))
     }
 
     /// Parse basic token information
-    fn parse_basic_info(&self, account_data: &serde_json::Value) -> AppResult<BasicTokenInfo> {
-        // Extract mint data from account
-        let data = account_data.get("data")
-            .ok_or_else(|| AppError::internal("Missing account data"))?;
+    fn parse_basic_info(&self, account_data: &AccountInfo) -> AppResult<BasicTokenInfo> {
+        if account_data.data.is_empty() {
+            return Err(AppError::internal("Missing account data"));
+        }
 
         // This would decode the actual mint account data
         // For now, using placeholder values
@@ -217,13 +300,10 @@ impl TokenParser {
     /// Fetch token metadata (Metaplex)
     async fn fetch_token_metadata(
         &self,
-        token_address: &TokenAddress,
+        metaplex: Option<&MetaplexMetadata>,
         basic_info: &BasicTokenInfo,
     ) -> AppResult<TokenMetadata> {
-        // Try to fetch Metaplex metadata
-        let metadata_account = self.get_metadata_account(token_address).await?;
-
-        if let Some(metadata) = metadata_account {
+        if let Some(metadata) = metaplex {
             self.parse_metaplex_metadata(metadata, basic_info).await
         } else {
             // No Metaplex metadata, return basic info
@@ -238,70 +318,134 @@ impl TokenParser {
                 metadata_program: None,
                 is_verified: false,
                 social_links: SocialLinks::default(),
+                update_authority: None,
+                creators: Vec::new(),
             })
         }
     }
 
-    /// Get metadata account address
-    async fn get_metadata_account(&self, token_address: &TokenAddress) -> AppResult<Option<serde_json::Value>> {
-        // Derive metadata PDA
-        let metadata_program_id = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+    /// Fetch and decode the Metaplex metadata account
+    async fn get_metadata_account(&self, token_address: &TokenAddress) -> AppResult<Option<MetaplexMetadata>> {
+        let mint = Pubkey::from_str(token_address.as_str())
+            .map_err(|e| AppError::validation(format!("Invalid mint address: {}", e)))?;
+        let metadata_address = metadata_pda(&mint);
+
+        let Some(account) = self.solana.find_account_info(&metadata_address.to_string()).await? else {
+            debug!("No metadata account for {}", token_address);
+            return Ok(None);
+        };
+
+        if account.owner != METADATA_PROGRAM_ID {
+            warn!("Metadata PDA for {} is owned by {}", token_address, account.owner);
+            return Ok(None);
+        }
 
-        // This would calculate the PDA and fetch the account
-        // For now, returning None
-        Ok(None)
+        match decode_metadata(&account.data) {
+            Ok(metadata) => Ok(Some(metadata)),
+            Err(e) => {
+                warn!("Failed to decode metadata for {}: {}", token_address, e);
+                Ok(None)
+            }
+        }
     }
 
     /// Parse Metaplex metadata
     async fn parse_metaplex_metadata(
         &self,
-        metadata_account: serde_json::Value,
+        metadata: &MetaplexMetadata,
         basic_info: &BasicTokenInfo,
     ) -> AppResult<TokenMetadata> {
-        // This would decode Metaplex metadata
-        // For now, using placeholder
+        // Off-chain metadata is best effort: a slow or broken URI only loses social links
+        let social_links = if metadata.uri.is_empty() {
+            SocialLinks::default()
+        } else {
+            match self.fetch_off_chain_metadata(&metadata.uri).await {
+                Ok(json) => extract_social_links(&json),
+                Err(e) => {
+                    debug!("Failed to fetch off-chain metadata from {}: {}", metadata.uri, e);
+                    SocialLinks::default()
+                }
+            }
+        };
+
+        let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_string());
 
         Ok(TokenMetadata {
-            symbol: Some("TOKEN".to_string()),
-            name: Some("Test Token".to_string()),
-            uri: None,
+            symbol: non_empty(&metadata.symbol),
+            name: non_empty(&metadata.name),
+            uri: non_empty(&metadata.uri),
             decimals: basic_info.decimals,
             total_supply: basic_info.supply,
             mint_authority: basic_info.mint_authority.clone(),
             freeze_authority: basic_info.freeze_authority.clone(),
-            metadata_program: Some("metaplex".to_string()),
-            is_verified: false,
-            social_links: SocialLinks::default(),
+            metadata_program: Some(METADATA_PROGRAM_ID.to_string()),
+            is_verified: metadata.collection.as_ref().is_some_and(|c| c.verified),
+            social_links,
+            update_authority: Some(metadata.update_authority.clone()),
+            creators: metadata.creators.clone(),
         })
     }
 
+    /// Fetch the off-chain metadata JSON, enforcing the size cap
+    async fn fetch_off_chain_metadata(&self, uri: &str) -> AppResult<serde_json::Value> {
+        let url = resolve_metadata_uri(uri)
+            .ok_or_else(|| AppError::validation(format!("Unsupported metadata URI: {}", uri)))?;
+
+        let mut response = self.http.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::network(format!("Metadata URI returned status {}", response.status())));
+        }
+
+        if response.content_length().is_some_and(|len| len as usize > OFF_CHAIN_METADATA_MAX_BYTES) {
+            return Err(AppError::validation("Off-chain metadata exceeds size cap"));
+        }
+
+        // Content-Length can be absent or wrong, so the cap is enforced while reading
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > OFF_CHAIN_METADATA_MAX_BYTES {
+                return Err(AppError::validation("Off-chain metadata exceeds size cap"));
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
     /// Fetch market data from external sources
     async fn fetch_market_data(&self, token_address: &TokenAddress) -> AppResult<MarketData> {
         // Fetch from multiple sources in parallel
-        let (birdeye_data, helius_data) = tokio::join!(
-            self.fetch_birdeye_data(token_address),
-            self.fetch_helius_market_data(token_address)
+        let (pool_price, birdeye_data) = tokio::join!(
+            self.fetch_pool_price(token_address),
+            self.fetch_birdeye_data(token_address)
         );
 
         // Merge data from multiple sources
-        self.merge_market_data(birdeye_data.ok(), helius_data.ok())
+        self.merge_market_data(pool_price.ok(), birdeye_data.ok())
     }
 
-    /// Fetch data from Birdeye
-    async fn fetch_birdeye_data(&self, token_address: &TokenAddress) -> AppResult<BirdeyeTokenData> {
-        self.solana.fetch_birdeye_token_data(token_address.as_str()).await
+    /// Read the price from the token's pool on-chain
+    async fn fetch_pool_price(&self, token_address: &TokenAddress) -> AppResult<PoolPrice> {
+        let mint = Pubkey::from_str(token_address.as_str())
+            .map_err(|e| AppError::validation(format!("Invalid token address: {}", e)))?;
+        self.solana.price_oracle().price(&mint).await
     }
 
-    /// Fetch market data from Helius
-    async fn fetch_helius_market_data(&self, token_address: &TokenAddress) -> AppResult<serde_json::Value> {
-        self.solana.fetch_helius_token_data(token_address.as_str()).await
+    /// Fetch data from Birdeye
+    async fn fetch_birdeye_data(&self, token_address: &TokenAddress) -> AppResult<BirdeyeTokenData> {
+        let overview = self.birdeye.token_overview(token_address.as_str()).await?;
+        Ok(overview.into())
     }
 
     /// Merge market data from multiple sources
+    ///
+    /// Pool reserves are authoritative for price and liquidity; Birdeye fills
+    /// what the chain cannot provide, like volume and market cap.
     fn merge_market_data(
         &self,
+        pool_price: Option<PoolPrice>,
         birdeye_data: Option<BirdeyeTokenData>,
-        helius_data: Option<serde_json::Value>,
     ) -> AppResult<MarketData> {
         let mut market_data = MarketData {
             market_cap_usd: None,
@@ -322,9 +466,14 @@ impl TokenParser {
             market_data.market_cap_usd = birdeye.market_cap_usd.and_then(|m| Decimal::try_from(m).ok());
         }
 
-        // Merge Helius data
-        if let Some(_helius) = helius_data {
-            // Parse Helius-specific fields
+        // Merge on-chain pool data
+        if let Some(pool) = pool_price {
+            market_data.price_sol = Some(pool.price_sol);
+            market_data.liquidity_sol = Some(pool.liquidity_sol);
+            market_data.price_usd = pool.price_usd.or(market_data.price_usd);
+            market_data.liquidity_usd = pool.liquidity_usd.or(market_data.liquidity_usd);
+            market_data.pool_count = market_data.pool_count.max(1);
+            market_data.primary_dex = Some(pool.dex.as_str().to_string());
         }
 
         Ok(market_data)
@@ -334,43 +483,231 @@ impl TokenParser {
     async fn fetch_on_chain_data(
         &self,
         token_address: &TokenAddress,
-        token_account: &serde_json::Value,
+        token_account: &AccountInfo,
+        metaplex: Option<&MetaplexMetadata>,
     ) -> AppResult<OnChainData> {
+        let program_id = if token_account.owner.is_empty() {
+            SPL_TOKEN_PROGRAM_ID.to_string()
+        } else {
+            token_account.owner.clone()
+        };
+
         // Get token creation time
-        let creation_data = self.get_token_creation_data(token_address).await?;
+        let creation = self.get_token_creation_data(token_address).await?;
+        let age_seconds = creation_age_seconds(creation.as_ref(), chrono::Utc::now())?;
 
         // Get holder count
-        let holder_count = self.get_holder_count(token_address).await?;
+        // A failed lookup leaves the count unknown rather than failing the parse
+        let holder_count = match self.get_holder_count(token_address, &program_id).await {
+            Ok(count) => Some(count),
+            Err(e) => {
+                warn!("Holder count unavailable for {}: {}", token_address, e);
+                None
+            }
+        };
+
+        let (creator, first_tx_signature, creation_slot) = creation_identity(creation);
 
         Ok(OnChainData {
-            age_seconds: creation_data.age_seconds,
+            age_seconds,
             holder_count,
-            creator_address: creation_data.creator,
-            first_tx_signature: creation_data.first_tx,
-            program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+            creator_address: metaplex
+                .and_then(|m| m.creators.iter().find(|c| c.verified))
+                .map(|c| c.address.clone())
+                .or(creator),
+            first_tx_signature,
+            creation_slot,
+            program_id,
             associated_token_program: Some("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL".to_string()),
-            is_mutable: true, // Would check from metadata
+            // Without metadata, assume it can still be changed
+            is_mutable: metaplex.map_or(true, |m| m.is_mutable),
             mint_authority_status: MintAuthorityStatus::Unknown,
             freeze_authority_status: FreezeAuthorityStatus::Unknown,
         })
     }
 
-    /// Get token creation data
-    async fn get_token_creation_data(&self, token_address: &TokenAddress) -> AppResult<CreationData> {
-        // Would fetch transaction history to find creation
-        // For now, using placeholder
-        Ok(CreationData {
-            age_seconds: 300, // 5 minutes
-            creator: Some("Creator11111111111111111111111111111111111".to_string()),
-            first_tx: Some("FirstTx111111111111111111111111111111111111".to_string()),
+    /// Get token creation data, cached per mint
+    async fn get_token_creation_data(&self, token_address: &TokenAddress) -> AppResult<Option<MintCreation>> {
+        let cache_key = format!("token:creation:{}", token_address);
+
+        match self.database.redis.get_cached_json::<MintCreation>(&cache_key).await {
+            Ok(Some(creation)) => return Ok(Some(creation)),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to read cached creation data for {}: {}", token_address, e),
+        }
+
+        let creation = self.solana
+            .get_mint_creation(token_address.as_str(), MAX_CREATION_SIGNATURE_PAGES)
+            .await?;
+
+        // A mint without signatures is not indexed yet; look again next time
+        if let Some(creation) = &creation {
+            if let Err(e) = self.database.redis.cache_json(&cache_key, creation, Some(CREATION_CACHE_TTL)).await {
+                warn!("Failed to cache creation data for {}: {}", token_address, e);
+            }
+        }
+
+        Ok(creation)
+    }
+
+    /// Get holder count, cached per mint
+    async fn get_holder_count(&self, token_address: &TokenAddress, program_id: &str) -> AppResult<u32> {
+        let cache_key = format!("token:holders:{}", token_address);
+
+        match self.database.redis.get_cached_json::<u32>(&cache_key).await {
+            Ok(Some(count)) => return Ok(count),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to read cached holder count for {}: {}", token_address, e),
+        }
+
+        let count = self.solana.get_token_holder_count(token_address.as_str(), program_id).await?;
+
+        if let Err(e) = self.database.redis.cache_json(&cache_key, &count, Some(HOLDER_COUNT_CACHE_TTL)).await {
+            warn!("Failed to cache holder count for {}: {}", token_address, e);
+        }
+
+        Ok(count)
+    }
+}
+
+/// Token age in seconds from its creation data
+///
+/// A mint with no signatures yet has just been created. For incomplete
+/// signature walks the age is a lower bound, which is enough for age filters.
+fn creation_age_seconds(
+    creation: Option<&MintCreation>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> AppResult<u64> {
+    let Some(creation) = creation else {
+        return Ok(0);
+    };
+
+    let created_at = creation.block_time.ok_or_else(|| AppError::network(format!(
+        "Block time unavailable for creation transaction {}", creation.signature
+    )))?;
+
+    Ok((now - created_at).num_seconds().max(0) as u64)
+}
+
+/// Creator, first transaction and creation slot from creation data
+///
+/// An incomplete signature walk only found the oldest transaction it
+/// reached, which is not the creation, so none of these are reported.
+fn creation_identity(creation: Option<MintCreation>) -> (Option<String>, Option<String>, Option<u64>) {
+    match creation {
+        Some(creation) if creation.is_complete => (creation.creator, Some(creation.signature), Some(creation.slot)),
+        _ => (None, None, None),
+    }
+}
+
+/// Resolve a metadata URI to an HTTPS URL
+///
+/// IP literal hosts must be public; named hosts are checked by
+/// [`PublicResolver`] when the request connects.
+fn resolve_metadata_uri(uri: &str) -> Option<String> {
+    let uri = uri.trim();
+
+    let resolved = if let Some(path) = uri.strip_prefix("ipfs://") {
+        format!("{}{}", IPFS_GATEWAY, path.trim_start_matches("ipfs/"))
+    } else if let Some(path) = uri.strip_prefix("ar://") {
+        format!("{}{}", ARWEAVE_GATEWAY, path)
+    } else {
+        uri.to_string()
+    };
+
+    let url = url::Url::parse(&resolved).ok()?;
+    (url.scheme() == "https" && has_public_host(&url)).then_some(resolved)
+}
+
+/// Whether a URL's host is a domain name or a public IP literal
+///
+/// Domain names are checked by [`PublicResolver`] when the request connects.
+fn has_public_host(url: &url::Url) -> bool {
+    match url.host() {
+        Some(url::Host::Domain(_)) => true,
+        Some(url::Host::Ipv4(ip)) => is_public_ip(IpAddr::V4(ip)),
+        Some(url::Host::Ipv6(ip)) => is_public_ip(IpAddr::V6(ip)),
+        None => false,
+    }
+}
+
+/// Whether an address is routable on the public internet
+///
+/// Rejects loopback, private, link-local, shared, unspecified, broadcast,
+/// documentation and multicast ranges, including IPv4-mapped IPv6 forms.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(mapped));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// DNS resolver that only returns public addresses
+///
+/// Filtering at resolution time means the checked address is the one
+/// connected to, so a host cannot pass the check and then rebind.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+
+            if addrs.is_empty() {
+                return Err(format!("{} does not resolve to a public address", host).into());
+            }
+
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
         })
     }
+}
 
-    /// Get holder count
-    async fn get_holder_count(&self, token_address: &TokenAddress) -> AppResult<u32> {
-        // Would query token accounts
-        // For now, returning placeholder
-        Ok(42)
+/// Extract social links from off-chain metadata JSON
+///
+/// Links are read from the top level, `extensions` and `links` objects, which
+/// covers the layouts used by pump.fun, Metaplex fungible metadata and most
+/// launchpads. `external_url` is used as the website fallback.
+fn extract_social_links(json: &serde_json::Value) -> SocialLinks {
+    let find = |key: &str| {
+        [&json[key], &json["extensions"][key], &json["links"][key]]
+            .into_iter()
+            .filter_map(|value| value.as_str())
+            .map(str::trim)
+            .find(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    SocialLinks {
+        website: find("website").or_else(|| find("external_url")),
+        twitter: find("twitter"),
+        telegram: find("telegram"),
+        discord: find("discord"),
     }
 }
 
@@ -396,11 +733,20 @@ pub struct BirdeyeTokenData {
     pub holder_count: Option<u32>,
 }
 
-/// Token creation data
-struct CreationData {
-    age_seconds: u64,
-    creator: Option<String>,
-    first_tx: Option<String>,
+impl From<TokenOverview> for BirdeyeTokenData {
+    fn from(overview: TokenOverview) -> Self {
+        Self {
+            address: overview.address,
+            symbol: overview.symbol,
+            name: overview.name,
+            decimals: overview.decimals,
+            price_usd: overview.price,
+            volume_24h_usd: overview.volume_24h_usd,
+            liquidity_usd: overview.liquidity,
+            market_cap_usd: overview.market_cap,
+            holder_count: overview.holder_count,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -420,9 +766,105 @@ mod tests {
             metadata_program: None,
             is_verified: false,
             social_links: SocialLinks::default(),
+            update_authority: None,
+            creators: Vec::new(),
         };
 
         let json = serde_json::to_string(&metadata).unwrap();
         assert!(json.contains("TEST"));
     }
+
+    #[test]
+    fn test_extract_social_links() {
+        let pump = serde_json::json!({
+            "name": "Test",
+            "twitter": "https://x.com/test",
+            "telegram": "https://t.me/test",
+            "website": "",
+        });
+        let links = extract_social_links(&pump);
+        assert_eq!(links.twitter.as_deref(), Some("https://x.com/test"));
+        assert_eq!(links.telegram.as_deref(), Some("https://t.me/test"));
+        assert!(links.website.is_none());
+
+        let metaplex = serde_json::json!({
+            "external_url": "https://test.io",
+            "extensions": { "discord": "https://discord.gg/test" },
+        });
+        let links = extract_social_links(&metaplex);
+        assert_eq!(links.website.as_deref(), Some("https://test.io"));
+        assert_eq!(links.discord.as_deref(), Some("https://discord.gg/test"));
+    }
+
+    #[test]
+    fn test_resolve_metadata_uri() {
+        assert_eq!(resolve_metadata_uri("ipfs://QmHash").as_deref(), Some("https://ipfs.io/ipfs/QmHash"));
+        assert_eq!(resolve_metadata_uri("ar://tx").as_deref(), Some("https://arweave.net/tx"));
+        assert_eq!(resolve_metadata_uri(" https://a.io/m.json ").as_deref(), Some("https://a.io/m.json"));
+        assert!(resolve_metadata_uri("file:///etc/passwd").is_none());
+        assert!(resolve_metadata_uri("http://a.io/m.json").is_none());
+        assert!(resolve_metadata_uri("https://127.0.0.1/m.json").is_none());
+        assert!(resolve_metadata_uri("https://169.254.169.254/latest/meta-data").is_none());
+        assert!(resolve_metadata_uri("https://[::ffff:10.0.0.1]/m.json").is_none());
+    }
+
+    #[test]
+    fn test_redirect_targets() {
+        let host = |url: &str| has_public_host(&url::Url::parse(url).unwrap());
+
+        // arweave.net redirects to per-transaction sandbox subdomains
+        assert!(host("https://abc123.arweave.net/tx"));
+        assert!(host("https://1.1.1.1/m.json"));
+        assert!(!host("https://127.0.0.1/m.json"));
+        assert!(!host("https://[::1]/m.json"));
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1", "0.0.0.0", "::1", "fe80::1", "fd00::1", "::ffff:192.168.0.1"] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{} should be rejected", ip);
+        }
+        for ip in ["1.1.1.1", "104.16.0.1", "2606:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{} should be allowed", ip);
+        }
+    }
+
+    #[test]
+    fn test_creation_age_seconds() {
+        let now = chrono::Utc::now();
+        let mut creation = MintCreation {
+            signature: "sig".to_string(),
+            slot: 1,
+            block_time: Some(now - chrono::Duration::seconds(90)),
+            creator: None,
+            is_complete: true,
+        };
+
+        assert_eq!(creation_age_seconds(Some(&creation), now).unwrap(), 90);
+        assert_eq!(creation_age_seconds(None, now).unwrap(), 0);
+
+        creation.block_time = None;
+        assert!(creation_age_seconds(Some(&creation), now).is_err());
+    }
+
+    #[test]
+    fn test_creation_identity_truncated_walk() {
+        let mut creation = MintCreation {
+            signature: "sig".to_string(),
+            slot: 42,
+            block_time: None,
+            creator: Some("creator".to_string()),
+            is_complete: true,
+        };
+
+        assert_eq!(
+            creation_identity(Some(creation.clone())),
+            (Some("creator".to_string()), Some("sig".to_string()), Some(42))
+        );
+
+        // The walk stopped at max_pages: the oldest transaction found is not the creation
+        creation.is_complete = false;
+        assert_eq!(creation_identity(Some(creation)), (None, None, None));
+        assert_eq!(creation_identity(None), (None, None, None));
+    }
 }
\ No newline at end of file
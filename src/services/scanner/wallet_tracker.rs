@@ -0,0 +1,547 @@
+//! Copy-trade wallet tracking
+//!
+//! Follows configured smart-money wallets, decodes their swaps from token
+//! balance changes and emits a `TokenEvent` whenever a tracked wallet buys a
+//! token it did not hold before. In mirror mode each buy also carries a
+//! sized `MirrorOrder` for the trader to act on.
+//!
+//! Wallet activity is pushed over `logsSubscribe` with a `mentions` filter;
+//! each notification catches the wallet up from its signature cursor. When
+//! the subscriptions cannot be opened the wallets are polled instead.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::config::models::{CopyTradeConfig, TrackedWalletConfig};
+use crate::core::result::AppResult;
+use crate::core::types::{TokenAddress, Timestamp};
+use crate::services::solana::{HeliusWebsocket, SolanaService};
+use crate::services::solana::decoders::{USDC_MINT, WSOL_MINT};
+use crate::services::solana::types::{SwapEvent, TransactionInfo, TransactionStatus};
+use super::event_listener::{EventSink, EventType, TokenEvent};
+
+/// Detection source label for tracked wallet buys
+pub const COPY_TRADE_SOURCE: &str = "copy_trade";
+
+/// Signatures fetched per wallet poll
+const WALLET_SIGNATURE_LIMIT: usize = 25;
+
+/// Lamports per SOL
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Programs that never route a swap
+const NON_SWAP_PROGRAMS: &[&str] = &[
+    "ComputeBudget111111111111111111111111111111",
+    "11111111111111111111111111111111",
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH8ETMU9ddK48Y3B",
+];
+
+/// Sized buy mirroring a tracked wallet's purchase
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MirrorOrder {
+    /// Tracked wallet that bought
+    pub wallet: String,
+
+    /// Token to buy
+    pub token_mint: String,
+
+    /// Buy size in SOL
+    pub amount_sol: Decimal,
+
+    /// Do not buy before this time
+    pub execute_after: DateTime<Utc>,
+
+    /// Do not buy after this time
+    pub expires_at: DateTime<Utc>,
+
+    /// Signature of the tracked wallet's swap
+    pub source_signature: String,
+}
+
+/// Sizes mirror orders and enforces per-wallet caps
+#[derive(Debug)]
+pub struct MirrorSizer {
+    /// Tracked wallets by address
+    wallets: HashMap<String, TrackedWalletConfig>,
+
+    /// Minimum delay after the tracked swap
+    delay: chrono::Duration,
+
+    /// Window after the tracked swap during which a buy is mirrored
+    window: chrono::Duration,
+
+    /// SOL mirrored so far by wallet
+    mirrored_sol: HashMap<String, Decimal>,
+}
+
+impl MirrorSizer {
+    /// Create a sizer from the copy-trade configuration
+    pub fn new(config: &CopyTradeConfig) -> Self {
+        Self {
+            wallets: config.wallets.iter()
+                .map(|wallet| (wallet.address.clone(), wallet.clone()))
+                .collect(),
+            delay: chrono::Duration::milliseconds(config.mirror_delay_ms as i64),
+            window: chrono::Duration::milliseconds(config.mirror_window_ms as i64),
+            mirrored_sol: HashMap::new(),
+        }
+    }
+
+    /// Size a mirror order for a tracked wallet's buy
+    ///
+    /// Only SOL-denominated buys are mirrored. The order is proportional to
+    /// the wallet's spend, capped per buy and by the wallet's remaining total
+    /// allowance; buys older than the mirror window are skipped.
+    pub fn size(&mut self, swap: &SwapEvent, now: DateTime<Utc>) -> Option<MirrorOrder> {
+        let wallet = self.wallets.get(&swap.trader)?;
+
+        if swap.token_in != WSOL_MINT || now > swap.swapped_at + self.window {
+            return None;
+        }
+
+        let spent_sol = Decimal::from(swap.amount_in) / Decimal::from(LAMPORTS_PER_SOL);
+        let mut amount = spent_sol * wallet.mirror_ratio;
+
+        if let Some(max_buy) = wallet.max_buy_sol {
+            amount = amount.min(max_buy);
+        }
+
+        let mirrored = self.mirrored_sol.entry(swap.trader.clone()).or_insert(Decimal::ZERO);
+        if let Some(max_total) = wallet.max_total_sol {
+            amount = amount.min(max_total - *mirrored);
+        }
+
+        if amount <= Decimal::ZERO {
+            debug!("Mirror allowance for {} exhausted", swap.trader);
+            return None;
+        }
+
+        *mirrored += amount;
+
+        Some(MirrorOrder {
+            wallet: swap.trader.clone(),
+            token_mint: swap.token_out.clone(),
+            amount_sol: amount,
+            execute_after: (swap.swapped_at + self.delay).max(now),
+            expires_at: swap.swapped_at + self.window,
+            source_signature: swap.transaction.clone(),
+        })
+    }
+}
+
+/// Decode a wallet's swap from a transaction's balance changes
+///
+/// Working from balance changes instead of instructions covers every DEX and
+/// aggregator route. Native SOL and wrapped SOL are netted, with the fee and
+/// the rent of token accounts the swap opened for the wallet added back.
+/// `pool_address` is the first program the transaction invokes that can
+/// route a swap.
+pub fn decode_swap(transaction: &TransactionInfo, wallet: &str) -> Option<SwapEvent> {
+    if transaction.status != TransactionStatus::Success {
+        return None;
+    }
+
+    // Net token changes of the wallet by mint
+    let mut deltas: HashMap<&str, i128> = HashMap::new();
+    for change in &transaction.token_balance_changes {
+        if change.owner.as_deref() == Some(wallet) {
+            *deltas.entry(change.mint.as_str()).or_default() +=
+                change.post_amount as i128 - change.pre_amount as i128;
+        }
+    }
+
+    // Fold native SOL into wrapped SOL
+    if let Some(position) = transaction.balance_changes.iter().position(|change| change.account == wallet) {
+        let change = &transaction.balance_changes[position];
+        let mut native = change.post_lamports as i128 - change.pre_lamports as i128;
+        if position == 0 {
+            native += transaction.fee as i128;
+        }
+        native += opened_account_rent(transaction, wallet) as i128;
+        *deltas.entry(WSOL_MINT).or_default() += native;
+    }
+
+    let received = deltas.iter()
+        .filter(|(_, delta)| **delta > 0)
+        .max_by_key(|(mint, delta)| (**mint != WSOL_MINT && **mint != USDC_MINT, **delta))?;
+    let spent = deltas.iter()
+        .filter(|(mint, delta)| **delta < 0 && *mint != received.0)
+        .max_by_key(|(mint, delta)| (**mint == WSOL_MINT || **mint == USDC_MINT, -**delta))?;
+
+    let pool_address = transaction.instructions.iter()
+        .map(|ix| ix.program_id.as_str())
+        .find(|program| !NON_SWAP_PROGRAMS.contains(program))
+        .unwrap_or_default();
+
+    Some(SwapEvent {
+        pool_address: pool_address.to_string(),
+        trader: wallet.to_string(),
+        token_in: spent.0.to_string(),
+        token_out: received.0.to_string(),
+        amount_in: (-*spent.1).try_into().unwrap_or(u64::MAX),
+        amount_out: (*received.1).try_into().unwrap_or(u64::MAX),
+        transaction: transaction.signature.clone(),
+        swapped_at: transaction.block_time.unwrap_or_else(Utc::now),
+    })
+}
+
+/// Rent deposited into token accounts of the wallet created by the transaction
+///
+/// Wrapped SOL accounts are skipped: they are closed within the swap and
+/// their balance already shows up in the token deltas.
+fn opened_account_rent(transaction: &TransactionInfo, wallet: &str) -> u64 {
+    transaction.token_balance_changes.iter()
+        .filter(|change| change.owner.as_deref() == Some(wallet) && change.mint != WSOL_MINT)
+        .filter_map(|change| transaction.balance_changes.iter().find(|balance| balance.account == change.account))
+        .filter(|balance| balance.pre_lamports == 0)
+        .map(|balance| balance.post_lamports)
+        .sum()
+}
+
+/// Whether the wallet held none of the bought token before the swap
+fn is_first_buy(transaction: &TransactionInfo, swap: &SwapEvent) -> bool {
+    swap.token_out != WSOL_MINT
+        && swap.token_out != USDC_MINT
+        && transaction.token_balance_changes.iter()
+            .filter(|change| change.owner.as_deref() == Some(swap.trader.as_str()) && change.mint == swap.token_out)
+            .all(|change| change.pre_amount == 0)
+}
+
+/// Follows tracked wallets and publishes their new-token buys
+#[derive(Debug)]
+pub struct WalletTracker {
+    /// Solana service
+    solana: Arc<SolanaService>,
+
+    /// Copy-trade configuration
+    config: CopyTradeConfig,
+
+    /// Listener event sink
+    sink: EventSink,
+
+    /// Newest processed signature by wallet
+    cursors: Mutex<HashMap<String, String>>,
+
+    /// Mirror order sizing
+    sizer: Mutex<MirrorSizer>,
+}
+
+impl WalletTracker {
+    /// Create a tracker for the configured wallets
+    pub(super) fn new(solana: Arc<SolanaService>, config: CopyTradeConfig, sink: EventSink) -> Self {
+        let sizer = MirrorSizer::new(&config);
+
+        Self {
+            solana,
+            config,
+            sink,
+            cursors: Mutex::new(HashMap::new()),
+            sizer: Mutex::new(sizer),
+        }
+    }
+
+    /// Addresses of the tracked wallets
+    pub fn addresses(&self) -> Vec<String> {
+        self.config.wallets.iter().map(|wallet| wallet.address.clone()).collect()
+    }
+
+    /// Spawn the tracking loop
+    ///
+    /// While the wallet subscriptions are up, every notification triggers a
+    /// catch-up of the mentioned wallet. If they fail to open or drop, all
+    /// wallets are polled once per poll interval until they reconnect.
+    pub fn spawn(self) -> JoinHandle<()> {
+        info!("👛 Tracking {} wallets (mirror mode: {})", self.config.wallets.len(), self.config.mirror_mode);
+
+        tokio::spawn(async move {
+            let poll_interval = Duration::from_millis(self.config.poll_interval_ms);
+
+            while self.sink.is_running().await {
+                match self.subscribe().await {
+                    Ok(subscriptions) => {
+                        // Catch up on anything missed while disconnected
+                        self.poll_all().await;
+                        self.follow(subscriptions, poll_interval).await;
+                    }
+                    Err(e) => {
+                        warn!("Wallet subscriptions unavailable, polling instead: {}", e);
+                        self.poll_all().await;
+                        tokio::time::sleep(poll_interval).await;
+                    }
+                }
+            }
+
+            info!("🛑 Wallet tracking stopped");
+        })
+    }
+
+    /// Open one `mentions` log subscription per tracked wallet
+    async fn subscribe(&self) -> AppResult<Vec<HeliusWebsocket>> {
+        let mut subscriptions = Vec::with_capacity(self.config.wallets.len());
+        for wallet in &self.config.wallets {
+            subscriptions.push(self.solana.subscribe_program_events(&wallet.address).await?);
+        }
+        Ok(subscriptions)
+    }
+
+    /// Catch wallets up as their subscriptions report activity
+    ///
+    /// Returns when any subscription ends or fails, or the listener stops.
+    async fn follow(&self, subscriptions: Vec<HeliusWebsocket>, poll_interval: Duration) {
+        let mut notifications = futures::stream::select_all(
+            subscriptions.into_iter()
+                .enumerate()
+                .map(|(index, subscription)| subscription.map(move |event| (index, event))),
+        );
+
+        while self.sink.is_running().await {
+            let next = match tokio::time::timeout(poll_interval, notifications.next()).await {
+                Ok(next) => next,
+                Err(_) => continue,
+            };
+
+            let wallet = match next {
+                Some((index, Ok(event))) => {
+                    // Failed transactions cannot contain a buy
+                    if !event["value"]["err"].is_null() {
+                        continue;
+                    }
+                    &self.config.wallets[index]
+                }
+                Some((_, Err(e))) => {
+                    warn!("Wallet subscription failed: {}", e);
+                    return;
+                }
+                None => {
+                    warn!("Wallet subscription closed");
+                    return;
+                }
+            };
+
+            if let Err(e) = self.poll_wallet(wallet).await {
+                warn!("Failed to catch up tracked wallet {}: {}", wallet.address, e);
+            }
+        }
+    }
+
+    /// Poll every tracked wallet once
+    async fn poll_all(&self) {
+        for wallet in &self.config.wallets {
+            if let Err(e) = self.poll_wallet(wallet).await {
+                warn!("Failed to poll tracked wallet {}: {}", wallet.address, e);
+            }
+        }
+    }
+
+    /// Process a wallet's transactions since its cursor
+    ///
+    /// The first poll only records the cursor, so history is never replayed
+    /// as fresh buys. The cursor then advances one processed signature at a
+    /// time, so a transaction that fails to load is retried on the next poll.
+    async fn poll_wallet(&self, wallet: &TrackedWalletConfig) -> AppResult<()> {
+        let signatures = self.solana
+            .get_signatures_for_address(&wallet.address, None, WALLET_SIGNATURE_LIMIT)
+            .await?;
+        let received_at = Instant::now();
+
+        let Some(newest) = signatures.first() else {
+            return Ok(());
+        };
+
+        let previous = {
+            let mut cursors = self.cursors.lock().await;
+            match cursors.get(&wallet.address) {
+                Some(previous) => previous.clone(),
+                None => {
+                    cursors.insert(wallet.address.clone(), newest.signature.clone());
+                    return Ok(());
+                }
+            }
+        };
+
+        let fresh: Vec<_> = signatures.iter()
+            .take_while(|info| info.signature != previous)
+            .collect();
+        if fresh.len() == signatures.len() {
+            warn!("More than {} new transactions for {}; older ones skipped", WALLET_SIGNATURE_LIMIT, wallet.address);
+        }
+
+        // Oldest first, so events follow the wallet's own order
+        for info in fresh.into_iter().rev() {
+            if !info.is_error {
+                let transaction = self.solana.get_transaction(&info.signature).await?;
+                self.process_transaction(wallet, &transaction, received_at).await;
+            }
+
+            self.cursors.lock().await.insert(wallet.address.clone(), info.signature.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Publish a wallet's transaction if it is a first buy of a token
+    async fn process_transaction(&self, wallet: &TrackedWalletConfig, transaction: &TransactionInfo, received_at: Instant) {
+        let Some(swap) = decode_swap(transaction, &wallet.address) else {
+            return;
+        };
+        if !is_first_buy(transaction, &swap) {
+            return;
+        }
+
+        let mirror_order = if self.config.mirror_mode {
+            self.sizer.lock().await.size(&swap, Utc::now())
+        } else {
+            None
+        };
+
+        info!("👛 {} bought {} ({})", wallet.label.as_deref().unwrap_or(&wallet.address), swap.token_out, swap.transaction);
+
+        let event = TokenEvent {
+            event_type: EventType::WalletBuy,
+            token_address: TokenAddress::new_unchecked(swap.token_out.clone()),
+            source: COPY_TRADE_SOURCE.to_string(),
+            timestamp: Timestamp::now(),
+            latency_ms: 0,
+            data: serde_json::json!({
+                "wallet": wallet.address,
+                "label": wallet.label,
+                "swap": swap,
+                "mirror_order": mirror_order,
+            }),
+            decoded: None,
+            signature: Some(swap.transaction.clone()),
+        };
+
+        self.sink.publish(COPY_TRADE_SOURCE, event, received_at).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::solana::types::{BalanceChange, InstructionInfo, TokenBalanceChange};
+
+    const WALLET: &str = "Wa11et1111111111111111111111111111111111111";
+    const MINT: &str = "NewMint111111111111111111111111111111111111";
+
+    fn buy_transaction(spent_lamports: u64, pre_token: u64) -> TransactionInfo {
+        TransactionInfo {
+            signature: "sig".to_string(),
+            slot: 1,
+            block_time: Some(Utc::now()),
+            status: TransactionStatus::Success,
+            fee: 5_000,
+            instructions: vec![InstructionInfo {
+                program_id: "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4".to_string(),
+                accounts: Vec::new(),
+                data: Vec::new(),
+            }],
+            inner_instructions: Vec::new(),
+            logs: Vec::new(),
+            balance_changes: vec![BalanceChange {
+                account: WALLET.to_string(),
+                pre_lamports: 10 * LAMPORTS_PER_SOL,
+                post_lamports: 10 * LAMPORTS_PER_SOL - spent_lamports - 5_000,
+            }],
+            token_balance_changes: vec![TokenBalanceChange {
+                account: "ata".to_string(),
+                owner: Some(WALLET.to_string()),
+                mint: MINT.to_string(),
+                pre_amount: pre_token,
+                post_amount: pre_token + 1_000_000,
+                decimals: 6,
+            }],
+        }
+    }
+
+    fn wallet(max_buy_sol: Option<Decimal>, max_total_sol: Option<Decimal>) -> CopyTradeConfig {
+        CopyTradeConfig {
+            wallets: vec![TrackedWalletConfig {
+                address: WALLET.to_string(),
+                label: None,
+                mirror_ratio: Decimal::new(5, 1),
+                max_buy_sol,
+                max_total_sol,
+            }],
+            mirror_mode: true,
+            mirror_delay_ms: 200,
+            mirror_window_ms: 5_000,
+            poll_interval_ms: 400,
+        }
+    }
+
+    #[test]
+    fn test_decode_swap_from_balances() {
+        let transaction = buy_transaction(2 * LAMPORTS_PER_SOL, 0);
+        let swap = decode_swap(&transaction, WALLET).unwrap();
+
+        assert_eq!(swap.token_in, WSOL_MINT);
+        assert_eq!(swap.token_out, MINT);
+        // The fee is not part of the spend
+        assert_eq!(swap.amount_in, 2 * LAMPORTS_PER_SOL);
+        assert_eq!(swap.amount_out, 1_000_000);
+        assert!(is_first_buy(&transaction, &swap));
+
+        let transaction = buy_transaction(LAMPORTS_PER_SOL, 500);
+        assert!(!is_first_buy(&transaction, &decode_swap(&transaction, WALLET).unwrap()));
+    }
+
+    #[test]
+    fn test_decode_swap_excludes_opened_account_rent() {
+        const RENT: u64 = 2_039_280;
+
+        let mut transaction = buy_transaction(2 * LAMPORTS_PER_SOL + RENT, 0);
+        transaction.balance_changes.push(BalanceChange {
+            account: "ata".to_string(),
+            pre_lamports: 0,
+            post_lamports: RENT,
+        });
+
+        let swap = decode_swap(&transaction, WALLET).unwrap();
+        assert_eq!(swap.amount_in, 2 * LAMPORTS_PER_SOL);
+
+        // Rent of an account opened for someone else is still the wallet's spend
+        transaction.token_balance_changes[0].owner = Some("other".to_string());
+        transaction.token_balance_changes.push(TokenBalanceChange {
+            account: "wallet_ata".to_string(),
+            owner: Some(WALLET.to_string()),
+            mint: MINT.to_string(),
+            pre_amount: 0,
+            post_amount: 1_000_000,
+            decimals: 6,
+        });
+        let swap = decode_swap(&transaction, WALLET).unwrap();
+        assert_eq!(swap.amount_in, 2 * LAMPORTS_PER_SOL + RENT);
+    }
+
+    #[test]
+    fn test_mirror_sizing_caps_and_window() {
+        let swap = decode_swap(&buy_transaction(4 * LAMPORTS_PER_SOL, 0), WALLET).unwrap();
+        let now = swap.swapped_at;
+
+        // Half of 4 SOL, capped at 1.5 per buy and 2.5 in total
+        let mut sizer = MirrorSizer::new(&wallet(Some(Decimal::new(15, 1)), Some(Decimal::new(25, 1))));
+        let order = sizer.size(&swap, now).unwrap();
+        assert_eq!(order.amount_sol, Decimal::new(15, 1));
+        assert_eq!(order.execute_after, now + chrono::Duration::milliseconds(200));
+        assert_eq!(order.expires_at, now + chrono::Duration::milliseconds(5_000));
+
+        assert_eq!(sizer.size(&swap, now).unwrap().amount_sol, Decimal::ONE);
+        assert!(sizer.size(&swap, now).is_none());
+
+        // Outside the mirror window
+        let mut sizer = MirrorSizer::new(&wallet(None, None));
+        assert_eq!(sizer.size(&swap, now).unwrap().amount_sol, Decimal::from(2));
+        assert!(sizer.size(&swap, now + chrono::Duration::seconds(6)).is_none());
+    }
+}
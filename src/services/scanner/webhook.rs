@@ -0,0 +1,445 @@
+//! Helius webhook receiver
+//!
+//! Helius pushes enhanced transactions touching the watched accounts to an
+//! HTTP endpoint. Deliveries must carry the auth header the webhook was
+//! registered with. Helius retries deliveries it considers failed, so
+//! transactions already seen, or too old to trade on, are dropped as
+//! replays. The webhook is registered on start and deleted on stop so a
+//! stopped bot does not leave Helius posting to a dead endpoint.
+
+use std::collections::{HashSet, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::{Body, Bytes};
+use hyper::header::AUTHORIZATION;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{HeaderMap, Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use crate::core::types::{TokenAddress, Timestamp};
+use crate::services::solana::decoders::{USDC_MINT, WSOL_MINT};
+use crate::services::solana::{DecoderRegistry, HeliusClient, HeliusWebhookPayload, SolanaService, WebhookRegistration};
+use super::event_listener::{EventSink, EventType, TokenEvent};
+
+/// Detection source label for Helius webhook deliveries
+pub const WEBHOOK_SOURCE: &str = "helius_webhook";
+
+/// Transaction types the webhook is registered for
+const WEBHOOK_TRANSACTION_TYPES: [&str; 3] = ["TOKEN_MINT", "CREATE_POOL", "ADD_LIQUIDITY"];
+
+/// Largest delivery body accepted
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+
+/// Transactions older than this are dropped as replays
+///
+/// Helius keeps retrying failed deliveries for a while; a launch seen this
+/// late is no longer worth acting on.
+const MAX_DELIVERY_AGE: Duration = Duration::from_secs(300);
+
+/// Signatures remembered for replay detection
+const SEEN_SIGNATURE_CAPACITY: usize = 10_000;
+
+/// Delay after a failed accept, so a full file table does not spin
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Bounded memory of delivered signatures
+#[derive(Debug, Default)]
+struct ReplayGuard {
+    seen: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl ReplayGuard {
+    /// Record a signature, returning whether this is its first delivery
+    fn first_delivery(&mut self, signature: &str) -> bool {
+        if self.seen.contains(signature) {
+            return false;
+        }
+
+        if self.order.len() >= SEEN_SIGNATURE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+
+        self.seen.insert(signature.to_string());
+        self.order.push_back(signature.to_string());
+        true
+    }
+}
+
+/// Authenticates deliveries and publishes their token events
+#[derive(Debug)]
+struct WebhookHandler {
+    /// Expected `Authorization` header value
+    auth_header: String,
+    /// Listener event sink
+    sink: EventSink,
+    /// Pool and mint creation decoders
+    decoders: Arc<DecoderRegistry>,
+    /// Signatures already delivered
+    replays: std::sync::Mutex<ReplayGuard>,
+}
+
+impl WebhookHandler {
+    fn new(auth_header: String, sink: EventSink, decoders: Arc<DecoderRegistry>) -> Self {
+        Self {
+            auth_header,
+            sink,
+            decoders,
+            replays: std::sync::Mutex::new(ReplayGuard::default()),
+        }
+    }
+
+    async fn handle<B>(&self, request: Request<B>) -> Response<Full<Bytes>>
+    where
+        B: Body,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let received_at = Instant::now();
+
+        if request.method() != Method::POST {
+            return status(StatusCode::METHOD_NOT_ALLOWED);
+        }
+
+        if !self.authorized(request.headers()) {
+            warn!("Rejected webhook delivery with a missing or wrong auth header");
+            return status(StatusCode::UNAUTHORIZED);
+        }
+
+        let body = match Limited::new(request.into_body(), MAX_BODY_BYTES).collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(e) if e.downcast_ref::<LengthLimitError>().is_some() => {
+                warn!("Rejected webhook delivery over {} bytes", MAX_BODY_BYTES);
+                return status(StatusCode::PAYLOAD_TOO_LARGE);
+            }
+            Err(e) => {
+                warn!("Failed to read webhook delivery: {}", e);
+                return status(StatusCode::BAD_REQUEST);
+            }
+        };
+
+        let deliveries = match HeliusClient::parse_webhook_payloads(&body) {
+            Ok(deliveries) => deliveries,
+            Err(e) => {
+                warn!("{}", e);
+                return status(StatusCode::BAD_REQUEST);
+            }
+        };
+
+        for event in self.accept(deliveries, Utc::now()) {
+            self.sink.publish(WEBHOOK_SOURCE, event, received_at).await;
+        }
+
+        status(StatusCode::OK)
+    }
+
+    fn authorized(&self, headers: &HeaderMap) -> bool {
+        headers.get(AUTHORIZATION)
+            .is_some_and(|provided| constant_time_eq(provided.as_bytes(), self.auth_header.as_bytes()))
+    }
+
+    /// Token events of the fresh, successful transactions of a delivery
+    fn accept(&self, deliveries: Vec<HeliusWebhookPayload>, now: DateTime<Utc>) -> Vec<TokenEvent> {
+        let mut replays = self.replays.lock().unwrap();
+        let mut events = Vec::new();
+
+        for delivery in deliveries {
+            let age = now.timestamp().saturating_sub(delivery.timestamp);
+            if age > MAX_DELIVERY_AGE.as_secs() as i64 {
+                debug!("Dropping webhook delivery of {}, {}s old", delivery.signature, age);
+                continue;
+            }
+
+            if !replays.first_delivery(&delivery.signature) {
+                debug!("Dropping replayed webhook delivery of {}", delivery.signature);
+                continue;
+            }
+
+            if delivery.succeeded() {
+                events.extend(delivery_events(&delivery, &self.decoders));
+            }
+        }
+
+        events
+    }
+}
+
+/// Helius webhook registration and HTTP receiver
+#[derive(Debug)]
+pub struct WebhookReceiver {
+    /// Solana service, for the Helius client
+    solana: Arc<SolanaService>,
+    /// URL Helius delivers to
+    callback_url: String,
+    /// Address the HTTP server listens on
+    listen_addr: SocketAddr,
+    /// Delivery handling shared with connections
+    handler: Arc<WebhookHandler>,
+    /// Accounts whose transactions are delivered
+    addresses: Mutex<Vec<String>>,
+    /// ID of the registered webhook
+    webhook_id: Mutex<Option<String>>,
+    /// HTTP server task
+    server: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl WebhookReceiver {
+    /// Create a receiver for deliveries to `callback_url`
+    ///
+    /// The webhook initially watches the programs of `decoders`.
+    pub(super) fn new(
+        solana: Arc<SolanaService>,
+        callback_url: String,
+        decoders: Arc<DecoderRegistry>,
+        sink: EventSink,
+    ) -> AppResult<Self> {
+        let config = solana.helius_config();
+        let listen_addr = config.webhook_listen_addr.parse()
+            .map_err(|e| AppError::config(format!("Invalid webhook listen address {}: {}", config.webhook_listen_addr, e)))?;
+        let auth_header = config.webhook_auth_header.clone()
+            .filter(|header| !header.is_empty())
+            .ok_or_else(|| AppError::config("Webhook auth header not configured"))?;
+
+        Ok(Self {
+            solana,
+            callback_url,
+            listen_addr,
+            addresses: Mutex::new(decoders.program_ids()),
+            handler: Arc::new(WebhookHandler::new(auth_header, sink, decoders)),
+            webhook_id: Mutex::new(None),
+            server: Mutex::new(None),
+        })
+    }
+
+    /// Start the HTTP server, then register the webhook
+    pub async fn start(&self) -> AppResult<()> {
+        let listener = TcpListener::bind(self.listen_addr).await
+            .map_err(|e| AppError::network(format!("Failed to bind webhook receiver to {}: {}", self.listen_addr, e)))?;
+        *self.server.lock().await = Some(tokio::spawn(serve(listener, self.handler.clone())));
+        info!("🌐 Webhook receiver listening on {}", self.listen_addr);
+
+        let registration = self.registration().await;
+        match self.solana.helius().create_webhook(&registration).await {
+            Ok(webhook) => {
+                *self.webhook_id.lock().await = Some(webhook.webhook_id);
+                Ok(())
+            }
+            Err(e) => {
+                if let Some(server) = self.server.lock().await.take() {
+                    server.abort();
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Replace the accounts whose transactions are delivered
+    pub async fn update_addresses(&self, addresses: Vec<String>) -> AppResult<()> {
+        *self.addresses.lock().await = addresses;
+
+        let webhook_id = self.webhook_id.lock().await.clone();
+        if let Some(webhook_id) = webhook_id {
+            let registration = self.registration().await;
+            self.solana.helius().update_webhook(&webhook_id, &registration).await?;
+            info!("🌐 Webhook {} now watches {} accounts", webhook_id, registration.account_addresses.len());
+        }
+
+        Ok(())
+    }
+
+    /// Delete the webhook and stop the HTTP server
+    pub async fn stop(&self) -> AppResult<()> {
+        let webhook_id = self.webhook_id.lock().await.take();
+        let deleted = match webhook_id {
+            Some(webhook_id) => self.solana.helius().delete_webhook(&webhook_id).await,
+            None => Ok(()),
+        };
+
+        if let Some(server) = self.server.lock().await.take() {
+            server.abort();
+        }
+
+        deleted
+    }
+
+    async fn registration(&self) -> WebhookRegistration {
+        WebhookRegistration {
+            webhook_url: self.callback_url.clone(),
+            transaction_types: WEBHOOK_TRANSACTION_TYPES.iter().map(|t| t.to_string()).collect(),
+            account_addresses: self.addresses.lock().await.clone(),
+            webhook_type: "enhanced".to_string(),
+            auth_header: Some(self.handler.auth_header.clone()),
+        }
+    }
+}
+
+/// Accept connections and serve deliveries over HTTP/1
+async fn serve(listener: TcpListener, handler: Arc<WebhookHandler>) {
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                warn!("Webhook receiver failed to accept a connection: {}", e);
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let handler = handler.clone();
+                async move { Ok::<_, Infallible>(handler.handle(request).await) }
+            });
+
+            if let Err(e) = http1::Builder::new().serve_connection(TokioIo::new(stream), service).await {
+                debug!("Webhook connection from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
+
+/// Token events of a delivered transaction, one per new mint
+///
+/// Pool deposits leave out the LP token minted to the depositor; a token
+/// mint keeps every mint, since its creator receives the new token. Each
+/// event carries the creation event the decoders found for its mint in the
+/// delivered instructions, if any, so the pool can be priced.
+fn delivery_events(delivery: &HeliusWebhookPayload, decoders: &DecoderRegistry) -> Vec<TokenEvent> {
+    let (event_type, mints) = match delivery.transaction_type.as_str() {
+        "TOKEN_MINT" => (EventType::TokenMint, delivery.mints()),
+        "CREATE_POOL" => (EventType::LiquidityPool, delivery.pool_mints()),
+        "ADD_LIQUIDITY" => (EventType::LiquidityAdded, delivery.pool_mints()),
+        _ => return Vec::new(),
+    };
+    let source = delivery.source.to_lowercase();
+    let data = serde_json::to_value(delivery).unwrap_or_default();
+    let decoded = decoders.decode_transaction(&delivery.transaction_info());
+
+    mints
+        .into_iter()
+        .filter(|mint| *mint != WSOL_MINT && *mint != USDC_MINT)
+        .map(|mint| TokenEvent {
+            event_type: event_type.clone(),
+            token_address: TokenAddress::new_unchecked(mint.to_string()),
+            source: source.clone(),
+            timestamp: Timestamp::now(),
+            latency_ms: 0,
+            data: data.clone(),
+            decoded: decoded.iter()
+                .find(|event| event.token_mints().iter().any(|decoded_mint| decoded_mint == mint))
+                .cloned(),
+            signature: Some(delivery.signature.clone()),
+        })
+        .collect()
+}
+
+/// Compare secrets without leaking the length of the matching prefix
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn status(status: StatusCode) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::default());
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::{broadcast, RwLock};
+    use super::super::event_listener::ListenerState;
+
+    fn delivery(signature: &str, transaction_type: &str, timestamp: i64) -> serde_json::Value {
+        serde_json::json!({
+            "signature": signature,
+            "slot": 250_000_000u64,
+            "timestamp": timestamp,
+            "type": transaction_type,
+            "source": "RAYDIUM",
+            "fee": 5000,
+            "feePayer": "creator",
+            "tokenTransfers": [
+                { "fromUserAccount": "creator", "toUserAccount": "pool", "tokenAmount": 1000.0, "mint": "new-mint" },
+                { "fromUserAccount": "creator", "toUserAccount": "pool", "tokenAmount": 10.0, "mint": WSOL_MINT },
+            ],
+        })
+    }
+
+    fn request(auth_header: Option<&str>, body: &serde_json::Value) -> Request<Full<Bytes>> {
+        let mut request = Request::builder().method(Method::POST);
+        if let Some(auth_header) = auth_header {
+            request = request.header(AUTHORIZATION, auth_header);
+        }
+        request.body(Full::new(Bytes::from(body.to_string()))).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_webhook_deliveries_are_authenticated_and_deduplicated() {
+        let (sender, mut events) = broadcast::channel(16);
+        let sink = EventSink::new(sender, Arc::new(RwLock::new(ListenerState::new())));
+        let handler = WebhookHandler::new("secret".to_string(), sink, Arc::new(DecoderRegistry::with_defaults()));
+
+        let now = Utc::now().timestamp();
+        let batch = serde_json::json!([
+            delivery("sig-1", "CREATE_POOL", now),
+            delivery("sig-2", "SWAP", now),
+            delivery("sig-3", "CREATE_POOL", now - 3600),
+        ]);
+
+        assert_eq!(handler.handle(request(None, &batch)).await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(handler.handle(request(Some("wrong"), &batch)).await.status(), StatusCode::UNAUTHORIZED);
+        assert!(events.try_recv().is_err());
+
+        // Only the fresh pool creation yields an event, for the non-SOL mint
+        assert_eq!(handler.handle(request(Some("secret"), &batch)).await.status(), StatusCode::OK);
+        let event = events.try_recv().unwrap();
+        assert!(matches!(event.event_type, EventType::LiquidityPool));
+        assert_eq!(event.token_address.as_str(), "new-mint");
+        assert_eq!(event.source, "raydium");
+        assert!(events.try_recv().is_err());
+
+        // A retried delivery is acknowledged but not published again
+        assert_eq!(handler.handle(request(Some("secret"), &batch)).await.status(), StatusCode::OK);
+        assert!(events.try_recv().is_err());
+
+        let malformed = serde_json::json!({ "not": "a batch" });
+        assert_eq!(handler.handle(request(Some("secret"), &malformed)).await.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_pool_deliveries_skip_lp_mints() {
+        let now = Utc::now().timestamp();
+        let lp_mint = serde_json::json!({
+            "fromUserAccount": "", "toUserAccount": "creator", "tokenAmount": 31.6, "mint": "lp-mint",
+        });
+
+        let mut created = delivery("sig-1", "CREATE_POOL", now);
+        created["tokenTransfers"].as_array_mut().unwrap().push(lp_mint.clone());
+        let created: HeliusWebhookPayload = serde_json::from_value(created).unwrap();
+        let mints: Vec<String> = delivery_events(&created).into_iter().map(|e| e.token_address.0).collect();
+        assert_eq!(mints, vec!["new-mint".to_string()]);
+
+        // The creator of a new token receives its supply
+        let mut minted = delivery("sig-2", "TOKEN_MINT", now);
+        minted["tokenTransfers"] = serde_json::json!([lp_mint]);
+        let minted: HeliusWebhookPayload = serde_json::from_value(minted).unwrap();
+        let events = delivery_events(&minted);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].event_type, EventType::TokenMint));
+        assert_eq!(events[0].token_address.as_str(), "lp-mint");
+    }
+}
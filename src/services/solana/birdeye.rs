@@ -0,0 +1,847 @@
+//! Birdeye API client implementation
+//!
+//! Birdeye supplies market data the chain does not: USD prices, 24h volume,
+//! trade history and candles. Plans are metered per minute, so requests are
+//! drawn from a per-minute budget whose remaining size is reported to
+//! metrics, and responses are cached in Redis for `cache_ttl_seconds` so
+//! repeated lookups of the same token do not spend it.
+
+use reqwest::{Client, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn, instrument};
+
+use crate::config::models::BirdeyeConfig;
+use crate::core::result::AppResult;
+use crate::core::error::AppError;
+use crate::infrastructure::database::RedisService;
+use crate::infrastructure::monitoring::MetricsService;
+
+/// Length of a rate limit window
+const BUDGET_WINDOW: Duration = Duration::from_secs(60);
+
+/// Maximum tokens per `multi_price` request
+const MULTI_PRICE_BATCH: usize = 100;
+
+/// Maximum trades per `txs/token` page
+const TRADE_PAGE_LIMIT: u32 = 50;
+
+/// Attempts per request when Birdeye answers 429
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Longest wait for the budget after a 429 before giving up on the request
+///
+/// A 429 spends the rest of the window, which can be most of a minute; a
+/// caller on the detection path is better served by an error.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(2);
+
+/// Redis key prefix of cached responses
+const CACHE_KEY_PREFIX: &str = "birdeye";
+
+/// Birdeye API client
+#[derive(Debug)]
+pub struct BirdeyeClient {
+    /// HTTP client
+    http_client: Client,
+    /// API configuration
+    config: BirdeyeConfig,
+    /// Requests left in the current minute
+    budget: RwLock<RequestBudget>,
+    /// Redis response cache, if enabled
+    cache: Option<RedisService>,
+    /// Metrics receiving budget usage
+    metrics: Option<Arc<MetricsService>>,
+    /// Client statistics
+    stats: RwLock<BirdeyeStats>,
+}
+
+impl BirdeyeClient {
+    /// Create a new Birdeye client
+    ///
+    /// Responses are cached when `cache` is given and `cache_ttl_seconds`
+    /// is non-zero.
+    pub fn new(
+        config: &BirdeyeConfig,
+        cache: Option<RedisService>,
+        metrics: Option<Arc<MetricsService>>,
+    ) -> AppResult<Self> {
+        info!("🦅 Initializing Birdeye API client");
+
+        if config.api_key.is_empty() {
+            warn!("⚠️  Birdeye API key not configured - market data will be unavailable");
+        }
+
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .connect_timeout(Duration::from_secs(5))
+            .build()
+            .map_err(|e| AppError::network(format!("Failed to create HTTP client: {}", e)))?;
+
+        let cache = cache.filter(|_| config.cache_ttl_seconds > 0);
+
+        Ok(Self {
+            http_client,
+            config: config.clone(),
+            budget: RwLock::new(RequestBudget::new(config.rate_limit_per_minute)),
+            cache,
+            metrics,
+            stats: RwLock::new(BirdeyeStats::default()),
+        })
+    }
+
+    /// Get the USD price of a token
+    #[instrument(skip(self))]
+    pub async fn price(&self, address: &str) -> AppResult<TokenPrice> {
+        self.prices(&[address.to_string()]).await?
+            .remove(address)
+            .ok_or_else(|| AppError::network(format!("Birdeye has no price for {}", address)))
+    }
+
+    /// Get USD prices of many tokens, batching uncached ones
+    ///
+    /// Tokens Birdeye does not price are missing from the result.
+    #[instrument(skip(self, addresses))]
+    pub async fn prices(&self, addresses: &[String]) -> AppResult<HashMap<String, TokenPrice>> {
+        let mut prices = HashMap::with_capacity(addresses.len());
+        let mut missing = Vec::new();
+
+        for address in addresses {
+            if prices.contains_key(address) || missing.contains(address) {
+                continue;
+            }
+            match self.cache_get::<TokenPrice>(&cache_key("price", address)).await {
+                Some(price) => {
+                    prices.insert(address.clone(), price);
+                }
+                None => missing.push(address.clone()),
+            }
+        }
+
+        for batch in missing.chunks(MULTI_PRICE_BATCH) {
+            let list = batch.join(",");
+            let fetched: HashMap<String, Option<TokenPrice>> =
+                self.get("/defi/multi_price", &[("list_address", list.as_str())]).await?;
+
+            for (address, price) in fetched {
+                if let Some(mut price) = price {
+                    price.address = address.clone();
+                    self.cache_put(&cache_key("price", &address), &price).await;
+                    prices.insert(address, price);
+                }
+            }
+        }
+
+        Ok(prices)
+    }
+
+    /// Get the market overview of a token
+    #[instrument(skip(self))]
+    pub async fn token_overview(&self, address: &str) -> AppResult<TokenOverview> {
+        let key = cache_key("overview", address);
+        if let Some(overview) = self.cache_get(&key).await {
+            return Ok(overview);
+        }
+
+        let overview: TokenOverview = self.get("/defi/token_overview", &[("address", address)]).await?;
+        self.cache_put(&key, &overview).await;
+        Ok(overview)
+    }
+
+    /// Get one page of recent swaps of a token, newest first
+    ///
+    /// Trades are not cached, since callers page through them for fresh
+    /// activity.
+    #[instrument(skip(self))]
+    pub async fn trades(&self, address: &str, offset: u32, limit: u32) -> AppResult<TradePage> {
+        let offset = offset.to_string();
+        let limit = limit.clamp(1, TRADE_PAGE_LIMIT).to_string();
+
+        self.get("/defi/txs/token", &[
+            ("address", address),
+            ("offset", offset.as_str()),
+            ("limit", limit.as_str()),
+            ("tx_type", "swap"),
+        ]).await
+    }
+
+    /// Get up to `max_trades` recent swaps of a token, across pages
+    pub async fn recent_trades(&self, address: &str, max_trades: usize) -> AppResult<Vec<TokenTrade>> {
+        let mut trades = Vec::new();
+
+        while trades.len() < max_trades {
+            let limit = (max_trades - trades.len()).min(TRADE_PAGE_LIMIT as usize) as u32;
+            let page = self.trades(address, trades.len() as u32, limit).await?;
+            let done = !page.has_next || page.items.is_empty();
+            trades.extend(page.items);
+
+            if done {
+                break;
+            }
+        }
+
+        trades.truncate(max_trades);
+        Ok(trades)
+    }
+
+    /// Get OHLCV candles of a token between two Unix timestamps
+    #[instrument(skip(self))]
+    pub async fn ohlcv(&self, address: &str, interval: CandleInterval, from: i64, to: i64) -> AppResult<Vec<Candle>> {
+        let key = cache_key("ohlcv", &format!("{}:{}:{}:{}", address, interval.as_str(), from, to));
+        if let Some(candles) = self.cache_get(&key).await {
+            return Ok(candles);
+        }
+
+        let (from, to) = (from.to_string(), to.to_string());
+        let page: CandlePage = self.get("/defi/ohlcv", &[
+            ("address", address),
+            ("type", interval.as_str()),
+            ("time_from", from.as_str()),
+            ("time_to", to.as_str()),
+        ]).await?;
+
+        self.cache_put(&key, &page.items).await;
+        Ok(page.items)
+    }
+
+    /// Get client statistics
+    pub async fn statistics(&self) -> BirdeyeStats {
+        let mut stats = self.stats.read().await.clone();
+        stats.requests_remaining = self.budget.read().await.remaining(Instant::now());
+        stats
+    }
+
+    /// Execute a GET request drawn from the minute budget
+    async fn get<T>(&self, path: &str, params: &[(&str, &str)]) -> AppResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.check_api_key()?;
+
+        let url = format!("{}{}", self.config.base_url, path);
+        let start_time = Instant::now();
+        let mut rate_limited = false;
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            if let Err(wait) = self.take_budget(rate_limited.then_some(MAX_RATE_LIMIT_WAIT)).await {
+                self.record_request(false, start_time.elapsed()).await;
+                return Err(AppError::network(format!(
+                    "Birdeye rate limit exceeded; budget refills in {}s",
+                    wait.as_secs()
+                )));
+            }
+
+            let response = self.http_client
+                .get(&url)
+                .header("X-API-KEY", &self.config.api_key)
+                .header("x-chain", "solana")
+                .query(params)
+                .send()
+                .await;
+
+            let result = match response {
+                Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    // Our count is behind Birdeye's, so wait for the next window
+                    warn!("⚠️  Birdeye rate limit hit on attempt {}", attempt);
+                    self.budget.write().await.exhaust();
+                    rate_limited = true;
+                    continue;
+                }
+                Ok(response) if response.status().is_success() => {
+                    match response.json::<BirdeyeResponse<T>>().await {
+                        Ok(BirdeyeResponse { success: true, data: Some(data), .. }) => Ok(data),
+                        Ok(body) => Err(AppError::network(format!(
+                            "Birdeye request failed: {}",
+                            body.message.unwrap_or_else(|| "no data".to_string())
+                        ))),
+                        Err(e) => Err(AppError::network(format!("Failed to parse Birdeye response: {}", e))),
+                    }
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let error_text = response.text().await.unwrap_or_default();
+                    Err(AppError::network(format!("Birdeye API error ({}): {}", status, error_text)))
+                }
+                Err(e) => Err(AppError::network(format!("Birdeye request failed: {}", e))),
+            };
+
+            self.record_request(result.is_ok(), start_time.elapsed()).await;
+            return result;
+        }
+
+        self.record_request(false, start_time.elapsed()).await;
+        Err(AppError::network("Birdeye rate limit exceeded"))
+    }
+
+    /// Wait for a request from the minute budget and report what is left
+    ///
+    /// Gives up, returning the wait, when the budget refills later than
+    /// `max_wait`.
+    async fn take_budget(&self, max_wait: Option<Duration>) -> Result<(), Duration> {
+        loop {
+            let taken = self.budget.write().await.try_take(Instant::now());
+            match taken {
+                Ok(remaining) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.set_gauge("sniper_bot_birdeye_requests_remaining", remaining as f64, None);
+                        metrics.increment_counter("sniper_bot_birdeye_requests_total", None);
+                    }
+                    return Ok(());
+                }
+                Err(wait) if max_wait.is_some_and(|max_wait| wait > max_wait) => return Err(wait),
+                Err(wait) => {
+                    debug!("⏳ Birdeye budget spent, waiting {:?}", wait);
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+    }
+
+    /// Read a cached response; cache errors count as misses
+    async fn cache_get<T>(&self, key: &str) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        let cache = self.cache.as_ref()?;
+
+        let cached = match cache.get_cached_json::<T>(key).await {
+            Ok(cached) => cached,
+            Err(e) => {
+                warn!("Failed to read cached Birdeye response {}: {}", key, e);
+                None
+            }
+        };
+
+        let hit = cached.is_some();
+        {
+            let mut stats = self.stats.write().await;
+            if hit {
+                stats.cache_hits += 1;
+            } else {
+                stats.cache_misses += 1;
+            }
+        }
+        if hit {
+            if let Some(metrics) = &self.metrics {
+                metrics.increment_counter("sniper_bot_birdeye_cache_hits_total", None);
+            }
+        }
+
+        cached
+    }
+
+    /// Cache a response for `cache_ttl_seconds`
+    async fn cache_put<T>(&self, key: &str, value: &T)
+    where
+        T: Serialize,
+    {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+
+        let ttl = Duration::from_secs(self.config.cache_ttl_seconds);
+        if let Err(e) = cache.cache_json(key, value, Some(ttl)).await {
+            warn!("Failed to cache Birdeye response {}: {}", key, e);
+        }
+    }
+
+    /// Check if API key is configured
+    fn check_api_key(&self) -> AppResult<()> {
+        if self.config.api_key.is_empty() {
+            Err(AppError::config("Birdeye API key not configured"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Record a completed request
+    async fn record_request(&self, success: bool, duration: Duration) {
+        let mut stats = self.stats.write().await;
+        stats.total_requests += 1;
+        if success {
+            stats.successful_requests += 1;
+            stats.total_duration += duration;
+        } else {
+            stats.failed_requests += 1;
+        }
+    }
+}
+
+/// Redis key of a cached response
+fn cache_key(kind: &str, id: &str) -> String {
+    format!("{}:{}:{}", CACHE_KEY_PREFIX, kind, id)
+}
+
+/// Requests allowed per minute, counted in fixed one-minute windows
+#[derive(Debug)]
+struct RequestBudget {
+    /// Requests per window
+    limit: u32,
+    /// Requests taken in the current window
+    used: u32,
+    /// Start of the current window
+    window_start: Instant,
+}
+
+impl RequestBudget {
+    fn new(limit: u32) -> Self {
+        Self {
+            limit: limit.max(1),
+            used: 0,
+            window_start: Instant::now(),
+        }
+    }
+
+    /// Take a request, returning how many are left, or how long until the next window
+    fn try_take(&mut self, now: Instant) -> Result<u32, Duration> {
+        self.roll(now);
+
+        if self.used < self.limit {
+            self.used += 1;
+            Ok(self.limit - self.used)
+        } else {
+            Err(self.window_start + BUDGET_WINDOW - now)
+        }
+    }
+
+    /// Requests left in the current window
+    fn remaining(&self, now: Instant) -> u32 {
+        if now.saturating_duration_since(self.window_start) >= BUDGET_WINDOW {
+            self.limit
+        } else {
+            self.limit - self.used
+        }
+    }
+
+    /// Spend the rest of the current window
+    fn exhaust(&mut self) {
+        self.used = self.limit;
+    }
+
+    fn roll(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.window_start) >= BUDGET_WINDOW {
+            self.window_start = now;
+            self.used = 0;
+        }
+    }
+}
+
+/// Birdeye statistics
+#[derive(Debug, Clone, Default)]
+pub struct BirdeyeStats {
+    /// Requests sent
+    pub total_requests: u64,
+    /// Requests that returned data
+    pub successful_requests: u64,
+    /// Requests that failed
+    pub failed_requests: u64,
+    /// Time spent on successful requests
+    pub total_duration: Duration,
+    /// Lookups answered from the cache
+    pub cache_hits: u64,
+    /// Lookups the cache could not answer
+    pub cache_misses: u64,
+    /// Requests left in the current minute
+    pub requests_remaining: u32,
+}
+
+impl BirdeyeStats {
+    /// Share of cache lookups that hit, in percent
+    pub fn cache_hit_rate(&self) -> f64 {
+        let lookups = self.cache_hits + self.cache_misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / lookups as f64 * 100.0
+        }
+    }
+}
+
+/// Birdeye API response types
+
+#[derive(Debug, Deserialize)]
+struct BirdeyeResponse<T> {
+    success: bool,
+    data: Option<T>,
+    message: Option<String>,
+}
+
+/// USD price of a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPrice {
+    /// Token mint address
+    #[serde(default)]
+    pub address: String,
+    /// Price in USD
+    pub value: f64,
+    /// When the price was last updated, in Unix seconds
+    pub update_unix_time: i64,
+    /// 24h price change in percent
+    #[serde(default)]
+    pub price_change_24h: Option<f64>,
+    /// Liquidity in USD
+    #[serde(default)]
+    pub liquidity: Option<f64>,
+}
+
+/// Market overview of a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenOverview {
+    /// Token mint address
+    pub address: String,
+    /// Token symbol
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Token name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Number of decimals
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Price in USD
+    #[serde(default)]
+    pub price: Option<f64>,
+    /// 24h price change in percent
+    #[serde(default)]
+    pub price_change_24h_percent: Option<f64>,
+    /// Liquidity in USD
+    #[serde(default)]
+    pub liquidity: Option<f64>,
+    /// Market cap in USD
+    #[serde(default, alias = "mc")]
+    pub market_cap: Option<f64>,
+    /// 24h volume in USD
+    #[serde(default, rename = "v24hUSD")]
+    pub volume_24h_usd: Option<f64>,
+    /// Number of holders
+    #[serde(default, rename = "holder")]
+    pub holder_count: Option<u32>,
+    /// Trades in the last 24h
+    #[serde(default, rename = "trade24h")]
+    pub trades_24h: Option<u32>,
+    /// Unique wallets trading in the last 24h
+    #[serde(default, rename = "uniqueWallet24h")]
+    pub unique_wallets_24h: Option<u32>,
+}
+
+/// Page of token swaps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradePage {
+    /// Swaps, newest first
+    pub items: Vec<TokenTrade>,
+    /// Whether more pages follow
+    #[serde(default)]
+    pub has_next: bool,
+}
+
+/// Swap of a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTrade {
+    /// Transaction signature
+    pub tx_hash: String,
+    /// Block time in Unix seconds
+    pub block_unix_time: i64,
+    /// DEX the swap went through
+    pub source: String,
+    /// Wallet that swapped
+    pub owner: String,
+    /// `buy` or `sell`, from the token's side
+    pub side: String,
+    /// Token sold
+    pub from: TradeLeg,
+    /// Token bought
+    pub to: TradeLeg,
+}
+
+/// One side of a swap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeLeg {
+    /// Token mint address
+    pub address: String,
+    /// Token symbol
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Amount in UI units
+    pub ui_amount: f64,
+}
+
+/// Candle width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandleInterval {
+    /// 1 minute
+    OneMinute,
+    /// 5 minutes
+    FiveMinutes,
+    /// 15 minutes
+    FifteenMinutes,
+    /// 1 hour
+    OneHour,
+    /// 4 hours
+    FourHours,
+    /// 1 day
+    OneDay,
+}
+
+impl CandleInterval {
+    /// Birdeye name of the interval
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CandleInterval::OneMinute => "1m",
+            CandleInterval::FiveMinutes => "5m",
+            CandleInterval::FifteenMinutes => "15m",
+            CandleInterval::OneHour => "1H",
+            CandleInterval::FourHours => "4H",
+            CandleInterval::OneDay => "1D",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CandlePage {
+    items: Vec<Candle>,
+}
+
+/// OHLCV candle in USD
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candle {
+    /// Candle open, in Unix seconds
+    #[serde(rename = "unixTime")]
+    pub unix_time: i64,
+    /// Open price
+    #[serde(rename = "o")]
+    pub open: f64,
+    /// High price
+    #[serde(rename = "h")]
+    pub high: f64,
+    /// Low price
+    #[serde(rename = "l")]
+    pub low: f64,
+    /// Close price
+    #[serde(rename = "c")]
+    pub close: f64,
+    /// Volume in USD
+    #[serde(rename = "v")]
+    pub volume: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigLoader;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer, rate_limit_per_minute: u32) -> BirdeyeClient {
+        let mut config = ConfigLoader::new().without_env().create_default_config().birdeye;
+        config.api_key = "test-api-key".to_string();
+        config.base_url = server.uri();
+        config.rate_limit_per_minute = rate_limit_per_minute;
+        BirdeyeClient::new(&config, None, None).unwrap()
+    }
+
+    fn data(data: serde_json::Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true, "data": data }))
+    }
+
+    #[test]
+    fn test_request_budget() {
+        let mut budget = RequestBudget::new(2);
+        let now = budget.window_start;
+
+        assert_eq!(budget.try_take(now), Ok(1));
+        assert_eq!(budget.try_take(now + Duration::from_secs(10)), Ok(0));
+        assert_eq!(budget.try_take(now + Duration::from_secs(15)), Err(Duration::from_secs(45)));
+        assert_eq!(budget.remaining(now + Duration::from_secs(15)), 0);
+
+        // A new window starts a full budget
+        assert_eq!(budget.remaining(now + BUDGET_WINDOW), 2);
+        assert_eq!(budget.try_take(now + BUDGET_WINDOW), Ok(1));
+
+        // A 429 spends the rest of the window
+        budget.exhaust();
+        assert!(budget.try_take(now + BUDGET_WINDOW).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prices_are_batched() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/defi/multi_price"))
+            .and(header("X-API-KEY", "test-api-key"))
+            .and(header("x-chain", "solana"))
+            .and(query_param("list_address", "mint-a,mint-b,mint-c"))
+            .respond_with(data(serde_json::json!({
+                "mint-a": { "value": 0.5, "updateUnixTime": 1_700_000_000, "priceChange24h": 12.5 },
+                "mint-b": { "value": 2.0, "updateUnixTime": 1_700_000_001, "liquidity": 15_000.0 },
+                "mint-c": null,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let birdeye = client(&server, 100);
+        let addresses: Vec<String> = ["mint-a", "mint-b", "mint-a", "mint-c"].iter().map(|a| a.to_string()).collect();
+        let prices = birdeye.prices(&addresses).await.unwrap();
+
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices["mint-a"].address, "mint-a");
+        assert_eq!(prices["mint-a"].price_change_24h, Some(12.5));
+        assert_eq!(prices["mint-b"].liquidity, Some(15_000.0));
+        assert!(!prices.contains_key("mint-c"));
+
+        let stats = birdeye.statistics().await;
+        assert_eq!((stats.total_requests, stats.requests_remaining), (1, 99));
+    }
+
+    #[tokio::test]
+    async fn test_overview_trades_and_ohlcv() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/defi/token_overview"))
+            .and(query_param("address", "mint"))
+            .respond_with(data(serde_json::json!({
+                "address": "mint",
+                "symbol": "EXM",
+                "decimals": 6,
+                "price": 0.0042,
+                "liquidity": 52_000.0,
+                "mc": 4_200_000.0,
+                "v24hUSD": 310_000.0,
+                "holder": 1_250,
+                "trade24h": 8_400,
+                "uniqueWallet24h": 900,
+            })))
+            .mount(&server)
+            .await;
+
+        let trade = |i: u32| serde_json::json!({
+            "txHash": format!("sig-{}", i),
+            "blockUnixTime": 1_700_000_000 - i as i64,
+            "source": "raydium",
+            "owner": "wallet",
+            "side": "buy",
+            "from": { "address": "So11111111111111111111111111111111111111112", "symbol": "SOL", "uiAmount": 1.0 },
+            "to": { "address": "mint", "symbol": "EXM", "uiAmount": 1_000.0 },
+        });
+        Mock::given(method("GET"))
+            .and(path("/defi/txs/token"))
+            .and(query_param("offset", "0"))
+            .respond_with(data(serde_json::json!({ "items": (0..50).map(trade).collect::<Vec<_>>(), "hasNext": true })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/defi/txs/token"))
+            .and(query_param("offset", "50"))
+            .and(query_param("limit", "10"))
+            .respond_with(data(serde_json::json!({ "items": (50..52).map(trade).collect::<Vec<_>>(), "hasNext": false })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/defi/ohlcv"))
+            .and(query_param("type", "5m"))
+            .respond_with(data(serde_json::json!({ "items": [
+                { "unixTime": 1_700_000_000, "o": 1.0, "h": 1.4, "l": 0.9, "c": 1.2, "v": 5_000.0, "type": "5m", "address": "mint" },
+            ] })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/defi/token_overview"))
+            .and(query_param("address", "unknown"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": false, "message": "Token not found",
+            })))
+            .mount(&server)
+            .await;
+
+        let birdeye = client(&server, 100);
+
+        let overview = birdeye.token_overview("mint").await.unwrap();
+        assert_eq!(overview.market_cap, Some(4_200_000.0));
+        assert_eq!(overview.volume_24h_usd, Some(310_000.0));
+        assert_eq!((overview.holder_count, overview.unique_wallets_24h), (Some(1_250), Some(900)));
+
+        let trades = birdeye.recent_trades("mint", 60).await.unwrap();
+        assert_eq!(trades.len(), 52);
+        assert_eq!(trades[51].tx_hash, "sig-51");
+        assert_eq!(trades[0].to.ui_amount, 1_000.0);
+
+        let candles = birdeye.ohlcv("mint", CandleInterval::FiveMinutes, 1_699_999_700, 1_700_000_000).await.unwrap();
+        assert_eq!(candles.len(), 1);
+        assert_eq!((candles[0].high, candles[0].close), (1.4, 1.2));
+
+        let error = birdeye.token_overview("unknown").await.unwrap_err();
+        assert!(error.to_string().contains("Token not found"));
+
+        let stats = birdeye.statistics().await;
+        assert_eq!((stats.successful_requests, stats.failed_requests), (4, 1));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_gives_up_instead_of_waiting_for_window() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/defi/token_overview"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let birdeye = client(&server, 100);
+        let result = tokio::time::timeout(Duration::from_secs(5), birdeye.token_overview("mint")).await
+            .expect("request waited for the next budget window");
+
+        assert!(result.unwrap_err().to_string().contains("rate limit"));
+        assert_eq!(birdeye.statistics().await.failed_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_redis_cache_hit_miss_and_ttl() {
+        let config = ConfigLoader::new().without_env().create_default_config();
+        let Ok(redis) = RedisService::new(&config.redis).await else {
+            // Expected in test environment without Redis
+            return;
+        };
+
+        let server = MockServer::start().await;
+        let address = format!("mint-{}", uuid::Uuid::new_v4());
+        Mock::given(method("GET"))
+            .and(path("/defi/token_overview"))
+            .and(query_param("address", address.as_str()))
+            .respond_with(data(serde_json::json!({ "address": address, "price": 0.0042 })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let mut birdeye_config = config.birdeye.clone();
+        birdeye_config.api_key = "test-api-key".to_string();
+        birdeye_config.base_url = server.uri();
+        birdeye_config.cache_ttl_seconds = 1;
+        let birdeye = BirdeyeClient::new(&birdeye_config, Some(redis.clone()), None).unwrap();
+
+        // Miss, then served from the cache
+        assert_eq!(birdeye.token_overview(&address).await.unwrap().price, Some(0.0042));
+        assert_eq!(birdeye.token_overview(&address).await.unwrap().price, Some(0.0042));
+        let stats = birdeye.statistics().await;
+        assert_eq!((stats.cache_misses, stats.cache_hits, stats.total_requests), (1, 1, 1));
+
+        // Expired entries are fetched again
+        tokio::time::sleep(Duration::from_millis(1_500)).await;
+        birdeye.token_overview(&address).await.unwrap();
+        let stats = birdeye.statistics().await;
+        assert_eq!((stats.cache_misses, stats.total_requests), (2, 2));
+
+        let _ = redis.delete(&cache_key("overview", &address)).await;
+    }
+}
@@ -0,0 +1,193 @@
+//! Background blockhash cache
+//!
+//! Every transaction needs a recent blockhash, and fetching one per trade
+//! adds a round trip to the hot path. The cache refreshes the latest
+//! blockhash and its `last_valid_block_height` in the background so
+//! transactions can be built without waiting on the RPC.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use solana_sdk::hash::Hash;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::core::result::AppResult;
+use super::rpc::RpcPool;
+
+/// Age after which a cached blockhash is refetched on demand
+///
+/// Blockhashes stay valid for 150 blocks, about a minute. Past a few
+/// seconds a cached one has burnt enough of that window that a failing
+/// refresher should not be trusted.
+const MAX_BLOCKHASH_AGE: Duration = Duration::from_secs(5);
+
+/// Blockhash with its validity window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedBlockhash {
+    /// Blockhash
+    pub blockhash: Hash,
+    /// Last block height at which transactions using it can land
+    pub last_valid_block_height: u64,
+    /// When it was fetched
+    pub fetched_at: Instant,
+}
+
+impl CachedBlockhash {
+    /// Whether the blockhash was fetched within `max_age`
+    pub fn is_fresh(&self, max_age: Duration) -> bool {
+        self.fetched_at.elapsed() <= max_age
+    }
+
+    /// The blockhash that stays valid longer
+    ///
+    /// A lagging endpoint in the pool can answer with an older blockhash
+    /// than one already cached, which would shorten the validity window.
+    fn newest(cached: Option<Self>, fetched: Self) -> Self {
+        match cached {
+            Some(cached) if cached.last_valid_block_height > fetched.last_valid_block_height => cached,
+            _ => fetched,
+        }
+    }
+}
+
+/// Blockhash cache refreshed in the background
+#[derive(Debug)]
+pub struct BlockhashCache {
+    /// Pool used to fetch blockhashes
+    pool: Arc<RpcPool>,
+    /// Latest fetched blockhash
+    latest: Arc<RwLock<Option<CachedBlockhash>>>,
+    /// Background refresher
+    refresher: std::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+impl BlockhashCache {
+    /// Create the cache and start refreshing every `refresh_interval`
+    pub fn spawn(pool: Arc<RpcPool>, refresh_interval: Duration) -> Self {
+        let latest = Arc::new(RwLock::new(None));
+        let refresher = tokio::spawn(Self::refresh_loop(pool.clone(), latest.clone(), refresh_interval));
+
+        info!("🧱 Blockhash cache refreshing every {:?}", refresh_interval);
+
+        Self {
+            pool,
+            latest,
+            refresher: std::sync::Mutex::new(Some(refresher)),
+        }
+    }
+
+    async fn refresh_loop(
+        pool: Arc<RpcPool>,
+        latest: Arc<RwLock<Option<CachedBlockhash>>>,
+        refresh_interval: Duration,
+    ) {
+        let mut interval = tokio::time::interval(refresh_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+
+            match Self::fetch(&pool).await {
+                Ok(fetched) => {
+                    let mut latest = latest.write().await;
+                    let newest = CachedBlockhash::newest(*latest, fetched);
+                    if newest != fetched {
+                        debug!("🧱 Ignoring blockhash {} valid until height {}, older than the cached one",
+                               fetched.blockhash, fetched.last_valid_block_height);
+                    } else if latest.map(|cached| cached.blockhash) != Some(fetched.blockhash) {
+                        debug!("🧱 New blockhash {} valid until height {}",
+                               fetched.blockhash, fetched.last_valid_block_height);
+                    }
+                    *latest = Some(newest);
+                }
+                Err(e) => warn!("Blockhash refresh failed: {}", e),
+            }
+        }
+    }
+
+    async fn fetch(pool: &RpcPool) -> AppResult<CachedBlockhash> {
+        let connection = pool.get_connection().await?;
+        let (blockhash, last_valid_block_height) = connection.get_latest_blockhash_with_height().await?;
+
+        Ok(CachedBlockhash {
+            blockhash,
+            last_valid_block_height,
+            fetched_at: Instant::now(),
+        })
+    }
+
+    /// Cached blockhash, without a round trip; `None` if missing or stale
+    pub async fn latest(&self) -> Option<CachedBlockhash> {
+        self.latest.read().await.filter(|cached| cached.is_fresh(MAX_BLOCKHASH_AGE))
+    }
+
+    /// Cached blockhash, fetching one if the cache is empty or stale
+    pub async fn get(&self) -> AppResult<CachedBlockhash> {
+        if let Some(cached) = self.latest().await {
+            return Ok(cached);
+        }
+
+        debug!("🧱 Blockhash cache stale, fetching on demand");
+        let fetched = Self::fetch(&self.pool).await?;
+        let mut latest = self.latest.write().await;
+        let newest = CachedBlockhash::newest(*latest, fetched);
+        *latest = Some(newest);
+        Ok(newest)
+    }
+
+    /// Stop the background refresher
+    pub fn close(&self) {
+        if let Some(refresher) = self.refresher.lock().unwrap().take() {
+            refresher.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_blockhash_freshness() {
+        let fresh = CachedBlockhash {
+            blockhash: Hash::new_unique(),
+            last_valid_block_height: 1_000,
+            fetched_at: Instant::now(),
+        };
+        assert!(fresh.is_fresh(MAX_BLOCKHASH_AGE));
+
+        let stale = CachedBlockhash {
+            fetched_at: Instant::now() - MAX_BLOCKHASH_AGE - Duration::from_millis(1),
+            ..fresh
+        };
+        assert!(!stale.is_fresh(MAX_BLOCKHASH_AGE));
+    }
+
+    #[test]
+    fn test_newest_blockhash_kept() {
+        let cached = CachedBlockhash {
+            blockhash: Hash::new_unique(),
+            last_valid_block_height: 1_000,
+            fetched_at: Instant::now(),
+        };
+        let lagging = CachedBlockhash {
+            blockhash: Hash::new_unique(),
+            last_valid_block_height: 990,
+            ..cached
+        };
+        let newer = CachedBlockhash {
+            blockhash: Hash::new_unique(),
+            last_valid_block_height: 1_010,
+            ..cached
+        };
+
+        assert_eq!(CachedBlockhash::newest(None, lagging), lagging);
+        assert_eq!(CachedBlockhash::newest(Some(cached), lagging), cached);
+        assert_eq!(CachedBlockhash::newest(Some(cached), newer), newer);
+
+        // A refetch of the same blockhash refreshes its age
+        let refetched = CachedBlockhash { fetched_at: Instant::now(), ..cached };
+        assert_eq!(CachedBlockhash::newest(Some(cached), refetched).fetched_at, refetched.fetched_at);
+    }
+}
@@ -0,0 +1,711 @@
+//! Multi-endpoint transaction broadcasting
+//!
+//! A signed transaction is fanned out to every configured RPC and sender
+//! endpoint at once and re-sent on an interval until it lands or its
+//! blockhash expires. Confirmation comes from a signature subscription over
+//! one shared WebSocket connection, with status polling through the RPC pool
+//! as a fallback for when the WebSocket is down or lagging.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use serde::Serialize;
+use solana_client::{
+    nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient as SolanaRpcClient},
+    rpc_config::{RpcSendTransactionConfig, RpcSignatureSubscribeConfig},
+    rpc_response::RpcSignatureResult,
+};
+use solana_sdk::{
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::config::models::SolanaConfig;
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use super::nonce::{durable_nonce_account, parse_nonce_account};
+use super::rpc::{RpcConnection, RpcPool};
+use super::websocket::websocket_url;
+
+/// Interval between signature status polls
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Where a landing was first observed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmationSource {
+    /// Signature subscription over WebSocket
+    Subscription,
+    /// Signature status polling
+    Polling,
+}
+
+/// Final state of a broadcast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum BroadcastStatus {
+    /// The transaction landed and succeeded
+    Landed {
+        /// Slot the transaction landed in
+        slot: u64,
+    },
+    /// The transaction landed but its execution failed
+    Failed {
+        /// Slot the transaction landed in
+        slot: u64,
+        /// Transaction error
+        error: String,
+    },
+    /// The blockhash expired before the transaction landed
+    Expired,
+}
+
+/// Sends of the transaction through one endpoint
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EndpointSubmission {
+    /// Endpoint URL
+    pub endpoint: String,
+    /// Send attempts
+    pub sends: u32,
+    /// Send attempts the endpoint rejected
+    pub errors: u32,
+    /// Time from broadcast start to the first accepted send
+    pub first_accepted_ms: Option<u64>,
+    /// Time from the first accepted send to confirmation
+    pub confirm_latency_ms: Option<u64>,
+}
+
+/// Outcome of broadcasting a transaction
+#[derive(Debug, Clone, Serialize)]
+pub struct BroadcastOutcome {
+    /// Transaction signature
+    pub signature: String,
+    /// Final state
+    pub status: BroadcastStatus,
+    /// Endpoint credited with landing the transaction
+    pub landed_by: Option<String>,
+    /// How the landing was observed
+    pub confirmed_via: Option<ConfirmationSource>,
+    /// Time from broadcast start to the final state in milliseconds
+    pub elapsed_ms: u64,
+    /// Per-endpoint sends
+    pub submissions: Vec<EndpointSubmission>,
+}
+
+/// Confirmation latency of one endpoint
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfirmLatencyStats {
+    /// Confirmed transactions the endpoint accepted
+    pub count: u64,
+    /// Average submit-to-confirm latency in milliseconds
+    pub avg_ms: f64,
+    /// Maximum submit-to-confirm latency in milliseconds
+    pub max_ms: u64,
+}
+
+impl ConfirmLatencyStats {
+    fn record(&mut self, latency_ms: u64) {
+        self.avg_ms = (self.avg_ms * self.count as f64 + latency_ms as f64) / (self.count + 1) as f64;
+        self.count += 1;
+        self.max_ms = self.max_ms.max(latency_ms);
+    }
+}
+
+/// Broadcast statistics
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BroadcastStatistics {
+    /// Transactions broadcast
+    pub broadcasts: u64,
+    /// Transactions that landed and succeeded
+    pub landed: u64,
+    /// Transactions that landed with an execution error
+    pub failed: u64,
+    /// Transactions whose blockhash expired first
+    pub expired: u64,
+    /// Landings credited to each endpoint
+    pub landed_first_by_endpoint: HashMap<String, u64>,
+    /// Submit-to-confirm latency by endpoint
+    pub confirm_latency_by_endpoint: HashMap<String, ConfirmLatencyStats>,
+    /// Landings first observed through the subscription
+    pub confirmed_via_subscription: u64,
+    /// Landings first observed through polling
+    pub confirmed_via_polling: u64,
+}
+
+/// Observed landing of a transaction
+#[derive(Debug, Clone)]
+struct Landing {
+    slot: u64,
+    error: Option<String>,
+}
+
+/// Endpoint a transaction is sent through
+#[derive(Clone)]
+struct BroadcastEndpoint {
+    url: String,
+    client: Arc<SolanaRpcClient>,
+}
+
+impl std::fmt::Debug for BroadcastEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BroadcastEndpoint")
+            .field("url", &self.url)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Send through one endpoint, resolving to the endpoint index
+type SendFuture<'a> = Pin<Box<dyn Future<Output = (usize, AppResult<Signature>)> + Send + 'a>>;
+
+/// Sends transactions through every endpoint until they land or expire
+#[derive(Debug)]
+pub struct TransactionBroadcaster {
+    /// RPC and sender endpoints
+    endpoints: Vec<BroadcastEndpoint>,
+    /// Pool used for status polling and blockhash checks
+    pool: Arc<RpcPool>,
+    /// WebSocket endpoint for signature subscriptions
+    websocket_url: String,
+    /// Connection shared by all signature subscriptions, opened on first use
+    pubsub: Mutex<Option<Arc<PubsubClient>>>,
+    /// Commitment a landing must reach
+    commitment: CommitmentConfig,
+    /// Interval between re-sends
+    rebroadcast_interval: Duration,
+    /// Broadcast statistics
+    stats: Arc<RwLock<BroadcastStatistics>>,
+}
+
+impl TransactionBroadcaster {
+    /// Create a broadcaster over the configured RPC and sender endpoints
+    pub fn new(config: &SolanaConfig, pool: Arc<RpcPool>) -> Self {
+        let commitment = match config.commitment.as_str() {
+            "processed" => CommitmentLevel::Processed,
+            "confirmed" => CommitmentLevel::Confirmed,
+            "finalized" => CommitmentLevel::Finalized,
+            _ => CommitmentLevel::Confirmed,
+        };
+        let commitment = CommitmentConfig { commitment };
+        let timeout = Duration::from_millis(config.connection_timeout_ms);
+
+        let mut seen = HashSet::new();
+        let endpoints: Vec<BroadcastEndpoint> = std::iter::once(&config.rpc_url)
+            .chain(&config.fallback_rpc_urls)
+            .chain(&config.sender_urls)
+            .filter(|url| seen.insert(url.as_str()))
+            .map(|url| BroadcastEndpoint {
+                url: url.clone(),
+                client: Arc::new(SolanaRpcClient::new_with_timeout_and_commitment(url.clone(), timeout, commitment)),
+            })
+            .collect();
+
+        info!("📡 Transaction broadcaster using {} endpoints", endpoints.len());
+
+        Self {
+            endpoints,
+            pool,
+            websocket_url: websocket_url(config),
+            pubsub: Mutex::new(None),
+            commitment,
+            rebroadcast_interval: Duration::from_millis(config.rebroadcast_interval_ms),
+            stats: Arc::new(RwLock::new(BroadcastStatistics::default())),
+        }
+    }
+
+    /// Broadcast a signed transaction until it lands or its blockhash expires
+    ///
+    /// A transaction signed against a durable nonce expires once its nonce
+    /// advances instead. Every endpoint is sent the transaction on each
+    /// rebroadcast tick, with at most one send in flight per endpoint so a
+    /// slow endpoint cannot pile up requests.
+    pub async fn broadcast(&self, transaction: &VersionedTransaction) -> AppResult<BroadcastOutcome> {
+        let signature = *transaction.signatures.first()
+            .ok_or_else(|| AppError::validation("Transaction is not signed"))?;
+        let blockhash = *transaction.message.recent_blockhash();
+        let nonce_account = durable_nonce_account(&transaction.message);
+        let started = Instant::now();
+
+        debug!("📡 Broadcasting {} to {} endpoints", signature, self.endpoints.len());
+
+        let mut submissions: Vec<EndpointSubmission> = self.endpoints.iter()
+            .map(|endpoint| EndpointSubmission { endpoint: endpoint.url.clone(), ..Default::default() })
+            .collect();
+        let mut in_flight: FuturesUnordered<SendFuture<'_>> = FuturesUnordered::new();
+        let mut busy = HashSet::new();
+        let mut interval = tokio::time::interval(self.rebroadcast_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        let confirmation = self.confirm(signature, blockhash, nonce_account);
+        tokio::pin!(confirmation);
+
+        let (landing, confirmed_via) = loop {
+            tokio::select! {
+                biased;
+                confirmed = &mut confirmation => break confirmed,
+                Some((index, result)) = in_flight.next(), if !in_flight.is_empty() => {
+                    busy.remove(&index);
+                    let submission = &mut submissions[index];
+                    match result {
+                        Ok(_) => {
+                            submission.first_accepted_ms.get_or_insert(started.elapsed().as_millis() as u64);
+                        }
+                        Err(e) => {
+                            submission.errors += 1;
+                            debug!("Send of {} through {} failed: {}", signature, submission.endpoint, e);
+                        }
+                    }
+                }
+                _ = interval.tick() => {
+                    for (index, endpoint) in self.endpoints.iter().enumerate() {
+                        if busy.insert(index) {
+                            submissions[index].sends += 1;
+                            in_flight.push(Box::pin(self.send(index, endpoint, transaction)));
+                        }
+                    }
+                }
+            }
+        };
+
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        let (status, landed_by) = match landing {
+            Some(landing) => {
+                let landed_by = settle(&mut submissions, elapsed_ms);
+                let status = match landing.error {
+                    None => BroadcastStatus::Landed { slot: landing.slot },
+                    Some(error) => BroadcastStatus::Failed { slot: landing.slot, error },
+                };
+                (status, landed_by)
+            }
+            None => (BroadcastStatus::Expired, None),
+        };
+
+        let outcome = BroadcastOutcome {
+            signature: signature.to_string(),
+            status,
+            landed_by,
+            confirmed_via,
+            elapsed_ms,
+            submissions,
+        };
+
+        match &outcome.status {
+            BroadcastStatus::Expired => warn!("⌛ Blockhash of {} expired before it landed", signature),
+            _ => info!("🎯 {} landed in {}ms via {}", signature, elapsed_ms,
+                       outcome.landed_by.as_deref().unwrap_or("unknown endpoint")),
+        }
+
+        self.record(&outcome).await;
+        Ok(outcome)
+    }
+
+    /// Send the transaction once through an endpoint
+    async fn send(
+        &self,
+        index: usize,
+        endpoint: &BroadcastEndpoint,
+        transaction: &VersionedTransaction,
+    ) -> (usize, AppResult<Signature>) {
+        // Preflight would cost a simulation per endpoint and retries are ours
+        let config = RpcSendTransactionConfig {
+            skip_preflight: true,
+            preflight_commitment: None,
+            encoding: None,
+            max_retries: Some(0),
+            min_context_slot: None,
+        };
+
+        let result = endpoint.client.send_transaction_with_config(transaction, config).await
+            .map_err(|e| AppError::network(format!("Send through {} failed: {}", endpoint.url, e)));
+        (index, result)
+    }
+
+    /// Wait for the transaction to land; `None` once the blockhash expired
+    async fn confirm(
+        &self,
+        signature: Signature,
+        blockhash: Hash,
+        nonce_account: Option<Pubkey>,
+    ) -> (Option<Landing>, Option<ConfirmationSource>) {
+        let subscription = async {
+            match self.subscribe(signature).await {
+                Some(landing) => landing,
+                None => futures::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            landing = subscription => (Some(landing), Some(ConfirmationSource::Subscription)),
+            landing = self.poll(signature, blockhash, nonce_account) => {
+                let source = landing.as_ref().map(|_| ConfirmationSource::Polling);
+                (landing, source)
+            }
+        }
+    }
+
+    /// Shared pubsub connection, connecting if there is none
+    async fn pubsub(&self) -> AppResult<Arc<PubsubClient>> {
+        let mut pubsub = self.pubsub.lock().await;
+        if let Some(client) = pubsub.as_ref() {
+            return Ok(client.clone());
+        }
+
+        let client = PubsubClient::new(&self.websocket_url).await
+            .map_err(|e| AppError::network(format!("WebSocket connection failed: {}", e)))?;
+        let client = Arc::new(client);
+        *pubsub = Some(client.clone());
+        Ok(client)
+    }
+
+    /// Drop a broken pubsub connection so the next subscription reconnects
+    async fn reset_pubsub(&self, client: &Arc<PubsubClient>) {
+        let mut pubsub = self.pubsub.lock().await;
+        if pubsub.as_ref().is_some_and(|current| Arc::ptr_eq(current, client)) {
+            *pubsub = None;
+        }
+    }
+
+    /// Wait for the landing through a signature subscription
+    ///
+    /// Returns `None` if the subscription could not be set up or closed
+    /// early, leaving confirmation to polling.
+    async fn subscribe(&self, signature: Signature) -> Option<Landing> {
+        let client = match self.pubsub().await {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("Signature subscription unavailable, polling instead: {}", e);
+                return None;
+            }
+        };
+
+        let config = RpcSignatureSubscribeConfig {
+            commitment: Some(self.commitment),
+            enable_received_notification: Some(false),
+        };
+        let (mut notifications, unsubscribe) = match client.signature_subscribe(&signature, Some(config)).await {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                warn!("Failed to subscribe to {}: {}", signature, e);
+                self.reset_pubsub(&client).await;
+                return None;
+            }
+        };
+
+        let notification = notifications.next().await;
+        drop(notifications);
+        unsubscribe().await;
+
+        let Some(notification) = notification else {
+            // The connection closed under the subscription
+            self.reset_pubsub(&client).await;
+            return None;
+        };
+        match notification.value {
+            RpcSignatureResult::ProcessedSignature(result) => Some(Landing {
+                slot: notification.context.slot,
+                error: result.err.map(|e| e.to_string()),
+            }),
+            RpcSignatureResult::ReceivedSignature(_) => None,
+        }
+    }
+
+    /// Poll the signature status until it lands or the blockhash expires
+    async fn poll(&self, signature: Signature, blockhash: Hash, nonce_account: Option<Pubkey>) -> Option<Landing> {
+        let mut interval = tokio::time::interval(STATUS_POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            let connection = match self.pool.get_connection().await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    warn!("No RPC connection for status polling: {}", e);
+                    continue;
+                }
+            };
+
+            match connection.get_signature_status(&signature).await {
+                Ok(Some(status)) if status.satisfies_commitment(self.commitment) => {
+                    return Some(Landing { slot: status.slot, error: status.err.map(|e| e.to_string()) });
+                }
+                Ok(_) => {}
+                Err(e) => debug!("Status poll for {} failed: {}", signature, e),
+            }
+
+            if let Ok(true) = Self::expired(&connection, &blockhash, nonce_account).await {
+                // It may still have landed in the last valid slots
+                return match connection.get_signature_status(&signature).await {
+                    Ok(Some(status)) => Some(Landing { slot: status.slot, error: status.err.map(|e| e.to_string()) }),
+                    _ => None,
+                };
+            }
+        }
+    }
+
+    /// Whether the transaction can no longer land
+    ///
+    /// A durable nonce transaction expires when the stored nonce no longer
+    /// matches its blockhash, which includes it having landed.
+    async fn expired(connection: &RpcConnection, blockhash: &Hash, nonce_account: Option<Pubkey>) -> AppResult<bool> {
+        match nonce_account {
+            Some(account) => {
+                let info = connection.get_account_info(&account.to_string()).await?;
+                Ok(parse_nonce_account(account, &info.data)?.nonce != *blockhash)
+            }
+            None => Ok(!connection.is_blockhash_valid(blockhash).await?),
+        }
+    }
+
+    /// Update statistics with a broadcast outcome
+    async fn record(&self, outcome: &BroadcastOutcome) {
+        let mut stats = self.stats.write().await;
+        stats.broadcasts += 1;
+
+        match outcome.status {
+            BroadcastStatus::Landed { .. } => stats.landed += 1,
+            BroadcastStatus::Failed { .. } => stats.failed += 1,
+            BroadcastStatus::Expired => stats.expired += 1,
+        }
+        match outcome.confirmed_via {
+            Some(ConfirmationSource::Subscription) => stats.confirmed_via_subscription += 1,
+            Some(ConfirmationSource::Polling) => stats.confirmed_via_polling += 1,
+            None => {}
+        }
+        if let Some(endpoint) = &outcome.landed_by {
+            *stats.landed_first_by_endpoint.entry(endpoint.clone()).or_insert(0) += 1;
+        }
+        for submission in &outcome.submissions {
+            if let Some(latency_ms) = submission.confirm_latency_ms {
+                stats.confirm_latency_by_endpoint.entry(submission.endpoint.clone())
+                    .or_default()
+                    .record(latency_ms);
+            }
+        }
+    }
+
+    /// Get broadcast statistics
+    pub async fn statistics(&self) -> BroadcastStatistics {
+        self.stats.read().await.clone()
+    }
+}
+
+/// Fill in confirmation latencies and pick the endpoint that landed the transaction
+///
+/// Every endpoint sends the same signed bytes, so which copy the leader
+/// included cannot be told apart. The endpoint that accepted it first is
+/// credited with the landing.
+fn settle(submissions: &mut [EndpointSubmission], confirmed_ms: u64) -> Option<String> {
+    for submission in submissions.iter_mut() {
+        submission.confirm_latency_ms = submission.first_accepted_ms
+            .map(|accepted_ms| confirmed_ms.saturating_sub(accepted_ms));
+    }
+
+    submissions.iter()
+        .filter_map(|submission| submission.first_accepted_ms.map(|ms| (ms, &submission.endpoint)))
+        .min_by_key(|(ms, _)| *ms)
+        .map(|(_, endpoint)| endpoint.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigLoader;
+    use solana_sdk::{signature::Keypair, signer::Signer, system_instruction, transaction::Transaction};
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn rpc_result(result: serde_json::Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+    }
+
+    async fn mock_method(server: &MockServer, name: &str, response: ResponseTemplate, times: Option<u64>) {
+        let mock = Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": name })))
+            .respond_with(response);
+        match times {
+            Some(times) => mock.up_to_n_times(times).mount(server).await,
+            None => mock.mount(server).await,
+        }
+    }
+
+    fn statuses(status: serde_json::Value) -> ResponseTemplate {
+        rpc_result(serde_json::json!({ "context": { "slot": 77 }, "value": [status] }))
+    }
+
+    fn landed(error: Option<serde_json::Value>) -> serde_json::Value {
+        let status = match &error {
+            Some(error) => serde_json::json!({ "Err": error }),
+            None => serde_json::json!({ "Ok": null }),
+        };
+        serde_json::json!({
+            "slot": 77,
+            "confirmations": null,
+            "err": error,
+            "status": status,
+            "confirmationStatus": "confirmed",
+        })
+    }
+
+    fn blockhash_valid(valid: bool) -> ResponseTemplate {
+        rpc_result(serde_json::json!({ "context": { "slot": 77 }, "value": valid }))
+    }
+
+    fn signed_transaction() -> VersionedTransaction {
+        let payer = Keypair::new();
+        let instruction = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &[&payer], Hash::new_unique()).into()
+    }
+
+    /// Mount the calls every endpoint answers, accepting sends of `transaction`
+    async fn mock_endpoint(server: &MockServer, transaction: &VersionedTransaction) {
+        mock_method(server, "getHealth", rpc_result(serde_json::json!("ok")), None).await;
+        mock_method(server, "getVersion", rpc_result(serde_json::json!({ "solana-core": "2.2.7", "feature-set": 1 })), None).await;
+        mock_method(server, "sendTransaction", rpc_result(serde_json::json!(transaction.signatures[0].to_string())), None).await;
+    }
+
+    /// Broadcaster over an RPC and a sender endpoint, without a WebSocket
+    async fn broadcaster(rpc: &MockServer, sender: &MockServer) -> TransactionBroadcaster {
+        let mut config = ConfigLoader::new().without_env().create_default_config().solana;
+        config.rpc_url = rpc.uri();
+        config.fallback_rpc_urls = Vec::new();
+        config.sender_urls = vec![sender.uri()];
+        // Nothing listens here, so confirmation falls back to polling
+        config.websocket_url = Some("ws://127.0.0.1:9".to_string());
+        config.commitment = "confirmed".to_string();
+        config.rebroadcast_interval_ms = 50;
+
+        let pool = Arc::new(RpcPool::new(&config).await.unwrap());
+        TransactionBroadcaster::new(&config, pool)
+    }
+
+    async fn sends(server: &MockServer) -> usize {
+        server.received_requests().await.unwrap_or_default().iter()
+            .filter_map(|request| serde_json::from_slice::<serde_json::Value>(&request.body).ok())
+            .filter(|body| body["method"] == "sendTransaction")
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_and_resend_until_polled_landing() {
+        let (rpc, sender) = (MockServer::start().await, MockServer::start().await);
+        let transaction = signed_transaction();
+        mock_endpoint(&rpc, &transaction).await;
+        mock_endpoint(&sender, &transaction).await;
+        mock_method(&rpc, "isBlockhashValid", blockhash_valid(true), None).await;
+        mock_method(&rpc, "getSignatureStatuses", statuses(serde_json::Value::Null), Some(2)).await;
+        mock_method(&rpc, "getSignatureStatuses", statuses(landed(None)), None).await;
+
+        let broadcaster = broadcaster(&rpc, &sender).await;
+        let outcome = broadcaster.broadcast(&transaction).await.unwrap();
+
+        assert_eq!(outcome.status, BroadcastStatus::Landed { slot: 77 });
+        assert_eq!(outcome.signature, transaction.signatures[0].to_string());
+        // The subscription could not connect, so polling saw the landing
+        assert_eq!(outcome.confirmed_via, Some(ConfirmationSource::Polling));
+        assert!(outcome.landed_by.is_some());
+
+        // Both endpoints got the transaction, and got it more than once
+        assert_eq!(outcome.submissions.len(), 2);
+        for submission in &outcome.submissions {
+            assert!(submission.sends > 1, "{} sent once", submission.endpoint);
+            assert_eq!(submission.errors, 0);
+            assert!(submission.confirm_latency_ms.is_some());
+        }
+        assert!(sends(&rpc).await > 1);
+        assert!(sends(&sender).await > 1);
+
+        let stats = broadcaster.statistics().await;
+        assert_eq!((stats.landed, stats.confirmed_via_polling), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_blockhash_expiry_ends_broadcast() {
+        let (rpc, sender) = (MockServer::start().await, MockServer::start().await);
+        let transaction = signed_transaction();
+        mock_endpoint(&rpc, &transaction).await;
+        // The sender rejects every send
+        mock_method(&sender, "getVersion", rpc_result(serde_json::json!({ "solana-core": "2.2.7", "feature-set": 1 })), None).await;
+        mock_method(&sender, "sendTransaction", ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32002, "message": "Blockhash not found" },
+        })), None).await;
+        mock_method(&rpc, "getSignatureStatuses", statuses(serde_json::Value::Null), None).await;
+        mock_method(&rpc, "isBlockhashValid", blockhash_valid(true), Some(1)).await;
+        mock_method(&rpc, "isBlockhashValid", blockhash_valid(false), None).await;
+
+        let broadcaster = broadcaster(&rpc, &sender).await;
+        let outcome = broadcaster.broadcast(&transaction).await.unwrap();
+
+        assert_eq!(outcome.status, BroadcastStatus::Expired);
+        assert_eq!((outcome.landed_by, outcome.confirmed_via), (None, None));
+        assert!(outcome.submissions[0].first_accepted_ms.is_some());
+        let rejected = &outcome.submissions[1];
+        assert_eq!((rejected.errors, rejected.first_accepted_ms), (rejected.sends, None));
+
+        let stats = broadcaster.statistics().await;
+        assert_eq!((stats.expired, stats.landed), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_failed_landing_is_not_landed() {
+        let (rpc, sender) = (MockServer::start().await, MockServer::start().await);
+        let transaction = signed_transaction();
+        mock_endpoint(&rpc, &transaction).await;
+        mock_endpoint(&sender, &transaction).await;
+        mock_method(&rpc, "isBlockhashValid", blockhash_valid(true), None).await;
+        mock_method(&rpc, "getSignatureStatuses", statuses(landed(Some(serde_json::json!({
+            "InstructionError": [0, { "Custom": 6001 }],
+        })))), None).await;
+
+        let broadcaster = broadcaster(&rpc, &sender).await;
+        let outcome = broadcaster.broadcast(&transaction).await.unwrap();
+
+        assert!(matches!(outcome.status, BroadcastStatus::Failed { slot: 77, .. }));
+        let stats = broadcaster.statistics().await;
+        assert_eq!((stats.failed, stats.landed), (1, 0));
+    }
+
+    #[test]
+    fn test_settle_credits_first_accepting_endpoint() {
+        let submission = |endpoint: &str, first_accepted_ms| EndpointSubmission {
+            endpoint: endpoint.to_string(),
+            sends: 3,
+            first_accepted_ms,
+            ..Default::default()
+        };
+        let mut submissions = vec![
+            submission("https://rpc.example", Some(40)),
+            submission("https://sender.example", Some(12)),
+            submission("https://down.example", None),
+        ];
+
+        assert_eq!(settle(&mut submissions, 900).as_deref(), Some("https://sender.example"));
+        assert_eq!(submissions[0].confirm_latency_ms, Some(860));
+        assert_eq!(submissions[1].confirm_latency_ms, Some(888));
+        assert_eq!(submissions[2].confirm_latency_ms, None);
+    }
+
+    #[test]
+    fn test_websocket_url() {
+        let mut config = ConfigLoader::new().without_env().create_default_config().solana;
+
+        config.rpc_url = "https://mainnet.helius-rpc.com/?api-key=abc".to_string();
+        assert_eq!(websocket_url(&config), "wss://mainnet.helius-rpc.com/?api-key=abc");
+
+        config.rpc_url = "http://127.0.0.1:8899".to_string();
+        assert_eq!(websocket_url(&config), "ws://127.0.0.1:8899");
+
+        config.websocket_url = Some("ws://127.0.0.1:8900".to_string());
+        assert_eq!(websocket_url(&config), "ws://127.0.0.1:8900");
+    }
+}
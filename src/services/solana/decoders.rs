@@ -0,0 +1,624 @@
+//! Program decoders for pool and mint creation
+//!
+//! This module turns transactions and program logs into typed
+//! `PoolCreationEvent`s and `TokenCreationEvent`s. Decoders are registered
+//! per program id; each one decodes instructions, `Program data:` event logs,
+//! or both, and can ask for the full transaction when logs alone do not carry
+//! the accounts it needs.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use super::types::{InstructionInfo, PoolCreationEvent, TokenCreationEvent, TransactionInfo};
+
+/// Raydium AMM v4 program
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+
+/// pump.fun bonding curve program
+pub const PUMP_FUN_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+
+/// Meteora dynamic AMM program
+pub const METEORA_AMM_PROGRAM_ID: &str = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB";
+
+/// Orca Whirlpool program
+pub const ORCA_WHIRLPOOL_PROGRAM_ID: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+
+/// Wrapped SOL mint
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// USDC mint
+pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+/// Raydium AMM v4 `initialize2` instruction tag
+const RAYDIUM_INITIALIZE2_TAG: u8 = 1;
+
+/// Anchor discriminator of pump.fun `CreateEvent`
+const PUMP_FUN_CREATE_EVENT_DISCRIMINATOR: [u8; 8] = [27, 114, 169, 77, 222, 235, 99, 118];
+
+/// pump.fun token decimals
+const PUMP_FUN_DECIMALS: u8 = 6;
+
+/// pump.fun total supply in base units (one billion tokens)
+const PUMP_FUN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+
+/// Anchor discriminator of Meteora `initialize_permissionless_pool`
+const METEORA_INITIALIZE_PERMISSIONLESS_POOL_DISCRIMINATOR: [u8; 8] = [118, 173, 41, 157, 173, 72, 97, 103];
+
+/// Size of the Meteora `CurveType::Stable` payload after its variant byte
+const METEORA_STABLE_CURVE_LEN: usize = 50;
+
+/// Anchor discriminator of Whirlpool `initialize_pool`
+const ORCA_INITIALIZE_POOL_DISCRIMINATOR: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+
+/// Anchor discriminator of Whirlpool `initialize_pool_v2`
+const ORCA_INITIALIZE_POOL_V2_DISCRIMINATOR: [u8; 8] = [207, 45, 87, 242, 27, 63, 204, 67];
+
+/// Decoded creation event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedEvent {
+    /// Transaction signature
+    pub signature: String,
+    /// Slot of the transaction
+    pub slot: u64,
+    /// Program that emitted the event
+    pub program_id: String,
+    /// Event payload
+    pub kind: DecodedEventKind,
+}
+
+/// Decoded event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DecodedEventKind {
+    /// A liquidity pool was created
+    PoolCreated(PoolCreationEvent),
+    /// A token mint was created
+    TokenCreated(TokenCreationEvent),
+}
+
+impl DecodedEvent {
+    /// Mints this event is about, excluding SOL and USDC
+    pub fn token_mints(&self) -> Vec<String> {
+        match &self.kind {
+            DecodedEventKind::PoolCreated(pool) => non_quote_mints(&pool.token_a, &pool.token_b),
+            DecodedEventKind::TokenCreated(token) => vec![token.mint.clone()],
+        }
+    }
+}
+
+/// Transaction context passed to decoders
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeContext<'a> {
+    /// Transaction signature
+    pub signature: &'a str,
+    /// Slot of the transaction
+    pub slot: u64,
+    /// Block time, or receive time for log notifications
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Decoder for a single program
+pub trait ProgramDecoder: Send + Sync + std::fmt::Debug {
+    /// Program id this decoder handles
+    fn program_id(&self) -> &'static str;
+
+    /// Decode an instruction of this program
+    fn decode_instruction(
+        &self,
+        _instruction: &InstructionInfo,
+        _context: &DecodeContext<'_>,
+    ) -> Option<DecodedEventKind> {
+        None
+    }
+
+    /// Decode a `Program data:` event emitted by this program
+    fn decode_event_data(&self, _data: &[u8], _context: &DecodeContext<'_>) -> Option<DecodedEventKind> {
+        None
+    }
+
+    /// Whether a log line of this program means the full transaction is
+    /// needed to decode the event
+    fn requires_transaction(&self, _log: &str) -> bool {
+        false
+    }
+}
+
+/// Registry of program decoders keyed by program id
+#[derive(Debug, Clone, Default)]
+pub struct DecoderRegistry {
+    decoders: HashMap<String, Arc<dyn ProgramDecoder>>,
+}
+
+impl DecoderRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the Raydium, pump.fun, Meteora and Orca decoders
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(RaydiumAmmDecoder));
+        registry.register(Arc::new(PumpFunDecoder));
+        registry.register(Arc::new(MeteoraAmmDecoder));
+        registry.register(Arc::new(OrcaWhirlpoolDecoder));
+        registry
+    }
+
+    /// Register a decoder, replacing any decoder for the same program
+    pub fn register(&mut self, decoder: Arc<dyn ProgramDecoder>) {
+        self.decoders.insert(decoder.program_id().to_string(), decoder);
+    }
+
+    /// Get the decoder for a program
+    pub fn get(&self, program_id: &str) -> Option<&Arc<dyn ProgramDecoder>> {
+        self.decoders.get(program_id)
+    }
+
+    /// Registered program ids
+    pub fn program_ids(&self) -> Vec<String> {
+        self.decoders.keys().cloned().collect()
+    }
+
+    /// Decode all creation events of a transaction
+    ///
+    /// Top-level and inner instructions are decoded first, followed by
+    /// event logs.
+    pub fn decode_transaction(&self, transaction: &TransactionInfo) -> Vec<DecodedEvent> {
+        let context = DecodeContext {
+            signature: &transaction.signature,
+            slot: transaction.slot,
+            timestamp: transaction.block_time.unwrap_or_else(Utc::now),
+        };
+
+        let mut events: Vec<DecodedEvent> = transaction.instructions.iter()
+            .chain(transaction.inner_instructions.iter())
+            .filter_map(|instruction| {
+                let decoder = self.get(&instruction.program_id)?;
+                let kind = decoder.decode_instruction(instruction, &context)?;
+                Some(DecodedEvent {
+                    signature: transaction.signature.clone(),
+                    slot: transaction.slot,
+                    program_id: instruction.program_id.clone(),
+                    kind,
+                })
+            })
+            .collect();
+
+        events.extend(self.decode_logs_with_context(&transaction.logs, &context));
+        events
+    }
+
+    /// Decode creation events from the logs of a log notification
+    pub fn decode_logs(&self, signature: &str, slot: u64, logs: &[String]) -> Vec<DecodedEvent> {
+        let context = DecodeContext {
+            signature,
+            slot,
+            timestamp: Utc::now(),
+        };
+
+        self.decode_logs_with_context(logs, &context)
+    }
+
+    /// Whether any registered program logged something that needs the full
+    /// transaction to decode
+    pub fn requires_transaction(&self, logs: &[String]) -> bool {
+        attribute_logs(logs).into_iter().any(|(program_id, line)| {
+            self.get(program_id)
+                .map_or(false, |decoder| decoder.requires_transaction(line))
+        })
+    }
+
+    fn decode_logs_with_context(&self, logs: &[String], context: &DecodeContext<'_>) -> Vec<DecodedEvent> {
+        attribute_logs(logs)
+            .into_iter()
+            .filter_map(|(program_id, line)| {
+                let data = BASE64.decode(line.strip_prefix("Program data: ")?).ok()?;
+                let kind = self.get(program_id)?.decode_event_data(&data, context)?;
+                Some(DecodedEvent {
+                    signature: context.signature.to_string(),
+                    slot: context.slot,
+                    program_id: program_id.to_string(),
+                    kind,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Pair each log line with the program that emitted it
+///
+/// Tracks the invoke stack from `Program <id> invoke [n]` and
+/// `Program <id> success|failed` lines.
+fn attribute_logs(logs: &[String]) -> Vec<(&str, &str)> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut attributed = Vec::new();
+
+    for line in logs {
+        let mut words = line.split_whitespace();
+        if let (Some("Program"), Some(program_id), Some(verb)) = (words.next(), words.next(), words.next()) {
+            // `Program log:` and `Program data:` lines never change the stack
+            let verb = if program_id.ends_with(':') { "" } else { verb };
+            match verb {
+                "invoke" => {
+                    stack.push(program_id);
+                    continue;
+                }
+                "success" | "failed:" => {
+                    stack.pop();
+                    continue;
+                }
+                "consumed" => continue,
+                _ => {}
+            }
+        }
+
+        if let Some(program_id) = stack.last() {
+            attributed.push((*program_id, line.as_str()));
+        }
+    }
+
+    attributed
+}
+
+/// Mints of a pool that are not SOL or USDC
+pub fn non_quote_mints(mint_a: &str, mint_b: &str) -> Vec<String> {
+    [mint_a, mint_b]
+        .into_iter()
+        .filter(|mint| *mint != WSOL_MINT && *mint != USDC_MINT)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Build a pool creation event from instruction account indexes
+fn pool_event(
+    dex: &str,
+    instruction: &InstructionInfo,
+    context: &DecodeContext<'_>,
+    (pool, mint_a, mint_b, creator): (usize, usize, usize, usize),
+    (liquidity_a, liquidity_b): (u64, u64),
+) -> Option<DecodedEventKind> {
+    let account = |index: usize| instruction.accounts.get(index).cloned();
+
+    Some(DecodedEventKind::PoolCreated(PoolCreationEvent {
+        pool_address: account(pool)?,
+        dex: dex.to_string(),
+        token_a: account(mint_a)?,
+        token_b: account(mint_b)?,
+        initial_liquidity_a: liquidity_a,
+        initial_liquidity_b: liquidity_b,
+        creator: account(creator)?,
+        transaction: context.signature.to_string(),
+        created_at: context.timestamp,
+    }))
+}
+
+/// Read a little-endian u64 at an offset
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(offset..offset.checked_add(8)?)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Raydium AMM v4 `initialize2` decoder
+///
+/// Accounts: amm (4), coin mint (8), pc mint (9), user wallet (17). Data:
+/// tag, nonce, open time, then the initial pc and coin amounts.
+#[derive(Debug)]
+pub struct RaydiumAmmDecoder;
+
+impl ProgramDecoder for RaydiumAmmDecoder {
+    fn program_id(&self) -> &'static str {
+        RAYDIUM_AMM_V4_PROGRAM_ID
+    }
+
+    fn decode_instruction(
+        &self,
+        instruction: &InstructionInfo,
+        context: &DecodeContext<'_>,
+    ) -> Option<DecodedEventKind> {
+        if instruction.data.first() != Some(&RAYDIUM_INITIALIZE2_TAG) {
+            return None;
+        }
+
+        let init_pc_amount = read_u64(&instruction.data, 10)?;
+        let init_coin_amount = read_u64(&instruction.data, 18)?;
+        pool_event("raydium", instruction, context, (4, 8, 9, 17), (init_coin_amount, init_pc_amount))
+    }
+
+    fn requires_transaction(&self, log: &str) -> bool {
+        log.starts_with("Program log: initialize2")
+    }
+}
+
+/// pump.fun `CreateEvent` decoder
+///
+/// The event carries name, symbol and URI strings followed by the mint,
+/// bonding curve and creator keys. Later program versions append fields,
+/// which are ignored.
+#[derive(Debug)]
+pub struct PumpFunDecoder;
+
+impl ProgramDecoder for PumpFunDecoder {
+    fn program_id(&self) -> &'static str {
+        PUMP_FUN_PROGRAM_ID
+    }
+
+    fn decode_event_data(&self, data: &[u8], context: &DecodeContext<'_>) -> Option<DecodedEventKind> {
+        let mut rest = data.strip_prefix(&PUMP_FUN_CREATE_EVENT_DISCRIMINATOR)?;
+
+        // name, symbol, uri
+        for _ in 0..3 {
+            let len = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+            rest = rest.get(4usize.checked_add(len)?..)?;
+        }
+
+        let pubkey = |index: usize| -> Option<String> {
+            let bytes: [u8; 32] = rest.get(index * 32..(index + 1) * 32)?.try_into().ok()?;
+            Some(Pubkey::new_from_array(bytes).to_string())
+        };
+
+        // The bonding curve holds the supply; mint and freeze authorities
+        // are revoked during create
+        Some(DecodedEventKind::TokenCreated(TokenCreationEvent {
+            mint: pubkey(0)?,
+            creator: pubkey(2)?,
+            initial_supply: PUMP_FUN_TOTAL_SUPPLY,
+            decimals: PUMP_FUN_DECIMALS,
+            transaction: context.signature.to_string(),
+            created_at: context.timestamp,
+            has_freeze_authority: false,
+            has_mint_authority: false,
+        }))
+    }
+}
+
+/// Meteora dynamic AMM `initialize_permissionless_pool` decoder
+///
+/// Accounts: pool (0), token A mint (2), token B mint (3), payer (15). Data:
+/// discriminator, curve type, then the token A and B amounts.
+#[derive(Debug)]
+pub struct MeteoraAmmDecoder;
+
+impl ProgramDecoder for MeteoraAmmDecoder {
+    fn program_id(&self) -> &'static str {
+        METEORA_AMM_PROGRAM_ID
+    }
+
+    fn decode_instruction(
+        &self,
+        instruction: &InstructionInfo,
+        context: &DecodeContext<'_>,
+    ) -> Option<DecodedEventKind> {
+        let args = instruction.data.strip_prefix(&METEORA_INITIALIZE_PERMISSIONLESS_POOL_DISCRIMINATOR)?;
+
+        let amounts_offset = match args.first()? {
+            0 => 1,
+            1 => 1 + METEORA_STABLE_CURVE_LEN,
+            _ => return None,
+        };
+
+        let token_a_amount = read_u64(args, amounts_offset)?;
+        let token_b_amount = read_u64(args, amounts_offset + 8)?;
+        pool_event("meteora", instruction, context, (0, 2, 3, 15), (token_a_amount, token_b_amount))
+    }
+
+    fn requires_transaction(&self, log: &str) -> bool {
+        log == "Program log: Instruction: InitializePermissionlessPool"
+    }
+}
+
+/// Orca Whirlpool `initialize_pool` and `initialize_pool_v2` decoder
+///
+/// Whirlpools start without liquidity, so initial amounts are zero.
+#[derive(Debug)]
+pub struct OrcaWhirlpoolDecoder;
+
+impl ProgramDecoder for OrcaWhirlpoolDecoder {
+    fn program_id(&self) -> &'static str {
+        ORCA_WHIRLPOOL_PROGRAM_ID
+    }
+
+    fn decode_instruction(
+        &self,
+        instruction: &InstructionInfo,
+        context: &DecodeContext<'_>,
+    ) -> Option<DecodedEventKind> {
+        // v1 accounts: config, mint a, mint b, funder, whirlpool, ...
+        // v2 accounts: config, mint a, mint b, badge a, badge b, funder, whirlpool, ...
+        let accounts = if instruction.data.starts_with(&ORCA_INITIALIZE_POOL_DISCRIMINATOR) {
+            (4, 1, 2, 3)
+        } else if instruction.data.starts_with(&ORCA_INITIALIZE_POOL_V2_DISCRIMINATOR) {
+            (6, 1, 2, 5)
+        } else {
+            return None;
+        };
+
+        pool_event("orca", instruction, context, accounts, (0, 0))
+    }
+
+    fn requires_transaction(&self, log: &str) -> bool {
+        log.starts_with("Program log: Instruction: InitializePool")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+    use crate::services::solana::rpc::transaction_info_from_encoded;
+
+    /// Expected decoding of a transaction fixture
+    struct Case {
+        fixture: &'static str,
+        signature: &'static str,
+        program_id: &'static str,
+        slot: u64,
+        expected: Expected,
+        requires_transaction: bool,
+    }
+
+    enum Expected {
+        Pool {
+            dex: &'static str,
+            pool: &'static str,
+            token_a: &'static str,
+            token_b: &'static str,
+            creator: &'static str,
+            liquidity: (u64, u64),
+        },
+        Token {
+            mint: &'static str,
+            creator: &'static str,
+        },
+    }
+
+    fn cases() -> Vec<Case> {
+        vec![
+            Case {
+                fixture: include_str!("fixtures/raydium_initialize2.json"),
+                signature: "31MooKb4eJgGURbXVaj9sqhrDLoZEaQJpujLTu8C9nDJBoqVvaz8bT8J1KFWXfzbG9mx76wmVJxWi2xsLZBkNEDC",
+                program_id: RAYDIUM_AMM_V4_PROGRAM_ID,
+                slot: 285_000_001,
+                expected: Expected::Pool {
+                    dex: "raydium",
+                    pool: "BfeNPyScwnz9XckCYeCQe6LHdd18XQ5Xpao7kuZJgSct",
+                    token_a: "H6zyNRbyZQyvZ91q2W4hp4B5aPs1gp7qkJPQQ7qNYtX3",
+                    token_b: WSOL_MINT,
+                    creator: "DyVjJvwpo64hhUyjfWog7ag4zDiX3N4ry9WDcyTgyqHv",
+                    liquidity: (206_900_000_000_000, 79_005_359_057),
+                },
+                requires_transaction: true,
+            },
+            Case {
+                fixture: include_str!("fixtures/pump_fun_create.json"),
+                signature: "DuJFjEdBoQwb17WBK1yH19RFFssPyhA2zVutJuoj7o1rEhAqDoinBunHRWvoiKjw6cdBJ8kGbzRR6hmovEsnv7F",
+                program_id: PUMP_FUN_PROGRAM_ID,
+                slot: 285_000_002,
+                expected: Expected::Token {
+                    mint: "B4h1PcNEZPMP8PHxumLNg7vrthZb2F2Q8JAcB264VXzY",
+                    creator: "CC4jA8PTVsLwKVAhbbQwdBstHUap7a5FZTUPsYn7FcPq",
+                },
+                requires_transaction: false,
+            },
+            Case {
+                fixture: include_str!("fixtures/meteora_initialize_permissionless_pool.json"),
+                signature: "2r4n9w9PiZsQLXAprt63CWwCvQXK3wCoGnyy73T7pVk2fXQgYz5HGFKvw5fRxeuKUF1Zi2WnU69TVKFxq86QVUXR",
+                program_id: METEORA_AMM_PROGRAM_ID,
+                slot: 285_000_003,
+                expected: Expected::Pool {
+                    dex: "meteora",
+                    pool: "EFepKbTW1WRGwxRZsQd5SAhWxWbb7eER5cSDCV29o88Y",
+                    token_a: "3hcd22VJx5weo8NK28PCxUxhv6aeYh4QW4ZCX5dudTec",
+                    token_b: USDC_MINT,
+                    creator: "5THmdDF7qB864uK25eWUYC9vZM8jUvuhzvPUCZitUgRh",
+                    liquidity: (500_000_000_000, 25_000_000_000),
+                },
+                requires_transaction: true,
+            },
+            Case {
+                fixture: include_str!("fixtures/orca_initialize_pool_v2.json"),
+                signature: "5VfSgqQB85to7ELELAq5XbVxJPnKUiN1qJc4LdsojiTQj9onDcH7dkdgrrhC4SrmJ8QXhS3boPMfgKZjFHy15M21",
+                program_id: ORCA_WHIRLPOOL_PROGRAM_ID,
+                slot: 285_000_004,
+                expected: Expected::Pool {
+                    dex: "orca",
+                    pool: "GL6syNvokAa9q2gD1QxMUfKrzLewDUTgNB8iYY4F4QXx",
+                    token_a: "HAvtAbqgAhj2dTp1hnFuqjySqPg57RVLT5KvnUrzjABq",
+                    token_b: WSOL_MINT,
+                    creator: "8TTYZg2eSj7qHpj9K2UxK1DqYMXiuaW4XzQF4D9trmj9",
+                    liquidity: (0, 0),
+                },
+                requires_transaction: true,
+            },
+        ]
+    }
+
+    fn load(case: &Case) -> TransactionInfo {
+        let confirmed: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_str(case.fixture).unwrap();
+        transaction_info_from_encoded(case.signature, confirmed).unwrap()
+    }
+
+    #[test]
+    fn test_decode_transaction_fixtures() {
+        let registry = DecoderRegistry::with_defaults();
+
+        for case in cases() {
+            let transaction = load(&case);
+            let events = registry.decode_transaction(&transaction);
+            assert_eq!(events.len(), 1, "{}: {:?}", case.program_id, events);
+
+            let event = &events[0];
+            assert_eq!(event.signature, case.signature);
+            assert_eq!(event.slot, case.slot);
+            assert_eq!(event.program_id, case.program_id);
+
+            match (&event.kind, &case.expected) {
+                (
+                    DecodedEventKind::PoolCreated(pool),
+                    Expected::Pool { dex, pool: address, token_a, token_b, creator, liquidity },
+                ) => {
+                    assert_eq!(pool.dex, *dex);
+                    assert_eq!(pool.pool_address, *address);
+                    assert_eq!(pool.token_a, *token_a);
+                    assert_eq!(pool.token_b, *token_b);
+                    assert_eq!(pool.creator, *creator);
+                    assert_eq!((pool.initial_liquidity_a, pool.initial_liquidity_b), *liquidity);
+                    assert_eq!(event.token_mints(), vec![token_a.to_string()]);
+                }
+                (DecodedEventKind::TokenCreated(token), Expected::Token { mint, creator }) => {
+                    assert_eq!(token.mint, *mint);
+                    assert_eq!(token.creator, *creator);
+                    assert_eq!(token.decimals, PUMP_FUN_DECIMALS);
+                    assert!(!token.has_mint_authority);
+                }
+                (kind, _) => panic!("{}: unexpected event {:?}", case.program_id, kind),
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_logs_fixtures() {
+        let registry = DecoderRegistry::with_defaults();
+
+        for case in cases() {
+            let transaction = load(&case);
+            let events = registry.decode_logs(&transaction.signature, transaction.slot, &transaction.logs);
+
+            // Only event logs decode without the transaction
+            assert_eq!(events.is_empty(), case.requires_transaction, "{}", case.program_id);
+            assert_eq!(registry.requires_transaction(&transaction.logs), case.requires_transaction,
+                       "{}", case.program_id);
+        }
+    }
+
+    #[test]
+    fn test_attribute_logs_tracks_invoke_stack() {
+        let logs: Vec<String> = [
+            "Program A invoke [1]",
+            "Program log: outer",
+            "Program B invoke [2]",
+            "Program data: AAAA",
+            "Program B consumed 10 of 100 compute units",
+            "Program B success",
+            "Program log: back in outer",
+            "Program A success",
+        ].iter().map(|line| line.to_string()).collect();
+
+        assert_eq!(attribute_logs(&logs), vec![
+            ("A", "Program log: outer"),
+            ("B", "Program data: AAAA"),
+            ("A", "Program log: back in outer"),
+        ]);
+    }
+
+    #[test]
+    fn test_unregistered_programs_are_ignored() {
+        let registry = DecoderRegistry::new();
+        let case = &cases()[0];
+        assert!(registry.decode_transaction(&load(case)).is_empty());
+        assert!(!registry.requires_transaction(&load(case).logs));
+    }
+}
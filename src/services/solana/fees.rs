@@ -0,0 +1,297 @@
+//! Priority fee estimation and compute budget planning
+//!
+//! Swaps compete for the same pool accounts, so the fee that lands depends
+//! on what others recently paid to lock those accounts. The estimator asks
+//! Helius for its fee levels when an API key is configured and otherwise
+//! samples `getRecentPrioritizationFees` for the writable accounts. The
+//! compute unit limit is sized from a simulation so the price pays only for
+//! units the transaction uses, and the total priority fee is capped per
+//! trade by `TradingConfig::max_priority_fee_sol`.
+
+use std::sync::Arc;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    compute_budget::{self, ComputeBudgetInstruction},
+    instruction::Instruction,
+    pubkey::Pubkey,
+};
+use tracing::{debug, warn};
+
+use crate::core::result::AppResult;
+use super::helius::{HeliusClient, PriorityFeeLevels};
+use super::rpc::RpcPool;
+
+/// Compute unit limit used when no simulation result is available
+const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 300_000;
+
+/// Smallest compute unit limit requested
+const MIN_COMPUTE_UNIT_LIMIT: u32 = 10_000;
+
+/// Largest compute unit limit a transaction may request
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Margin over simulated units, for state changing before the transaction lands
+const COMPUTE_UNIT_MARGIN: f64 = 0.1;
+
+/// Extra units for the compute budget instructions themselves
+const COMPUTE_UNIT_HEADROOM: u64 = 1_000;
+
+/// Micro-lamports per lamport
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// Lamports per SOL
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// How urgently a transaction needs to land
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeUrgency {
+    /// Exits and housekeeping that can wait a few slots
+    Low,
+    /// Regular trades
+    Medium,
+    /// Snipes and stop losses
+    High,
+    /// Must land in the next slot
+    VeryHigh,
+}
+
+impl FeeUrgency {
+    /// Percentile of recent fees to pay
+    pub fn percentile(&self) -> f64 {
+        match self {
+            FeeUrgency::Low => 25.0,
+            FeeUrgency::Medium => 50.0,
+            FeeUrgency::High => 75.0,
+            FeeUrgency::VeryHigh => 95.0,
+        }
+    }
+
+    /// Matching Helius fee level
+    fn helius_level(&self, levels: &PriorityFeeLevels) -> f64 {
+        match self {
+            FeeUrgency::Low => levels.low,
+            FeeUrgency::Medium => levels.medium,
+            FeeUrgency::High => levels.high,
+            FeeUrgency::VeryHigh => levels.very_high,
+        }
+    }
+}
+
+/// Where a fee estimate came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeeSource {
+    /// Helius priority fee API
+    Helius,
+    /// `getRecentPrioritizationFees` samples
+    Rpc,
+}
+
+/// Estimated compute unit price
+#[derive(Debug, Clone, Serialize)]
+pub struct PriorityFeeEstimate {
+    /// Price in micro-lamports per compute unit
+    pub micro_lamports_per_cu: u64,
+    /// Urgency the estimate was made for
+    pub urgency: FeeUrgency,
+    /// Source of the estimate
+    pub source: FeeSource,
+}
+
+/// Compute budget of a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComputeBudgetPlan {
+    /// Compute unit limit
+    pub unit_limit: u32,
+    /// Compute unit price in micro-lamports
+    pub unit_price_micro_lamports: u64,
+    /// Priority fee paid at the limit, in lamports
+    pub priority_fee_lamports: u64,
+    /// Whether the price was lowered to respect the per-trade cap
+    pub capped: bool,
+}
+
+impl ComputeBudgetPlan {
+    /// Plan a budget from simulated units and a fee estimate, capped at `max_fee_lamports`
+    pub fn new(units_consumed: Option<u64>, estimate: &PriorityFeeEstimate, max_fee_lamports: u64) -> Self {
+        let unit_limit = compute_unit_limit(units_consumed);
+        let max_price = (max_fee_lamports as u128 * MICRO_LAMPORTS_PER_LAMPORT / unit_limit as u128) as u64;
+        let unit_price_micro_lamports = estimate.micro_lamports_per_cu.min(max_price);
+
+        Self {
+            unit_limit,
+            unit_price_micro_lamports,
+            priority_fee_lamports: priority_fee_lamports(unit_limit, unit_price_micro_lamports),
+            capped: unit_price_micro_lamports < estimate.micro_lamports_per_cu,
+        }
+    }
+
+    /// Compute unit limit and price instructions
+    pub fn instructions(&self) -> [Instruction; 2] {
+        [
+            ComputeBudgetInstruction::set_compute_unit_limit(self.unit_limit),
+            ComputeBudgetInstruction::set_compute_unit_price(self.unit_price_micro_lamports),
+        ]
+    }
+
+    /// Prepend the budget instructions, replacing any already present
+    pub fn apply(&self, instructions: Vec<Instruction>) -> Vec<Instruction> {
+        let mut budgeted: Vec<Instruction> = self.instructions().into();
+        budgeted.extend(instructions.into_iter().filter(|ix| !compute_budget::check_id(&ix.program_id)));
+        budgeted
+    }
+}
+
+/// Compute unit limit for a transaction that consumed `units_consumed` in simulation
+pub fn compute_unit_limit(units_consumed: Option<u64>) -> u32 {
+    match units_consumed {
+        Some(units) => {
+            let limit = (units as f64 * (1.0 + COMPUTE_UNIT_MARGIN)) as u64 + COMPUTE_UNIT_HEADROOM;
+            limit.clamp(MIN_COMPUTE_UNIT_LIMIT as u64, MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+        }
+        None => DEFAULT_COMPUTE_UNIT_LIMIT,
+    }
+}
+
+/// Priority fee in lamports for a limit and price, rounded up
+fn priority_fee_lamports(unit_limit: u32, unit_price_micro_lamports: u64) -> u64 {
+    (unit_limit as u128 * unit_price_micro_lamports as u128).div_ceil(MICRO_LAMPORTS_PER_LAMPORT) as u64
+}
+
+/// Nearest-rank percentile of fee samples
+fn fee_percentile(mut fees: Vec<u64>, percentile: f64) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+
+    fees.sort_unstable();
+    let rank = ((percentile / 100.0) * fees.len() as f64).ceil() as usize;
+    fees[rank.clamp(1, fees.len()) - 1]
+}
+
+/// Priority fee estimator
+#[derive(Debug)]
+pub struct PriorityFeeEstimator {
+    /// Pool used to sample recent fees
+    pool: Arc<RpcPool>,
+    /// Helius client, used when an API key is configured
+    helius: Option<Arc<HeliusClient>>,
+    /// Per-trade priority fee cap in lamports
+    max_fee_lamports: u64,
+}
+
+impl PriorityFeeEstimator {
+    /// Create an estimator; pass a Helius client to prefer its fee levels
+    pub fn new(pool: Arc<RpcPool>, helius: Option<Arc<HeliusClient>>, max_fee_sol: Decimal) -> Self {
+        let max_fee_lamports = (max_fee_sol * Decimal::from(LAMPORTS_PER_SOL)).to_u64().unwrap_or(0);
+
+        Self { pool, helius, max_fee_lamports }
+    }
+
+    /// Per-trade priority fee cap in lamports
+    pub fn max_fee_lamports(&self) -> u64 {
+        self.max_fee_lamports
+    }
+
+    /// Estimate the compute unit price for a transaction writing `writable_accounts`
+    ///
+    /// Helius errors fall back to sampling recent fees over RPC.
+    pub async fn estimate(&self, writable_accounts: &[Pubkey], urgency: FeeUrgency) -> AppResult<PriorityFeeEstimate> {
+        if let Some(helius) = &self.helius {
+            let accounts: Vec<String> = writable_accounts.iter().map(|account| account.to_string()).collect();
+
+            match helius.get_priority_fee_estimate(&accounts).await {
+                Ok(levels) => {
+                    return Ok(PriorityFeeEstimate {
+                        micro_lamports_per_cu: urgency.helius_level(&levels).ceil() as u64,
+                        urgency,
+                        source: FeeSource::Helius,
+                    });
+                }
+                Err(e) => warn!("Helius priority fee estimate failed, sampling RPC: {}", e),
+            }
+        }
+
+        let connection = self.pool.get_connection().await?;
+        let fees = connection.get_recent_prioritization_fees(writable_accounts).await?;
+        debug!("💸 Sampled {} recent prioritization fees", fees.len());
+
+        Ok(PriorityFeeEstimate {
+            micro_lamports_per_cu: fee_percentile(fees, urgency.percentile()),
+            urgency,
+            source: FeeSource::Rpc,
+        })
+    }
+
+    /// Estimate a fee and plan the compute budget around simulated units
+    pub async fn plan(
+        &self,
+        writable_accounts: &[Pubkey],
+        urgency: FeeUrgency,
+        units_consumed: Option<u64>,
+    ) -> AppResult<ComputeBudgetPlan> {
+        let estimate = self.estimate(writable_accounts, urgency).await?;
+        let plan = ComputeBudgetPlan::new(units_consumed, &estimate, self.max_fee_lamports);
+
+        if plan.capped {
+            warn!("💸 Priority fee capped at {} lamports ({} µlamports/CU wanted)",
+                  plan.priority_fee_lamports, estimate.micro_lamports_per_cu);
+        }
+
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(micro_lamports_per_cu: u64) -> PriorityFeeEstimate {
+        PriorityFeeEstimate { micro_lamports_per_cu, urgency: FeeUrgency::High, source: FeeSource::Rpc }
+    }
+
+    #[test]
+    fn test_fee_percentile() {
+        let fees = vec![0, 0, 1_000, 5_000, 10_000, 20_000, 50_000, 100_000];
+
+        assert_eq!(fee_percentile(fees.clone(), FeeUrgency::Low.percentile()), 0);
+        assert_eq!(fee_percentile(fees.clone(), FeeUrgency::Medium.percentile()), 5_000);
+        assert_eq!(fee_percentile(fees.clone(), FeeUrgency::High.percentile()), 20_000);
+        assert_eq!(fee_percentile(fees, FeeUrgency::VeryHigh.percentile()), 100_000);
+        assert_eq!(fee_percentile(vec![], 50.0), 0);
+    }
+
+    #[test]
+    fn test_compute_budget_plan() {
+        assert_eq!(compute_unit_limit(Some(100_000)), 111_000);
+        assert_eq!(compute_unit_limit(Some(2_000_000)), MAX_COMPUTE_UNIT_LIMIT);
+        assert_eq!(compute_unit_limit(None), DEFAULT_COMPUTE_UNIT_LIMIT);
+
+        // 111k units at 10k µlamports is 1110 lamports, under the cap
+        let plan = ComputeBudgetPlan::new(Some(100_000), &estimate(10_000), 5_000_000);
+        assert_eq!((plan.unit_limit, plan.unit_price_micro_lamports, plan.priority_fee_lamports), (111_000, 10_000, 1_110));
+        assert!(!plan.capped);
+
+        // A cap of 555 lamports halves the price
+        let plan = ComputeBudgetPlan::new(Some(100_000), &estimate(10_000), 555);
+        assert_eq!(plan.unit_price_micro_lamports, 5_000);
+        assert!(plan.priority_fee_lamports <= 555);
+        assert!(plan.capped);
+    }
+
+    #[test]
+    fn test_apply_replaces_existing_budget() {
+        let plan = ComputeBudgetPlan::new(Some(50_000), &estimate(1_000), 5_000_000);
+        let swap = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]);
+        let instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(200_000), swap.clone()];
+
+        let budgeted = plan.apply(instructions);
+        assert_eq!(budgeted.len(), 3);
+        assert_eq!(budgeted[0], ComputeBudgetInstruction::set_compute_unit_limit(plan.unit_limit));
+        assert_eq!(budgeted[1], ComputeBudgetInstruction::set_compute_unit_price(1_000));
+        assert_eq!(budgeted[2], swap);
+    }
+}
@@ -3,25 +3,32 @@
 //! This module provides integration with Helius RPC and enhanced APIs
 //! for real-time token detection, webhooks, and enhanced metadata.
 
-use reqwest::{Client, StatusCode};
+use reqwest::{header::{HeaderMap, RETRY_AFTER}, Client, Method, RequestBuilder, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, Semaphore};
 use tracing::{debug, info, warn, error, instrument};
 use backoff::{ExponentialBackoff, backoff::Backoff};
+use base58::FromBase58;
 
 use crate::config::models::HeliusConfig;
 use crate::core::result::AppResult;
 use crate::core::error::AppError;
-use super::types::{TokenMetadata, TokenEvent, LiquidityPool};
-
-/// Helius API base URL
-const HELIUS_API_BASE: &str = "https://api.helius.xyz/v0";
+use super::types::{InstructionInfo, TokenMetadata, LiquidityPool, TransactionInfo, TransactionStatus};
 
 /// Maximum concurrent Helius requests
 const MAX_CONCURRENT_REQUESTS: usize = 5;
 
+/// Pause after a 429 that carries no usable `Retry-After`
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Maximum page size of DAS requests
+const DAS_PAGE_LIMIT: u32 = 1000;
+
+/// Maximum page size of enhanced transaction requests
+const ENHANCED_PAGE_LIMIT: u32 = 100;
+
 /// Helius API client
 #[derive(Debug, Clone)]
 pub struct HeliusClient {
@@ -31,8 +38,8 @@ pub struct HeliusClient {
     config: HeliusConfig,
     /// Request semaphore for rate limiting
     semaphore: Arc<Semaphore>,
-    /// Rate limiter
-    rate_limiter: Arc<RwLock<RateLimiter>>,
+    /// Token bucket pacing requests
+    rate_limiter: Arc<RwLock<TokenBucket>>,
     /// Client statistics
     stats: Arc<RwLock<HeliusStats>>,
 }
@@ -52,7 +59,7 @@ impl HeliusClient {
             .build()
             .map_err(|e| AppError::network(format!("Failed to create HTTP client: {}", e)))?;
 
-        let rate_limiter = Arc::new(RwLock::new(RateLimiter::new(config.rate_limit_per_second)));
+        let rate_limiter = Arc::new(RwLock::new(TokenBucket::new(config.rate_limit_per_second)));
 
         Ok(Self {
             http_client,
@@ -115,37 +122,37 @@ impl HeliusClient {
         self.execute_request(&url, &params).await
     }
 
-    /// Subscribe to webhooks for real-time events
-    #[instrument(skip(self))]
-    pub async fn subscribe_to_webhooks(&self, callback_url: &str) -> AppResult<String> {
+    /// Register a webhook
+    #[instrument(skip(self, registration))]
+    pub async fn create_webhook(&self, registration: &WebhookRegistration) -> AppResult<HeliusWebhook> {
         self.check_api_key()?;
 
-        let url = format!("{}/webhooks", self.config.base_url);
+        let webhook: HeliusWebhook = self.execute_webhook_request(Method::POST, None, Some(registration)).await?;
+        info!("✅ Webhook {} registered for {}", webhook.webhook_id, webhook.webhook_url);
+        Ok(webhook)
+    }
 
-        let webhook_config = WebhookSubscription {
-            webhook_url: callback_url.to_string(),
-            transaction_types: vec![
-                "TOKEN_MINT".to_string(),
-                "CREATE_POOL".to_string(),
-                "ADD_LIQUIDITY".to_string(),
-            ],
-            account_addresses: vec![],
-            webhook_type: "enhanced".to_string(),
-            auth_header: None,
-        };
+    /// Replace the settings of a webhook, e.g. its account addresses
+    #[instrument(skip(self, registration))]
+    pub async fn update_webhook(&self, webhook_id: &str, registration: &WebhookRegistration) -> AppResult<HeliusWebhook> {
+        self.check_api_key()?;
+        self.execute_webhook_request(Method::PUT, Some(webhook_id), Some(registration)).await
+    }
 
-        let response: WebhookResponse = self.execute_post_request(&url, &webhook_config).await?;
+    /// Delete a webhook
+    #[instrument(skip(self))]
+    pub async fn delete_webhook(&self, webhook_id: &str) -> AppResult<()> {
+        self.check_api_key()?;
 
-        info!("✅ Webhook subscription created: {}", response.webhook_id);
-        Ok(response.webhook_id)
+        self.execute_webhook_request::<()>(Method::DELETE, Some(webhook_id), None).await?;
+        info!("🗑️  Webhook {} deleted", webhook_id);
+        Ok(())
     }
 
-    /// Parse webhook event
-    pub fn parse_webhook_event(&self, payload: &str) -> AppResult<TokenEvent> {
-        let event: HeliusWebhookPayload = serde_json::from_str(payload)
-            .map_err(|e| AppError::network(format!("Failed to parse webhook payload: {}", e)))?;
-
-        Ok(event.into())
+    /// Parse a webhook delivery, a batch of enhanced transactions
+    pub fn parse_webhook_payloads(body: &[u8]) -> AppResult<Vec<HeliusWebhookPayload>> {
+        serde_json::from_slice(body)
+            .map_err(|e| AppError::validation(format!("Failed to parse webhook payload: {}", e)))
     }
 
     /// Get liquidity pool information
@@ -159,6 +166,198 @@ impl HeliusClient {
         self.execute_request(&url, &params).await
     }
 
+    /// Get priority fee levels for a transaction locking `accounts`
+    ///
+    /// Levels are in micro-lamports per compute unit.
+    #[instrument(skip(self, accounts))]
+    pub async fn get_priority_fee_estimate(&self, accounts: &[String]) -> AppResult<PriorityFeeLevels> {
+        self.check_api_key()?;
+
+        let params = serde_json::json!([{
+            "accountKeys": accounts,
+            "options": { "includeAllPriorityFeeLevels": true },
+        }]);
+
+        let result: PriorityFeeResult = self.rpc_request("getPriorityFeeEstimate", params).await?;
+        Ok(result.priority_fee_levels)
+    }
+
+    /// Get a DAS asset, e.g. a token by its mint
+    #[instrument(skip(self))]
+    pub async fn get_asset(&self, asset_id: &str) -> AppResult<DasAsset> {
+        self.check_api_key()?;
+        self.rpc_request("getAsset", serde_json::json!({ "id": asset_id })).await
+    }
+
+    /// Get one page of the assets created by `creator`
+    ///
+    /// Pages start at 1 and hold at most 1000 assets.
+    #[instrument(skip(self))]
+    pub async fn get_assets_by_creator(&self, creator: &str, page: u32, limit: u32) -> AppResult<AssetPage> {
+        self.check_api_key()?;
+        self.rpc_request("getAssetsByCreator", serde_json::json!({
+            "creatorAddress": creator,
+            "onlyVerified": false,
+            "page": page.max(1),
+            "limit": limit.clamp(1, DAS_PAGE_LIMIT),
+        })).await
+    }
+
+    /// Get up to `max_assets` assets created by `creator`, across pages
+    pub async fn get_all_assets_by_creator(&self, creator: &str, max_assets: usize) -> AppResult<Vec<DasAsset>> {
+        let mut assets = Vec::new();
+        if max_assets == 0 {
+            return Ok(assets);
+        }
+
+        // The page size must stay fixed, as pages are offsets of it
+        let limit = max_assets.min(DAS_PAGE_LIMIT as usize) as u32;
+        let mut page = 1;
+
+        loop {
+            let result = self.get_assets_by_creator(creator, page, limit).await?;
+            let full_page = result.items.len() >= limit as usize;
+            assets.extend(result.items);
+
+            if !full_page || assets.len() >= max_assets {
+                break;
+            }
+            page += 1;
+        }
+
+        assets.truncate(max_assets);
+        Ok(assets)
+    }
+
+    /// Get one page of token accounts, continuing from `cursor`
+    #[instrument(skip(self))]
+    pub async fn get_token_accounts(
+        &self,
+        filter: &TokenAccountFilter,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> AppResult<TokenAccountPage> {
+        self.check_api_key()?;
+
+        let mut params = serde_json::json!({ "limit": limit.clamp(1, DAS_PAGE_LIMIT) });
+        match filter {
+            TokenAccountFilter::Mint(mint) => params["mint"] = serde_json::json!(mint),
+            TokenAccountFilter::Owner(owner) => params["owner"] = serde_json::json!(owner),
+        }
+        if let Some(cursor) = cursor {
+            params["cursor"] = serde_json::json!(cursor);
+        }
+
+        self.rpc_request("getTokenAccounts", params).await
+    }
+
+    /// Get up to `max_accounts` token accounts, following cursors
+    pub async fn get_all_token_accounts(
+        &self,
+        filter: &TokenAccountFilter,
+        max_accounts: usize,
+    ) -> AppResult<Vec<DasTokenAccount>> {
+        let mut accounts = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        while accounts.len() < max_accounts {
+            let limit = (max_accounts - accounts.len()).min(DAS_PAGE_LIMIT as usize) as u32;
+            let page = self.get_token_accounts(filter, cursor.as_deref(), limit).await?;
+            let full_page = page.token_accounts.len() >= limit as usize;
+            accounts.extend(page.token_accounts);
+
+            match page.cursor {
+                Some(next) if full_page => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        accounts.truncate(max_accounts);
+        Ok(accounts)
+    }
+
+    /// Get one page of parsed transactions involving `address`, newest first
+    #[instrument(skip(self, query))]
+    pub async fn get_enhanced_transactions(
+        &self,
+        address: &str,
+        query: &EnhancedTransactionQuery,
+    ) -> AppResult<Vec<EnhancedTransaction>> {
+        self.check_api_key()?;
+
+        let url = format!("{}/addresses/{}/transactions", self.config.api_url, address);
+        let limit = query.limit.unwrap_or(ENHANCED_PAGE_LIMIT).clamp(1, ENHANCED_PAGE_LIMIT).to_string();
+
+        let mut params = vec![
+            ("api-key", self.config.api_key.as_str()),
+            ("limit", limit.as_str()),
+        ];
+        if let Some(before) = &query.before {
+            params.push(("before", before));
+        }
+        if let Some(until) = &query.until {
+            params.push(("until", until));
+        }
+        if let Some(transaction_type) = &query.transaction_type {
+            params.push(("type", transaction_type));
+        }
+
+        self.execute_request(&url, &params).await
+    }
+
+    /// Get up to `max_transactions` parsed transactions, paging backwards
+    ///
+    /// `query.before` is where paging starts and `query.until` where it
+    /// stops; both are optional.
+    pub async fn get_all_enhanced_transactions(
+        &self,
+        address: &str,
+        query: &EnhancedTransactionQuery,
+        max_transactions: usize,
+    ) -> AppResult<Vec<EnhancedTransaction>> {
+        let mut transactions: Vec<EnhancedTransaction> = Vec::new();
+        let mut page_query = query.clone();
+
+        while transactions.len() < max_transactions {
+            let limit = (max_transactions - transactions.len()).min(ENHANCED_PAGE_LIMIT as usize) as u32;
+            page_query.limit = Some(limit);
+
+            let page = self.get_enhanced_transactions(address, &page_query).await?;
+            let full_page = page.len() >= limit as usize;
+            page_query.before = page.last().map(|transaction| transaction.signature.clone());
+            transactions.extend(page);
+
+            if !full_page || page_query.before.is_none() {
+                break;
+            }
+        }
+
+        transactions.truncate(max_transactions);
+        Ok(transactions)
+    }
+
+    /// Parse transactions by signature, in batches of 100
+    #[instrument(skip(self, signatures))]
+    pub async fn parse_transactions(&self, signatures: &[String]) -> AppResult<Vec<EnhancedTransaction>> {
+        self.check_api_key()?;
+
+        let url = format!("{}/transactions", self.config.api_url);
+        let mut transactions = Vec::with_capacity(signatures.len());
+
+        for batch in signatures.chunks(ENHANCED_PAGE_LIMIT as usize) {
+            let body = serde_json::json!({ "transactions": batch });
+            let parsed: Vec<EnhancedTransaction> = self.send(|| {
+                self.http_client
+                    .post(&url)
+                    .query(&[("api-key", self.config.api_key.as_str())])
+                    .json(&body)
+            }, true).await?;
+            transactions.extend(parsed);
+        }
+
+        Ok(transactions)
+    }
+
     /// Health check
     pub async fn health_check(&self) -> AppResult<String> {
         if self.config.api_key.is_empty() {
@@ -187,95 +386,151 @@ impl HeliusClient {
 
     /// Execute GET request with retry logic
     async fn execute_request<T>(&self, url: &str, params: &[(&str, &str)]) -> AppResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.send(|| self.http_client.get(url).query(params), true).await
+    }
+
+    /// Execute a webhook management request against the REST API
+    ///
+    /// Only rate limited attempts are retried, as other failures may have
+    /// taken effect.
+    async fn execute_webhook_request<T>(
+        &self,
+        method: Method,
+        webhook_id: Option<&str>,
+        registration: Option<&WebhookRegistration>,
+    ) -> AppResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let url = match webhook_id {
+            Some(webhook_id) => format!("{}/webhooks/{}", self.config.api_url, webhook_id),
+            None => format!("{}/webhooks", self.config.api_url),
+        };
+
+        self.send(|| {
+            let request = self.http_client.request(method.clone(), &url)
+                .query(&[("api-key", self.config.api_key.as_str())]);
+            match registration {
+                Some(registration) => request.json(registration),
+                None => request,
+            }
+        }, false).await
+    }
+
+    /// Execute a JSON-RPC request against the Helius RPC, e.g. a DAS method
+    async fn rpc_request<T>(&self, method: &str, params: serde_json::Value) -> AppResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let url = format!("{}/?api-key={}", self.config.base_url, self.config.api_key);
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": method,
+            "method": method,
+            "params": params,
+        });
+
+        let response: JsonRpcResponse<T> = self.send(|| self.http_client.post(&url).json(&request), true).await?;
+        match response.result {
+            Some(result) => Ok(result),
+            None => Err(AppError::network(format!(
+                "Helius {} failed: {}",
+                method,
+                response.error.map(|e| e.to_string()).unwrap_or_else(|| "empty response".to_string())
+            ))),
+        }
+    }
+
+    /// Send a request paced by the token bucket, retrying with backoff
+    ///
+    /// A 429 pauses the bucket for the server's `Retry-After` and is always
+    /// retried, since the request was not processed. Other failures are
+    /// retried only with `retry_failures`.
+    async fn send<T>(&self, request: impl Fn() -> RequestBuilder, retry_failures: bool) -> AppResult<T>
     where
         T: for<'de> Deserialize<'de>,
     {
         let _permit = self.semaphore.acquire().await
             .map_err(|_| AppError::internal("Failed to acquire Helius semaphore"))?;
 
-        // Wait for rate limit
-        self.rate_limiter.write().await.wait_if_needed().await;
-
         let mut backoff = ExponentialBackoff::default();
         backoff.max_elapsed_time = Some(Duration::from_secs(30));
 
-        let start_time = std::time::Instant::now();
-        let mut last_error = None;
+        let start_time = Instant::now();
 
         loop {
-            match self.http_client
-                .get(url)
-                .query(params)
-                .send()
-                .await
-            {
+            self.wait_for_rate_limit().await;
+
+            let mut rate_limited = false;
+            let error = match request().send().await {
                 Ok(response) => {
                     let status = response.status();
 
                     if status.is_success() {
-                        let data = response.json::<T>().await
-                            .map_err(|e| AppError::network(format!("Failed to parse response: {}", e)))?;
-
-                        self.record_success(start_time.elapsed()).await;
-                        return Ok(data);
+                        // Deletes answer with an empty body
+                        let parsed = match response.bytes().await {
+                            Ok(body) if body.is_empty() => serde_json::from_slice::<T>(b"null"),
+                            Ok(body) => serde_json::from_slice::<T>(&body),
+                            Err(e) => {
+                                self.record_failure().await;
+                                return Err(AppError::network(format!("Failed to read response: {}", e)));
+                            }
+                        };
+                        return match parsed {
+                            Ok(data) => {
+                                self.record_success(start_time.elapsed()).await;
+                                Ok(data)
+                            }
+                            Err(e) => {
+                                self.record_failure().await;
+                                Err(AppError::network(format!("Failed to parse response: {}", e)))
+                            }
+                        };
                     } else if status == StatusCode::TOO_MANY_REQUESTS {
-                        warn!("⚠️  Helius rate limit hit");
-                        self.rate_limiter.write().await.add_penalty();
-                        last_error = Some(AppError::network("Rate limit exceeded"));
+                        let pause = retry_after(response.headers()).unwrap_or(DEFAULT_RETRY_AFTER);
+                        warn!("⚠️  Helius rate limit hit, pausing for {:?}", pause);
+                        self.rate_limiter.write().await.pause(Instant::now(), pause);
+                        rate_limited = true;
+                        AppError::network("Rate limit exceeded")
                     } else {
                         let error_text = response.text().await.unwrap_or_default();
-                        last_error = Some(AppError::network(format!(
-                            "Helius API error ({}): {}",
-                            status, error_text
-                        )));
+                        AppError::network(format!("Helius API error ({}): {}", status, error_text))
                     }
                 }
-                Err(e) => {
-                    last_error = Some(AppError::network(format!("Request failed: {}", e)));
-                }
+                Err(e) => AppError::network(format!("Request failed: {}", e)),
+            };
+
+            if !rate_limited && !retry_failures {
+                self.record_failure().await;
+                return Err(error);
             }
 
             match backoff.next_backoff() {
+                // The paused bucket already holds back the retry
+                Some(_) if rate_limited => debug!("🔄 Retrying Helius request after rate limit"),
                 Some(duration) => {
                     debug!("🔄 Retrying Helius request after {:?}", duration);
                     tokio::time::sleep(duration).await;
                 }
                 None => {
                     self.record_failure().await;
-                    return Err(last_error.unwrap_or_else(|| AppError::network("Request failed")));
+                    return Err(error);
                 }
             }
         }
     }
 
-    /// Execute POST request
-    async fn execute_post_request<B, T>(&self, url: &str, body: &B) -> AppResult<T>
-    where
-        B: Serialize,
-        T: for<'de> Deserialize<'de>,
-    {
-        let _permit = self.semaphore.acquire().await
-            .map_err(|_| AppError::internal("Failed to acquire Helius semaphore"))?;
-
-        self.rate_limiter.write().await.wait_if_needed().await;
-
-        let response = self.http_client
-            .post(url)
-            .header("x-api-key", &self.config.api_key)
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| AppError::network(format!("POST request failed: {}", e)))?;
-
-        if response.status().is_success() {
-            response.json::<T>().await
-                .map_err(|e| AppError::network(format!("Failed to parse response: {}", e)))
-        } else {
-            let error_text = response.text().await.unwrap_or_default();
-            Err(AppError::network(format!(
-                "Helius API error ({}): {}",
-                response.status(), error_text
-            )))
+    /// Wait until the token bucket grants a request
+    async fn wait_for_rate_limit(&self) {
+        loop {
+            let granted = self.rate_limiter.write().await.try_acquire(Instant::now());
+            match granted {
+                Ok(()) => return,
+                Err(wait) => tokio::time::sleep(wait).await,
+            }
         }
     }
 
@@ -315,53 +570,73 @@ impl HeliusClient {
     }
 }
 
-/// Rate limiter for API requests
+/// Token bucket pacing API requests
+///
+/// The bucket holds one second worth of requests, so short bursts go out
+/// at once while the sustained rate stays at `rate` per second.
 #[derive(Debug)]
-struct RateLimiter {
-    /// Requests per second limit
-    limit: u32,
-    /// Last request timestamp
-    last_request: Option<std::time::Instant>,
-    /// Penalty delay for rate limit hits
-    penalty_until: Option<std::time::Instant>,
+struct TokenBucket {
+    /// Requests per second, also the bucket capacity
+    rate: u32,
+    /// Requests currently available
+    tokens: f64,
+    /// Last refill
+    refilled_at: Instant,
+    /// No requests before this instant, set from `Retry-After`
+    paused_until: Option<Instant>,
 }
 
-impl RateLimiter {
-    fn new(limit: u32) -> Self {
+impl TokenBucket {
+    fn new(rate: u32) -> Self {
+        let rate = rate.max(1);
         Self {
-            limit,
-            last_request: None,
-            penalty_until: None,
+            rate,
+            tokens: rate as f64,
+            refilled_at: Instant::now(),
+            paused_until: None,
         }
     }
 
-    async fn wait_if_needed(&mut self) {
-        // Check if we're in penalty
-        if let Some(penalty_until) = self.penalty_until {
-            if penalty_until > std::time::Instant::now() {
-                let wait_duration = penalty_until - std::time::Instant::now();
-                tokio::time::sleep(wait_duration).await;
-                self.penalty_until = None;
+    /// Take a token, or return how long to wait before trying again
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(paused_until) = self.paused_until {
+            if paused_until > now {
+                return Err(paused_until - now);
             }
+            self.paused_until = None;
         }
 
-        // Check rate limit
-        if let Some(last_request) = self.last_request {
-            let min_interval = Duration::from_millis(1000 / self.limit as u64);
-            let elapsed = last_request.elapsed();
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate as f64).min(self.rate as f64);
+        self.refilled_at = now;
 
-            if elapsed < min_interval {
-                tokio::time::sleep(min_interval - elapsed).await;
-            }
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.rate as f64))
         }
+    }
 
-        self.last_request = Some(std::time::Instant::now());
+    /// Hand out no tokens for `duration`, then restart from an empty bucket
+    fn pause(&mut self, now: Instant, duration: Duration) {
+        let until = now + duration;
+        self.paused_until = Some(self.paused_until.map_or(until, |paused_until| paused_until.max(until)));
+        self.tokens = 0.0;
+        self.refilled_at = until;
     }
+}
 
-    fn add_penalty(&mut self) {
-        // Add 1 second penalty for rate limit hit
-        self.penalty_until = Some(std::time::Instant::now() + Duration::from_secs(1));
+/// Delay requested by a `Retry-After` header, in seconds or as an HTTP date
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
     }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()
 }
 
 /// Helius statistics
@@ -404,47 +679,79 @@ impl HeliusStats {
 
 /// Helius webhook types and structures
 
+/// Registered webhook
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct HeliusWebhook {
+    /// Webhook ID
+    #[serde(rename = "webhookID")]
     pub webhook_id: String,
+    /// URL deliveries are posted to
+    #[serde(rename = "webhookURL")]
     pub webhook_url: String,
+    /// Transaction types delivered
+    #[serde(default)]
     pub transaction_types: Vec<String>,
+    /// Accounts whose transactions are delivered
+    #[serde(default)]
+    pub account_addresses: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
-struct WebhookSubscription {
-    webhook_url: String,
-    transaction_types: Vec<String>,
-    account_addresses: Vec<String>,
-    webhook_type: String,
-    auth_header: Option<String>,
+/// Settings of a webhook, for registering or updating it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookRegistration {
+    /// URL deliveries are posted to
+    #[serde(rename = "webhookURL")]
+    pub webhook_url: String,
+    /// Transaction types to deliver, e.g. `CREATE_POOL`
+    pub transaction_types: Vec<String>,
+    /// Accounts whose transactions are delivered
+    pub account_addresses: Vec<String>,
+    /// `enhanced` for parsed transactions
+    pub webhook_type: String,
+    /// Value Helius sends back in the `Authorization` header
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_header: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct WebhookResponse {
-    webhook_id: String,
+/// Transaction delivered by an enhanced webhook
+///
+/// Helius posts deliveries as JSON arrays of these.
+pub type HeliusWebhookPayload = EnhancedTransaction;
+
+/// Helius API response types
+
+/// Priority fee levels in micro-lamports per compute unit
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityFeeLevels {
+    /// Minimum fee paid recently
+    pub min: f64,
+    /// 25th percentile
+    pub low: f64,
+    /// 50th percentile
+    pub medium: f64,
+    /// 75th percentile
+    pub high: f64,
+    /// 95th percentile
+    pub very_high: f64,
+    /// Maximum fee paid recently
+    pub unsafe_max: f64,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct HeliusWebhookPayload {
-    pub timestamp: String,
-    pub transaction_type: String,
-    pub signature: String,
-    pub accounts: Vec<String>,
-    pub token_transfers: Option<Vec<TokenTransfer>>,
-    pub metadata: Option<serde_json::Value>,
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct TokenTransfer {
-    pub from: String,
-    pub to: String,
-    pub amount: u64,
-    pub mint: String,
+#[serde(rename_all = "camelCase")]
+struct PriorityFeeResult {
+    priority_fee_levels: PriorityFeeLevels,
 }
 
-/// Helius API response types
-
 #[derive(Debug, Deserialize)]
 struct HeliusTokenMetadata {
     pub account: String,
@@ -488,6 +795,332 @@ pub struct TokenTransaction {
     pub to: Option<String>,
 }
 
+/// Helius DAS types
+
+/// Digital asset from the DAS API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasAsset {
+    /// Asset id, the mint for tokens
+    pub id: String,
+    /// Asset interface, e.g. `FungibleToken` or `V1_NFT`
+    pub interface: String,
+    /// Metadata content
+    #[serde(default)]
+    pub content: Option<AssetContent>,
+    /// Authorities and their scopes
+    #[serde(default)]
+    pub authorities: Vec<AssetAuthority>,
+    /// Metadata creators
+    #[serde(default)]
+    pub creators: Vec<AssetCreator>,
+    /// Whether the metadata can still be changed
+    #[serde(default)]
+    pub mutable: bool,
+    /// Whether the asset was burnt
+    #[serde(default)]
+    pub burnt: bool,
+    /// SPL token details, for fungible assets
+    #[serde(default)]
+    pub token_info: Option<AssetTokenInfo>,
+}
+
+/// Metadata content of a DAS asset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetContent {
+    /// Off-chain metadata URI
+    #[serde(default)]
+    pub json_uri: String,
+    /// Metadata fields
+    #[serde(default)]
+    pub metadata: AssetMetadata,
+    /// Links from the off-chain metadata
+    #[serde(default)]
+    pub links: Option<AssetLinks>,
+}
+
+/// Metadata fields of a DAS asset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetMetadata {
+    /// Name
+    pub name: Option<String>,
+    /// Symbol
+    pub symbol: Option<String>,
+    /// Description
+    pub description: Option<String>,
+}
+
+/// Links of a DAS asset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetLinks {
+    /// Image URL
+    pub image: Option<String>,
+    /// Project website
+    pub external_url: Option<String>,
+}
+
+/// Authority of a DAS asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetAuthority {
+    /// Authority address
+    pub address: String,
+    /// What the authority may change, e.g. `full` or `metadata`
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// Creator of a DAS asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetCreator {
+    /// Creator address
+    pub address: String,
+    /// Royalty share in percent
+    pub share: u8,
+    /// Whether the creator signed the metadata
+    pub verified: bool,
+}
+
+/// SPL token details of a DAS asset
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetTokenInfo {
+    /// Token symbol
+    pub symbol: Option<String>,
+    /// Total supply in base units
+    pub supply: Option<u64>,
+    /// Number of decimals
+    pub decimals: Option<u8>,
+    /// Token program
+    pub token_program: Option<String>,
+    /// Mint authority, if not renounced
+    pub mint_authority: Option<String>,
+    /// Freeze authority, if not renounced
+    pub freeze_authority: Option<String>,
+    /// Price, for tokens Helius prices
+    pub price_info: Option<AssetPriceInfo>,
+}
+
+/// Price of a token from the DAS API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetPriceInfo {
+    /// Price of one token
+    pub price_per_token: f64,
+    /// Quote currency, e.g. `USDC`
+    pub currency: String,
+}
+
+/// Page of DAS assets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetPage {
+    /// Assets on this page
+    pub total: u32,
+    /// Page size requested
+    pub limit: u32,
+    /// Page number, starting at 1
+    pub page: u32,
+    /// Assets
+    pub items: Vec<DasAsset>,
+}
+
+/// Which token accounts to list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenAccountFilter {
+    /// Accounts holding a mint
+    Mint(String),
+    /// Accounts owned by a wallet
+    Owner(String),
+}
+
+/// Page of token accounts from the DAS API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenAccountPage {
+    /// Accounts on this page
+    pub total: u32,
+    /// Page size requested
+    pub limit: u32,
+    /// Cursor of the next page
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Token accounts
+    pub token_accounts: Vec<DasTokenAccount>,
+}
+
+/// Token account from the DAS API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DasTokenAccount {
+    /// Token account address
+    pub address: String,
+    /// Mint held
+    pub mint: String,
+    /// Wallet owning the account
+    pub owner: String,
+    /// Balance in base units
+    pub amount: u64,
+    /// Whether the account is frozen
+    #[serde(default)]
+    pub frozen: bool,
+}
+
+/// Helius enhanced transaction types
+
+/// Filters for a page of enhanced transactions
+#[derive(Debug, Clone, Default)]
+pub struct EnhancedTransactionQuery {
+    /// Only transactions older than this signature
+    pub before: Option<String>,
+    /// Only transactions newer than this signature
+    pub until: Option<String>,
+    /// Helius transaction type, e.g. `SWAP` or `CREATE_POOL`
+    pub transaction_type: Option<String>,
+    /// Page size, at most 100
+    pub limit: Option<u32>,
+}
+
+/// Transaction parsed by the Helius enhanced transactions API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnhancedTransaction {
+    /// Transaction signature
+    pub signature: String,
+    /// Slot the transaction landed in
+    pub slot: u64,
+    /// Block time in Unix seconds
+    pub timestamp: i64,
+    /// Helius transaction type, e.g. `SWAP`
+    #[serde(rename = "type")]
+    pub transaction_type: String,
+    /// Program or protocol the transaction went through, e.g. `RAYDIUM`
+    pub source: String,
+    /// Fee in lamports
+    pub fee: u64,
+    /// Fee payer
+    pub fee_payer: String,
+    /// Human readable summary
+    #[serde(default)]
+    pub description: String,
+    /// SOL transfers
+    #[serde(default)]
+    pub native_transfers: Vec<NativeTransfer>,
+    /// SPL token transfers
+    #[serde(default)]
+    pub token_transfers: Vec<EnhancedTokenTransfer>,
+    /// Error, if the transaction failed
+    #[serde(default)]
+    pub transaction_error: Option<serde_json::Value>,
+    /// Top-level instructions with their inner instructions
+    #[serde(default)]
+    pub instructions: Vec<EnhancedInstruction>,
+}
+
+impl EnhancedTransaction {
+    /// Whether the transaction succeeded
+    pub fn succeeded(&self) -> bool {
+        !matches!(&self.transaction_error, Some(error) if !error.is_null())
+    }
+
+    /// Raw form of the transaction, for the program decoders
+    ///
+    /// Enhanced transactions carry no logs or balances, and instructions
+    /// whose data is not valid base58 are left out.
+    pub fn transaction_info(&self) -> TransactionInfo {
+        let resolve = |instruction: &EnhancedInstruction| {
+            instruction.data.from_base58().ok().map(|data| InstructionInfo {
+                program_id: instruction.program_id.clone(),
+                data,
+                accounts: instruction.accounts.clone(),
+            })
+        };
+
+        TransactionInfo {
+            signature: self.signature.clone(),
+            slot: self.slot,
+            block_time: chrono::DateTime::from_timestamp(self.timestamp, 0),
+            status: if self.succeeded() { TransactionStatus::Success } else { TransactionStatus::Failed },
+            fee: self.fee,
+            instructions: self.instructions.iter().filter_map(resolve).collect(),
+            inner_instructions: self.instructions.iter()
+                .flat_map(|instruction| &instruction.inner_instructions)
+                .filter_map(resolve)
+                .collect(),
+            logs: Vec::new(),
+            balance_changes: Vec::new(),
+            token_balance_changes: Vec::new(),
+        }
+    }
+
+    /// Mints transferred, in order of first transfer
+    pub fn mints(&self) -> Vec<&str> {
+        let mut mints: Vec<&str> = Vec::new();
+        for transfer in &self.token_transfers {
+            if !mints.contains(&transfer.mint.as_str()) {
+                mints.push(&transfer.mint);
+            }
+        }
+        mints
+    }
+
+    /// Mints a pool creation transaction deposits, in order of first transfer
+    ///
+    /// The pool mints its LP token to the creator, so mints the fee payer
+    /// receives are left out.
+    pub fn pool_mints(&self) -> Vec<&str> {
+        self.mints()
+            .into_iter()
+            .filter(|mint| !self.token_transfers.iter()
+                .any(|transfer| transfer.mint == *mint && transfer.to_user_account == self.fee_payer))
+            .collect()
+    }
+}
+
+/// Instruction of an enhanced transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnhancedInstruction {
+    /// Invoked program
+    pub program_id: String,
+    /// Accounts passed to the instruction
+    #[serde(default)]
+    pub accounts: Vec<String>,
+    /// Instruction data, base58 encoded
+    #[serde(default)]
+    pub data: String,
+    /// Instructions invoked by this one
+    #[serde(default)]
+    pub inner_instructions: Vec<EnhancedInstruction>,
+}
+
+/// SOL transfer in an enhanced transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTransfer {
+    /// Sender
+    pub from_user_account: String,
+    /// Recipient
+    pub to_user_account: String,
+    /// Amount in lamports
+    pub amount: u64,
+}
+
+/// SPL token transfer in an enhanced transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnhancedTokenTransfer {
+    /// Sending wallet, empty for mints
+    #[serde(default)]
+    pub from_user_account: String,
+    /// Receiving wallet, empty for burns
+    #[serde(default)]
+    pub to_user_account: String,
+    /// Sending token account
+    #[serde(default)]
+    pub from_token_account: String,
+    /// Receiving token account
+    #[serde(default)]
+    pub to_token_account: String,
+    /// Amount in UI units
+    pub token_amount: f64,
+    /// Mint transferred
+    pub mint: String,
+}
+
 /// Conversion implementations
 
 impl From<HeliusTokenMetadata> for TokenMetadata {
@@ -514,32 +1147,47 @@ impl From<HeliusTokenMetadata> for TokenMetadata {
     }
 }
 
-impl From<HeliusWebhookPayload> for TokenEvent {
-    fn from(payload: HeliusWebhookPayload) -> Self {
-        TokenEvent {
-            signature: payload.signature,
-            timestamp: chrono::Utc::now(), // Parse from payload.timestamp
-            event_type: payload.transaction_type,
-            accounts: payload.accounts,
-            token_transfers: payload.token_transfers.map(|transfers| {
-                transfers.into_iter()
-                    .map(|t| super::types::TokenTransferInfo {
-                        from: t.from,
-                        to: t.to,
-                        amount: t.amount,
-                        mint: t.mint,
-                    })
-                    .collect()
-            }),
-            metadata: payload.metadata,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::ConfigLoader;
+    use wiremock::matchers::{body_partial_json, method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn client(server: &MockServer) -> HeliusClient {
+        let mut config = ConfigLoader::new().without_env().create_default_config().helius;
+        config.api_key = "test-api-key".to_string();
+        config.base_url = server.uri();
+        config.api_url = server.uri();
+        HeliusClient::new(&config).await.unwrap()
+    }
+
+    fn rpc_result(result: serde_json::Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+    }
+
+    fn enhanced_transaction(signature: &str, mint: &str) -> serde_json::Value {
+        serde_json::json!({
+            "signature": signature,
+            "slot": 250_000_000u64,
+            "timestamp": 1_700_000_000,
+            "type": "SWAP",
+            "source": "RAYDIUM",
+            "fee": 5000,
+            "feePayer": "payer",
+            "description": "payer swapped 1 SOL",
+            "nativeTransfers": [{ "fromUserAccount": "payer", "toUserAccount": "pool", "amount": 1_000_000_000u64 }],
+            "tokenTransfers": [{
+                "fromUserAccount": "pool",
+                "toUserAccount": "payer",
+                "fromTokenAccount": "vault",
+                "toTokenAccount": "ata",
+                "tokenAmount": 1250.5,
+                "mint": mint,
+            }],
+            "transactionError": null,
+        })
+    }
 
     #[tokio::test]
     async fn test_helius_client_creation() {
@@ -556,9 +1204,256 @@ mod tests {
 
     #[test]
     fn test_rate_limiter() {
-        let limiter = RateLimiter::new(10); // 10 requests per second
-        // Basic creation test
-        assert_eq!(limiter.limit, 10);
+        let mut limiter = TokenBucket::new(10); // 10 requests per second
+        assert_eq!(limiter.rate, 10);
+
+        // A full bucket allows a one second burst, then paces requests
+        let now = Instant::now();
+        for _ in 0..10 {
+            assert!(limiter.try_acquire(now).is_ok());
+        }
+        let wait = limiter.try_acquire(now).unwrap_err();
+        assert!(wait > Duration::from_millis(90) && wait <= Duration::from_millis(100));
+        assert!(limiter.try_acquire(now + Duration::from_millis(150)).is_ok());
+
+        // Retry-After pauses the bucket entirely
+        let paused_at = now + Duration::from_secs(1);
+        limiter.pause(paused_at, Duration::from_secs(2));
+        assert_eq!(limiter.try_acquire(paused_at).unwrap_err(), Duration::from_secs(2));
+        assert!(limiter.try_acquire(paused_at + Duration::from_secs(2)).is_err());
+        assert!(limiter.try_acquire(paused_at + Duration::from_millis(2200)).is_ok());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "3".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+        headers.insert(RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_honors_retry_after() {
+        let server = MockServer::start().await;
+        let asset = serde_json::json!({ "id": "mint", "interface": "FungibleToken" });
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(rpc_result(asset))
+            .mount(&server)
+            .await;
+
+        let helius = client(&server).await;
+        let started = Instant::now();
+        let asset = helius.get_asset("mint").await.unwrap();
+
+        assert_eq!(asset.id, "mint");
+        assert!(started.elapsed() >= Duration::from_secs(1));
+
+        let stats = helius.get_statistics().await;
+        assert_eq!((stats.successful_requests, stats.failed_requests), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn test_das_assets_and_token_account_pages() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": "getAsset", "params": { "id": "mint" } })))
+            .respond_with(rpc_result(serde_json::json!({
+                "id": "mint",
+                "interface": "FungibleToken",
+                "content": {
+                    "json_uri": "https://example.com/mint.json",
+                    "metadata": { "name": "Example", "symbol": "EXM" },
+                    "links": { "image": "https://example.com/mint.png" },
+                },
+                "authorities": [{ "address": "update-authority", "scopes": ["full"] }],
+                "mutable": true,
+                "burnt": false,
+                "token_info": {
+                    "symbol": "EXM",
+                    "supply": 1_000_000_000u64,
+                    "decimals": 6,
+                    "token_program": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                    "mint_authority": null,
+                    "price_info": { "price_per_token": 0.0042, "currency": "USDC" },
+                },
+            })))
+            .mount(&server)
+            .await;
+
+        let accounts = |count: usize, offset: usize| -> Vec<serde_json::Value> {
+            (offset..offset + count)
+                .map(|i| serde_json::json!({ "address": format!("ata-{}", i), "mint": "mint", "owner": format!("owner-{}", i), "amount": i }))
+                .collect()
+        };
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": "getTokenAccounts", "params": { "mint": "mint", "cursor": "page-2" } })))
+            .respond_with(rpc_result(serde_json::json!({
+                "total": 3, "limit": 500, "cursor": "page-3", "token_accounts": accounts(3, 1000),
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": "getTokenAccounts", "params": { "mint": "mint", "limit": 1000 } })))
+            .respond_with(rpc_result(serde_json::json!({
+                "total": 1000, "limit": 1000, "cursor": "page-2", "token_accounts": accounts(1000, 0),
+            })))
+            .mount(&server)
+            .await;
+
+        let helius = client(&server).await;
+
+        let asset = helius.get_asset("mint").await.unwrap();
+        let content = asset.content.unwrap();
+        assert_eq!(content.metadata.symbol.as_deref(), Some("EXM"));
+        assert_eq!(content.links.unwrap().image.as_deref(), Some("https://example.com/mint.png"));
+        let token_info = asset.token_info.unwrap();
+        assert_eq!((token_info.decimals, token_info.supply), (Some(6), Some(1_000_000_000)));
+        assert_eq!(token_info.mint_authority, None);
+        assert_eq!(token_info.price_info.unwrap().price_per_token, 0.0042);
+
+        // A short page ends paging even though it carries a cursor
+        let holders = helius.get_all_token_accounts(&TokenAccountFilter::Mint("mint".to_string()), 1500).await.unwrap();
+        assert_eq!(holders.len(), 1003);
+        assert_eq!(holders[1002].address, "ata-1002");
+    }
+
+    #[tokio::test]
+    async fn test_enhanced_transaction_pages() {
+        let server = MockServer::start().await;
+        let transactions_path = "/addresses/pool/transactions";
+
+        let page: Vec<serde_json::Value> = (0..100)
+            .map(|i| enhanced_transaction(&format!("sig-{}", i), "mint-a"))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path(transactions_path))
+            .and(query_param("api-key", "test-api-key"))
+            .and(query_param("type", "SWAP"))
+            .and(query_param_is_missing("before"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(transactions_path))
+            .and(query_param("before", "sig-99"))
+            .and(query_param("limit", "50"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+                enhanced_transaction("sig-100", "mint-b"),
+            ]))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/transactions"))
+            .and(body_partial_json(serde_json::json!({ "transactions": ["sig-100"] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+                enhanced_transaction("sig-100", "mint-b"),
+            ]))
+            .mount(&server)
+            .await;
+
+        let helius = client(&server).await;
+        let query = EnhancedTransactionQuery {
+            transaction_type: Some("SWAP".to_string()),
+            ..Default::default()
+        };
+
+        let transactions = helius.get_all_enhanced_transactions("pool", &query, 150).await.unwrap();
+        assert_eq!(transactions.len(), 101);
+
+        let last = &transactions[100];
+        assert_eq!(last.signature, "sig-100");
+        assert_eq!(last.native_transfers[0].amount, 1_000_000_000);
+        assert_eq!(last.token_transfers[0].token_amount, 1250.5);
+        assert_eq!(last.mints(), vec!["mint-b"]);
+        assert!(last.succeeded());
+
+        let parsed = helius.parse_transactions(&["sig-100".to_string()]).await.unwrap();
+        assert_eq!(parsed[0].transaction_type, "SWAP");
+        assert_eq!(parsed[0].fee_payer, "payer");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_lifecycle() {
+        let server = MockServer::start().await;
+        let webhook = |addresses: &[&str]| serde_json::json!({
+            "webhookID": "webhook-1",
+            "webhookURL": "https://bot.example.com/webhook",
+            "transactionTypes": ["CREATE_POOL"],
+            "accountAddresses": addresses,
+            "webhookType": "enhanced",
+        });
+
+        Mock::given(method("POST"))
+            .and(path("/webhooks"))
+            .and(query_param("api-key", "test-api-key"))
+            .and(body_partial_json(serde_json::json!({
+                "webhookURL": "https://bot.example.com/webhook",
+                "accountAddresses": ["program"],
+                "authHeader": "secret",
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(webhook(&["program"])))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/webhooks/webhook-1"))
+            .and(body_partial_json(serde_json::json!({ "accountAddresses": ["program", "wallet"] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(webhook(&["program", "wallet"])))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/webhooks/webhook-1"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let helius = client(&server).await;
+        let mut registration = WebhookRegistration {
+            webhook_url: "https://bot.example.com/webhook".to_string(),
+            transaction_types: vec!["CREATE_POOL".to_string()],
+            account_addresses: vec!["program".to_string()],
+            webhook_type: "enhanced".to_string(),
+            auth_header: Some("secret".to_string()),
+        };
+
+        let created = helius.create_webhook(&registration).await.unwrap();
+        assert_eq!(created.webhook_id, "webhook-1");
+
+        registration.account_addresses.push("wallet".to_string());
+        let updated = helius.update_webhook(&created.webhook_id, &registration).await.unwrap();
+        assert_eq!(updated.account_addresses, vec!["program", "wallet"]);
+
+        // The empty delete response is not a parse failure
+        helius.delete_webhook(&created.webhook_id).await.unwrap();
+    }
+
+    #[test]
+    fn test_pool_mints_exclude_lp_mint() {
+        let transfer = |from: &str, to: &str, mint: &str| serde_json::json!({
+            "fromUserAccount": from,
+            "toUserAccount": to,
+            "tokenAmount": 1.0,
+            "mint": mint,
+        });
+        let mut transaction = enhanced_transaction("sig-pool", "unused");
+        transaction["type"] = serde_json::json!("CREATE_POOL");
+        transaction["tokenTransfers"] = serde_json::json!([
+            transfer("payer", "pool", "new-mint"),
+            transfer("payer", "pool", "So11111111111111111111111111111111111111112"),
+            transfer("", "payer", "lp-mint"),
+        ]);
+        let transaction: EnhancedTransaction = serde_json::from_value(transaction).unwrap();
+
+        assert_eq!(transaction.mints().len(), 3);
+        assert_eq!(
+            transaction.pool_mints(),
+            vec!["new-mint", "So11111111111111111111111111111111111111112"]
+        );
     }
 
     #[test]
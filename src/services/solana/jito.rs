@@ -0,0 +1,590 @@
+//! Jito block-engine bundle submission
+//!
+//! Bundlers win races by paying validators directly. The tip transfer is
+//! the last instruction of the swap transaction itself, so the tip is only
+//! paid if the swap executes and the bundle cannot be split to land the tip
+//! alone. This client submits the tipped swap as a bundle to every
+//! configured block engine and polls its status. A bundle that has not
+//! landed within `JitoConfig::max_bundle_slots` is reported as expired so
+//! the caller can fall back to a normal send of the same transaction.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{
+    instruction::Instruction,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    system_instruction,
+    transaction::VersionedTransaction,
+};
+use std::str::FromStr;
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::config::models::JitoConfig;
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use super::fees::FeeUrgency;
+
+/// Bundle API path on a block engine
+const BUNDLES_PATH: &str = "/api/v1/bundles";
+
+/// How long a fetched tip floor is reused
+const TIP_FLOOR_TTL: Duration = Duration::from_secs(10);
+
+/// Expected slot time, used to bound status polling when slots are unknown
+const SLOT_DURATION: Duration = Duration::from_millis(400);
+
+/// Tip transfer to append as the last instruction of a bundled transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BundleTip {
+    /// Tip account receiving the transfer
+    pub account: Pubkey,
+    /// Tip in lamports
+    pub lamports: u64,
+}
+
+impl BundleTip {
+    /// Transfer instruction paying the tip from `payer`
+    pub fn instruction(&self, payer: &Pubkey) -> Instruction {
+        system_instruction::transfer(payer, &self.account, self.lamports)
+    }
+
+    /// Whether the message's last instruction pays this tip
+    ///
+    /// The tip account has to be a static key; tip accounts loaded through
+    /// a lookup table are not accepted by the block engine.
+    pub fn is_paid_by(&self, message: &VersionedMessage) -> bool {
+        let keys = message.static_account_keys();
+        let Some(last) = message.instructions().last() else {
+            return false;
+        };
+        let Some(payer) = last.accounts.first().and_then(|index| keys.get(*index as usize)) else {
+            return false;
+        };
+
+        keys.get(last.program_id_index as usize) == Some(&solana_sdk::system_program::id())
+            && last.accounts.get(1).and_then(|index| keys.get(*index as usize)) == Some(&self.account)
+            && last.data == self.instruction(payer).data
+    }
+}
+
+/// Final state of a bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum BundleStatus {
+    /// The bundle landed
+    Landed {
+        /// Slot the bundle landed in
+        slot: u64,
+    },
+    /// The block engine rejected or dropped the bundle
+    Failed {
+        /// Status reported by the block engine
+        reason: String,
+    },
+    /// The bundle did not land within the slot limit
+    Expired {
+        /// Slots waited
+        slots_waited: u64,
+    },
+}
+
+/// Outcome of submitting a bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleOutcome {
+    /// Bundle identifier
+    pub bundle_id: String,
+    /// Tip paid if the bundle lands, in lamports
+    pub tip_lamports: u64,
+    /// Tip account used
+    pub tip_account: String,
+    /// Final state
+    pub status: BundleStatus,
+    /// Time from submission to the final state in milliseconds
+    pub elapsed_ms: u64,
+}
+
+impl BundleOutcome {
+    /// Whether the bundle landed
+    pub fn landed(&self) -> bool {
+        matches!(self.status, BundleStatus::Landed { .. })
+    }
+}
+
+/// Bundle statistics
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct JitoStatistics {
+    /// Bundles submitted
+    pub bundles_submitted: u64,
+    /// Bundles that landed
+    pub bundles_landed: u64,
+    /// Bundles the block engine rejected or dropped
+    pub bundles_failed: u64,
+    /// Bundles that expired, falling back to a normal send
+    pub bundles_expired: u64,
+    /// Tips paid by landed bundles in lamports
+    pub tips_paid_lamports: u64,
+}
+
+/// Landed tip percentiles in SOL
+#[derive(Debug, Clone, Deserialize)]
+struct TipFloor {
+    landed_tips_25th_percentile: f64,
+    landed_tips_50th_percentile: f64,
+    landed_tips_75th_percentile: f64,
+    landed_tips_95th_percentile: f64,
+}
+
+impl TipFloor {
+    /// Tip in lamports for an urgency
+    fn lamports(&self, urgency: FeeUrgency) -> u64 {
+        let sol = match urgency {
+            FeeUrgency::Low => self.landed_tips_25th_percentile,
+            FeeUrgency::Medium => self.landed_tips_50th_percentile,
+            FeeUrgency::High => self.landed_tips_75th_percentile,
+            FeeUrgency::VeryHigh => self.landed_tips_95th_percentile,
+        };
+        (sol * 1_000_000_000.0).round() as u64
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InflightStatuses {
+    context: StatusContext,
+    #[serde(default)]
+    value: Vec<InflightStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusContext {
+    slot: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct InflightStatus {
+    status: String,
+    landed_slot: Option<u64>,
+}
+
+/// Jito block-engine client
+#[derive(Debug)]
+pub struct JitoClient {
+    /// HTTP client
+    http_client: Client,
+    /// Jito configuration
+    config: JitoConfig,
+    /// Tip accounts, from config or the block engine
+    tip_accounts: RwLock<Vec<Pubkey>>,
+    /// Round-robin index into the tip accounts
+    next_tip_account: AtomicUsize,
+    /// Cached tip floor and when it was fetched
+    tip_floor: RwLock<Option<(Instant, TipFloor)>>,
+    /// Bundle statistics
+    stats: Arc<RwLock<JitoStatistics>>,
+}
+
+impl JitoClient {
+    /// Create a Jito client
+    pub fn new(config: &JitoConfig) -> AppResult<Self> {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .map_err(|e| AppError::network(format!("Failed to create HTTP client: {}", e)))?;
+
+        let tip_accounts = config.tip_accounts.iter()
+            .map(|account| Pubkey::from_str(account)
+                .map_err(|e| AppError::config(format!("Invalid Jito tip account {}: {}", account, e))))
+            .collect::<AppResult<Vec<_>>>()?;
+
+        info!("🥷 Jito bundles enabled via {} block engines", config.block_engine_urls.len());
+
+        Ok(Self {
+            http_client,
+            config: config.clone(),
+            tip_accounts: RwLock::new(tip_accounts),
+            next_tip_account: AtomicUsize::new(0),
+            tip_floor: RwLock::new(None),
+            stats: Arc::new(RwLock::new(JitoStatistics::default())),
+        })
+    }
+
+    /// Tip for the next bundle at an urgency
+    ///
+    /// Append `BundleTip::instruction` as the last instruction of the
+    /// transaction before signing it.
+    pub async fn tip(&self, urgency: FeeUrgency) -> AppResult<BundleTip> {
+        let lamports = self.tip_lamports(urgency).await;
+        let account = self.tip_account().await?;
+        Ok(BundleTip { account, lamports })
+    }
+
+    /// Submit a tipped transaction as a bundle and wait for it to land
+    ///
+    /// The transaction's last instruction must pay `tip`.
+    pub async fn submit(&self, transaction: &VersionedTransaction, tip: &BundleTip) -> AppResult<BundleOutcome> {
+        if !tip.is_paid_by(&transaction.message) {
+            return Err(AppError::validation(format!(
+                "Bundled transaction must end with a {} lamport tip to {}",
+                tip.lamports, tip.account
+            )));
+        }
+
+        let started = Instant::now();
+        let bundle_id = self.send_bundle(std::slice::from_ref(transaction)).await?;
+        debug!("🥷 Bundle {} submitted with {} lamport tip", bundle_id, tip.lamports);
+
+        let status = self.wait_for_bundle(&bundle_id).await;
+        let tip_lamports = tip.lamports;
+        let outcome = BundleOutcome {
+            bundle_id,
+            tip_lamports,
+            tip_account: tip.account.to_string(),
+            status,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+
+        let mut stats = self.stats.write().await;
+        stats.bundles_submitted += 1;
+        match &outcome.status {
+            BundleStatus::Landed { slot } => {
+                info!("🥷 Bundle {} landed in slot {}", outcome.bundle_id, slot);
+                stats.bundles_landed += 1;
+                stats.tips_paid_lamports += tip_lamports;
+            }
+            BundleStatus::Failed { reason } => {
+                warn!("Bundle {} failed: {}", outcome.bundle_id, reason);
+                stats.bundles_failed += 1;
+            }
+            BundleStatus::Expired { slots_waited } => {
+                warn!("Bundle {} not landed after {} slots", outcome.bundle_id, slots_waited);
+                stats.bundles_expired += 1;
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Tip for an urgency, from the tip floor when configured
+    ///
+    /// Falls back to the minimum tip when no floor is available.
+    pub async fn tip_lamports(&self, urgency: FeeUrgency) -> u64 {
+        let floor = match self.fetch_tip_floor().await {
+            Some(floor) => (floor.lamports(urgency) as f64 * self.config.tip_multiplier) as u64,
+            None => self.config.min_tip_lamports,
+        };
+
+        floor.clamp(self.config.min_tip_lamports, self.config.max_tip_lamports)
+    }
+
+    async fn fetch_tip_floor(&self) -> Option<TipFloor> {
+        let url = self.config.tip_floor_url.as_ref()?;
+
+        if let Some((fetched_at, floor)) = self.tip_floor.read().await.as_ref() {
+            if fetched_at.elapsed() < TIP_FLOOR_TTL {
+                return Some(floor.clone());
+            }
+        }
+
+        let response = self.http_client.get(url).send().await
+            .and_then(|response| response.error_for_status());
+        let floors: Vec<TipFloor> = match response {
+            Ok(response) => match response.json().await {
+                Ok(floors) => floors,
+                Err(e) => {
+                    warn!("Failed to parse Jito tip floor: {}", e);
+                    return None;
+                }
+            },
+            Err(e) => {
+                warn!("Failed to fetch Jito tip floor: {}", e);
+                return None;
+            }
+        };
+
+        let floor = floors.into_iter().next()?;
+        *self.tip_floor.write().await = Some((Instant::now(), floor.clone()));
+        Some(floor)
+    }
+
+    /// Next tip account, fetching the list from the block engine if needed
+    pub async fn tip_account(&self) -> AppResult<Pubkey> {
+        if self.tip_accounts.read().await.is_empty() {
+            let accounts: Vec<String> = self.call_any("getTipAccounts", serde_json::json!([])).await?;
+            let accounts = accounts.iter()
+                .filter_map(|account| Pubkey::from_str(account).ok())
+                .collect::<Vec<_>>();
+            *self.tip_accounts.write().await = accounts;
+        }
+
+        let accounts = self.tip_accounts.read().await;
+        if accounts.is_empty() {
+            return Err(AppError::network("Block engine returned no tip accounts"));
+        }
+
+        let index = self.next_tip_account.fetch_add(1, Ordering::Relaxed) % accounts.len();
+        Ok(accounts[index])
+    }
+
+    /// Submit a bundle to every block engine; returns the bundle id
+    pub async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> AppResult<String> {
+        let encoded = transactions.iter()
+            .map(|transaction| {
+                bincode::serde::encode_to_vec(transaction, bincode::config::legacy())
+                    .map(|bytes| BASE64.encode(bytes))
+                    .map_err(|e| AppError::internal(format!("Failed to serialize transaction: {}", e)))
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        self.call_any("sendBundle", serde_json::json!([encoded, { "encoding": "base64" }])).await
+    }
+
+    /// Poll the bundle until it lands, fails or exceeds the slot limit
+    async fn wait_for_bundle(&self, bundle_id: &str) -> BundleStatus {
+        let poll_interval = Duration::from_millis(self.config.status_poll_interval_ms);
+        // Bounds polling if the block engine stops reporting slots
+        let deadline = Instant::now() + SLOT_DURATION * (self.config.max_bundle_slots as u32 * 2 + 2);
+        let mut first_slot = None;
+        let mut slots_waited = 0;
+
+        loop {
+            tokio::time::sleep(poll_interval).await;
+
+            match self.call_any::<InflightStatuses>("getInflightBundleStatuses", serde_json::json!([[bundle_id]])).await {
+                Ok(statuses) => {
+                    let first_slot = *first_slot.get_or_insert(statuses.context.slot);
+                    slots_waited = statuses.context.slot.saturating_sub(first_slot);
+
+                    match statuses.value.first() {
+                        Some(status) if status.status == "Landed" => {
+                            return BundleStatus::Landed {
+                                slot: status.landed_slot.unwrap_or(statuses.context.slot),
+                            };
+                        }
+                        Some(status) if status.status == "Failed" || status.status == "Invalid" => {
+                            return BundleStatus::Failed { reason: status.status.clone() };
+                        }
+                        _ => {}
+                    }
+                }
+                Err(e) => debug!("Bundle status poll for {} failed: {}", bundle_id, e),
+            }
+
+            if slots_waited >= self.config.max_bundle_slots || Instant::now() >= deadline {
+                return BundleStatus::Expired { slots_waited };
+            }
+        }
+    }
+
+    /// Call a bundle API method on every block engine; the first success wins
+    async fn call_any<T>(&self, method: &str, params: serde_json::Value) -> AppResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        if self.config.block_engine_urls.is_empty() {
+            return Err(AppError::config("No Jito block-engine URLs configured"));
+        }
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let calls = self.config.block_engine_urls.iter()
+            .map(|url| Box::pin(self.call(url, &request)));
+
+        let (result, _) = futures::future::select_ok(calls).await?;
+        Ok(result)
+    }
+
+    async fn call<T>(&self, url: &str, request: &serde_json::Value) -> AppResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let url = format!("{}{}", url.trim_end_matches('/'), BUNDLES_PATH);
+        let response = self.http_client.post(&url).json(request).send().await
+            .map_err(|e| AppError::network(format!("Block engine request to {} failed: {}", url, e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::network(format!("Block engine error ({}): {}", status, error_text)));
+        }
+
+        let response: JsonRpcResponse<T> = response.json().await
+            .map_err(|e| AppError::network(format!("Failed to parse block engine response: {}", e)))?;
+
+        match response.result {
+            Some(result) => Ok(result),
+            None => Err(AppError::network(format!(
+                "Block engine error: {}",
+                response.error.map(|e| e.to_string()).unwrap_or_else(|| "empty response".to_string())
+            ))),
+        }
+    }
+
+    /// Get bundle statistics
+    pub async fn statistics(&self) -> JitoStatistics {
+        self.stats.read().await.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        hash::Hash,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn rpc_result(result: serde_json::Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+    }
+
+    fn inflight(slot: u64, status: &str) -> ResponseTemplate {
+        rpc_result(serde_json::json!({
+            "context": { "slot": slot },
+            "value": [{ "bundle_id": "bundle-1", "status": status, "landed_slot": (status == "Landed").then_some(slot) }],
+        }))
+    }
+
+    async fn mock_method(server: &MockServer, name: &str, response: ResponseTemplate, times: Option<u64>) {
+        let mock = Mock::given(method("POST"))
+            .and(path(BUNDLES_PATH))
+            .and(body_partial_json(serde_json::json!({ "method": name })))
+            .respond_with(response);
+        match times {
+            Some(times) => mock.up_to_n_times(times).mount(server).await,
+            None => mock.mount(server).await,
+        }
+    }
+
+    fn client(server: &MockServer) -> JitoClient {
+        let config = JitoConfig {
+            enabled: true,
+            block_engine_urls: vec![server.uri()],
+            tip_floor_url: Some(format!("{}/tip_floor", server.uri())),
+            tip_accounts: vec![],
+            min_tip_lamports: 1_000,
+            max_tip_lamports: 100_000,
+            tip_multiplier: 1.5,
+            max_bundle_slots: 3,
+            status_poll_interval_ms: 5,
+        };
+        JitoClient::new(&config).unwrap()
+    }
+
+    fn swap(payer: &Keypair, tip: Option<&BundleTip>) -> VersionedTransaction {
+        let mut instructions = vec![system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1)];
+        instructions.extend(tip.map(|tip| tip.instruction(&payer.pubkey())));
+        Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[payer], Hash::default()).into()
+    }
+
+    async fn bundled_transactions(server: &MockServer) -> Vec<usize> {
+        server.received_requests().await.unwrap_or_default().iter()
+            .filter_map(|request| serde_json::from_slice::<serde_json::Value>(&request.body).ok())
+            .filter(|body| body["method"] == "sendBundle")
+            .map(|body| body["params"][0].as_array().map_or(0, Vec::len))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_bundle_lands() {
+        let server = MockServer::start().await;
+        let tip_account = Pubkey::new_unique();
+
+        Mock::given(method("GET"))
+            .and(path("/tip_floor"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "landed_tips_25th_percentile": 0.000001,
+                "landed_tips_50th_percentile": 0.00001,
+                "landed_tips_75th_percentile": 0.00002,
+                "landed_tips_95th_percentile": 0.001,
+            }])))
+            .mount(&server)
+            .await;
+        mock_method(&server, "getTipAccounts", rpc_result(serde_json::json!([tip_account.to_string()])), None).await;
+        mock_method(&server, "sendBundle", rpc_result(serde_json::json!("bundle-1")), None).await;
+        mock_method(&server, "getInflightBundleStatuses", inflight(100, "Pending"), Some(2)).await;
+        mock_method(&server, "getInflightBundleStatuses", inflight(101, "Landed"), None).await;
+
+        let jito = client(&server);
+        let payer = Keypair::new();
+        let tip = jito.tip(FeeUrgency::High).await.unwrap();
+        let outcome = jito.submit(&swap(&payer, Some(&tip)), &tip).await.unwrap();
+
+        assert_eq!(outcome.bundle_id, "bundle-1");
+        // The tip travels inside the swap, not as a second transaction
+        assert_eq!(bundled_transactions(&server).await, vec![1]);
+        assert_eq!(outcome.status, BundleStatus::Landed { slot: 101 });
+        assert_eq!(outcome.tip_account, tip_account.to_string());
+        // 75th percentile of 20k lamports, times 1.5
+        assert_eq!(outcome.tip_lamports, 30_000);
+
+        // The 95th percentile is clamped to the max tip
+        assert_eq!(jito.tip_lamports(FeeUrgency::VeryHigh).await, 100_000);
+
+        let stats = jito.statistics().await;
+        assert_eq!((stats.bundles_landed, stats.tips_paid_lamports), (1, 30_000));
+    }
+
+    #[tokio::test]
+    async fn test_bundle_expires_after_max_slots() {
+        let server = MockServer::start().await;
+
+        mock_method(&server, "getTipAccounts", rpc_result(serde_json::json!([Pubkey::new_unique().to_string()])), None).await;
+        mock_method(&server, "sendBundle", rpc_result(serde_json::json!("bundle-1")), None).await;
+        for slot in 200..210 {
+            mock_method(&server, "getInflightBundleStatuses", inflight(slot, "Pending"), Some(1)).await;
+        }
+
+        let jito = client(&server);
+        let payer = Keypair::new();
+        let tip = jito.tip(FeeUrgency::Medium).await.unwrap();
+        let outcome = jito.submit(&swap(&payer, Some(&tip)), &tip).await.unwrap();
+
+        assert_eq!(outcome.status, BundleStatus::Expired { slots_waited: 3 });
+        assert!(!outcome.landed());
+        // The tip floor is unreachable, so the minimum tip is used
+        assert_eq!(outcome.tip_lamports, 1_000);
+        assert_eq!(jito.statistics().await.bundles_expired, 1);
+    }
+
+    #[tokio::test]
+    async fn test_untipped_transaction_rejected() {
+        let server = MockServer::start().await;
+        let jito = client(&server);
+        let payer = Keypair::new();
+        let tip = BundleTip { account: Pubkey::new_unique(), lamports: 5_000 };
+
+        assert!(jito.submit(&swap(&payer, None), &tip).await.is_err());
+
+        // A tip that is not the last instruction does not count
+        let mut instructions = vec![tip.instruction(&payer.pubkey())];
+        instructions.push(system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1));
+        let transaction = Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[&payer], Hash::default());
+        assert!(!tip.is_paid_by(&VersionedTransaction::from(transaction).message));
+
+        // Nor does a smaller tip
+        let smaller = BundleTip { lamports: 4_999, ..tip };
+        assert!(!smaller.is_paid_by(&swap(&payer, Some(&tip)).message));
+        assert!(tip.is_paid_by(&swap(&payer, Some(&tip)).message));
+
+        assert!(bundled_transactions(&server).await.is_empty());
+    }
+}
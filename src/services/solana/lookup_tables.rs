@@ -0,0 +1,208 @@
+//! Address lookup tables and versioned transactions
+//!
+//! Jupiter routes and multi-hop swaps touch more accounts than fit in a
+//! legacy transaction, so they are built as v0 messages that reference
+//! accounts through address lookup tables. Tables are cached since routes
+//! keep reusing the same few, and lookups in received messages are resolved
+//! against the cache.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, AddressLookupTableAccount, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::VersionedTransaction,
+};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use super::rpc::RpcPool;
+
+/// How long a cached lookup table is trusted
+///
+/// Tables are append-only while active, so a stale copy only misses newly
+/// added addresses; compiling against it still produces a valid message.
+const LOOKUP_TABLE_TTL: Duration = Duration::from_secs(300);
+
+/// Accounts of a message, with lookup table addresses resolved
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedAccounts {
+    /// Accounts in message index order: static, loaded writable, loaded readonly
+    pub account_keys: Vec<Pubkey>,
+    /// Accounts the message may write to
+    pub writable: Vec<Pubkey>,
+}
+
+/// Cache of address lookup tables
+#[derive(Debug)]
+pub struct LookupTableCache {
+    /// Pool used to fetch tables
+    pool: Arc<RpcPool>,
+    /// Cached tables and when they were fetched
+    tables: RwLock<HashMap<Pubkey, (Instant, AddressLookupTableAccount)>>,
+}
+
+impl LookupTableCache {
+    /// Create an empty cache
+    pub fn new(pool: Arc<RpcPool>) -> Self {
+        Self {
+            pool,
+            tables: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get lookup tables, fetching missing or expired ones in one request
+    pub async fn get(&self, addresses: &[Pubkey]) -> AppResult<Vec<AddressLookupTableAccount>> {
+        let missing: Vec<Pubkey> = {
+            let tables = self.tables.read().await;
+            addresses.iter()
+                .filter(|address| !matches!(tables.get(address), Some((fetched_at, _)) if fetched_at.elapsed() < LOOKUP_TABLE_TTL))
+                .copied()
+                .collect()
+        };
+
+        if !missing.is_empty() {
+            debug!("📇 Fetching {} lookup tables", missing.len());
+            let connection = self.pool.get_connection().await?;
+            let fetched = connection.get_address_lookup_tables(&missing).await?;
+
+            let mut tables = self.tables.write().await;
+            for table in fetched {
+                tables.insert(table.key, (Instant::now(), table));
+            }
+        }
+
+        let tables = self.tables.read().await;
+        addresses.iter()
+            .map(|address| tables.get(address)
+                .map(|(_, table)| table.clone())
+                .ok_or_else(|| AppError::internal(format!("Lookup table {} missing from cache", address))))
+            .collect()
+    }
+
+    /// Drop a table, e.g. after a message failed to compile against it
+    pub async fn invalidate(&self, address: &Pubkey) {
+        self.tables.write().await.remove(address);
+    }
+
+    /// Resolve the accounts of a legacy or v0 message
+    pub async fn resolve(&self, message: &VersionedMessage) -> AppResult<ResolvedAccounts> {
+        let lookups = message.address_table_lookups().unwrap_or_default();
+        let table_keys: Vec<Pubkey> = lookups.iter().map(|lookup| lookup.account_key).collect();
+        let tables = if table_keys.is_empty() {
+            Vec::new()
+        } else {
+            self.get(&table_keys).await?
+        };
+
+        resolve_accounts(message, &tables)
+    }
+}
+
+/// Resolve message accounts against already fetched lookup tables
+///
+/// `tables` must hold every table the message references.
+pub fn resolve_accounts(message: &VersionedMessage, tables: &[AddressLookupTableAccount]) -> AppResult<ResolvedAccounts> {
+    let static_keys = message.static_account_keys();
+    let mut writable: Vec<Pubkey> = static_keys.iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_maybe_writable(*index, None))
+        .map(|(_, key)| *key)
+        .collect();
+
+    let mut loaded_writable = Vec::new();
+    let mut loaded_readonly = Vec::new();
+
+    for lookup in message.address_table_lookups().unwrap_or_default() {
+        let table = tables.iter()
+            .find(|table| table.key == lookup.account_key)
+            .ok_or_else(|| AppError::validation(format!("Lookup table {} not loaded", lookup.account_key)))?;
+
+        let load = |indexes: &[u8], into: &mut Vec<Pubkey>| -> AppResult<()> {
+            for index in indexes {
+                let address = table.addresses.get(*index as usize).ok_or_else(|| AppError::validation(format!(
+                    "Lookup table {} has no index {}", table.key, index
+                )))?;
+                into.push(*address);
+            }
+            Ok(())
+        };
+
+        load(&lookup.writable_indexes, &mut loaded_writable)?;
+        load(&lookup.readonly_indexes, &mut loaded_readonly)?;
+    }
+
+    writable.extend_from_slice(&loaded_writable);
+
+    let mut account_keys = static_keys.to_vec();
+    account_keys.extend(loaded_writable);
+    account_keys.extend(loaded_readonly);
+
+    Ok(ResolvedAccounts { account_keys, writable })
+}
+
+/// Build and sign a v0 transaction that uses `lookup_tables`
+///
+/// The payer signs first; `signers` holds any other required signers.
+pub fn build_v0_transaction(
+    payer: &Keypair,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+    signers: &[&Keypair],
+) -> AppResult<VersionedTransaction> {
+    let message = v0::Message::try_compile(&payer.pubkey(), instructions, lookup_tables, blockhash)
+        .map_err(|e| AppError::validation(format!("Failed to compile v0 message: {}", e)))?;
+
+    let mut all_signers = vec![payer];
+    all_signers.extend(signers.iter().copied().filter(|signer| signer.pubkey() != payer.pubkey()));
+
+    VersionedTransaction::try_new(VersionedMessage::V0(message), all_signers.as_slice())
+        .map_err(|e| AppError::validation(format!("Failed to sign v0 transaction: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::AccountMeta;
+
+    #[test]
+    fn test_v0_transaction_resolves_through_lookup_table() {
+        let payer = Keypair::new();
+        let pool = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![Pubkey::new_unique(), pool, vault],
+        };
+
+        let swap = Instruction::new_with_bytes(program, &[9], vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(vault, false),
+        ]);
+
+        let transaction = build_v0_transaction(&payer, &[swap], &[table.clone()], Hash::new_unique(), &[]).unwrap();
+        let message = &transaction.message;
+
+        // Pool and vault are loaded through the table, not stored statically
+        assert_eq!(message.static_account_keys(), &[payer.pubkey(), program]);
+        assert_eq!(message.address_table_lookups().unwrap().len(), 1);
+        assert!(transaction.verify_with_results().iter().all(|ok| *ok));
+
+        let resolved = resolve_accounts(message, &[table]).unwrap();
+        assert_eq!(resolved.account_keys, vec![payer.pubkey(), program, pool, vault]);
+        assert_eq!(resolved.writable, vec![payer.pubkey(), pool]);
+
+        // A message referencing an unknown table cannot be resolved
+        assert!(resolve_accounts(message, &[]).is_err());
+    }
+}
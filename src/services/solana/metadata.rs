@@ -0,0 +1,289 @@
+//! Metaplex token metadata decoding
+//!
+//! This module derives the Metaplex metadata PDA for a mint and decodes the
+//! Borsh-encoded metadata account: name, symbol, URI, creators, update
+//! authority, mutability and collection.
+
+use solana_sdk::pubkey::Pubkey;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::core::result::AppResult;
+use crate::core::error::AppError;
+
+/// Metaplex token metadata program
+pub const METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// Account key discriminator of a v1 metadata account
+const METADATA_V1_KEY: u8 = 4;
+
+/// Metadata creator entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataCreator {
+    /// Creator address
+    pub address: String,
+    /// Whether the creator signed the metadata
+    pub verified: bool,
+    /// Royalty share in percent
+    pub share: u8,
+}
+
+/// Metadata collection reference
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataCollection {
+    /// Collection mint
+    pub key: String,
+    /// Whether the collection authority verified membership
+    pub verified: bool,
+}
+
+/// Decoded Metaplex metadata account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetaplexMetadata {
+    /// Update authority
+    pub update_authority: String,
+    /// Token mint
+    pub mint: String,
+    /// Token name
+    pub name: String,
+    /// Token symbol
+    pub symbol: String,
+    /// Off-chain metadata URI
+    pub uri: String,
+    /// Royalty in basis points
+    pub seller_fee_basis_points: u16,
+    /// Creators
+    pub creators: Vec<MetadataCreator>,
+    /// Primary sale happened
+    pub primary_sale_happened: bool,
+    /// Whether the metadata can still be changed
+    pub is_mutable: bool,
+    /// Collection, if any
+    pub collection: Option<MetadataCollection>,
+}
+
+/// Metaplex metadata program id
+pub fn metadata_program_id() -> Pubkey {
+    // The constant is a valid base58 key
+    Pubkey::from_str(METADATA_PROGRAM_ID).unwrap_or_default()
+}
+
+/// Derive the metadata PDA for a mint
+pub fn metadata_pda(mint: &Pubkey) -> Pubkey {
+    let program_id = metadata_program_id();
+    let (pda, _) = Pubkey::find_program_address(
+        &[b"metadata", program_id.as_ref(), mint.as_ref()],
+        &program_id,
+    );
+    pda
+}
+
+/// Decode a Metaplex metadata account
+///
+/// Fields after `is_mutable` were added in later program versions and are
+/// decoded only when present.
+pub fn decode_metadata(data: &[u8]) -> AppResult<MetaplexMetadata> {
+    let mut reader = BorshReader::new(data);
+
+    let key = reader.read_u8()?;
+    if key != METADATA_V1_KEY {
+        return Err(AppError::validation(format!("Not a metadata account (key {})", key)));
+    }
+
+    let update_authority = reader.read_pubkey()?;
+    let mint = reader.read_pubkey()?;
+    let name = reader.read_string()?;
+    let symbol = reader.read_string()?;
+    let uri = reader.read_string()?;
+    let seller_fee_basis_points = reader.read_u16()?;
+
+    let mut creators = Vec::new();
+    if reader.read_bool()? {
+        let count = reader.read_u32()?;
+        for _ in 0..count {
+            creators.push(MetadataCreator {
+                address: reader.read_pubkey()?,
+                verified: reader.read_bool()?,
+                share: reader.read_u8()?,
+            });
+        }
+    }
+
+    let primary_sale_happened = reader.read_bool()?;
+    let is_mutable = reader.read_bool()?;
+
+    Ok(MetaplexMetadata {
+        update_authority,
+        mint,
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        creators,
+        primary_sale_happened,
+        is_mutable,
+        collection: decode_collection(&mut reader),
+    })
+}
+
+/// Decode the optional collection, skipping edition nonce and token standard
+fn decode_collection(reader: &mut BorshReader<'_>) -> Option<MetadataCollection> {
+    // edition_nonce: Option<u8>
+    if reader.read_bool().ok()? {
+        reader.read_u8().ok()?;
+    }
+
+    // token_standard: Option<TokenStandard>
+    if reader.read_bool().ok()? {
+        reader.read_u8().ok()?;
+    }
+
+    // collection: Option<Collection>
+    if !reader.read_bool().ok()? {
+        return None;
+    }
+
+    let verified = reader.read_bool().ok()?;
+    let key = reader.read_pubkey().ok()?;
+    Some(MetadataCollection { key, verified })
+}
+
+/// Minimal Borsh reader over account data
+struct BorshReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BorshReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> AppResult<&'a [u8]> {
+        let end = self.offset.checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| AppError::validation(format!(
+                "Metadata account truncated at offset {}", self.offset
+            )))?;
+
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> AppResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_bool(&mut self) -> AppResult<bool> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(AppError::validation(format!("Invalid bool byte {}", other))),
+        }
+    }
+
+    fn read_u16(&mut self) -> AppResult<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> AppResult<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_pubkey(&mut self) -> AppResult<String> {
+        let bytes = self.take(32)?;
+        let mut key = [0u8; 32];
+        key.copy_from_slice(bytes);
+        Ok(Pubkey::new_from_array(key).to_string())
+    }
+
+    /// Read a string, trimming the NUL padding Metaplex stores
+    fn read_string(&mut self) -> AppResult<String> {
+        let len = self.read_u32()? as usize;
+        let bytes = self.take(len)?;
+        Ok(String::from_utf8_lossy(bytes).trim_end_matches('\0').trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_string(buf: &mut Vec<u8>, value: &str, padded_len: usize) {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.resize(padded_len, 0);
+        buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&bytes);
+    }
+
+    fn encode_metadata(creator: Pubkey, with_collection: bool) -> Vec<u8> {
+        let mut buf = vec![METADATA_V1_KEY];
+        buf.extend_from_slice(Pubkey::new_from_array([1; 32]).as_ref());
+        buf.extend_from_slice(Pubkey::new_from_array([2; 32]).as_ref());
+        push_string(&mut buf, "Bonk", 32);
+        push_string(&mut buf, "BONK", 10);
+        push_string(&mut buf, "https://arweave.net/abc", 200);
+        buf.extend_from_slice(&500u16.to_le_bytes());
+
+        // creators: Some(vec![creator])
+        buf.push(1);
+        buf.extend_from_slice(&1u32.to_le_bytes());
+        buf.extend_from_slice(creator.as_ref());
+        buf.push(1);
+        buf.push(100);
+
+        buf.push(0); // primary_sale_happened
+        buf.push(1); // is_mutable
+
+        if with_collection {
+            buf.extend_from_slice(&[1, 255]); // edition_nonce
+            buf.extend_from_slice(&[1, 2]); // token_standard: Fungible
+            buf.extend_from_slice(&[1, 1]); // collection: verified
+            buf.extend_from_slice(Pubkey::new_from_array([3; 32]).as_ref());
+        }
+
+        buf
+    }
+
+    #[test]
+    fn test_decode_metadata() {
+        let creator = Pubkey::new_from_array([9; 32]);
+        let metadata = decode_metadata(&encode_metadata(creator, true)).unwrap();
+
+        assert_eq!(metadata.name, "Bonk");
+        assert_eq!(metadata.symbol, "BONK");
+        assert_eq!(metadata.uri, "https://arweave.net/abc");
+        assert_eq!(metadata.update_authority, Pubkey::new_from_array([1; 32]).to_string());
+        assert_eq!(metadata.creators, vec![MetadataCreator {
+            address: creator.to_string(),
+            verified: true,
+            share: 100,
+        }]);
+        assert!(metadata.is_mutable);
+        assert!(metadata.collection.unwrap().verified);
+    }
+
+    #[test]
+    fn test_decode_legacy_and_invalid_accounts() {
+        // Accounts created before the collection field decode without it
+        let legacy = decode_metadata(&encode_metadata(Pubkey::new_from_array([9; 32]), false)).unwrap();
+        assert!(legacy.collection.is_none());
+
+        let data = encode_metadata(Pubkey::new_from_array([9; 32]), false);
+        assert!(decode_metadata(&data[..100]).is_err());
+
+        let mut wrong_key = data;
+        wrong_key[0] = 6;
+        assert!(decode_metadata(&wrong_key).is_err());
+    }
+
+    #[test]
+    fn test_metadata_pda_is_deterministic() {
+        let mint = Pubkey::new_from_array([7; 32]);
+        assert_eq!(metadata_pda(&mint), metadata_pda(&mint));
+        assert_ne!(metadata_pda(&mint), mint);
+    }
+}
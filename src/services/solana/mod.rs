@@ -1,17 +1,44 @@
 //! Solana blockchain service module
 //!
 //! This module provides Solana RPC connectivity, Helius API integration,
-//! token metadata retrieval, and optimized blockchain interactions.
+//! Birdeye market data, on-chain pool pricing, token metadata retrieval, and
+//! optimized blockchain interactions.
 
 pub mod rpc;
+pub mod birdeye;
+pub mod blockhash;
+pub mod broadcast;
+pub mod fees;
+pub mod jito;
 pub mod helius;
+pub mod lookup_tables;
+pub mod decoders;
+pub mod metadata;
+pub mod nonce;
+pub mod price_oracle;
 pub mod types;
+pub mod websocket;
 
 // Re-export commonly used types
-pub use rpc::{RpcClient, RpcConnection, RpcPool};
-pub use helius::{HeliusClient, HeliusWebhook};
-pub use types::{TokenMetadata, AccountInfo, TokenAccount, LiquidityPool,TokenEvent};
-
+pub use rpc::{EndpointScore, RpcClient, RpcConnection, RpcPool};
+pub use birdeye::{BirdeyeClient, BirdeyeStats, Candle, CandleInterval, TokenOverview, TokenPrice, TokenTrade};
+pub use blockhash::{BlockhashCache, CachedBlockhash};
+pub use broadcast::{BroadcastOutcome, BroadcastStatistics, BroadcastStatus, TransactionBroadcaster};
+pub use fees::{ComputeBudgetPlan, FeeUrgency, PriorityFeeEstimate, PriorityFeeEstimator};
+pub use helius::{
+    DasAsset, EnhancedInstruction, EnhancedTransaction, EnhancedTransactionQuery, HeliusClient, HeliusWebhook, HeliusWebhookPayload,
+    TokenAccountFilter, WebhookRegistration,
+};
+pub use jito::{BundleOutcome, BundleStatus, BundleTip, JitoClient, JitoStatistics};
+pub use lookup_tables::{LookupTableCache, ResolvedAccounts, build_v0_transaction};
+pub use decoders::{DecodedEvent, DecodedEventKind, DecoderRegistry, ProgramDecoder};
+pub use nonce::{DurableNonce, NoncePool, presign_with_nonce};
+pub use price_oracle::{PoolDex, PoolPrice, PoolReserves, PoolState, PriceOracle, bonding_curve_address};
+pub use metadata::{MetaplexMetadata, MetadataCreator, decode_metadata, metadata_pda};
+pub use types::{TokenMetadata, AccountInfo, MintCreation, TokenAccount, LiquidityPool,TokenEvent, SimulationResult};
+pub use websocket::HeliusWebsocket;
+
+use std::str::FromStr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn, error, instrument};
@@ -21,6 +48,18 @@ use crate::core::result::AppResult;
 use crate::core::error::AppError;
 use crate::application::health::{ComponentHealth, HealthStatus};
 
+/// Helius transaction type of new liquidity pools
+const HELIUS_CREATE_POOL_TYPE: &str = "CREATE_POOL";
+
+/// SPL token instruction tag of `InitializeMint`
+const TOKEN_INITIALIZE_MINT: u8 = 0;
+
+/// SPL token instruction tag of `InitializeMint2`
+const TOKEN_INITIALIZE_MINT2: u8 = 20;
+
+/// Log lines reported for a failed simulation
+const SIMULATION_LOG_TAIL_LINES: usize = 5;
+
 /// Solana blockchain service coordinator
 #[derive(Debug)]
 pub struct SolanaService {
@@ -28,6 +67,20 @@ pub struct SolanaService {
     rpc_pool: Arc<RpcPool>,
     /// Helius API client
     helius_client: Arc<HeliusClient>,
+    /// Multi-endpoint transaction broadcaster
+    broadcaster: Arc<TransactionBroadcaster>,
+    /// Priority fee estimator
+    fee_estimator: Arc<PriorityFeeEstimator>,
+    /// Jito bundle client, if enabled
+    jito: Option<Arc<JitoClient>>,
+    /// Background-refreshed blockhash
+    blockhash_cache: Arc<BlockhashCache>,
+    /// Durable nonce accounts for pre-signed exits
+    nonce_pool: Arc<NoncePool>,
+    /// Address lookup table cache
+    lookup_tables: Arc<LookupTableCache>,
+    /// On-chain price oracle
+    price_oracle: Arc<PriceOracle>,
     /// Service configuration
     config: Arc<AppConfig>,
     /// Connection statistics
@@ -42,10 +95,31 @@ impl SolanaService {
 
         // Create RPC connection pool
         let rpc_pool = Arc::new(RpcPool::new(&config.solana).await?);
+        let broadcaster = Arc::new(TransactionBroadcaster::new(&config.solana, rpc_pool.clone()));
+        let blockhash_cache = Arc::new(BlockhashCache::spawn(
+            rpc_pool.clone(),
+            std::time::Duration::from_millis(config.solana.blockhash_refresh_ms),
+        ));
+        let nonce_pool = Arc::new(NoncePool::new(rpc_pool.clone(), &config.solana.nonce_accounts)?);
+        let lookup_tables = Arc::new(LookupTableCache::new(rpc_pool.clone()));
+        let price_oracle = Arc::new(PriceOracle::new(rpc_pool.clone(), &config.solana)?);
+        let jito = if config.solana.jito.enabled {
+            Some(Arc::new(JitoClient::new(&config.solana.jito)?))
+        } else {
+            None
+        };
 
         // Initialize Helius client
         let helius_client = Arc::new(HeliusClient::new(&config.helius).await?);
 
+        // Prefer Helius fee levels when an API key is configured
+        let fee_helius = (!config.helius.api_key.is_empty()).then(|| helius_client.clone());
+        let fee_estimator = Arc::new(PriorityFeeEstimator::new(
+            rpc_pool.clone(),
+            fee_helius,
+            config.trading.max_priority_fee_sol,
+        ));
+
         // Initialize statistics
         let stats = Arc::new(RwLock::new(ConnectionStats::new()));
 
@@ -56,6 +130,13 @@ impl SolanaService {
         Ok(Self {
             rpc_pool,
             helius_client,
+            broadcaster,
+            fee_estimator,
+            jito,
+            blockhash_cache,
+            nonce_pool,
+            lookup_tables,
+            price_oracle,
             config: Arc::new(config.clone()),
             stats,
         })
@@ -71,6 +152,139 @@ impl SolanaService {
         self.rpc_pool.get_dedicated_client().await
     }
 
+    /// Broadcast a signed transaction through every endpoint until it lands
+    #[instrument(skip(self, transaction))]
+    pub async fn broadcast_transaction(
+        &self,
+        transaction: &solana_sdk::transaction::VersionedTransaction,
+    ) -> AppResult<BroadcastOutcome> {
+        let start_time = std::time::Instant::now();
+        let result = self.broadcaster.broadcast(transaction).await;
+
+        let landed = matches!(&result, Ok(outcome) if matches!(outcome.status, BroadcastStatus::Landed { .. }));
+        self.update_stats("broadcast_transaction", landed, start_time.elapsed()).await;
+
+        result
+    }
+
+    /// Get the latest blockhash, from the cache when fresh
+    pub async fn get_latest_blockhash(&self) -> AppResult<CachedBlockhash> {
+        self.blockhash_cache.get().await
+    }
+
+    /// Get the durable nonce pool
+    pub fn nonce_pool(&self) -> &Arc<NoncePool> {
+        &self.nonce_pool
+    }
+
+    /// Get the address lookup table cache
+    pub fn lookup_tables(&self) -> &Arc<LookupTableCache> {
+        &self.lookup_tables
+    }
+
+    /// Get the on-chain price oracle
+    pub fn price_oracle(&self) -> &Arc<PriceOracle> {
+        &self.price_oracle
+    }
+
+    /// Jito tip to end a transaction with, if bundles are enabled
+    ///
+    /// Append `BundleTip::instruction` as the transaction's last instruction
+    /// and pass the tip on to `submit_transaction`.
+    pub async fn jito_tip(&self, urgency: FeeUrgency) -> AppResult<Option<BundleTip>> {
+        match &self.jito {
+            Some(jito) => Ok(Some(jito.tip(urgency).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Submit a signed transaction, as a Jito bundle when it carries a tip
+    ///
+    /// A bundle that fails or does not land within the configured slots
+    /// falls back to a normal multi-endpoint broadcast of the same
+    /// transaction, tip included. Both paths carry the same signature, so
+    /// the transaction cannot land twice.
+    #[instrument(skip(self, transaction))]
+    pub async fn submit_transaction(
+        &self,
+        transaction: &solana_sdk::transaction::VersionedTransaction,
+        tip: Option<&BundleTip>,
+    ) -> AppResult<SubmissionOutcome> {
+        let bundle = match (&self.jito, tip) {
+            (Some(jito), Some(tip)) => match jito.submit(transaction, tip).await {
+                Ok(outcome) => Some(outcome),
+                Err(e) => {
+                    warn!("Jito bundle submission failed, sending normally: {}", e);
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        if bundle.as_ref().is_some_and(|outcome| outcome.landed()) {
+            return Ok(SubmissionOutcome { bundle, broadcast: None });
+        }
+
+        let broadcast = self.broadcast_transaction(transaction).await?;
+        Ok(SubmissionOutcome { bundle, broadcast: Some(broadcast) })
+    }
+
+    /// Get Jito bundle statistics, if bundles are enabled
+    pub async fn get_jito_statistics(&self) -> Option<JitoStatistics> {
+        match &self.jito {
+            Some(jito) => Some(jito.statistics().await),
+            None => None,
+        }
+    }
+
+    /// Get the priority fee estimator
+    pub fn fee_estimator(&self) -> &Arc<PriorityFeeEstimator> {
+        &self.fee_estimator
+    }
+
+    /// Plan the compute budget of a draft transaction
+    ///
+    /// The draft is simulated to size the compute unit limit, and the fee is
+    /// estimated for the accounts it writes to, including those loaded
+    /// through lookup tables. A failed simulation falls back to the default
+    /// limit rather than failing the trade.
+    #[instrument(skip(self, draft))]
+    pub async fn plan_compute_budget(
+        &self,
+        draft: &solana_sdk::transaction::VersionedTransaction,
+        urgency: FeeUrgency,
+    ) -> AppResult<ComputeBudgetPlan> {
+        let start_time = std::time::Instant::now();
+        let conn = self.get_rpc_client().await?;
+
+        let units_consumed = match conn.simulate_transaction(draft).await {
+            Ok(simulation) if simulation.succeeded() => simulation.units_consumed,
+            Ok(simulation) => {
+                warn!(
+                    "Simulated execution failed, using default compute limit: {}; logs: {}",
+                    simulation.err.as_deref().unwrap_or_default(),
+                    simulation.log_tail(SIMULATION_LOG_TAIL_LINES).join(" | ")
+                );
+                None
+            }
+            Err(e) => {
+                warn!("Simulation for compute budget failed, using default limit: {}", e);
+                None
+            }
+        };
+
+        let writable_accounts = self.lookup_tables.resolve(&draft.message).await?.writable;
+
+        let result = self.fee_estimator.plan(&writable_accounts, urgency, units_consumed).await;
+        self.update_stats("plan_compute_budget", result.is_ok(), start_time.elapsed()).await;
+        result
+    }
+
+    /// Get transaction broadcast statistics
+    pub async fn get_broadcast_statistics(&self) -> BroadcastStatistics {
+        self.broadcaster.statistics().await
+    }
+
     /// Get Helius client
     pub fn helius(&self) -> &Arc<HeliusClient> {
         &self.helius_client
@@ -111,6 +325,27 @@ impl SolanaService {
         conn.get_account_info(address).await
     }
 
+    /// Get account information, or `None` if the account does not exist
+    ///
+    /// Unlike [`Self::get_account_info`], a missing account is not an error,
+    /// so RPC failures can be told apart from absent accounts.
+    #[instrument(skip(self))]
+    pub async fn find_account_info(&self, address: &str) -> AppResult<Option<AccountInfo>> {
+        let pubkey = solana_sdk::pubkey::Pubkey::from_str(address)
+            .map_err(|e| AppError::validation(format!("Invalid address: {}", e)))?;
+        let conn = self.get_rpc_client().await?;
+
+        let account = conn.get_multiple_accounts(&[pubkey]).await?.into_iter().next().flatten();
+        Ok(account.map(|account| AccountInfo {
+            address: address.to_string(),
+            lamports: account.lamports,
+            data: account.data,
+            owner: account.owner.to_string(),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        }))
+    }
+
     /// Get token account balance
     #[instrument(skip(self))]
     pub async fn get_token_balance(
@@ -129,13 +364,225 @@ impl SolanaService {
         conn.get_sol_balance(address).await
     }
 
-    /// Subscribe to token events via Helius webhooks
+    /// Get a page of signatures for an address, newest first
+    #[instrument(skip(self))]
+    pub async fn get_signatures_for_address(
+        &self,
+        address: &str,
+        before: Option<&str>,
+        limit: usize,
+    ) -> AppResult<Vec<types::SignatureInfo>> {
+        let conn = self.get_rpc_client().await?;
+        conn.get_signatures_for_address(address, before, limit).await
+    }
+
+    /// Get a confirmed transaction with resolved instructions
+    #[instrument(skip(self))]
+    pub async fn get_transaction(&self, signature: &str) -> AppResult<types::TransactionInfo> {
+        let conn = self.get_rpc_client().await?;
+        conn.get_transaction(signature).await
+    }
+
+    /// Get the current slot
+    #[instrument(skip(self))]
+    pub async fn get_slot(&self) -> AppResult<u64> {
+        let conn = self.get_rpc_client().await?;
+        conn.get_slot().await
+    }
+
+    /// Get accounts a program initialized in `start_slot..=end_slot`
+    ///
+    /// `getProgramAccounts` cannot filter by creation slot, so the confirmed
+    /// blocks of the range are read and the program's `InitializeMint` and
+    /// `InitializeMint2` instructions, including inner ones, are collected.
+    /// Each entry holds the initialized `pubkey` with its `slot` and
+    /// `signature`. At most `limit` accounts are returned, oldest first.
+    #[instrument(skip(self))]
+    pub async fn get_program_accounts_in_slot_range(
+        &self,
+        program_id: &str,
+        start_slot: u64,
+        end_slot: u64,
+        limit: u32,
+    ) -> AppResult<Vec<serde_json::Value>> {
+        let start_time = std::time::Instant::now();
+        let conn = self.get_rpc_client().await?;
+        let slots = conn.get_blocks(start_slot, end_slot).await?;
+
+        let mut accounts = Vec::new();
+        'blocks: for slot in slots {
+            let transactions = conn.get_block_transactions(slot).await?;
+
+            for transaction in transactions {
+                if transaction.status != types::TransactionStatus::Success {
+                    continue;
+                }
+
+                let initialized = transaction.instructions.iter()
+                    .chain(&transaction.inner_instructions)
+                    .filter(|ix| ix.program_id == program_id)
+                    .filter(|ix| matches!(ix.data.first(), Some(&TOKEN_INITIALIZE_MINT) | Some(&TOKEN_INITIALIZE_MINT2)))
+                    .filter_map(|ix| ix.accounts.first());
+
+                for pubkey in initialized {
+                    if accounts.len() >= limit as usize {
+                        break 'blocks;
+                    }
+                    accounts.push(serde_json::json!({
+                        "pubkey": pubkey,
+                        "slot": slot,
+                        "signature": transaction.signature,
+                    }));
+                }
+            }
+        }
+
+        self.update_stats("rpc_program_accounts_in_slot_range", true, start_time.elapsed()).await;
+        Ok(accounts)
+    }
+
+    /// Find the creation transaction of a mint
+    ///
+    /// Signature walks can span many pages, so they run on a dedicated client.
+    #[instrument(skip(self))]
+    pub async fn get_mint_creation(&self, mint_address: &str, max_pages: usize) -> AppResult<Option<MintCreation>> {
+        let start_time = std::time::Instant::now();
+        let client = self.get_dedicated_rpc_client().await?;
+        let result = client.get_mint_creation(mint_address, max_pages).await;
+
+        self.update_stats("rpc_mint_creation", result.is_ok(), start_time.elapsed()).await;
+        result
+    }
+
+    /// Count holders of a mint with a nonzero balance
+    ///
+    /// Uses `getProgramAccounts` and falls back to Helius when the RPC node
+    /// rejects or fails the scan. The Helius fallback pages through every
+    /// token account of the mint, so large tokens are not undercounted.
+    #[instrument(skip(self))]
+    pub async fn get_token_holder_count(&self, mint_address: &str, token_program: &str) -> AppResult<u32> {
+        let start_time = std::time::Instant::now();
+
+        let client = self.get_dedicated_rpc_client().await?;
+        match client.get_token_holder_count(mint_address, token_program).await {
+            Ok(count) => {
+                self.update_stats("rpc_holder_count", true, start_time.elapsed()).await;
+                return Ok(count);
+            }
+            Err(e) => {
+                warn!("RPC holder count failed, falling back to Helius: {}", e);
+                self.update_stats("rpc_holder_count", false, start_time.elapsed()).await;
+            }
+        }
+
+        let accounts = self.helius_client
+            .get_all_token_accounts(&TokenAccountFilter::Mint(mint_address.to_string()), usize::MAX)
+            .await?;
+        let count = accounts.iter().filter(|account| account.amount > 0).count() as u32;
+
+        self.update_stats("helius_holder_count", true, start_time.elapsed()).await;
+        Ok(count)
+    }
+
+    /// Whether a Helius API key is configured
+    pub fn has_helius_api_key(&self) -> bool {
+        !self.config.helius.api_key.is_empty()
+    }
+
+    /// Fetch mints of pools created on every decoded DEX since `cursors`
+    ///
+    /// Cursors hold the last processed pool creation signature by program.
+    /// A program with a cursor is paged back to it and its pools are taken
+    /// oldest first; a program without one only has its newest page read.
+    /// Once `limit` mints are found the remaining pools are left for the
+    /// next fetch, as each cursor only advances past processed pools. The
+    /// LP mint, which the pool sends to its creator, is skipped.
     #[instrument(skip(self))]
-    pub async fn subscribe_to_token_events(
+    pub async fn fetch_helius_new_tokens(
         &self,
-        callback_url: &str
-    ) -> AppResult<String> {
-        self.helius_client.subscribe_to_webhooks(callback_url).await
+        cursors: &std::collections::HashMap<String, String>,
+        limit: u32,
+    ) -> AppResult<HeliusNewTokens> {
+        let limit = limit as usize;
+        let mut new_tokens = HeliusNewTokens {
+            mints: Vec::new(),
+            cursors: cursors.clone(),
+        };
+        let mut last_error = None;
+        let mut fetched_any = false;
+
+        for program_id in DecoderRegistry::with_defaults().program_ids() {
+            if new_tokens.mints.len() >= limit {
+                break;
+            }
+
+            let cursor = cursors.get(&program_id).cloned();
+            let max_transactions = if cursor.is_some() { usize::MAX } else { limit - new_tokens.mints.len() };
+            let query = EnhancedTransactionQuery {
+                until: cursor,
+                transaction_type: Some(HELIUS_CREATE_POOL_TYPE.to_string()),
+                ..Default::default()
+            };
+
+            let start_time = std::time::Instant::now();
+            let result = self.helius_client
+                .get_all_enhanced_transactions(&program_id, &query, max_transactions)
+                .await;
+            self.update_stats("helius_new_tokens", result.is_ok(), start_time.elapsed()).await;
+
+            let transactions = match result {
+                Ok(transactions) => transactions,
+                Err(e) => {
+                    warn!("Helius pool query for {} failed: {}", program_id, e);
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+            fetched_any = true;
+
+            // Oldest first, so the cursor only moves past processed pools
+            for transaction in transactions.iter().rev() {
+                if new_tokens.mints.len() >= limit {
+                    break;
+                }
+
+                if transaction.succeeded() {
+                    for mint in transaction.pool_mints() {
+                        if mint != decoders::WSOL_MINT
+                            && mint != decoders::USDC_MINT
+                            && !new_tokens.mints.iter().any(|seen| seen == mint)
+                        {
+                            new_tokens.mints.push(mint.to_string());
+                        }
+                    }
+                }
+                new_tokens.cursors.insert(program_id.clone(), transaction.signature.clone());
+            }
+        }
+
+        match last_error {
+            Some(e) if !fetched_any => Err(e),
+            _ => Ok(new_tokens),
+        }
+    }
+
+    /// Callback URL Helius webhooks deliver to, if webhooks are enabled
+    pub fn helius_webhook_url(&self) -> Option<String> {
+        if self.config.helius.enable_webhooks {
+            self.config.helius.webhook_url.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Helius configuration
+    pub fn helius_config(&self) -> &crate::config::models::HeliusConfig {
+        &self.config.helius
+    }
+
+    /// Subscribe to the logs of transactions mentioning a program
+    pub async fn subscribe_program_events(&self, program_id: &str) -> AppResult<HeliusWebsocket> {
+        HeliusWebsocket::connect(&websocket::websocket_url(&self.config.solana), program_id).await
     }
 
     /// Health check for Solana services
@@ -208,13 +655,17 @@ impl SolanaService {
 
     /// Get connection statistics
     pub async fn get_statistics(&self) -> ConnectionStats {
-        self.stats.read().await.clone()
+        let mut stats = self.stats.read().await.clone();
+        stats.endpoint_scores = self.rpc_pool.endpoint_scores().await;
+        stats
     }
 
     /// Shutdown the service
     pub async fn shutdown(&self) -> AppResult<()> {
         info!("🛑 Shutting down Solana service");
 
+        self.blockhash_cache.close();
+        self.price_oracle.close();
         self.rpc_pool.close().await?;
         self.helius_client.close().await?;
 
@@ -223,6 +674,24 @@ impl SolanaService {
     }
 }
 
+/// New token mints found through Helius
+#[derive(Debug, Clone, Default)]
+pub struct HeliusNewTokens {
+    /// Mints of newly created pools, oldest first per program
+    pub mints: Vec<String>,
+    /// Cursors by program to pass to the next fetch
+    pub cursors: std::collections::HashMap<String, String>,
+}
+
+/// Outcome of submitting a transaction
+#[derive(Debug, Clone)]
+pub struct SubmissionOutcome {
+    /// Bundle attempt, if Jito is enabled
+    pub bundle: Option<BundleOutcome>,
+    /// Normal broadcast, if the bundle was skipped or did not land
+    pub broadcast: Option<BroadcastOutcome>,
+}
+
 /// Connection statistics
 #[derive(Debug, Clone)]
 pub struct ConnectionStats {
@@ -238,6 +707,8 @@ pub struct ConnectionStats {
     pub operations_by_type: std::collections::HashMap<String, OperationStats>,
     /// Service start time
     pub started_at: chrono::DateTime<chrono::Utc>,
+    /// Routing score of each RPC endpoint, best first
+    pub endpoint_scores: Vec<EndpointScore>,
 }
 
 impl ConnectionStats {
@@ -249,6 +720,7 @@ impl ConnectionStats {
             avg_response_time_ms: 0.0,
             operations_by_type: std::collections::HashMap::new(),
             started_at: chrono::Utc::now(),
+            endpoint_scores: Vec::new(),
         }
     }
 
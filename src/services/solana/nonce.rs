@@ -0,0 +1,380 @@
+//! Durable nonces for pre-signed transactions
+//!
+//! A transaction signed against a recent blockhash expires in about a
+//! minute, so an emergency exit would otherwise have to be built and signed
+//! at the moment it is needed. Signing against a durable nonce instead keeps
+//! the transaction valid until the nonce advances, which the transaction
+//! does itself in its first instruction. Each nonce account backs one
+//! pre-signed transaction at a time, and only returns to the pool once its
+//! nonce has moved on from the value that transaction was signed against.
+
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::{AddressLookupTableAccount, VersionedMessage},
+    nonce::state::{State, Versions},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction::{self, SystemInstruction},
+    system_program,
+    transaction::{Transaction, VersionedTransaction},
+};
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+use tracing::{debug, info};
+
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use super::lookup_tables::build_v0_transaction;
+use super::rpc::RpcPool;
+
+/// Interval between nonce reads while waiting for an advance to land
+const NONCE_ADVANCE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long an advance may take to land before release gives up
+const NONCE_ADVANCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Current value of a durable nonce account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurableNonce {
+    /// Nonce account
+    pub account: Pubkey,
+    /// Authority allowed to advance the nonce
+    pub authority: Pubkey,
+    /// Stored nonce, used in place of a recent blockhash
+    pub nonce: Hash,
+}
+
+/// Parse the state of a nonce account
+pub fn parse_nonce_account(account: Pubkey, data: &[u8]) -> AppResult<DurableNonce> {
+    let (versions, _): (Versions, usize) = bincode::serde::decode_from_slice(data, bincode::config::legacy())
+        .map_err(|e| AppError::validation(format!("Invalid nonce account {}: {}", account, e)))?;
+
+    match versions.state() {
+        State::Initialized(data) => Ok(DurableNonce {
+            account,
+            authority: data.authority,
+            nonce: data.blockhash(),
+        }),
+        State::Uninitialized => Err(AppError::validation(format!("Nonce account {} is not initialized", account))),
+    }
+}
+
+/// Sign `instructions` against a durable nonce
+///
+/// The nonce advance is prepended, as the runtime requires it to be the
+/// first instruction. `authority` may be the same keypair as `payer`.
+/// Lookup tables let routed swaps fit; the nonce account itself must not
+/// be in one.
+pub fn presign_with_nonce(
+    nonce: &DurableNonce,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    payer: &Keypair,
+    authority: &Keypair,
+) -> AppResult<VersionedTransaction> {
+    if authority.pubkey() != nonce.authority {
+        return Err(AppError::validation(format!(
+            "{} is not the authority of nonce account {}",
+            authority.pubkey(), nonce.account
+        )));
+    }
+
+    let mut nonced = vec![system_instruction::advance_nonce_account(&nonce.account, &nonce.authority)];
+    nonced.extend_from_slice(instructions);
+
+    build_v0_transaction(payer, &nonced, lookup_tables, nonce.nonce, &[authority])
+}
+
+/// Nonce account a message advances, if it uses a durable nonce
+pub fn durable_nonce_account(message: &VersionedMessage) -> Option<Pubkey> {
+    let account_keys = message.static_account_keys();
+    let instruction = message.instructions().first()?;
+    let program_id = account_keys.get(instruction.program_id_index as usize)?;
+
+    if !system_program::check_id(program_id) {
+        return None;
+    }
+
+    match bincode::serde::decode_from_slice(&instruction.data, bincode::config::legacy()) {
+        Ok((SystemInstruction::AdvanceNonceAccount, _)) => {
+            let index = *instruction.accounts.first()?;
+            account_keys.get(index as usize).copied()
+        }
+        _ => None,
+    }
+}
+
+/// Nonce accounts available for pre-signing
+#[derive(Debug)]
+pub struct NoncePool {
+    /// Pool used to read nonce accounts
+    pool: Arc<RpcPool>,
+    /// Nonce accounts not backing a pre-signed transaction
+    available: RwLock<VecDeque<Pubkey>>,
+    /// Nonce each taken account had when it was acquired
+    acquired: RwLock<HashMap<Pubkey, Hash>>,
+}
+
+impl NoncePool {
+    /// Create a pool over the configured nonce accounts
+    pub fn new(pool: Arc<RpcPool>, accounts: &[String]) -> AppResult<Self> {
+        let accounts = accounts.iter()
+            .map(|account| Pubkey::from_str(account)
+                .map_err(|e| AppError::config(format!("Invalid nonce account {}: {}", account, e))))
+            .collect::<AppResult<VecDeque<_>>>()?;
+
+        if !accounts.is_empty() {
+            info!("🔐 {} durable nonce accounts available", accounts.len());
+        }
+
+        Ok(Self {
+            pool,
+            available: RwLock::new(accounts),
+            acquired: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Take a free nonce account and read its current nonce
+    ///
+    /// Returns `None` when every account backs a pre-signed transaction.
+    /// The account stays taken until `release` is called.
+    pub async fn acquire(&self) -> AppResult<Option<DurableNonce>> {
+        let account = match self.available.write().await.pop_front() {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+
+        match self.fetch(&account).await {
+            Ok(nonce) => {
+                debug!("🔐 Acquired nonce account {}", account);
+                self.acquired.write().await.insert(account, nonce.nonce);
+                Ok(Some(nonce))
+            }
+            Err(e) => {
+                self.available.write().await.push_back(account);
+                Err(e)
+            }
+        }
+    }
+
+    /// Return a nonce account once its pre-signed transaction landed or was discarded
+    ///
+    /// A discarded transaction stays valid until its nonce advances, so if
+    /// the nonce has not moved since `acquire`, `authority` advances it and
+    /// the account only returns once the new nonce is visible. On error the
+    /// account stays taken and `release` can be retried.
+    pub async fn release(&self, account: Pubkey, authority: &Keypair) -> AppResult<()> {
+        let acquired = self.acquired.read().await.get(&account).copied()
+            .ok_or_else(|| AppError::validation(format!("Nonce account {} is not acquired", account)))?;
+
+        if self.fetch(&account).await?.nonce == acquired {
+            self.advance(&account, acquired, authority).await?;
+        }
+
+        self.acquired.write().await.remove(&account);
+        let mut available = self.available.write().await;
+        if !available.contains(&account) {
+            available.push_back(account);
+        }
+
+        debug!("🔐 Released nonce account {}", account);
+        Ok(())
+    }
+
+    /// Advance a nonce past `current` and wait until the new value is visible
+    async fn advance(&self, account: &Pubkey, current: Hash, authority: &Keypair) -> AppResult<()> {
+        let connection = self.pool.get_connection().await?;
+        let blockhash = connection.get_recent_blockhash().await?;
+        let instruction = system_instruction::advance_nonce_account(account, &authority.pubkey());
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&authority.pubkey()), &[authority], blockhash);
+
+        let signature = connection.send_transaction(&transaction).await?;
+        debug!("🔐 Advancing nonce account {} in {}", account, signature);
+
+        let deadline = Instant::now() + NONCE_ADVANCE_TIMEOUT;
+        while Instant::now() < deadline {
+            tokio::time::sleep(NONCE_ADVANCE_POLL_INTERVAL).await;
+
+            match self.fetch(account).await {
+                Ok(nonce) if nonce.nonce != current => return Ok(()),
+                Ok(_) => {}
+                Err(e) => debug!("Nonce read for {} failed: {}", account, e),
+            }
+        }
+
+        Err(AppError::network(format!("Nonce account {} did not advance in time", account)))
+    }
+
+    /// Read the current nonce of an account
+    pub async fn fetch(&self, account: &Pubkey) -> AppResult<DurableNonce> {
+        let connection = self.pool.get_connection().await?;
+        let info = connection.get_account_info(&account.to_string()).await?;
+
+        if info.owner != system_program::id().to_string() {
+            return Err(AppError::validation(format!("{} is not a nonce account", account)));
+        }
+
+        parse_nonce_account(*account, &info.data)
+    }
+
+    /// Number of free nonce accounts
+    pub async fn available(&self) -> usize {
+        self.available.read().await.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+    use solana_sdk::nonce::state::{Data, DurableNonce as NonceValue};
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn rpc_result(result: serde_json::Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+    }
+
+    async fn mock_method(server: &MockServer, name: &str, response: ResponseTemplate, times: Option<u64>) {
+        let mock = Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": name })))
+            .respond_with(response);
+        match times {
+            Some(times) => mock.up_to_n_times(times).mount(server).await,
+            None => mock.mount(server).await,
+        }
+    }
+
+    fn nonce_account(authority: &Pubkey, nonce: &Hash) -> ResponseTemplate {
+        let versions = Versions::new(State::Initialized(Data::new(*authority, NonceValue::from_blockhash(nonce), 5_000)));
+        let data = bincode::serde::encode_to_vec(&versions, bincode::config::legacy()).unwrap();
+        rpc_result(serde_json::json!({
+            "context": { "slot": 1 },
+            "value": {
+                "data": [BASE64.encode(&data), "base64"],
+                "executable": false,
+                "lamports": 1_447_680,
+                "owner": system_program::id().to_string(),
+                "rentEpoch": 0,
+                "space": data.len(),
+            },
+        }))
+    }
+
+    /// Pool over a mock endpoint with one nonce account
+    async fn nonce_pool(server: &MockServer, account: &Pubkey) -> NoncePool {
+        mock_method(server, "getHealth", rpc_result(serde_json::json!("ok")), None).await;
+
+        let mut config = crate::config::ConfigLoader::new().without_env().create_default_config().solana;
+        config.rpc_url = server.uri();
+        config.fallback_rpc_urls = Vec::new();
+        let pool = Arc::new(RpcPool::new(&config).await.unwrap());
+        NoncePool::new(pool, &[account.to_string()]).unwrap()
+    }
+
+    async fn sends(server: &MockServer) -> usize {
+        server.received_requests().await.unwrap_or_default().iter()
+            .filter_map(|request| serde_json::from_slice::<serde_json::Value>(&request.body).ok())
+            .filter(|body| body["method"] == "sendTransaction")
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_release_after_landing_reuses_advanced_nonce() {
+        let server = MockServer::start().await;
+        let (account, authority) = (Pubkey::new_unique(), Keypair::new());
+        let (signed, advanced) = (Hash::new_unique(), Hash::new_unique());
+        mock_method(&server, "getAccountInfo", nonce_account(&authority.pubkey(), &signed), Some(1)).await;
+        mock_method(&server, "getAccountInfo", nonce_account(&authority.pubkey(), &advanced), None).await;
+
+        let pool = nonce_pool(&server, &account).await;
+        assert_eq!(pool.acquire().await.unwrap().unwrap().nonce, signed);
+        assert!(pool.acquire().await.unwrap().is_none());
+
+        // The pre-signed transaction landed and advanced the nonce itself
+        pool.release(account, &authority).await.unwrap();
+        assert_eq!(sends(&server).await, 0);
+        assert_eq!(pool.acquire().await.unwrap().unwrap().nonce, advanced);
+
+        // Releasing an account that is not taken is refused
+        assert!(pool.release(Pubkey::new_unique(), &authority).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_release_after_discard_advances_nonce() {
+        let server = MockServer::start().await;
+        let (account, authority) = (Pubkey::new_unique(), Keypair::new());
+        let (signed, advanced, blockhash) = (Hash::new_unique(), Hash::new_unique(), Hash::new_unique());
+        // Read by acquire and by release, then once the advance landed
+        mock_method(&server, "getAccountInfo", nonce_account(&authority.pubkey(), &signed), Some(2)).await;
+        mock_method(&server, "getAccountInfo", nonce_account(&authority.pubkey(), &advanced), None).await;
+        mock_method(&server, "getLatestBlockhash", rpc_result(serde_json::json!({
+            "context": { "slot": 1 },
+            "value": { "blockhash": blockhash.to_string(), "lastValidBlockHeight": 100 },
+        })), None).await;
+        mock_method(&server, "getVersion", rpc_result(serde_json::json!({ "solana-core": "2.2.7", "feature-set": 1 })), None).await;
+
+        // Signing is deterministic, so the advance's signature is known
+        let advance = Transaction::new_signed_with_payer(
+            &[system_instruction::advance_nonce_account(&account, &authority.pubkey())],
+            Some(&authority.pubkey()),
+            &[&authority],
+            blockhash,
+        );
+        mock_method(&server, "sendTransaction", rpc_result(serde_json::json!(advance.signatures[0].to_string())), None).await;
+
+        let pool = nonce_pool(&server, &account).await;
+        assert_eq!(pool.acquire().await.unwrap().unwrap().nonce, signed);
+
+        // The pre-signed transaction was discarded, so its nonce must move on
+        pool.release(account, &authority).await.unwrap();
+        assert_eq!(sends(&server).await, 1);
+        assert_eq!(pool.acquire().await.unwrap().unwrap().nonce, advanced);
+    }
+
+    #[test]
+    fn test_parse_nonce_account() {
+        let account = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let value = NonceValue::from_blockhash(&Hash::new_unique());
+        let versions = Versions::new(State::Initialized(Data::new(authority, value, 5_000)));
+        let data = bincode::serde::encode_to_vec(&versions, bincode::config::legacy()).unwrap();
+
+        let nonce = parse_nonce_account(account, &data).unwrap();
+        assert_eq!(nonce, DurableNonce { account, authority, nonce: *value.as_hash() });
+
+        let uninitialized = bincode::serde::encode_to_vec(&Versions::new(State::Uninitialized), bincode::config::legacy()).unwrap();
+        assert!(parse_nonce_account(account, &uninitialized).is_err());
+    }
+
+    #[test]
+    fn test_presign_with_nonce() {
+        let payer = Keypair::new();
+        let nonce = DurableNonce {
+            account: Pubkey::new_unique(),
+            authority: payer.pubkey(),
+            nonce: Hash::new_unique(),
+        };
+        let sell = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+
+        let transaction = presign_with_nonce(&nonce, &[sell], &[], &payer, &payer).unwrap();
+        assert_eq!(*transaction.message.recent_blockhash(), nonce.nonce);
+        assert_eq!(transaction.message.instructions().len(), 2);
+        assert!(transaction.verify_with_results().iter().all(|ok| *ok));
+        assert_eq!(durable_nonce_account(&transaction.message), Some(nonce.account));
+
+        // Only the nonce authority can sign the advance
+        assert!(presign_with_nonce(&nonce, &[], &[], &payer, &Keypair::new()).is_err());
+
+        let regular = solana_sdk::message::Message::new(
+            &[system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1)],
+            Some(&payer.pubkey()),
+        );
+        assert_eq!(durable_nonce_account(&VersionedMessage::Legacy(regular)), None);
+    }
+}
@@ -0,0 +1,1111 @@
+//! On-chain price oracle
+//!
+//! Prices are computed from pool state read straight from the chain, so they
+//! follow every swap without waiting on third-party APIs or their rate
+//! limits. Each supported DEX keeps reserves differently: Raydium and
+//! Meteora in vault token accounts, pump.fun as virtual reserves on the
+//! bonding curve, and Orca Whirlpools as a concentrated-liquidity sqrt
+//! price. USD prices go through a SOL/USDC pool read the same way.
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use futures::stream::{select_all, StreamExt};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{nonblocking::pubsub_client::PubsubClient, rpc_config::RpcAccountInfoConfig};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::config::models::SolanaConfig;
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use crate::core::types::TokenAddress;
+use crate::services::analytics::PriceSource;
+use super::websocket::websocket_url;
+use super::decoders::{
+    DecodedEvent, DecodedEventKind, METEORA_AMM_PROGRAM_ID, ORCA_WHIRLPOOL_PROGRAM_ID, PUMP_FUN_PROGRAM_ID,
+    RAYDIUM_AMM_V4_PROGRAM_ID, USDC_MINT, WSOL_MINT,
+};
+use super::rpc::RpcPool;
+
+/// How long a SOL/USD price is reused before the pool is read again
+const SOL_USD_TTL: Duration = Duration::from_secs(10);
+
+/// Delay before a dropped account subscription is re-established
+const WATCH_RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Hours the pool of a token that is not watched is remembered
+///
+/// Filter attribution prices evaluated tokens for 24h after they are seen.
+const POOL_TTL_HOURS: i64 = 25;
+
+/// How often expired pool registrations are dropped
+const POOL_PRUNE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Capacity of the price update channel
+const UPDATE_CHANNEL_CAPACITY: usize = 1024;
+
+/// Decimals of pump.fun tokens
+const PUMP_FUN_DECIMALS: u8 = 6;
+
+/// Decimals of SOL
+const SOL_DECIMALS: u8 = 9;
+
+/// DEX a pool belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum PoolDex {
+    /// Raydium AMM v4
+    Raydium,
+    /// pump.fun bonding curve
+    PumpFun,
+    /// Orca Whirlpool
+    Orca,
+    /// Meteora dynamic AMM
+    Meteora,
+}
+
+impl PoolDex {
+    /// DEX owning pools of a program
+    pub fn from_program(program_id: &Pubkey) -> Option<Self> {
+        match program_id.to_string().as_str() {
+            RAYDIUM_AMM_V4_PROGRAM_ID => Some(Self::Raydium),
+            PUMP_FUN_PROGRAM_ID => Some(Self::PumpFun),
+            ORCA_WHIRLPOOL_PROGRAM_ID => Some(Self::Orca),
+            METEORA_AMM_PROGRAM_ID => Some(Self::Meteora),
+            _ => None,
+        }
+    }
+
+    /// DEX name, as used by the decoders
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Raydium => "raydium",
+            Self::PumpFun => "pump.fun",
+            Self::Orca => "orca",
+            Self::Meteora => "meteora",
+        }
+    }
+}
+
+/// How the spot price of a pool is derived
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpotPrice {
+    /// Ratio of the pool reserves
+    ReserveRatio,
+    /// Ratio of virtual reserves, in raw units of token A and B
+    VirtualReserves {
+        /// Virtual reserve of token A
+        a: u64,
+        /// Virtual reserve of token B
+        b: u64,
+    },
+    /// Q64.64 square root of the raw price of token A in token B
+    SqrtPriceX64(u128),
+}
+
+/// Reserves of a pool, in raw token units
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolReserves {
+    /// DEX of the pool
+    pub dex: PoolDex,
+    /// Token A mint
+    pub mint_a: Pubkey,
+    /// Token B mint
+    pub mint_b: Pubkey,
+    /// Token A held by the pool
+    pub reserve_a: u64,
+    /// Token B held by the pool
+    pub reserve_b: u64,
+    /// Token A decimals
+    pub decimals_a: u8,
+    /// Token B decimals
+    pub decimals_b: u8,
+    /// How the spot price is derived
+    pub spot: SpotPrice,
+}
+
+impl PoolReserves {
+    /// Price of `mint` in units of the other token of the pool
+    pub fn price_of(&self, mint: &Pubkey) -> Option<f64> {
+        let a_in_b = match self.spot {
+            SpotPrice::ReserveRatio => {
+                ui_amount(self.reserve_b, self.decimals_b) / ui_amount(self.reserve_a, self.decimals_a)
+            }
+            SpotPrice::VirtualReserves { a, b } => ui_amount(b, self.decimals_b) / ui_amount(a, self.decimals_a),
+            SpotPrice::SqrtPriceX64(sqrt_price) => {
+                let sqrt_price = sqrt_price as f64 / 2f64.powi(64);
+                sqrt_price * sqrt_price * 10f64.powi(self.decimals_a as i32 - self.decimals_b as i32)
+            }
+        };
+
+        if !a_in_b.is_finite() || a_in_b <= 0.0 {
+            return None;
+        }
+
+        if *mint == self.mint_a {
+            Some(a_in_b)
+        } else if *mint == self.mint_b {
+            Some(1.0 / a_in_b)
+        } else {
+            None
+        }
+    }
+
+    /// Amount of `mint` held by the pool, in UI units
+    pub fn reserve_of(&self, mint: &Pubkey) -> Option<f64> {
+        if *mint == self.mint_a {
+            Some(ui_amount(self.reserve_a, self.decimals_a))
+        } else if *mint == self.mint_b {
+            Some(ui_amount(self.reserve_b, self.decimals_b))
+        } else {
+            None
+        }
+    }
+
+    /// Other token of the pool
+    pub fn counterpart(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == self.mint_a {
+            Some(self.mint_b)
+        } else if *mint == self.mint_b {
+            Some(self.mint_a)
+        } else {
+            None
+        }
+    }
+}
+
+/// Pool account and the accounts its reserves are read from
+#[derive(Debug, Clone)]
+pub struct PoolState {
+    /// Pool address
+    pub address: Pubkey,
+    /// DEX of the pool
+    pub dex: PoolDex,
+    /// Token the pool was loaded for; pump.fun curves do not store it
+    pub mint: Pubkey,
+    /// Raw data of the pool and its dependencies
+    accounts: HashMap<Pubkey, Vec<u8>>,
+}
+
+impl PoolState {
+    /// Create a state from the raw pool account
+    pub fn new(address: Pubkey, dex: PoolDex, mint: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            address,
+            dex,
+            mint,
+            accounts: HashMap::from([(address, data)]),
+        }
+    }
+
+    /// Store new data for the pool or one of its dependencies
+    pub fn update(&mut self, account: Pubkey, data: Vec<u8>) {
+        self.accounts.insert(account, data);
+    }
+
+    /// Accounts the reserves depend on, besides the pool itself
+    ///
+    /// Meteora pools are two levels deep: the vault LP mints are only known
+    /// once the vaults are loaded.
+    pub fn dependencies(&self) -> Vec<Pubkey> {
+        let Some(data) = self.accounts.get(&self.address) else {
+            return Vec::new();
+        };
+        let keys = |offsets: &[usize]| offsets.iter().filter_map(|offset| read_pubkey(data, *offset)).collect::<Vec<_>>();
+
+        match self.dex {
+            // coin vault, pc vault
+            PoolDex::Raydium => keys(&[336, 368]),
+            PoolDex::PumpFun => Vec::new(),
+            // mint a, vault a, mint b, vault b
+            PoolDex::Orca => keys(&[101, 133, 181, 213]),
+            // mint a, mint b, vault a, vault b, vault a LP, vault b LP
+            PoolDex::Meteora => {
+                let mut dependencies = keys(&[40, 72, 104, 136, 168, 200]);
+                for vault in keys(&[104, 136]) {
+                    if let Some(lp_mint) = self.accounts.get(&vault).and_then(|vault| read_pubkey(vault, 115)) {
+                        dependencies.push(lp_mint);
+                    }
+                }
+                dependencies
+            }
+        }
+    }
+
+    /// Dependencies that have not been loaded yet
+    pub fn missing(&self) -> Vec<Pubkey> {
+        self.dependencies().into_iter().filter(|account| !self.accounts.contains_key(account)).collect()
+    }
+
+    /// Pool and dependency accounts to subscribe to
+    pub fn watched_accounts(&self) -> Vec<Pubkey> {
+        let mut accounts = vec![self.address];
+        accounts.extend(self.dependencies());
+        accounts
+    }
+
+    /// Current reserves, if every dependency is loaded and the pool is trading
+    pub fn reserves(&self) -> Option<PoolReserves> {
+        let data = self.accounts.get(&self.address)?;
+        let account = |offset: usize| read_pubkey(data, offset).and_then(|key| self.accounts.get(&key));
+
+        match self.dex {
+            // AmmInfo: coin/pc decimals at 32/40, need_take_pnl coin/pc at
+            // 192/200, vaults at 336/368, mints at 400/432. PnL owed to the
+            // protocol still sits in the vaults.
+            PoolDex::Raydium => Some(PoolReserves {
+                dex: self.dex,
+                mint_a: read_pubkey(data, 400)?,
+                mint_b: read_pubkey(data, 432)?,
+                reserve_a: token_account_amount(account(336)?)?.saturating_sub(read_u64(data, 192)?),
+                reserve_b: token_account_amount(account(368)?)?.saturating_sub(read_u64(data, 200)?),
+                decimals_a: read_u64(data, 32)? as u8,
+                decimals_b: read_u64(data, 40)? as u8,
+                spot: SpotPrice::ReserveRatio,
+            }),
+            // Bonding curve: virtual token/SOL reserves at 8/16, real
+            // token/SOL reserves at 24/32, complete flag at 48. A complete
+            // curve has migrated and no longer trades.
+            PoolDex::PumpFun => {
+                if *data.get(48)? != 0 {
+                    return None;
+                }
+                Some(PoolReserves {
+                    dex: self.dex,
+                    mint_a: self.mint,
+                    mint_b: Pubkey::from_str(WSOL_MINT).ok()?,
+                    reserve_a: read_u64(data, 24)?,
+                    reserve_b: read_u64(data, 32)?,
+                    decimals_a: PUMP_FUN_DECIMALS,
+                    decimals_b: SOL_DECIMALS,
+                    spot: SpotPrice::VirtualReserves { a: read_u64(data, 8)?, b: read_u64(data, 16)? },
+                })
+            }
+            // Whirlpool: sqrt price at 65, mint/vault a at 101/133, mint/vault
+            // b at 181/213
+            PoolDex::Orca => Some(PoolReserves {
+                dex: self.dex,
+                mint_a: read_pubkey(data, 101)?,
+                mint_b: read_pubkey(data, 181)?,
+                reserve_a: token_account_amount(account(133)?)?,
+                reserve_b: token_account_amount(account(213)?)?,
+                decimals_a: mint_decimals(account(101)?)?,
+                decimals_b: mint_decimals(account(181)?)?,
+                spot: SpotPrice::SqrtPriceX64(read_u128(data, 65)?),
+            }),
+            // Pool: mints at 40/72, vaults at 104/136, vault LP token
+            // accounts at 168/200. Each vault lends part of its tokens out, so
+            // the pool owns its share of the vault total, by LP tokens held.
+            PoolDex::Meteora => {
+                let vault_share = |vault_offset: usize, lp_offset: usize| -> Option<u64> {
+                    let vault = account(vault_offset)?;
+                    let total_amount = read_u64(vault, 11)?;
+                    let lp_supply = mint_supply(self.accounts.get(&read_pubkey(vault, 115)?)?)?;
+                    let lp_held = token_account_amount(account(lp_offset)?)?;
+                    if lp_supply == 0 {
+                        return Some(0);
+                    }
+                    u64::try_from(total_amount as u128 * lp_held as u128 / lp_supply as u128).ok()
+                };
+
+                Some(PoolReserves {
+                    dex: self.dex,
+                    mint_a: read_pubkey(data, 40)?,
+                    mint_b: read_pubkey(data, 72)?,
+                    reserve_a: vault_share(104, 168)?,
+                    reserve_b: vault_share(136, 200)?,
+                    decimals_a: mint_decimals(account(40)?)?,
+                    decimals_b: mint_decimals(account(72)?)?,
+                    spot: SpotPrice::ReserveRatio,
+                })
+            }
+        }
+    }
+}
+
+/// Price of a token read from its pool
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolPrice {
+    /// Token mint
+    pub mint: Pubkey,
+    /// Pool the price was read from
+    pub pool: Pubkey,
+    /// DEX of the pool
+    pub dex: PoolDex,
+    /// Price per token in SOL
+    pub price_sol: Decimal,
+    /// Price per token in USD, if SOL/USD is known
+    pub price_usd: Option<Decimal>,
+    /// Value of both sides of the pool in SOL
+    pub liquidity_sol: Decimal,
+    /// Value of both sides of the pool in USD, if SOL/USD is known
+    pub liquidity_usd: Option<Decimal>,
+    /// When the pool state was read
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Prices tokens from the reserves of their pools
+#[derive(Debug)]
+pub struct PriceOracle {
+    /// Pool used to read accounts
+    pool: Arc<RpcPool>,
+    /// WebSocket URL for account subscriptions
+    websocket_url: String,
+    /// SOL/USDC pool used for USD conversion
+    sol_usd_pool: Pubkey,
+    /// Pool each token is priced from, and when it was registered
+    pools: RwLock<HashMap<Pubkey, (Pubkey, DateTime<Utc>)>>,
+    /// When expired pool registrations were last dropped
+    pools_pruned_at: std::sync::Mutex<Instant>,
+    /// State of watched pools, by token
+    states: RwLock<HashMap<Pubkey, PoolState>>,
+    /// Latest streamed price of watched tokens
+    prices: RwLock<HashMap<Pubkey, PoolPrice>>,
+    /// Cached SOL/USD price and when it was read
+    sol_usd: RwLock<Option<(f64, Instant)>>,
+    /// Streamed price updates
+    updates: broadcast::Sender<PoolPrice>,
+    /// Subscription task of each watched token
+    watchers: std::sync::Mutex<HashMap<Pubkey, JoinHandle<()>>>,
+}
+
+impl PriceOracle {
+    /// Create an oracle with no registered pools
+    pub fn new(pool: Arc<RpcPool>, config: &SolanaConfig) -> AppResult<Self> {
+        let sol_usd_pool = Pubkey::from_str(&config.sol_usd_pool)
+            .map_err(|e| AppError::config(format!("Invalid SOL/USD pool {}: {}", config.sol_usd_pool, e)))?;
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+
+        Ok(Self {
+            pool,
+            websocket_url: websocket_url(config),
+            sol_usd_pool,
+            pools: RwLock::new(HashMap::new()),
+            pools_pruned_at: std::sync::Mutex::new(Instant::now()),
+            states: RwLock::new(HashMap::new()),
+            prices: RwLock::new(HashMap::new()),
+            sol_usd: RwLock::new(None),
+            updates,
+            watchers: std::sync::Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Price `mint` from `pool` from now on
+    ///
+    /// The latest registration wins, so a token that migrates off its
+    /// bonding curve is priced from its new pool once that is seen. A token
+    /// already being watched keeps streaming from its previous pool until it
+    /// is watched again.
+    pub async fn register_pool(&self, mint: Pubkey, pool: Pubkey) {
+        let previous = self.pools.write().await.insert(mint, (pool, Utc::now()));
+        if previous.map(|(previous, _)| previous) != Some(pool) {
+            debug!("💱 Pricing {} from pool {}", mint, pool);
+        }
+
+        let prune = {
+            let mut pruned_at = self.pools_pruned_at.lock().unwrap();
+            let due = pruned_at.elapsed() >= POOL_PRUNE_INTERVAL;
+            if due {
+                *pruned_at = Instant::now();
+            }
+            due
+        };
+        if prune {
+            self.prune_pools().await;
+        }
+    }
+
+    /// Forget the pools of tokens registered more than `POOL_TTL_HOURS`
+    /// ago, unless they are being watched
+    pub async fn prune_pools(&self) {
+        let watched: HashSet<Pubkey> = self.watchers.lock().unwrap().keys().copied().collect();
+        let cutoff = Utc::now() - chrono::Duration::hours(POOL_TTL_HOURS);
+        let mut pools = self.pools.write().await;
+        let before = pools.len();
+        pools.retain(|mint, (_, registered_at)| *registered_at > cutoff || watched.contains(mint));
+
+        if pools.len() < before {
+            debug!("💱 Dropped {} expired pool registrations", before - pools.len());
+        }
+    }
+
+    /// Register the pools of a decoded creation event
+    pub async fn register_event(&self, event: &DecodedEvent) {
+        let pools: Vec<(String, Pubkey)> = match &event.kind {
+            DecodedEventKind::PoolCreated(pool) => match Pubkey::from_str(&pool.pool_address) {
+                Ok(address) => event.token_mints().into_iter().map(|mint| (mint, address)).collect(),
+                Err(_) => return,
+            },
+            DecodedEventKind::TokenCreated(token) if event.program_id == PUMP_FUN_PROGRAM_ID => {
+                match Pubkey::from_str(&token.mint) {
+                    Ok(mint) => vec![(token.mint.clone(), bonding_curve_address(&mint))],
+                    Err(_) => return,
+                }
+            }
+            DecodedEventKind::TokenCreated(_) => return,
+        };
+
+        for (mint, pool) in pools {
+            if let Ok(mint) = Pubkey::from_str(&mint) {
+                self.register_pool(mint, pool).await;
+            }
+        }
+    }
+
+    /// Pool `mint` is priced from
+    ///
+    /// Tokens without a registered pool are assumed to still be on their
+    /// pump.fun bonding curve.
+    pub async fn pool_for(&self, mint: &Pubkey) -> Pubkey {
+        match self.pools.read().await.get(mint) {
+            Some((pool, _)) => *pool,
+            None => bonding_curve_address(mint),
+        }
+    }
+
+    /// Read the current price of a token from its pool
+    pub async fn price(&self, mint: &Pubkey) -> AppResult<PoolPrice> {
+        let pool = self.pool_for(mint).await;
+        let state = self.load(pool, *mint).await?;
+        self.quote(&state).await
+    }
+
+    /// Latest streamed price of a watched token, without a round trip
+    pub async fn latest(&self, mint: &Pubkey) -> Option<PoolPrice> {
+        self.prices.read().await.get(mint).cloned()
+    }
+
+    /// Current SOL price in USD, cached for a few seconds
+    pub async fn sol_usd(&self) -> AppResult<f64> {
+        if let Some((price, read_at)) = *self.sol_usd.read().await {
+            if read_at.elapsed() < SOL_USD_TTL {
+                return Ok(price);
+            }
+        }
+
+        let wsol = Pubkey::from_str(WSOL_MINT).map_err(|e| AppError::internal(e.to_string()))?;
+        let usdc = Pubkey::from_str(USDC_MINT).map_err(|e| AppError::internal(e.to_string()))?;
+        let state = self.load(self.sol_usd_pool, wsol).await?;
+        let reserves = state.reserves()
+            .ok_or_else(|| AppError::validation(format!("SOL/USD pool {} has no reserves", self.sol_usd_pool)))?;
+
+        if reserves.counterpart(&wsol) != Some(usdc) {
+            return Err(AppError::config(format!("{} is not a SOL/USDC pool", self.sol_usd_pool)));
+        }
+
+        let price = reserves.price_of(&wsol)
+            .ok_or_else(|| AppError::validation(format!("SOL/USD pool {} has no price", self.sol_usd_pool)))?;
+        *self.sol_usd.write().await = Some((price, Instant::now()));
+        Ok(price)
+    }
+
+    /// Stream price updates of a token from account subscriptions
+    ///
+    /// Updates are published through `subscribe` and kept for `latest`.
+    pub async fn watch(self: &Arc<Self>, mint: Pubkey) -> AppResult<()> {
+        if self.watchers.lock().unwrap().contains_key(&mint) {
+            return Ok(());
+        }
+
+        let pool = self.pool_for(&mint).await;
+        let state = self.load(pool, mint).await?;
+        let price = self.quote(&state).await?;
+        self.states.write().await.insert(mint, state);
+        self.publish(price).await;
+
+        let oracle = self.clone();
+        let watcher = tokio::spawn(async move { oracle.watch_loop(mint).await });
+
+        // A concurrent watch of the same token may have won the race
+        if let Some(previous) = self.watchers.lock().unwrap().insert(mint, watcher) {
+            previous.abort();
+        }
+
+        info!("💱 Streaming {} prices from pool {}", mint, pool);
+        Ok(())
+    }
+
+    /// Stop streaming prices of a token
+    pub async fn unwatch(&self, mint: &Pubkey) {
+        if let Some(watcher) = self.watchers.lock().unwrap().remove(mint) {
+            watcher.abort();
+        }
+        self.states.write().await.remove(mint);
+        self.prices.write().await.remove(mint);
+    }
+
+    /// Subscribe to streamed price updates
+    pub fn subscribe(&self) -> broadcast::Receiver<PoolPrice> {
+        self.updates.subscribe()
+    }
+
+    /// Stop every subscription
+    pub fn close(&self) {
+        for (_, watcher) in self.watchers.lock().unwrap().drain() {
+            watcher.abort();
+        }
+    }
+
+    async fn watch_loop(&self, mint: Pubkey) {
+        loop {
+            if let Err(e) = self.stream(mint).await {
+                warn!("Price subscription for {} dropped: {}", mint, e);
+            }
+
+            tokio::time::sleep(WATCH_RECONNECT_DELAY).await;
+
+            // Updates may have been missed while disconnected
+            let pool = match self.states.read().await.get(&mint) {
+                Some(state) => state.address,
+                None => return,
+            };
+            match self.load(pool, mint).await {
+                Ok(state) => {
+                    if let Ok(price) = self.quote(&state).await {
+                        self.publish(price).await;
+                    }
+                    self.states.write().await.insert(mint, state);
+                }
+                Err(e) => warn!("Failed to reload pool {}: {}", pool, e),
+            }
+        }
+    }
+
+    /// Apply account updates until the subscription ends or the set of
+    /// accounts to watch changes
+    async fn stream(&self, mint: Pubkey) -> AppResult<()> {
+        let accounts = match self.states.read().await.get(&mint) {
+            Some(state) => state.watched_accounts(),
+            None => return Ok(()),
+        };
+
+        let client = PubsubClient::new(&self.websocket_url).await
+            .map_err(|e| AppError::network(format!("Account subscription unavailable: {}", e)))?;
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(CommitmentConfig::processed()),
+            ..Default::default()
+        };
+
+        let mut subscriptions = Vec::with_capacity(accounts.len());
+        let mut unsubscribes = Vec::with_capacity(accounts.len());
+        for account in &accounts {
+            let (notifications, unsubscribe) = client.account_subscribe(account, Some(config.clone())).await
+                .map_err(|e| AppError::network(format!("Failed to subscribe to {}: {}", account, e)))?;
+            let account = *account;
+            subscriptions.push(notifications.map(move |response| (account, response)));
+            unsubscribes.push(unsubscribe);
+        }
+
+        let mut notifications = select_all(subscriptions);
+        while let Some((account, response)) = notifications.next().await {
+            let Some(data) = response.value.data.decode() else {
+                continue;
+            };
+
+            let state = {
+                let mut states = self.states.write().await;
+                let Some(state) = states.get_mut(&mint) else {
+                    break;
+                };
+                state.update(account, data);
+                state.clone()
+            };
+
+            // A Meteora vault pointing at another LP mint needs resubscribing
+            if !state.missing().is_empty() {
+                debug!("💱 Pool {} dependencies changed, resubscribing", state.address);
+                break;
+            }
+
+            match self.quote(&state).await {
+                Ok(price) => self.publish(price).await,
+                Err(e) => debug!("No price for {} after update: {}", mint, e),
+            }
+        }
+
+        drop(notifications);
+        for unsubscribe in unsubscribes {
+            unsubscribe().await;
+        }
+
+        Err(AppError::network("Account subscription closed"))
+    }
+
+    async fn publish(&self, price: PoolPrice) {
+        self.prices.write().await.insert(price.mint, price.clone());
+        // Nobody listening is fine
+        let _ = self.updates.send(price);
+    }
+
+    /// Read a pool and every account its reserves depend on
+    async fn load(&self, address: Pubkey, mint: Pubkey) -> AppResult<PoolState> {
+        let connection = self.pool.get_connection().await?;
+        let account = connection.get_multiple_accounts(&[address]).await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| AppError::validation(format!("Pool {} not found", address)))?;
+        let dex = PoolDex::from_program(&account.owner)
+            .ok_or_else(|| AppError::validation(format!("Pool {} is owned by unsupported program {}", address, account.owner)))?;
+
+        let mut state = PoolState::new(address, dex, mint, account.data);
+        loop {
+            let missing = state.missing();
+            if missing.is_empty() {
+                return Ok(state);
+            }
+
+            let accounts = connection.get_multiple_accounts(&missing).await?;
+            for (key, account) in missing.into_iter().zip(accounts) {
+                let account = account.ok_or_else(|| AppError::validation(format!(
+                    "Account {} of pool {} not found", key, address
+                )))?;
+                state.update(key, account.data);
+            }
+        }
+    }
+
+    /// Price the token of a loaded pool in SOL and, when possible, USD
+    async fn quote(&self, state: &PoolState) -> AppResult<PoolPrice> {
+        let reserves = state.reserves()
+            .ok_or_else(|| AppError::validation(format!("Pool {} is not trading", state.address)))?;
+        let quote_mint = reserves.counterpart(&state.mint)
+            .ok_or_else(|| AppError::validation(format!("Pool {} does not hold {}", state.address, state.mint)))?;
+        let price = reserves.price_of(&state.mint)
+            .ok_or_else(|| AppError::validation(format!("Pool {} has no price for {}", state.address, state.mint)))?;
+        let token_reserve = reserves.reserve_of(&state.mint).unwrap_or_default();
+        let quote_reserve = reserves.reserve_of(&quote_mint).unwrap_or_default();
+
+        let sol_usd = match self.sol_usd().await {
+            Ok(sol_usd) => Some(sol_usd),
+            Err(e) => {
+                debug!("SOL/USD unavailable: {}", e);
+                None
+            }
+        };
+
+        let (price_sol, liquidity_sol) = match quote_mint.to_string().as_str() {
+            WSOL_MINT => (price, quote_reserve + token_reserve * price),
+            USDC_MINT => {
+                let sol_usd = sol_usd.ok_or_else(|| AppError::network(format!(
+                    "Pool {} is quoted in USDC and SOL/USD is unavailable", state.address
+                )))?;
+                (price / sol_usd, (quote_reserve + token_reserve * price) / sol_usd)
+            }
+            other => return Err(AppError::validation(format!(
+                "Pool {} is quoted in unsupported token {}", state.address, other
+            ))),
+        };
+
+        Ok(PoolPrice {
+            mint: state.mint,
+            pool: state.address,
+            dex: state.dex,
+            price_sol: to_decimal(price_sol)?,
+            price_usd: sol_usd.map(|sol_usd| to_decimal(price_sol * sol_usd)).transpose()?,
+            liquidity_sol: to_decimal(liquidity_sol)?,
+            liquidity_usd: sol_usd.map(|sol_usd| to_decimal(liquidity_sol * sol_usd)).transpose()?,
+            updated_at: Utc::now(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceSource for PriceOracle {
+    async fn get_price_sol(&self, token: &TokenAddress) -> AppResult<Option<Decimal>> {
+        let mint = Pubkey::from_str(token.as_str())
+            .map_err(|e| AppError::validation(format!("Invalid token address {}: {}", token.as_str(), e)))?;
+
+        if let Some(latest) = self.latest(&mint).await {
+            return Ok(Some(latest.price_sol));
+        }
+
+        match self.price(&mint).await {
+            Ok(price) => Ok(Some(price.price_sol)),
+            Err(e) => {
+                debug!("No on-chain price for {}: {}", mint, e);
+                Ok(None)
+            }
+        }
+    }
+
+    fn position_opened(self: Arc<Self>, token: &TokenAddress) {
+        let Ok(mint) = Pubkey::from_str(token.as_str()) else {
+            warn!("Not streaming prices of invalid token address {}", token.as_str());
+            return;
+        };
+
+        tokio::spawn(async move {
+            if let Err(e) = self.watch(mint).await {
+                warn!("Failed to stream prices of {}: {}", mint, e);
+            }
+        });
+    }
+
+    fn position_closed(self: Arc<Self>, token: &TokenAddress) {
+        let Ok(mint) = Pubkey::from_str(token.as_str()) else {
+            return;
+        };
+
+        tokio::spawn(async move { self.unwatch(&mint).await });
+    }
+}
+
+/// pump.fun bonding curve of a mint
+pub fn bonding_curve_address(mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(PUMP_FUN_PROGRAM_ID).expect("valid pump.fun program id");
+    Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], &program_id).0
+}
+
+/// Raw amount in UI units
+fn ui_amount(amount: u64, decimals: u8) -> f64 {
+    amount as f64 / 10f64.powi(decimals as i32)
+}
+
+fn to_decimal(value: f64) -> AppResult<Decimal> {
+    Decimal::try_from(value).map_err(|e| AppError::validation(format!("Unrepresentable price {}: {}", value, e)))
+}
+
+/// Amount held by an SPL token account
+fn token_account_amount(data: &[u8]) -> Option<u64> {
+    read_u64(data, 64)
+}
+
+/// Supply of an SPL mint
+fn mint_supply(data: &[u8]) -> Option<u64> {
+    read_u64(data, 36)
+}
+
+/// Decimals of an SPL mint
+fn mint_decimals(data: &[u8]) -> Option<u8> {
+    data.get(44).copied()
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(offset..offset.checked_add(8)?)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u128(data: &[u8], offset: usize) -> Option<u128> {
+    let bytes = data.get(offset..offset.checked_add(16)?)?;
+    Some(u128::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Option<Pubkey> {
+    let bytes: [u8; 32] = data.get(offset..offset.checked_add(32)?)?.try_into().ok()?;
+    Some(Pubkey::new_from_array(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+    use rust_decimal::prelude::ToPrimitive;
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    fn rpc_result(result: serde_json::Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+    }
+
+    async fn mock_method(server: &MockServer, name: &str, response: ResponseTemplate, times: Option<u64>) {
+        let mock = Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": name })))
+            .respond_with(response);
+        match times {
+            Some(times) => mock.up_to_n_times(times).mount(server).await,
+            None => mock.mount(server).await,
+        }
+    }
+
+    /// `getMultipleAccounts` result with accounts owned by `owner`
+    fn accounts(owner: &str, data: &[&[u8]]) -> ResponseTemplate {
+        let value: Vec<serde_json::Value> = data.iter()
+            .map(|data| serde_json::json!({
+                "data": [BASE64.encode(data), "base64"],
+                "executable": false,
+                "lamports": 2_039_280,
+                "owner": owner,
+                "rentEpoch": 0,
+                "space": data.len(),
+            }))
+            .collect();
+        rpc_result(serde_json::json!({ "context": { "slot": 1 }, "value": value }))
+    }
+
+    /// Oracle over a mock endpoint, converting to USD through `sol_usd_pool`
+    async fn oracle(server: &MockServer, sol_usd_pool: &Pubkey) -> Arc<PriceOracle> {
+        mock_method(server, "getHealth", rpc_result(serde_json::json!("ok")), None).await;
+
+        let mut config = crate::config::ConfigLoader::new().without_env().create_default_config().solana;
+        config.rpc_url = server.uri();
+        config.fallback_rpc_urls = Vec::new();
+        config.sol_usd_pool = sol_usd_pool.to_string();
+        let pool = Arc::new(RpcPool::new(&config).await.unwrap());
+        Arc::new(PriceOracle::new(pool, &config).unwrap())
+    }
+
+    fn write(data: &mut [u8], offset: usize, bytes: &[u8]) {
+        data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    fn token_account(amount: u64) -> Vec<u8> {
+        let mut data = vec![0; 165];
+        write(&mut data, 64, &amount.to_le_bytes());
+        data
+    }
+
+    fn mint(decimals: u8) -> Vec<u8> {
+        let mut data = vec![0; 82];
+        data[44] = decimals;
+        data
+    }
+
+    fn mint_with_supply(decimals: u8, supply: u64) -> Vec<u8> {
+        let mut data = mint(decimals);
+        write(&mut data, 36, &supply.to_le_bytes());
+        data
+    }
+
+    /// Whirlpool pricing `mint_a` at `price` raw units of `mint_b`
+    fn orca_pool(mint_a: &Pubkey, vault_a: &Pubkey, mint_b: &Pubkey, vault_b: &Pubkey, price: f64) -> Vec<u8> {
+        let sqrt_price = (price.sqrt() * 2f64.powi(64)) as u128;
+        let mut data = vec![0; 653];
+        write(&mut data, 65, &sqrt_price.to_le_bytes());
+        write(&mut data, 101, mint_a.as_ref());
+        write(&mut data, 133, vault_a.as_ref());
+        write(&mut data, 181, mint_b.as_ref());
+        write(&mut data, 213, vault_b.as_ref());
+        data
+    }
+
+    #[test]
+    fn test_raydium_reserves_exclude_pnl() {
+        let (pool, coin_vault, pc_vault) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let token = Pubkey::new_unique();
+        let wsol = Pubkey::from_str(WSOL_MINT).unwrap();
+
+        let mut data = vec![0; 752];
+        write(&mut data, 32, &6u64.to_le_bytes());
+        write(&mut data, 40, &9u64.to_le_bytes());
+        write(&mut data, 192, &1_000_000u64.to_le_bytes());
+        write(&mut data, 336, coin_vault.as_ref());
+        write(&mut data, 368, pc_vault.as_ref());
+        write(&mut data, 400, token.as_ref());
+        write(&mut data, 432, wsol.as_ref());
+
+        let mut state = PoolState::new(pool, PoolDex::Raydium, token, data);
+        assert_eq!(state.missing(), vec![coin_vault, pc_vault]);
+        assert!(state.reserves().is_none());
+
+        // 1,000 tokens after 1 token of PnL, against 2 SOL
+        state.update(coin_vault, token_account(1_001_000_000));
+        state.update(pc_vault, token_account(2_000_000_000));
+        assert!(state.missing().is_empty());
+
+        let reserves = state.reserves().unwrap();
+        assert_eq!(reserves.reserve_a, 1_000_000_000);
+        assert_eq!(reserves.counterpart(&token), Some(wsol));
+        assert!((reserves.price_of(&token).unwrap() - 0.002).abs() < 1e-12);
+        assert!((reserves.price_of(&wsol).unwrap() - 500.0).abs() < 1e-9);
+        assert_eq!(reserves.reserve_of(&wsol), Some(2.0));
+        assert_eq!(reserves.price_of(&Pubkey::new_unique()), None);
+    }
+
+    #[test]
+    fn test_pump_fun_prices_from_virtual_reserves() {
+        let token = Pubkey::new_unique();
+        let curve = bonding_curve_address(&token);
+
+        let mut data = vec![0; 49];
+        write(&mut data, 8, &1_073_000_000_000_000u64.to_le_bytes());
+        write(&mut data, 16, &30_000_000_000u64.to_le_bytes());
+        write(&mut data, 24, &793_100_000_000_000u64.to_le_bytes());
+        write(&mut data, 32, &0u64.to_le_bytes());
+
+        let state = PoolState::new(curve, PoolDex::PumpFun, token, data.clone());
+        assert!(state.dependencies().is_empty());
+
+        // A fresh curve prices at 30 SOL / 1.073B tokens despite holding no SOL
+        let reserves = state.reserves().unwrap();
+        assert_eq!(reserves.reserve_b, 0);
+        assert!((reserves.price_of(&token).unwrap() - 30.0 / 1_073_000_000.0).abs() < 1e-15);
+
+        // A completed curve has migrated and no longer has a price
+        data[48] = 1;
+        assert!(PoolState::new(curve, PoolDex::PumpFun, token, data).reserves().is_none());
+    }
+
+    #[test]
+    fn test_orca_sqrt_price() {
+        let (pool, vault_a, vault_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let wsol = Pubkey::from_str(WSOL_MINT).unwrap();
+        let usdc = Pubkey::from_str(USDC_MINT).unwrap();
+
+        // 150 USDC per SOL is a raw price of 150 * 10^(6 - 9)
+        let data = orca_pool(&wsol, &vault_a, &usdc, &vault_b, 0.15);
+
+        let mut state = PoolState::new(pool, PoolDex::Orca, wsol, data);
+        assert_eq!(state.watched_accounts(), vec![pool, wsol, vault_a, usdc, vault_b]);
+        state.update(wsol, mint(9));
+        state.update(usdc, mint(6));
+        state.update(vault_a, token_account(10_000_000_000));
+        state.update(vault_b, token_account(1_500_000_000));
+
+        let reserves = state.reserves().unwrap();
+        assert!((reserves.price_of(&wsol).unwrap() - 150.0).abs() < 1e-6);
+        assert!((reserves.price_of(&usdc).unwrap() - 1.0 / 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_meteora_reserves_are_vault_shares() {
+        let (pool, vault_a, vault_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (lp_held_a, lp_held_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (lp_mint_a, lp_mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let token = Pubkey::new_unique();
+        let wsol = Pubkey::from_str(WSOL_MINT).unwrap();
+
+        let mut data = vec![0; 944];
+        write(&mut data, 40, token.as_ref());
+        write(&mut data, 72, wsol.as_ref());
+        write(&mut data, 104, vault_a.as_ref());
+        write(&mut data, 136, vault_b.as_ref());
+        write(&mut data, 168, lp_held_a.as_ref());
+        write(&mut data, 200, lp_held_b.as_ref());
+
+        let vault = |total_amount: u64, lp_mint: &Pubkey| {
+            let mut data = vec![0; 1227];
+            write(&mut data, 11, &total_amount.to_le_bytes());
+            write(&mut data, 115, lp_mint.as_ref());
+            data
+        };
+
+        // The vault LP mints are only known once the vaults are loaded
+        let mut state = PoolState::new(pool, PoolDex::Meteora, token, data);
+        assert_eq!(state.missing(), vec![token, wsol, vault_a, vault_b, lp_held_a, lp_held_b]);
+        state.update(token, mint(6));
+        state.update(wsol, mint(9));
+        state.update(vault_a, vault(1_000_000_000, &lp_mint_a));
+        state.update(vault_b, vault(10_000_000_000, &lp_mint_b));
+        state.update(lp_held_a, token_account(50));
+        state.update(lp_held_b, token_account(100));
+        assert_eq!(state.missing(), vec![lp_mint_a, lp_mint_b]);
+        assert!(state.reserves().is_none());
+
+        // The pool holds half of vault A's LP tokens and half of vault B's
+        state.update(lp_mint_a, mint_with_supply(6, 100));
+        state.update(lp_mint_b, mint_with_supply(9, 200));
+        assert!(state.missing().is_empty());
+        assert_eq!(state.watched_accounts().len(), 9);
+
+        let reserves = state.reserves().unwrap();
+        assert_eq!(reserves.reserve_a, 500_000_000);
+        assert_eq!(reserves.reserve_b, 5_000_000_000);
+        assert!((reserves.price_of(&token).unwrap() - 0.01).abs() < 1e-12);
+
+        // An empty vault leaves the pool with nothing on that side
+        state.update(lp_mint_a, mint_with_supply(6, 0));
+        assert_eq!(state.reserves().unwrap().reserve_a, 0);
+    }
+
+    #[tokio::test]
+    async fn test_usdc_quoted_pool_converts_through_sol_usd() {
+        let server = MockServer::start().await;
+        let oracle = oracle(&server, &Pubkey::new_unique()).await;
+        *oracle.sol_usd.write().await = Some((150.0, Instant::now()));
+
+        let (pool, coin_vault, pc_vault) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let token = Pubkey::new_unique();
+        let usdc = Pubkey::from_str(USDC_MINT).unwrap();
+
+        let mut data = vec![0; 752];
+        write(&mut data, 32, &6u64.to_le_bytes());
+        write(&mut data, 40, &6u64.to_le_bytes());
+        write(&mut data, 336, coin_vault.as_ref());
+        write(&mut data, 368, pc_vault.as_ref());
+        write(&mut data, 400, token.as_ref());
+        write(&mut data, 432, usdc.as_ref());
+
+        // 1,000 tokens against 300 USDC is 0.3 USDC, or 0.002 SOL, per token
+        let mut state = PoolState::new(pool, PoolDex::Raydium, token, data);
+        state.update(coin_vault, token_account(1_000_000_000));
+        state.update(pc_vault, token_account(300_000_000));
+
+        let price = oracle.quote(&state).await.unwrap();
+        assert!((price.price_sol.to_f64().unwrap() - 0.002).abs() < 1e-12);
+        assert!((price.price_usd.unwrap().to_f64().unwrap() - 0.3).abs() < 1e-9);
+        assert!((price.liquidity_sol.to_f64().unwrap() - 4.0).abs() < 1e-9);
+        assert!((price.liquidity_usd.unwrap().to_f64().unwrap() - 600.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_sol_usd_reads_sol_usdc_pool() {
+        let (pool, vault_a, vault_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let wsol = Pubkey::from_str(WSOL_MINT).unwrap();
+        let usdc = Pubkey::from_str(USDC_MINT).unwrap();
+
+        let server = MockServer::start().await;
+        let data = orca_pool(&wsol, &vault_a, &usdc, &vault_b, 0.15);
+        mock_method(&server, "getMultipleAccounts", accounts(ORCA_WHIRLPOOL_PROGRAM_ID, &[&data]), Some(1)).await;
+        let dependencies = [mint(9), token_account(10_000_000_000), mint(6), token_account(1_500_000_000)];
+        let dependencies: Vec<&[u8]> = dependencies.iter().map(Vec::as_slice).collect();
+        mock_method(&server, "getMultipleAccounts", accounts(TOKEN_PROGRAM_ID, &dependencies), Some(1)).await;
+
+        let oracle = oracle(&server, &pool).await;
+        assert!((oracle.sol_usd().await.unwrap() - 150.0).abs() < 1e-6);
+
+        // Served from the cache without reading the pool again
+        assert!((oracle.sol_usd().await.unwrap() - 150.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_sol_usd_rejects_pool_not_quoted_in_usdc() {
+        let (pool, vault_a, vault_b) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let wsol = Pubkey::from_str(WSOL_MINT).unwrap();
+        let other = Pubkey::new_unique();
+
+        let server = MockServer::start().await;
+        let data = orca_pool(&wsol, &vault_a, &other, &vault_b, 0.15);
+        mock_method(&server, "getMultipleAccounts", accounts(ORCA_WHIRLPOOL_PROGRAM_ID, &[&data]), Some(1)).await;
+        let dependencies = [mint(9), token_account(10_000_000_000), mint(6), token_account(1_500_000_000)];
+        let dependencies: Vec<&[u8]> = dependencies.iter().map(Vec::as_slice).collect();
+        mock_method(&server, "getMultipleAccounts", accounts(TOKEN_PROGRAM_ID, &dependencies), Some(1)).await;
+
+        let oracle = oracle(&server, &pool).await;
+        assert!(oracle.sol_usd().await.is_err());
+        assert!(oracle.sol_usd.read().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prune_pools_keeps_recent_and_watched() {
+        let server = MockServer::start().await;
+        let oracle = oracle(&server, &Pubkey::new_unique()).await;
+        let (recent, expired, watched) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        for mint in [recent, expired, watched] {
+            oracle.register_pool(mint, Pubkey::new_unique()).await;
+        }
+        for mint in [expired, watched] {
+            oracle.pools.write().await.get_mut(&mint).unwrap().1 = Utc::now() - chrono::Duration::hours(POOL_TTL_HOURS + 1);
+        }
+        oracle.watchers.lock().unwrap().insert(watched, tokio::spawn(async {}));
+
+        oracle.prune_pools().await;
+        let pools = oracle.pools.read().await;
+        assert!(pools.contains_key(&recent));
+        assert!(pools.contains_key(&watched));
+        assert!(!pools.contains_key(&expired));
+
+        // Expired tokens fall back to their bonding curve
+        drop(pools);
+        assert_eq!(oracle.pool_for(&expired).await, bonding_curve_address(&expired));
+    }
+}
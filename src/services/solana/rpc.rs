@@ -5,17 +5,28 @@
 
 use solana_client::{
     nonblocking::rpc_client::RpcClient as SolanaRpcClient,
-    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig},
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, SerializableTransaction},
+    rpc_config::{
+        RpcAccountInfoConfig, RpcBlockConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig,
+        RpcSimulateTransactionConfig, RpcTransactionConfig,
+    },
     rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
 };
 use solana_sdk::{
     account::Account,
+    address_lookup_table::state::AddressLookupTable,
     commitment_config::{CommitmentConfig, CommitmentLevel},
+    message::AddressLookupTableAccount,
     pubkey::Pubkey,
     signature::Signature,
-    transaction::Transaction,
 };
 use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, TransactionDetails,
+    TransactionStatus as SignatureStatus,
+    UiInnerInstructions, UiInstruction,
+    UiLoadedAddresses, UiMessage, UiTransaction, UiTransactionEncoding, UiTransactionTokenBalance,
+};
 use spl_token::state::{Account as TokenAccount, Mint};
 use solana_sdk::program_pack::Pack;
 use std::sync::Arc;
@@ -23,15 +34,41 @@ use std::str::FromStr;
 use tokio::sync::{RwLock, Semaphore};
 use tracing::{debug, info, warn, error, instrument};
 use backoff::{ExponentialBackoff, backoff::Backoff};
+use base58::FromBase58;
 
 use crate::config::models::SolanaConfig;
 use crate::core::result::AppResult;
 use crate::core::error::AppError;
-use super::types::{AccountInfo, TokenMetadata, TokenBalance};
+use super::metadata::{decode_metadata, metadata_pda, metadata_program_id};
+use super::types::{
+    AccountInfo, BalanceChange, InstructionInfo, MintCreation, SignatureInfo, SimulationResult, TokenMetadata,
+    TokenBalance, TokenBalanceChange, TransactionInfo, TransactionStatus,
+};
 
 /// Maximum concurrent RPC requests
 const MAX_CONCURRENT_REQUESTS: usize = 10;
 
+/// Maximum signatures returned per `getSignaturesForAddress` page
+const SIGNATURE_PAGE_LIMIT: usize = 1000;
+
+/// Offset of the amount field in an SPL token account
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Request outcomes kept per endpoint for latency and error rate
+const ENDPOINT_SAMPLE_WINDOW: usize = 256;
+
+/// Slot probe latencies kept per endpoint
+const PROBE_SAMPLE_WINDOW: usize = 64;
+
+/// Interval between slot probes of every endpoint
+const SLOT_MONITOR_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Expected cost of one slot of lag, about one slot time
+const SLOT_LAG_COST_MS: f64 = 400.0;
+
+/// Expected cost of a failed request, a retry on another endpoint
+const ERROR_COST_MS: f64 = 1000.0;
+
 /// RPC client wrapper with retry logic
 #[derive(Debug, Clone)]
 pub struct RpcClient {
@@ -47,6 +84,106 @@ pub struct RpcClient {
     is_healthy: Arc<RwLock<bool>>,
     /// Last successful request time
     last_success: Arc<RwLock<Option<std::time::Instant>>>,
+    /// Rolling latency, error and slot metrics
+    metrics: Arc<RwLock<EndpointMetrics>>,
+}
+
+/// Rolling request metrics of one endpoint
+#[derive(Debug, Clone, Default)]
+struct EndpointMetrics {
+    /// Latency of recent requests in milliseconds
+    latencies_ms: std::collections::VecDeque<u64>,
+    /// Outcome of recent requests
+    outcomes: std::collections::VecDeque<bool>,
+    /// Latency of recent slot probes in milliseconds
+    probe_latencies_ms: std::collections::VecDeque<u64>,
+    /// Latest slot reported by the endpoint
+    slot: Option<u64>,
+}
+
+/// Routing score of one RPC endpoint
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EndpointScore {
+    /// Endpoint identifier
+    pub endpoint: String,
+    /// Median request latency in milliseconds
+    pub p50_ms: u64,
+    /// 99th percentile request latency in milliseconds
+    pub p99_ms: u64,
+    /// Share of recent requests that failed
+    pub error_rate: f64,
+    /// Latest slot reported by the endpoint
+    pub slot: Option<u64>,
+    /// Slots behind the highest slot seen across the pool
+    pub slot_lag: u64,
+    /// Health flag of the client
+    pub healthy: bool,
+    /// Excluded from routing for lagging too far behind
+    pub demoted: bool,
+    /// Routing score in (0, 100]; higher is better
+    pub score: f64,
+}
+
+impl EndpointMetrics {
+    fn record_request(&mut self, latency: std::time::Duration, success: bool) {
+        if self.latencies_ms.len() == ENDPOINT_SAMPLE_WINDOW {
+            self.latencies_ms.pop_front();
+        }
+        if self.outcomes.len() == ENDPOINT_SAMPLE_WINDOW {
+            self.outcomes.pop_front();
+        }
+        self.latencies_ms.push_back(latency.as_millis() as u64);
+        self.outcomes.push_back(success);
+    }
+
+    fn record_probe(&mut self, latency: std::time::Duration, slot: u64) {
+        if self.probe_latencies_ms.len() == PROBE_SAMPLE_WINDOW {
+            self.probe_latencies_ms.pop_front();
+        }
+        self.probe_latencies_ms.push_back(latency.as_millis() as u64);
+        self.slot = Some(slot);
+    }
+
+    /// Score the endpoint against the pool's highest slot
+    ///
+    /// The score turns expected request cost into a ranking: median latency,
+    /// a quarter of the tail, failed requests at the cost of a retry and each
+    /// slot of lag at one slot time. Latency comes from real requests, or
+    /// from slot probes for an endpoint that has not served any yet. An
+    /// endpoint that has not reported a slot yet counts as up to date.
+    fn score(&self, endpoint: &str, healthy: bool, highest_slot: u64, max_slot_lag: u64) -> EndpointScore {
+        let latencies = if self.latencies_ms.is_empty() { &self.probe_latencies_ms } else { &self.latencies_ms };
+        let mut sorted: Vec<u64> = latencies.iter().copied().collect();
+        sorted.sort_unstable();
+        let percentile = |p: usize| {
+            if sorted.is_empty() { 0 } else { sorted[((sorted.len() - 1) * p) / 100] }
+        };
+
+        let error_rate = if self.outcomes.is_empty() {
+            0.0
+        } else {
+            self.outcomes.iter().filter(|ok| !**ok).count() as f64 / self.outcomes.len() as f64
+        };
+        let slot_lag = self.slot.map_or(0, |slot| highest_slot.saturating_sub(slot));
+        let (p50_ms, p99_ms) = (percentile(50), percentile(99));
+
+        let cost_ms = p50_ms as f64
+            + p99_ms as f64 / 4.0
+            + error_rate * ERROR_COST_MS
+            + slot_lag as f64 * SLOT_LAG_COST_MS;
+
+        EndpointScore {
+            endpoint: endpoint.to_string(),
+            p50_ms,
+            p99_ms,
+            error_rate,
+            slot: self.slot,
+            slot_lag,
+            healthy,
+            demoted: slot_lag > max_slot_lag,
+            score: 100.0 / (1.0 + cost_ms / 100.0),
+        }
+    }
 }
 
 impl RpcClient {
@@ -78,6 +215,7 @@ impl RpcClient {
             semaphore: Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS)),
             is_healthy: Arc::new(RwLock::new(true)),
             last_success: Arc::new(RwLock::new(Some(std::time::Instant::now()))),
+            metrics: Arc::new(RwLock::new(EndpointMetrics::default())),
         })
     }
 
@@ -99,6 +237,47 @@ impl RpcClient {
         }
     }
 
+    /// Probe the endpoint's current slot, without retries
+    ///
+    /// Probe latency is kept apart from request latency and the probe leaves
+    /// the health flag alone; answering `getSlot` does not show the endpoint
+    /// serves real requests.
+    pub async fn get_slot(&self) -> AppResult<u64> {
+        let start_time = std::time::Instant::now();
+        let slot = self.client.get_slot().await
+            .map_err(|e| AppError::network(format!("RPC get_slot failed for {}: {}", self.id, e)))?;
+
+        self.metrics.write().await.record_probe(start_time.elapsed(), slot);
+        Ok(slot)
+    }
+
+    /// Mark an unhealthy client healthy again once the node reports healthy
+    ///
+    /// Makes a single `getHealth` call, without retries.
+    pub async fn recover(&self) -> bool {
+        if self.is_healthy().await {
+            return true;
+        }
+
+        match self.client.get_health().await {
+            Ok(()) => {
+                info!("✅ RPC endpoint {} recovered", self.id);
+                self.set_health(true).await;
+                true
+            }
+            Err(e) => {
+                debug!("RPC endpoint {} still unhealthy: {}", self.id, e);
+                false
+            }
+        }
+    }
+
+    /// Score the endpoint against the highest slot seen by the pool
+    pub async fn score(&self, highest_slot: u64, max_slot_lag: u64) -> EndpointScore {
+        let healthy = self.is_healthy().await;
+        self.metrics.read().await.score(&self.id, healthy, highest_slot, max_slot_lag)
+    }
+
     /// Execute RPC request with retry logic
     async fn execute_with_retry<T, F, Fut>(&self, operation: &str, f: F) -> AppResult<T>
     where
@@ -115,7 +294,11 @@ impl RpcClient {
         let mut last_error = None;
 
         loop {
-            match f().await {
+            let attempt_start = std::time::Instant::now();
+            let result = f().await;
+            self.metrics.write().await.record_request(attempt_start.elapsed(), result.is_ok());
+
+            match result {
                 Ok(result) => {
                     let elapsed = start_time.elapsed();
                     debug!("✅ RPC {} succeeded in {:?}", operation, elapsed);
@@ -264,6 +447,226 @@ impl RpcClient {
         Ok(balance as f64 / 1e9) // Convert lamports to SOL
     }
 
+    /// Find the creation transaction of a mint
+    ///
+    /// Walks `getSignaturesForAddress` back from the newest signature until
+    /// the last page, at most `max_pages` pages. The fee payer of the oldest
+    /// transaction is reported as the creator.
+    #[instrument(skip(self))]
+    pub async fn get_mint_creation(&self, mint_address: &str, max_pages: usize) -> AppResult<Option<MintCreation>> {
+        let mint_pubkey = Pubkey::from_str(mint_address)
+            .map_err(|e| AppError::validation(format!("Invalid mint address: {}", e)))?;
+
+        let mut before: Option<Signature> = None;
+        let mut oldest = None;
+        let mut is_complete = false;
+
+        for _ in 0..max_pages.max(1) {
+            let page = self.execute_with_retry("get_signatures_for_address", || {
+                self.client.get_signatures_for_address_with_config(
+                    &mint_pubkey,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before,
+                        until: None,
+                        limit: Some(SIGNATURE_PAGE_LIMIT),
+                        commitment: Some(self.commitment),
+                    },
+                )
+            }).await?;
+
+            let page_len = page.len();
+            if let Some(last) = page.into_iter().last() {
+                before = Some(Signature::from_str(&last.signature)
+                    .map_err(|e| AppError::internal(format!("Invalid signature from RPC: {}", e)))?);
+                oldest = Some(last);
+            }
+
+            if page_len < SIGNATURE_PAGE_LIMIT {
+                is_complete = true;
+                break;
+            }
+        }
+
+        let Some(oldest) = oldest else {
+            return Ok(None);
+        };
+
+        let creator = if is_complete {
+            self.get_fee_payer(&oldest.signature).await?
+        } else {
+            None
+        };
+
+        Ok(Some(MintCreation {
+            signature: oldest.signature,
+            slot: oldest.slot,
+            block_time: oldest.block_time
+                .and_then(|t| chrono::DateTime::from_timestamp(t, 0)),
+            creator,
+            is_complete,
+        }))
+    }
+
+    /// Get a page of signatures for an address, newest first
+    #[instrument(skip(self))]
+    pub async fn get_signatures_for_address(
+        &self,
+        address: &str,
+        before: Option<&str>,
+        limit: usize,
+    ) -> AppResult<Vec<SignatureInfo>> {
+        let pubkey = Pubkey::from_str(address)
+            .map_err(|e| AppError::validation(format!("Invalid address: {}", e)))?;
+        let before = before
+            .map(Signature::from_str)
+            .transpose()
+            .map_err(|e| AppError::validation(format!("Invalid signature: {}", e)))?;
+
+        let page = self.execute_with_retry("get_signatures_for_address", || {
+            self.client.get_signatures_for_address_with_config(
+                &pubkey,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: Some(limit.min(SIGNATURE_PAGE_LIMIT)),
+                    commitment: Some(self.commitment),
+                },
+            )
+        }).await?;
+
+        Ok(page.into_iter()
+            .map(|entry| SignatureInfo {
+                signature: entry.signature,
+                slot: entry.slot,
+                block_time: entry.block_time.and_then(|t| chrono::DateTime::from_timestamp(t, 0)),
+                is_error: entry.err.is_some(),
+            })
+            .collect())
+    }
+
+    /// Get a confirmed transaction with its instructions resolved
+    #[instrument(skip(self))]
+    pub async fn get_transaction(&self, signature: &str) -> AppResult<TransactionInfo> {
+        let parsed_signature = Signature::from_str(signature)
+            .map_err(|e| AppError::validation(format!("Invalid signature: {}", e)))?;
+
+        let confirmed = self.execute_with_retry("get_transaction", || {
+            self.client.get_transaction_with_config(
+                &parsed_signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(self.commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+        }).await?;
+
+        transaction_info_from_encoded(signature, confirmed)
+    }
+
+    /// Get the slots of confirmed blocks in `start_slot..=end_slot`
+    ///
+    /// Skipped slots are left out.
+    #[instrument(skip(self))]
+    pub async fn get_blocks(&self, start_slot: u64, end_slot: u64) -> AppResult<Vec<u64>> {
+        self.execute_with_retry("get_blocks", || {
+            self.client.get_blocks_with_commitment(start_slot, Some(end_slot), self.commitment)
+        }).await
+    }
+
+    /// Get the transactions of a confirmed block
+    #[instrument(skip(self))]
+    pub async fn get_block_transactions(&self, slot: u64) -> AppResult<Vec<TransactionInfo>> {
+        let block = self.execute_with_retry("get_block", || {
+            self.client.get_block_with_config(
+                slot,
+                RpcBlockConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    transaction_details: Some(TransactionDetails::Full),
+                    rewards: Some(false),
+                    commitment: Some(self.commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+        }).await?;
+
+        block.transactions.unwrap_or_default()
+            .into_iter()
+            .map(|transaction| {
+                let signature = match &transaction.transaction {
+                    EncodedTransaction::Json(ui) => ui.signatures.first().cloned(),
+                    encoded => encoded.decode().and_then(|tx| tx.signatures.first().map(|s| s.to_string())),
+                }.unwrap_or_default();
+
+                transaction_info_from_encoded(&signature, EncodedConfirmedTransactionWithStatusMeta {
+                    slot,
+                    transaction,
+                    block_time: block.block_time,
+                })
+            })
+            .collect()
+    }
+
+    /// Get the fee payer of a transaction
+    async fn get_fee_payer(&self, signature: &str) -> AppResult<Option<String>> {
+        let signature = Signature::from_str(signature)
+            .map_err(|e| AppError::validation(format!("Invalid signature: {}", e)))?;
+
+        let transaction = self.execute_with_retry("get_transaction", || {
+            self.client.get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(self.commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+        }).await?;
+
+        Ok(transaction.transaction.transaction.decode()
+            .and_then(|tx| tx.message.static_account_keys().first().map(|key| key.to_string())))
+    }
+
+    /// Count token accounts of a mint holding a nonzero balance
+    ///
+    /// Only the amount field is fetched for each account. Legacy SPL token
+    /// accounts are filtered by size; Token-2022 accounts carry extensions
+    /// and are matched on the mint alone.
+    #[instrument(skip(self))]
+    pub async fn get_token_holder_count(&self, mint_address: &str, token_program: &str) -> AppResult<u32> {
+        let mint_pubkey = Pubkey::from_str(mint_address)
+            .map_err(|e| AppError::validation(format!("Invalid mint address: {}", e)))?;
+        let program_id = Pubkey::from_str(token_program)
+            .map_err(|e| AppError::validation(format!("Invalid token program: {}", e)))?;
+
+        let mut filters = vec![
+            RpcFilterType::Memcmp(Memcmp::new(0, MemcmpEncodedBytes::Bytes(mint_pubkey.to_bytes().to_vec()))),
+        ];
+        if program_id == spl_token::id() {
+            filters.push(RpcFilterType::DataSize(TokenAccount::LEN as u64));
+        }
+
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(UiDataSliceConfig {
+                    offset: TOKEN_ACCOUNT_AMOUNT_OFFSET,
+                    length: 8,
+                }),
+                commitment: Some(self.commitment),
+                min_context_slot: None,
+            },
+            ..Default::default()
+        };
+
+        let accounts = self.execute_with_retry("get_program_accounts", || {
+            self.client.get_program_accounts_with_config(&program_id, config.clone())
+        }).await?;
+
+        Ok(count_funded_accounts(accounts.iter().map(|(_, account)| account.data.as_slice())))
+    }
+
     /// Get recent blockhash
     #[instrument(skip(self))]
     pub async fn get_recent_blockhash(&self) -> AppResult<solana_sdk::hash::Hash> {
@@ -276,9 +679,19 @@ impl RpcClient {
         Ok(blockhash)
     }
 
-    /// Send transaction
+    /// Get the latest blockhash and the last block height it is valid for
+    #[instrument(skip(self))]
+    pub async fn get_latest_blockhash_with_height(&self) -> AppResult<(solana_sdk::hash::Hash, u64)> {
+        let latest = self.execute_with_retry("get_latest_blockhash", || {
+            self.client.get_latest_blockhash_with_commitment(self.commitment)
+        }).await?;
+
+        Ok(latest)
+    }
+
+    /// Send a legacy or versioned transaction
     #[instrument(skip(self, transaction))]
-    pub async fn send_transaction(&self, transaction: &Transaction) -> AppResult<Signature> {
+    pub async fn send_transaction(&self, transaction: &impl SerializableTransaction) -> AppResult<Signature> {
         let config = RpcSendTransactionConfig {
             skip_preflight: false,
             preflight_commitment: Some(self.commitment.commitment),
@@ -294,19 +707,86 @@ impl RpcClient {
         Ok(signature)
     }
 
-    /// Simulate transaction
+    /// Get the status of a submitted transaction, `None` if not yet seen
+    pub async fn get_signature_status(&self, signature: &Signature) -> AppResult<Option<SignatureStatus>> {
+        let response = self.execute_with_retry("get_signature_statuses", || {
+            self.client.get_signature_statuses(std::slice::from_ref(signature))
+        }).await?;
+
+        Ok(response.value.into_iter().next().flatten())
+    }
+
+    /// Check whether a blockhash can still be used to land a transaction
+    pub async fn is_blockhash_valid(&self, blockhash: &solana_sdk::hash::Hash) -> AppResult<bool> {
+        let valid = self.execute_with_retry("is_blockhash_valid", || {
+            self.client.is_blockhash_valid(blockhash, CommitmentConfig::processed())
+        }).await?;
+
+        Ok(valid)
+    }
+
+    /// Simulate a legacy or versioned transaction
+    ///
+    /// Signatures are not verified and the blockhash is replaced, so
+    /// unsigned drafts can be simulated. A failed execution is reported in
+    /// `SimulationResult::err` with the full logs; only a failed request is
+    /// an error.
     #[instrument(skip(self, transaction))]
-    pub async fn simulate_transaction(&self, transaction: &Transaction) -> AppResult<()> {
+    pub async fn simulate_transaction(&self, transaction: &impl SerializableTransaction) -> AppResult<SimulationResult> {
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(self.commitment),
+            ..Default::default()
+        };
+
         let result = self.execute_with_retry("simulate_transaction", || {
-            self.client.simulate_transaction(transaction)
+            self.client.simulate_transaction_with_config(transaction, config.clone())
         }).await?;
 
-        if let Some(err) = result.value.err {
-            return Err(AppError::network(format!("Transaction simulation failed: {:?}", err)));
-        }
+        Ok(SimulationResult {
+            err: result.value.err.map(|err| err.to_string()),
+            units_consumed: result.value.units_consumed,
+            logs: result.value.logs.unwrap_or_default(),
+        })
+    }
 
+    /// Get raw accounts, in the order requested
+    pub async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> AppResult<Vec<Option<Account>>> {
+        self.execute_with_retry("get_multiple_accounts", || {
+            self.client.get_multiple_accounts(addresses)
+        }).await
+    }
 
-        Ok(())
+    /// Get address lookup tables, in the order requested
+    pub async fn get_address_lookup_tables(&self, addresses: &[Pubkey]) -> AppResult<Vec<AddressLookupTableAccount>> {
+        let accounts = self.get_multiple_accounts(addresses).await?;
+
+        addresses.iter()
+            .zip(accounts)
+            .map(|(address, account)| {
+                let account = account
+                    .ok_or_else(|| AppError::validation(format!("Lookup table {} not found", address)))?;
+                let table = AddressLookupTable::deserialize(&account.data)
+                    .map_err(|e| AppError::validation(format!("Invalid lookup table {}: {}", address, e)))?;
+
+                Ok(AddressLookupTableAccount {
+                    key: *address,
+                    addresses: table.addresses.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Get recent prioritization fees paid by transactions locking `accounts`
+    ///
+    /// Returns one fee per recent slot, in micro-lamports per compute unit.
+    pub async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> AppResult<Vec<u64>> {
+        let fees = self.execute_with_retry("get_recent_prioritization_fees", || {
+            self.client.get_recent_prioritization_fees(accounts)
+        }).await?;
+
+        Ok(fees.into_iter().map(|fee| fee.prioritization_fee).collect())
     }
 
     /// Health check
@@ -320,24 +800,23 @@ impl RpcClient {
 
     /// Get metadata pubkey for a mint
     fn get_metadata_pubkey(&self, mint: &Pubkey) -> AppResult<Pubkey> {
-        let metadata_program_id = mpl_token_metadata::id();
-        let seeds = &[
-            b"metadata",
-            metadata_program_id.as_ref(),
-            mint.as_ref(),
-        ];
-
-        let (metadata_pubkey, _) = Pubkey::find_program_address(seeds, &metadata_program_id);
-        Ok(metadata_pubkey)
+        Ok(metadata_pda(mint))
     }
 
     /// Parse metadata from account
     fn parse_metadata(&self, account: &Account) -> AppResult<std::collections::HashMap<String, String>> {
-        // This is a simplified version - actual implementation would properly parse metadata
         let mut metadata = std::collections::HashMap::new();
 
-        // In a real implementation, you would deserialize the metadata account
-        // For now, return empty metadata
+        if account.owner != metadata_program_id() {
+            return Ok(metadata);
+        }
+
+        let decoded = decode_metadata(&account.data)?;
+        metadata.insert("name".to_string(), decoded.name);
+        metadata.insert("symbol".to_string(), decoded.symbol);
+        metadata.insert("uri".to_string(), decoded.uri);
+        metadata.insert("update_authority".to_string(), decoded.update_authority);
+
         Ok(metadata)
     }
 
@@ -360,8 +839,10 @@ pub struct RpcPool {
     primary: Arc<RpcClient>,
     /// Fallback RPC clients
     fallbacks: Vec<Arc<RpcClient>>,
-    /// Current active client index
-    active_index: Arc<RwLock<usize>>,
+    /// Highest slot reported by any endpoint
+    highest_slot: Arc<std::sync::atomic::AtomicU64>,
+    /// Background slot monitor
+    slot_monitor: Arc<std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>>,
     /// Pool configuration
     config: SolanaConfig,
 }
@@ -396,39 +877,99 @@ impl RpcPool {
             }
         }
 
-        Ok(Self {
+        let pool = Self {
             primary,
             fallbacks,
-            active_index: Arc::new(RwLock::new(0)),
+            highest_slot: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            slot_monitor: Arc::new(std::sync::Mutex::new(None)),
             config: config.clone(),
-        })
+        };
+
+        let monitor = pool.spawn_slot_monitor();
+        *pool.slot_monitor.lock().unwrap() = Some(monitor);
+
+        Ok(pool)
     }
 
-    /// Get a connection from the pool
-    pub async fn get_connection(&self) -> AppResult<RpcConnection> {
-        let active_index = *self.active_index.read().await;
+    /// Poll every endpoint's slot to track lag, and health-check failed
+    /// endpoints so they rejoin routing once they recover
+    fn spawn_slot_monitor(&self) -> tokio::task::JoinHandle<()> {
+        let clients = self.get_all_clients();
+        let highest_slot = self.highest_slot.clone();
 
-        // Try active client first
-        let all_clients = self.get_all_clients();
-        if active_index < all_clients.len() {
-            let client = all_clients[active_index].clone();
-            if client.is_healthy().await {
-                return Ok(RpcConnection::new(client, self.clone()));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SLOT_MONITOR_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let probes = clients.iter().map(|client| client.get_slot());
+                for (client, result) in clients.iter().zip(futures::future::join_all(probes).await) {
+                    match result {
+                        Ok(slot) => {
+                            highest_slot.fetch_max(slot, std::sync::atomic::Ordering::Relaxed);
+                        }
+                        Err(e) => debug!("Slot probe failed for {}: {}", client.id(), e),
+                    }
+                }
+
+                futures::future::join_all(clients.iter().map(|client| client.recover())).await;
             }
+        })
+    }
+
+    /// Score all endpoints, best first
+    pub async fn endpoint_scores(&self) -> Vec<EndpointScore> {
+        let highest_slot = self.highest_slot.load(std::sync::atomic::Ordering::Relaxed);
+        let mut scores = Vec::new();
+
+        for client in self.get_all_clients() {
+            scores.push(client.score(highest_slot, self.config.max_slot_lag).await);
         }
 
-        // Find a healthy client
-        for (index, client) in all_clients.iter().enumerate() {
-            if client.is_healthy().await {
-                *self.active_index.write().await = index;
-                return Ok(RpcConnection::new(client.clone(), self.clone()));
+        scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scores
+    }
+
+    /// Pick the best-scoring client among `clients`
+    ///
+    /// Healthy endpoints within the slot lag limit win; endpoints that lag
+    /// too far behind are only used when nothing else is healthy.
+    async fn best_client(&self, clients: &[Arc<RpcClient>]) -> Option<Arc<RpcClient>> {
+        let highest_slot = self.highest_slot.load(std::sync::atomic::Ordering::Relaxed);
+        let mut best: Option<(bool, f64, &Arc<RpcClient>)> = None;
+
+        for client in clients {
+            let score = client.score(highest_slot, self.config.max_slot_lag).await;
+            if !score.healthy {
+                continue;
+            }
+
+            let candidate = (!score.demoted, score.score, client);
+            let better = match &best {
+                Some((in_sync, best_score, _)) => (candidate.0, candidate.1) > (*in_sync, *best_score),
+                None => true,
+            };
+            if better {
+                best = Some(candidate);
             }
         }
 
+        best.map(|(_, _, client)| client.clone())
+    }
+
+    /// Get a connection to the best-scoring endpoint
+    pub async fn get_connection(&self) -> AppResult<RpcConnection> {
+        let all_clients = self.get_all_clients();
+
+        if let Some(client) = self.best_client(&all_clients).await {
+            return Ok(RpcConnection::new(client, self.clone()));
+        }
+
         // Try to reconnect to any client
-        for (index, client) in all_clients.iter().enumerate() {
-            if let Ok(_) = client.health_check().await {
-                *self.active_index.write().await = index;
+        for client in &all_clients {
+            if client.health_check().await.is_ok() {
                 return Ok(RpcConnection::new(client.clone(), self.clone()));
             }
         }
@@ -439,10 +980,8 @@ impl RpcPool {
     /// Get a dedicated client for heavy operations
     pub async fn get_dedicated_client(&self) -> AppResult<Arc<RpcClient>> {
         // For dedicated clients, prefer fallbacks to avoid overloading primary
-        for client in &self.fallbacks {
-            if client.is_healthy().await {
-                return Ok(client.clone());
-            }
+        if let Some(client) = self.best_client(&self.fallbacks).await {
+            return Ok(client);
         }
 
         // Fall back to primary if no healthy fallbacks
@@ -472,18 +1011,8 @@ impl RpcPool {
             }
         }
 
-        // Switch to next healthy client
-        let current_index = *self.active_index.read().await;
-        let all_clients = self.get_all_clients();
-
-        for i in 1..all_clients.len() {
-            let next_index = (current_index + i) % all_clients.len();
-            if all_clients[next_index].is_healthy().await {
-                *self.active_index.write().await = next_index;
-                info!("🔄 Switched to RPC client: {}", all_clients[next_index].id());
-                break;
-            }
-        }
+        // The next connection goes to the best remaining endpoint; the slot
+        // monitor brings this one back once it passes a health check
     }
 
     /// Health check for the pool
@@ -503,6 +1032,9 @@ impl RpcPool {
     /// Close all connections
     pub async fn close(&self) -> AppResult<()> {
         info!("🔌 Closing RPC connection pool");
+        if let Some(monitor) = self.slot_monitor.lock().unwrap().take() {
+            monitor.abort();
+        }
         // Connections are closed automatically when dropped
         Ok(())
     }
@@ -565,6 +1097,81 @@ impl RpcConnection {
         }
     }
 
+    pub async fn get_mint_creation(&self, mint_address: &str, max_pages: usize) -> AppResult<Option<MintCreation>> {
+        match self.client.get_mint_creation(mint_address, max_pages).await {
+            Ok(creation) => Ok(creation),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_signatures_for_address(
+        &self,
+        address: &str,
+        before: Option<&str>,
+        limit: usize,
+    ) -> AppResult<Vec<SignatureInfo>> {
+        match self.client.get_signatures_for_address(address, before, limit).await {
+            Ok(signatures) => Ok(signatures),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_transaction(&self, signature: &str) -> AppResult<TransactionInfo> {
+        match self.client.get_transaction(signature).await {
+            Ok(transaction) => Ok(transaction),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_slot(&self) -> AppResult<u64> {
+        match self.client.get_slot().await {
+            Ok(slot) => Ok(slot),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_blocks(&self, start_slot: u64, end_slot: u64) -> AppResult<Vec<u64>> {
+        match self.client.get_blocks(start_slot, end_slot).await {
+            Ok(slots) => Ok(slots),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_block_transactions(&self, slot: u64) -> AppResult<Vec<TransactionInfo>> {
+        match self.client.get_block_transactions(slot).await {
+            Ok(transactions) => Ok(transactions),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_token_holder_count(&self, mint_address: &str, token_program: &str) -> AppResult<u32> {
+        match self.client.get_token_holder_count(mint_address, token_program).await {
+            Ok(count) => Ok(count),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
     pub async fn get_recent_blockhash(&self) -> AppResult<solana_sdk::hash::Hash> {
         match self.client.get_recent_blockhash().await {
             Ok(blockhash) => Ok(blockhash),
@@ -575,7 +1182,17 @@ impl RpcConnection {
         }
     }
 
-    pub async fn send_transaction(&self, transaction: &Transaction) -> AppResult<Signature> {
+    pub async fn get_latest_blockhash_with_height(&self) -> AppResult<(solana_sdk::hash::Hash, u64)> {
+        match self.client.get_latest_blockhash_with_height().await {
+            Ok(latest) => Ok(latest),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn send_transaction(&self, transaction: &impl SerializableTransaction) -> AppResult<Signature> {
         match self.client.send_transaction(transaction).await {
             Ok(signature) => Ok(signature),
             Err(e) => {
@@ -585,9 +1202,59 @@ impl RpcConnection {
         }
     }
 
-    pub async fn simulate_transaction(&self, transaction: &Transaction) -> AppResult<()> {
+    pub async fn get_signature_status(&self, signature: &Signature) -> AppResult<Option<SignatureStatus>> {
+        match self.client.get_signature_status(signature).await {
+            Ok(status) => Ok(status),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn is_blockhash_valid(&self, blockhash: &solana_sdk::hash::Hash) -> AppResult<bool> {
+        match self.client.is_blockhash_valid(blockhash).await {
+            Ok(valid) => Ok(valid),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_recent_prioritization_fees(&self, accounts: &[Pubkey]) -> AppResult<Vec<u64>> {
+        match self.client.get_recent_prioritization_fees(accounts).await {
+            Ok(fees) => Ok(fees),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> AppResult<Vec<Option<Account>>> {
+        match self.client.get_multiple_accounts(addresses).await {
+            Ok(accounts) => Ok(accounts),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_address_lookup_tables(&self, addresses: &[Pubkey]) -> AppResult<Vec<AddressLookupTableAccount>> {
+        match self.client.get_address_lookup_tables(addresses).await {
+            Ok(tables) => Ok(tables),
+            Err(e) => {
+                self.pool.report_failure(self.client.id()).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn simulate_transaction(&self, transaction: &impl SerializableTransaction) -> AppResult<SimulationResult> {
         match self.client.simulate_transaction(transaction).await {
-            Ok(()) => Ok(()),
+            Ok(result) => Ok(result),
             Err(e) => {
                 self.pool.report_failure(self.client.id()).await;
                 Err(e)
@@ -596,14 +1263,202 @@ impl RpcConnection {
     }
 }
 
-// Module-level constants for metadata program
-mod mpl_token_metadata {
-    use solana_sdk::pubkey::Pubkey;
-    use std::str::FromStr;
+/// Convert an RPC transaction into a `TransactionInfo`
+///
+/// Account keys include addresses loaded from lookup tables, so account
+/// indexes of v0 transactions resolve correctly. Inner instructions are kept
+/// separately from top-level ones.
+pub(crate) fn transaction_info_from_encoded(
+    signature: &str,
+    confirmed: EncodedConfirmedTransactionWithStatusMeta,
+) -> AppResult<TransactionInfo> {
+    let (mut account_keys, compiled) = match confirmed.transaction.transaction {
+        EncodedTransaction::Json(UiTransaction { message: UiMessage::Raw(message), .. }) => {
+            let compiled = message.instructions.iter()
+                .map(|ix| Ok((ix.program_id_index, ix.accounts.clone(), decode_instruction_data(&ix.data)?)))
+                .collect::<AppResult<Vec<_>>>()?;
+            (message.account_keys, compiled)
+        }
+        encoded => {
+            let transaction = encoded.decode()
+                .ok_or_else(|| AppError::internal(format!("Failed to decode transaction {}", signature)))?;
+            let keys = transaction.message.static_account_keys()
+                .iter()
+                .map(|key| key.to_string())
+                .collect();
+            let compiled = transaction.message.instructions()
+                .iter()
+                .map(|ix| (ix.program_id_index, ix.accounts.clone(), ix.data.clone()))
+                .collect();
+            (keys, compiled)
+        }
+    };
+
+    let meta = confirmed.transaction.meta;
+    if let Some(loaded) = meta.as_ref()
+        .and_then(|meta| Option::<UiLoadedAddresses>::from(meta.loaded_addresses.clone()))
+    {
+        account_keys.extend(loaded.writable);
+        account_keys.extend(loaded.readonly);
+    }
+
+    let instructions = compiled.into_iter()
+        .filter_map(|(program_id_index, accounts, data)| {
+            resolve_instruction(&account_keys, program_id_index, &accounts, data)
+        })
+        .collect();
+
+    let mut inner_instructions = Vec::new();
+    let inner = meta.as_ref()
+        .and_then(|meta| Option::<Vec<UiInnerInstructions>>::from(meta.inner_instructions.clone()))
+        .unwrap_or_default();
+    for ix in inner.into_iter().flat_map(|inner| inner.instructions) {
+        // Parsed inner instructions only appear with jsonParsed encoding
+        if let UiInstruction::Compiled(ix) = ix {
+            let data = decode_instruction_data(&ix.data)?;
+            inner_instructions.extend(resolve_instruction(&account_keys, ix.program_id_index, &ix.accounts, data));
+        }
+    }
+
+    let status = match meta.as_ref().map(|meta| meta.err.is_some()) {
+        Some(true) => TransactionStatus::Failed,
+        _ => TransactionStatus::Success,
+    };
+
+    let (balance_changes, token_balance_changes) = match meta.as_ref() {
+        Some(meta) => {
+            let token_balances = |balances: &Option<Vec<UiTransactionTokenBalance>>| {
+                balances.iter().flatten()
+                    .map(|balance| RawTokenBalance {
+                        account_index: balance.account_index as usize,
+                        mint: balance.mint.clone(),
+                        owner: Option::<String>::from(balance.owner.clone()),
+                        amount: balance.ui_token_amount.amount.parse().unwrap_or_default(),
+                        decimals: balance.ui_token_amount.decimals,
+                    })
+                    .collect()
+            };
+
+            (
+                balance_changes(&account_keys, &meta.pre_balances, &meta.post_balances),
+                token_balance_changes(
+                    &account_keys,
+                    token_balances(&meta.pre_token_balances.clone().into()),
+                    token_balances(&meta.post_token_balances.clone().into()),
+                ),
+            )
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    Ok(TransactionInfo {
+        signature: signature.to_string(),
+        slot: confirmed.slot,
+        block_time: confirmed.block_time.and_then(|t| chrono::DateTime::from_timestamp(t, 0)),
+        status,
+        fee: meta.as_ref().map_or(0, |meta| meta.fee),
+        instructions,
+        inner_instructions,
+        logs: meta
+            .and_then(|meta| Option::<Vec<String>>::from(meta.log_messages))
+            .unwrap_or_default(),
+        balance_changes,
+        token_balance_changes,
+    })
+}
+
+/// Token balance entry from transaction metadata
+#[derive(Debug, Clone)]
+pub(crate) struct RawTokenBalance {
+    pub(crate) account_index: usize,
+    pub(crate) mint: String,
+    pub(crate) owner: Option<String>,
+    pub(crate) amount: u64,
+    pub(crate) decimals: u8,
+}
+
+/// Pair pre- and post-transaction lamport balances with their accounts
+pub(crate) fn balance_changes(account_keys: &[String], pre: &[u64], post: &[u64]) -> Vec<BalanceChange> {
+    account_keys.iter()
+        .zip(pre.iter().zip(post))
+        .map(|(account, (pre, post))| BalanceChange {
+            account: account.clone(),
+            pre_lamports: *pre,
+            post_lamports: *post,
+        })
+        .collect()
+}
+
+/// Pair pre- and post-transaction token balances by token account
+///
+/// Accounts created by the transaction only have a post balance and closed
+/// accounts only a pre balance; the missing side counts as zero.
+pub(crate) fn token_balance_changes(
+    account_keys: &[String],
+    pre: Vec<RawTokenBalance>,
+    post: Vec<RawTokenBalance>,
+) -> Vec<TokenBalanceChange> {
+    let mut changes: Vec<(usize, TokenBalanceChange)> = Vec::new();
+
+    for (balance, is_post) in pre.into_iter().map(|b| (b, false)).chain(post.into_iter().map(|b| (b, true))) {
+        let Some(account) = account_keys.get(balance.account_index) else {
+            continue;
+        };
+
+        let position = match changes.iter().position(|(index, _)| *index == balance.account_index) {
+            Some(position) => position,
+            None => {
+                changes.push((balance.account_index, TokenBalanceChange {
+                    account: account.clone(),
+                    owner: balance.owner.clone(),
+                    mint: balance.mint.clone(),
+                    pre_amount: 0,
+                    post_amount: 0,
+                    decimals: balance.decimals,
+                }));
+                changes.len() - 1
+            }
+        };
+        let change = &mut changes[position].1;
 
-    pub fn id() -> Pubkey {
-        Pubkey::from_str("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").unwrap()
+        if is_post {
+            change.post_amount = balance.amount;
+        } else {
+            change.pre_amount = balance.amount;
+        }
+        change.owner = change.owner.take().or(balance.owner);
     }
+
+    changes.into_iter().map(|(_, change)| change).collect()
+}
+
+/// Resolve account indexes of a compiled instruction
+fn resolve_instruction(
+    account_keys: &[String],
+    program_id_index: u8,
+    accounts: &[u8],
+    data: Vec<u8>,
+) -> Option<InstructionInfo> {
+    Some(InstructionInfo {
+        program_id: account_keys.get(program_id_index as usize)?.clone(),
+        data,
+        accounts: accounts.iter()
+            .map(|index| account_keys.get(*index as usize).cloned())
+            .collect::<Option<Vec<_>>>()?,
+    })
+}
+
+/// Decode base58 instruction data from a JSON-encoded transaction
+fn decode_instruction_data(data: &str) -> AppResult<Vec<u8>> {
+    data.from_base58()
+        .map_err(|e| AppError::internal(format!("Invalid instruction data: {:?}", e)))
+}
+
+/// Count sliced token account amounts that are nonzero
+fn count_funded_accounts<'a>(amounts: impl Iterator<Item = &'a [u8]>) -> u32 {
+    amounts
+        .filter(|data| data.len() >= 8 && data[..8].iter().any(|byte| *byte != 0))
+        .count() as u32
 }
 
 #[cfg(test)]
@@ -633,4 +1488,94 @@ mod tests {
         // We expect this to fail in test environment without RPC
         assert!(result.is_err() || result.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_count_funded_accounts() {
+        let funded = 1_000u64.to_le_bytes();
+        let empty = 0u64.to_le_bytes();
+        let truncated = [1u8; 4];
+
+        let slices: Vec<&[u8]> = vec![&funded, &empty, &truncated, &funded];
+        assert_eq!(count_funded_accounts(slices.into_iter()), 2);
+    }
+
+    #[test]
+    fn test_token_balance_changes() {
+        let keys: Vec<String> = ["wallet", "ata_bonk", "ata_wsol"].iter().map(|k| k.to_string()).collect();
+        let balance = |account_index, mint: &str, amount| RawTokenBalance {
+            account_index,
+            mint: mint.to_string(),
+            owner: Some("wallet".to_string()),
+            amount,
+            decimals: 6,
+        };
+
+        // The bonk account is created by the transaction, the wsol one closed
+        let changes = token_balance_changes(
+            &keys,
+            vec![balance(2, "wsol", 500)],
+            vec![balance(1, "bonk", 42)],
+        );
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!((changes[0].account.as_str(), changes[0].pre_amount, changes[0].post_amount), ("ata_wsol", 500, 0));
+        assert_eq!((changes[1].account.as_str(), changes[1].pre_amount, changes[1].post_amount), ("ata_bonk", 0, 42));
+    }
+
+    #[test]
+    fn test_endpoint_scoring_and_demotion() {
+        let metrics = |latency_ms: u64, failures: usize, slot: u64| {
+            let mut metrics = EndpointMetrics::default();
+            for i in 0..100 {
+                metrics.record_request(std::time::Duration::from_millis(latency_ms), i >= failures);
+            }
+            metrics.slot = Some(slot);
+            metrics
+        };
+
+        let fast = metrics(20, 0, 1_000).score("fast", true, 1_000, 10);
+        let slow = metrics(200, 0, 1_000).score("slow", true, 1_000, 10);
+        let flaky = metrics(20, 20, 1_000).score("flaky", true, 1_000, 10);
+        let lagging = metrics(20, 0, 985).score("lagging", true, 1_000, 10);
+
+        assert_eq!((fast.p50_ms, fast.p99_ms), (20, 20));
+        assert!(fast.score > slow.score);
+        assert!(fast.score > flaky.score);
+        assert_eq!(flaky.error_rate, 0.2);
+        assert_eq!(lagging.slot_lag, 15);
+        assert!(lagging.demoted);
+        assert!(!fast.demoted);
+
+        // Only the most recent window of requests counts
+        let mut recovered = metrics(20, 100, 1_000);
+        for _ in 0..ENDPOINT_SAMPLE_WINDOW {
+            recovered.record_request(std::time::Duration::from_millis(20), true);
+        }
+        assert_eq!(recovered.score("recovered", true, 1_000, 10).error_rate, 0.0);
+    }
+
+    #[test]
+    fn test_probe_latency_kept_apart_from_requests() {
+        let mut metrics = EndpointMetrics::default();
+        for _ in 0..10 {
+            metrics.record_probe(std::time::Duration::from_millis(5), 1_000);
+        }
+
+        // An endpoint without traffic is ranked by its probes
+        let idle = metrics.score("idle", true, 1_000, 10);
+        assert_eq!((idle.p50_ms, idle.slot), (5, Some(1_000)));
+        assert_eq!(idle.error_rate, 0.0);
+
+        // Once it serves requests, only those count
+        metrics.record_request(std::time::Duration::from_millis(300), false);
+        let busy = metrics.score("busy", true, 1_000, 10);
+        assert_eq!(busy.p50_ms, 300);
+        assert_eq!(busy.error_rate, 1.0);
+
+        for _ in 0..PROBE_SAMPLE_WINDOW * 2 {
+            metrics.record_probe(std::time::Duration::from_millis(5), 1_001);
+        }
+        assert_eq!(metrics.probe_latencies_ms.len(), PROBE_SAMPLE_WINDOW);
+        assert_eq!(metrics.latencies_ms.len(), 1);
+    }
+}
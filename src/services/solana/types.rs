@@ -47,6 +47,22 @@ pub struct AccountInfo {
     pub rent_epoch: u64,
 }
 
+/// Creation data of a mint, derived from its oldest transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintCreation {
+    /// Signature of the oldest transaction found
+    pub signature: String,
+    /// Slot of the oldest transaction found
+    pub slot: u64,
+    /// Block time of the oldest transaction found
+    pub block_time: Option<DateTime<Utc>>,
+    /// Fee payer of the creation transaction
+    pub creator: Option<String>,
+    /// False when the signature walk stopped before reaching the first
+    /// transaction, in which case the age is only a lower bound
+    pub is_complete: bool,
+}
+
 /// Token account information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenAccount {
@@ -198,8 +214,81 @@ pub struct TransactionInfo {
     pub fee: u64,
     /// Instructions
     pub instructions: Vec<InstructionInfo>,
+    /// Inner instructions invoked by the top-level instructions
+    #[serde(default)]
+    pub inner_instructions: Vec<InstructionInfo>,
     /// Logs
     pub logs: Vec<String>,
+    /// Lamport balance changes by account
+    #[serde(default)]
+    pub balance_changes: Vec<BalanceChange>,
+    /// Token balance changes by token account
+    #[serde(default)]
+    pub token_balance_changes: Vec<TokenBalanceChange>,
+}
+
+/// Lamport balance of an account before and after a transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceChange {
+    /// Account address
+    pub account: String,
+    /// Lamports before
+    pub pre_lamports: u64,
+    /// Lamports after
+    pub post_lamports: u64,
+}
+
+/// Token account balance before and after a transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBalanceChange {
+    /// Token account address
+    pub account: String,
+    /// Wallet owning the token account
+    pub owner: Option<String>,
+    /// Token mint
+    pub mint: String,
+    /// Raw amount before (zero if the account was created)
+    pub pre_amount: u64,
+    /// Raw amount after (zero if the account was closed)
+    pub post_amount: u64,
+    /// Token decimals
+    pub decimals: u8,
+}
+
+/// Result of a transaction simulation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationResult {
+    /// Transaction error, if the simulated execution failed
+    pub err: Option<String>,
+    /// Compute units consumed, up to the failure if it failed
+    pub units_consumed: Option<u64>,
+    /// Program logs
+    pub logs: Vec<String>,
+}
+
+impl SimulationResult {
+    /// Whether the simulated execution succeeded
+    pub fn succeeded(&self) -> bool {
+        self.err.is_none()
+    }
+
+    /// Last `lines` program log lines, where the failing program reports
+    pub fn log_tail(&self, lines: usize) -> &[String] {
+        &self.logs[self.logs.len().saturating_sub(lines)..]
+    }
+}
+
+/// Signature entry from `getSignaturesForAddress`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureInfo {
+    /// Transaction signature
+    pub signature: String,
+    /// Slot number
+    pub slot: u64,
+    /// Block time
+    pub block_time: Option<DateTime<Utc>>,
+    /// Whether the transaction failed
+    pub is_error: bool,
 }
 
 /// Transaction status
@@ -436,6 +525,19 @@ mod tests {
         assert!(impact > 0.0); // Should have some price impact
     }
 
+    #[test]
+    fn test_simulation_log_tail() {
+        let simulation = SimulationResult {
+            err: Some("InstructionError(2, Custom(6001))".to_string()),
+            units_consumed: Some(41_000),
+            logs: (0..8).map(|i| format!("log {}", i)).collect(),
+        };
+
+        assert!(!simulation.succeeded());
+        assert_eq!(simulation.log_tail(3), ["log 5", "log 6", "log 7"]);
+        assert_eq!(simulation.log_tail(20).len(), 8);
+    }
+
     #[test]
     fn test_transaction_status() {
         assert_eq!(
@@ -0,0 +1,121 @@
+//! Program log subscriptions
+//!
+//! Streams `logsSubscribe` notifications for transactions that mention a
+//! program. The pubsub client lives in a background task, and each
+//! notification is forwarded as the JSON `{ context, value }` object the
+//! node sent.
+
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+
+use futures::channel::{mpsc, oneshot};
+use futures::{Stream, StreamExt};
+use solana_client::{
+    nonblocking::pubsub_client::PubsubClient,
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::config::models::SolanaConfig;
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+
+/// Log notifications of one program over a WebSocket subscription
+#[derive(Debug)]
+pub struct HeliusWebsocket {
+    /// Notifications forwarded by the subscription task
+    events: mpsc::UnboundedReceiver<AppResult<serde_json::Value>>,
+    /// Task owning the pubsub connection
+    task: JoinHandle<()>,
+}
+
+impl HeliusWebsocket {
+    /// Subscribe to the logs of transactions mentioning a program
+    ///
+    /// Returns once the subscription is acknowledged, so connection errors
+    /// surface here rather than as an empty stream.
+    pub async fn connect(websocket_url: &str, program_id: &str) -> AppResult<Self> {
+        let program = Pubkey::from_str(program_id)
+            .map_err(|e| AppError::validation(format!("Invalid program id {}: {}", program_id, e)))?;
+
+        let (sender, events) = mpsc::unbounded();
+        let (ready, connected) = oneshot::channel();
+        let url = websocket_url.to_string();
+
+        let task = tokio::spawn(async move {
+            let client = match PubsubClient::new(&url).await {
+                Ok(client) => client,
+                Err(e) => {
+                    let _ = ready.send(Err(AppError::network(format!("WebSocket connection failed: {}", e))));
+                    return;
+                }
+            };
+
+            let config = RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            };
+            let filter = RpcTransactionLogsFilter::Mentions(vec![program.to_string()]);
+            let (mut notifications, unsubscribe) = match client.logs_subscribe(filter, config).await {
+                Ok(subscription) => subscription,
+                Err(e) => {
+                    let _ = ready.send(Err(AppError::network(format!("Logs subscription failed: {}", e))));
+                    return;
+                }
+            };
+            let _ = ready.send(Ok(()));
+
+            while let Some(notification) = notifications.next().await {
+                let event = serde_json::to_value(&notification).map_err(AppError::from);
+                if sender.unbounded_send(event).is_err() {
+                    break;
+                }
+            }
+
+            debug!("Logs subscription for {} ended", program);
+            unsubscribe().await;
+        });
+
+        match connected.await {
+            Ok(Ok(())) => Ok(Self { events, task }),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(AppError::network("Logs subscription task exited before connecting")),
+        }
+    }
+
+    /// Close the subscription
+    pub async fn close(self) {
+        self.task.abort();
+    }
+}
+
+impl Stream for HeliusWebsocket {
+    type Item = AppResult<serde_json::Value>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for HeliusWebsocket {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// WebSocket URL for subscriptions, derived from the RPC URL unless configured
+pub(super) fn websocket_url(config: &SolanaConfig) -> String {
+    if let Some(url) = &config.websocket_url {
+        return url.clone();
+    }
+
+    if let Some(rest) = config.rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = config.rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        config.rpc_url.clone()
+    }
+}
@@ -0,0 +1,280 @@
+//! Telegram command handlers
+//!
+//! This module implements handlers for Telegram bot commands, including
+//! admin-only user management backed by `telegram_users` and `audit_logs`.
+
+use std::sync::Arc;
+use sqlx::Row;
+use tracing::{info, instrument};
+
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use crate::infrastructure::database::PostgresService;
+use crate::infrastructure::security::{
+    AuthenticationError, Permission, RoleChange, SecurityService, SensitiveAction, Session, User,
+    UserRole,
+};
+use super::models::AdminCommand;
+
+/// Handler for admin user management commands
+#[derive(Debug)]
+pub struct AdminCommandHandler {
+    /// Security service
+    security: Arc<SecurityService>,
+    /// PostgreSQL service
+    postgres: PostgresService,
+}
+
+impl AdminCommandHandler {
+    /// Create a new admin command handler
+    pub fn new(security: Arc<SecurityService>, postgres: PostgresService) -> Self {
+        Self { security, postgres }
+    }
+
+    /// Load persisted users and their TOTP enrollments into the security service
+    #[instrument(skip(self))]
+    pub async fn load_persisted_users(&self) -> AppResult<usize> {
+        let rows = sqlx::query(
+            "SELECT telegram_id, username, first_name, role, is_active, created_at, last_seen_at
+             FROM telegram_users"
+        )
+            .fetch_all(self.postgres.pool())
+            .await
+            .map_err(|e| AppError::database(format!("Failed to load Telegram users: {}", e), "load_telegram_users"))?;
+
+        let count = rows.len();
+
+        for row in rows {
+            let telegram_id: i64 = row.try_get("telegram_id")
+                .map_err(|e| AppError::database(e.to_string(), "load_telegram_users"))?;
+            let role: Option<String> = row.try_get("role").unwrap_or(None);
+
+            let user = User {
+                id: format!("user_{}", telegram_id),
+                telegram_id,
+                username: row.try_get("username").unwrap_or(None),
+                first_name: row.try_get("first_name").unwrap_or(None),
+                role: role.as_deref().and_then(|r| r.parse().ok()).unwrap_or(UserRole::User),
+                created_at: row.try_get("created_at").unwrap_or_else(|_| chrono::Utc::now()),
+                last_login: row.try_get("last_seen_at").unwrap_or(None),
+                is_active: row.try_get::<Option<bool>, _>("is_active").unwrap_or(None).unwrap_or(true),
+            };
+
+            self.security.authentication.restore_user(user).await;
+        }
+
+        info!("👥 Loaded {} persisted Telegram users", count);
+
+        // Enrollments live in the same table and are written back as they change
+        self.security.two_factor.attach_database(self.postgres.clone()).await;
+        self.security.two_factor.load_persisted_enrollments().await
+            .map_err(|e| AppError::database(e.to_string(), "load_totp_enrollments"))?;
+
+        Ok(count)
+    }
+
+    /// Handle an admin command and return the reply text
+    #[instrument(skip(self, session))]
+    pub async fn handle(&self, session: &Session, command: AdminCommand) -> AppResult<String> {
+        match command {
+            AdminCommand::Users => {
+                self.require_admin(session, None, false).await?;
+                Ok(self.format_users().await)
+            }
+            AdminCommand::Sessions { telegram_id } => {
+                self.require_admin(session, None, false).await?;
+                Ok(self.format_sessions(telegram_id).await)
+            }
+            AdminCommand::Grant { telegram_id, role, code } => {
+                // Checked first so a rejected command does not consume the 2FA code
+                self.ensure_not_self(session, telegram_id)?;
+                if !session.role.can_grant(&role) {
+                    return Err(AppError::validation("Cannot grant a role above your own"));
+                }
+                self.require_admin(session, code.as_deref(), true).await?;
+
+                let previous = self.security.authentication.get_user(telegram_id).await;
+                let change = self.security.authentication
+                    .set_user_role(telegram_id, role)
+                    .await
+                    .map_err(Self::auth_error)?;
+
+                self.commit_or_rollback(session, "user_role_granted", previous, &change).await?;
+
+                Ok(format!(
+                    "✅ User {} is now {} (was {}). {} session(s) invalidated.",
+                    telegram_id,
+                    change.user.role,
+                    change.previous_role.map_or_else(|| "new".to_string(), |r| r.to_string()),
+                    change.sessions_invalidated,
+                ))
+            }
+            AdminCommand::Revoke { telegram_id, code } => {
+                self.ensure_not_self(session, telegram_id)?;
+                self.require_admin(session, code.as_deref(), true).await?;
+
+                let previous = self.security.authentication.get_user(telegram_id).await;
+                let change = self.security.authentication
+                    .revoke_user(telegram_id)
+                    .await
+                    .map_err(Self::auth_error)?;
+
+                self.commit_or_rollback(session, "user_access_revoked", previous, &change).await?;
+
+                Ok(format!(
+                    "🚫 Access revoked for user {}. {} session(s) invalidated.",
+                    telegram_id, change.sessions_invalidated,
+                ))
+            }
+        }
+    }
+
+    /// Require the ManageUsers permission, with a fresh 2FA code for mutations
+    async fn require_admin(&self, session: &Session, code: Option<&str>, mutation: bool) -> AppResult<()> {
+        let result = if mutation {
+            self.security
+                .authorize_sensitive_action(session, &SensitiveAction::Permission(Permission::ManageUsers), code)
+                .await
+        } else {
+            self.security.authentication.check_permission(session, Permission::ManageUsers).await
+        };
+
+        result.map_err(Self::auth_error)
+    }
+
+    /// Prevent admins from demoting or revoking themselves
+    fn ensure_not_self(&self, session: &Session, telegram_id: i64) -> AppResult<()> {
+        if session.user_id == format!("user_{}", telegram_id) {
+            return Err(AppError::validation("Admins cannot change their own role or access"));
+        }
+        Ok(())
+    }
+
+    /// Persist a user's role and status to `telegram_users`
+    async fn persist_user(conn: &mut sqlx::PgConnection, user: &User) -> AppResult<()> {
+        sqlx::query(
+            "INSERT INTO telegram_users (telegram_id, username, first_name, role, is_active, updated_at)
+             VALUES ($1, $2, $3, $4, $5, NOW())
+             ON CONFLICT (telegram_id) DO UPDATE
+             SET role = EXCLUDED.role, is_active = EXCLUDED.is_active, updated_at = NOW()"
+        )
+            .bind(user.telegram_id)
+            .bind(&user.username)
+            .bind(&user.first_name)
+            .bind(user.role.as_db_str())
+            .bind(user.is_active)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| AppError::database(format!("Failed to persist Telegram user: {}", e), "persist_telegram_user"))?;
+
+        Ok(())
+    }
+
+    /// Persist a role change with its audit entry, restoring the in-memory
+    /// user if either write fails
+    ///
+    /// Without the rollback the bot would enforce a role the database does
+    /// not hold, or one that no audit entry accounts for.
+    async fn commit_or_rollback(
+        &self,
+        session: &Session,
+        action: &str,
+        previous: Option<User>,
+        change: &RoleChange,
+    ) -> AppResult<()> {
+        if let Err(e) = self.commit_role_change(session, action, change).await {
+            self.security.authentication.rollback_user(change.user.telegram_id, previous).await;
+            return Err(e);
+        }
+
+        info!("📝 Audit: {} by {} on user {}", action, session.user_id, change.user.telegram_id);
+        Ok(())
+    }
+
+    /// Write a role change and its audit entry in one transaction
+    async fn commit_role_change(&self, session: &Session, action: &str, change: &RoleChange) -> AppResult<()> {
+        let mut tx = self.postgres.begin_transaction().await?;
+
+        Self::persist_user(&mut tx, &change.user).await?;
+        Self::record_audit(&mut tx, session, action, change).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::database(format!("Failed to commit role change: {}", e), "commit_role_change"))
+    }
+
+    /// Record a user management change in `audit_logs`
+    async fn record_audit(conn: &mut sqlx::PgConnection, session: &Session, action: &str, change: &RoleChange) -> AppResult<()> {
+        let details = serde_json::json!({
+            "telegram_id": change.user.telegram_id,
+            "previous_role": change.previous_role.as_ref().map(|r| r.as_db_str()),
+            "new_role": change.user.role.as_db_str(),
+            "is_active": change.user.is_active,
+            "sessions_invalidated": change.sessions_invalidated,
+        });
+
+        sqlx::query(
+            "INSERT INTO audit_logs (user_id, action, resource_type, resource_id, details, ip_address)
+             VALUES ($1, $2, 'telegram_user', $3, $4, $5::INET)"
+        )
+            .bind(&session.user_id)
+            .bind(action)
+            .bind(change.user.telegram_id.to_string())
+            .bind(details)
+            .bind(&session.ip_address)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| AppError::database(format!("Failed to write audit log: {}", e), "insert_audit_log"))?;
+
+        Ok(())
+    }
+
+    /// Format the user list reply
+    async fn format_users(&self) -> String {
+        let users = self.security.authentication.list_users().await;
+
+        if users.is_empty() {
+            return "No users registered.".to_string();
+        }
+
+        let mut reply = format!("👥 Users ({}):\n", users.len());
+        for user in users {
+            reply.push_str(&format!(
+                "• {} {} — {}{}\n",
+                user.telegram_id,
+                user.username.as_deref().map(|u| format!("@{}", u)).unwrap_or_default(),
+                user.role,
+                if user.is_active { "" } else { " (revoked)" },
+            ));
+        }
+        reply
+    }
+
+    /// Format the session list reply for a user
+    async fn format_sessions(&self, telegram_id: i64) -> String {
+        let sessions = self.security.authentication
+            .get_user_sessions(&format!("user_{}", telegram_id))
+            .await;
+
+        if sessions.is_empty() {
+            return format!("No active sessions for user {}.", telegram_id);
+        }
+
+        let mut reply = format!("🔑 Sessions for user {} ({}):\n", telegram_id, sessions.len());
+        for session in sessions {
+            reply.push_str(&format!(
+                "• {} — {} — last active {} — expires {}{}\n",
+                session.id.get(..8).unwrap_or(&session.id),
+                session.role,
+                session.last_activity.format("%Y-%m-%d %H:%M:%S UTC"),
+                session.expires_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                if session.two_factor_verified { " — 2FA ✓" } else { "" },
+            ));
+        }
+        reply
+    }
+
+    fn auth_error(error: AuthenticationError) -> AppError {
+        AppError::security(error.to_string())
+    }
+}
@@ -0,0 +1,10 @@
+//! Telegram bot service module
+//!
+//! This module provides Telegram command parsing and handling for bot
+//! control, reporting, and user administration.
+
+pub mod handlers;
+pub mod models;
+
+pub use handlers::AdminCommandHandler;
+pub use models::AdminCommand;
@@ -0,0 +1,123 @@
+//! Telegram command models
+//!
+//! This module defines parsed command types for the Telegram bot.
+
+use crate::core::error::AppError;
+use crate::core::result::AppResult;
+use crate::infrastructure::security::UserRole;
+
+/// Admin-only user management commands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminCommand {
+    /// List all known users
+    Users,
+    /// Grant a role to a user
+    Grant {
+        /// Target Telegram ID
+        telegram_id: i64,
+        /// Role to grant
+        role: UserRole,
+        /// TOTP code when 2FA is enabled
+        code: Option<String>,
+    },
+    /// Revoke a user's access
+    Revoke {
+        /// Target Telegram ID
+        telegram_id: i64,
+        /// TOTP code when 2FA is enabled
+        code: Option<String>,
+    },
+    /// List active sessions for a user
+    Sessions {
+        /// Target Telegram ID
+        telegram_id: i64,
+    },
+}
+
+impl AdminCommand {
+    /// Parse an admin command from message text
+    ///
+    /// Returns `Ok(None)` when the text is not an admin command.
+    pub fn parse(text: &str) -> AppResult<Option<Self>> {
+        let mut parts = text.split_whitespace();
+
+        let Some(command) = parts.next() else {
+            return Ok(None);
+        };
+
+        // Strip bot mention suffix, e.g. "/grant@SniperBot"
+        let command = command.split('@').next().unwrap_or(command);
+        let args: Vec<&str> = parts.collect();
+
+        let parsed = match command {
+            "/users" => Self::Users,
+            "/grant" => {
+                if args.len() < 2 || args.len() > 3 {
+                    return Err(AppError::validation("Usage: /grant <telegram_id> <admin|user|viewer> [2fa_code]"));
+                }
+                Self::Grant {
+                    telegram_id: Self::parse_id(args[0])?,
+                    role: args[1].parse()
+                        .map_err(|_| AppError::validation(format!("Unknown role: {}", args[1])))?,
+                    code: args.get(2).map(|c| c.to_string()),
+                }
+            }
+            "/revoke" => {
+                if args.is_empty() || args.len() > 2 {
+                    return Err(AppError::validation("Usage: /revoke <telegram_id> [2fa_code]"));
+                }
+                Self::Revoke {
+                    telegram_id: Self::parse_id(args[0])?,
+                    code: args.get(1).map(|c| c.to_string()),
+                }
+            }
+            "/sessions" => {
+                if args.len() != 1 {
+                    return Err(AppError::validation("Usage: /sessions <telegram_id>"));
+                }
+                Self::Sessions {
+                    telegram_id: Self::parse_id(args[0])?,
+                }
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(parsed))
+    }
+
+    fn parse_id(value: &str) -> AppResult<i64> {
+        value.parse()
+            .map_err(|_| AppError::validation(format!("Invalid Telegram ID: {}", value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_admin_commands() {
+        assert_eq!(AdminCommand::parse("/users").unwrap(), Some(AdminCommand::Users));
+        assert_eq!(
+            AdminCommand::parse("/grant@SniperBot 42 viewer").unwrap(),
+            Some(AdminCommand::Grant { telegram_id: 42, role: UserRole::Viewer, code: None })
+        );
+        assert_eq!(
+            AdminCommand::parse("/revoke 42 123456").unwrap(),
+            Some(AdminCommand::Revoke { telegram_id: 42, code: Some("123456".to_string()) })
+        );
+        assert_eq!(
+            AdminCommand::parse("/sessions 42").unwrap(),
+            Some(AdminCommand::Sessions { telegram_id: 42 })
+        );
+        assert_eq!(AdminCommand::parse("/status").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_admin_commands() {
+        assert!(AdminCommand::parse("/grant 42").is_err());
+        assert!(AdminCommand::parse("/grant abc user").is_err());
+        assert!(AdminCommand::parse("/grant 42 root").is_err());
+        assert!(AdminCommand::parse("/sessions").is_err());
+    }
+}